@@ -14,15 +14,21 @@ pub use crate::messages::{
     BestiaryMessage,
     UniverseMessage,
     LocationsMessage,
+    GlossaryMessage,
     TimelineMessage,
     WorkspaceMessage,
+    SettingsMessage,
 };
-pub use crate::editors::{CreatureEditor, LocationEditor, EventEditor, EraEditor};
+pub use crate::editors::{CreatureEditor, LocationEditor, GlossaryEditor, EventEditor, EraEditor};
 
 pub const APP_NAME: &str = "Titan Architect Studio";
 pub const APP_ACRONYM: &str = "TAS";
-pub fn app_theme(_state: &AppState) -> Theme {
-    Theme::Dark
+pub fn app_theme(state: &AppState) -> Theme {
+    // Base de iced (scrollbars, pick_list, etc.) acompaña a los Tokens del shell
+    match state.ui_theme {
+        crate::ui::ThemeMode::Dark => Theme::Dark,
+        crate::ui::ThemeMode::Light => Theme::Light,
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,6 +40,7 @@ pub enum Route {
     UniverseDetail { universe_id: String },
     Bestiary { universe_id: String },
     Locations { universe_id: String },
+    Glossary { universe_id: String },
     Timeline { universe_id: String },
 
     PmList,
@@ -76,6 +83,8 @@ pub enum PmState {
         title: String,
         description: iced::widget::text_editor::Content,
         priority: String,
+        labels: String, // input libre, separado por comas
+        due_date: String, // "YYYY-MM-DD" o vacío
     },
 }
 
@@ -0,0 +1,179 @@
+// src/app_settings.rs
+// Preferencias globales de la app (no por proyecto): settings.json junto a projects.json.
+// Se cargan al crear AppState y se guardan en cada cambio (archivo chico, sync OK).
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    // --- Auto-backup ---
+    pub auto_backup_enabled: bool,
+    pub auto_backup_interval_min: u64,
+    pub auto_backup_keep: usize,
+
+    // --- Auto-snapshots ---
+    // Opt-in: snapshot "auto-before-*" antes de operaciones destructivas (reset demo / vaciar papelera)
+    pub auto_snapshot_enabled: bool,
+    // Se podan aparte de los manuales: quedan las N más nuevas por universo
+    pub auto_snapshot_keep: usize,
+
+    // --- Pausas (bienestar) ---
+    // Opt-in: toast tras N minutos de escritura continua en Forge
+    pub break_reminder_enabled: bool,
+    pub break_reminder_interval_min: u64,
+
+    // --- Forge ---
+    // Debounce del autosave del editor (ms, acotado a FORGE_AUTOSAVE_MIN_MS..=MAX)
+    pub forge_autosave_ms: u64,
+
+    // --- Sidebar ---
+    // Orden + visibilidad de las secciones de MODULES (Settings queda fijo abajo)
+    pub sidebar_sections: Vec<SidebarEntry>,
+
+    // --- Metas ---
+    // Meta de palabras por proyecto (key = Project.id); ausente => sin meta
+    pub project_word_goals: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SidebarSection {
+    Overview,
+    Workspaces,
+    Universe,
+    Forge,
+    PmTools,
+    Assets,
+    Trash,
+}
+
+impl SidebarSection {
+    /// Orden por defecto (el histórico del sidebar)
+    pub const ALL: [SidebarSection; 7] = [
+        SidebarSection::Overview,
+        SidebarSection::Workspaces,
+        SidebarSection::Universe,
+        SidebarSection::Forge,
+        SidebarSection::PmTools,
+        SidebarSection::Assets,
+        SidebarSection::Trash,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SidebarSection::Overview => "Overview",
+            SidebarSection::Workspaces => "Workspaces",
+            SidebarSection::Universe => "Universe",
+            SidebarSection::Forge => "The Forge",
+            SidebarSection::PmTools => "PM Tools",
+            SidebarSection::Assets => "Assets",
+            SidebarSection::Trash => "Trash",
+        }
+    }
+
+    /// Capability que gatea la sección (None = siempre visible)
+    pub fn capability(self) -> Option<&'static str> {
+        match self {
+            SidebarSection::Universe => Some("worldbuilding"),
+            SidebarSection::Forge => Some("novel"),
+            SidebarSection::PmTools => Some("pm"),
+            SidebarSection::Trash => Some("trash"),
+            SidebarSection::Overview | SidebarSection::Workspaces | SidebarSection::Assets => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SidebarEntry {
+    pub section: SidebarSection,
+    pub visible: bool,
+}
+
+pub const FORGE_AUTOSAVE_MIN_MS: u64 = 300;
+pub const FORGE_AUTOSAVE_MAX_MS: u64 = 5000;
+pub const FORGE_AUTOSAVE_DEFAULT_MS: u64 = 800;
+
+pub fn default_sidebar_sections() -> Vec<SidebarEntry> {
+    SidebarSection::ALL.iter().map(|s| SidebarEntry { section: *s, visible: true }).collect()
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            auto_backup_enabled: true,
+            auto_backup_interval_min: 15,
+            auto_backup_keep: 5,
+            auto_snapshot_enabled: false,
+            auto_snapshot_keep: 5,
+            break_reminder_enabled: false,
+            break_reminder_interval_min: 50,
+            forge_autosave_ms: FORGE_AUTOSAVE_DEFAULT_MS,
+            sidebar_sections: default_sidebar_sections(),
+            project_word_goals: HashMap::new(),
+        }
+    }
+}
+
+pub fn clamp_autosave_ms(ms: u64) -> u64 {
+    ms.clamp(FORGE_AUTOSAVE_MIN_MS, FORGE_AUTOSAVE_MAX_MS)
+}
+
+fn settings_path() -> PathBuf {
+    let dir = if let Some(p) = ProjectDirs::from("com", "TitanArchitects", "TAS") {
+        p.config_dir().to_path_buf()
+    } else {
+        PathBuf::from("config")
+    };
+    dir.join("settings.json")
+}
+
+impl AppSettings {
+    /// Best-effort: archivo ausente o corrupto => defaults (nunca bloquea el arranque).
+    pub fn load() -> Self {
+        fs::read_to_string(settings_path())
+            .ok()
+            .and_then(|s| serde_json::from_str::<AppSettings>(&s).ok())
+            .unwrap_or_default()
+            .normalized()
+    }
+
+    pub fn project_word_goal(&self, project_id: &str) -> Option<u64> {
+        self.project_word_goals.get(project_id).copied().filter(|g| *g > 0)
+    }
+
+    /// Sidebar sin duplicados y con todas las secciones (las nuevas entran visibles al final).
+    /// El view y el editor indexan esta lista, así que debe quedar normalizada al cargar/importar.
+    pub fn normalized(mut self) -> Self {
+        let mut seen = Vec::with_capacity(SidebarSection::ALL.len());
+        self.sidebar_sections.retain(|e| {
+            if seen.contains(&e.section) {
+                false
+            } else {
+                seen.push(e.section);
+                true
+            }
+        });
+        for s in SidebarSection::ALL {
+            if !seen.contains(&s) {
+                self.sidebar_sections.push(SidebarEntry { section: s, visible: true });
+            }
+        }
+        self.forge_autosave_ms = clamp_autosave_ms(self.forge_autosave_ms);
+        self.auto_snapshot_keep = self.auto_snapshot_keep.max(1);
+        self
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,138 @@
+// src/command_palette.rs
+// Paleta de comandos (Ctrl+P): lista estática (navegación / acciones) + dinámica
+// (universos, novels, boards del AppState actual), filtrada con un fuzzy match simple.
+// Solo arma la lista: el overlay está en ui_shell y el dispatch en messages_controller.
+
+use crate::app::{AppState, Message, Route};
+use crate::controllers::navigation_controller::{route_enabled, target_route};
+use crate::messages::{PmMessage, SettingsMessage, TheForgeMessage, UniverseMessage};
+
+/// Resultados visibles a la vez (el resto se alcanza escribiendo más)
+pub const MAX_RESULTS: usize = 12;
+
+#[derive(Debug, Default)]
+pub struct PaletteState {
+    pub query: String,
+    pub selected: usize, // índice dentro de `matches(...)`
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    pub label: String,
+    pub group: &'static str, // "Go to", "Universe", "Action"... (columna derecha)
+    pub message: Message,
+}
+
+fn cmd(label: impl Into<String>, group: &'static str, message: Message) -> PaletteCommand {
+    PaletteCommand { label: label.into(), group, message }
+}
+
+/// Todos los comandos disponibles ahora mismo (sin filtrar).
+pub fn commands(state: &AppState, query: &str) -> Vec<PaletteCommand> {
+    let mut out = vec![
+        cmd("Go to Overview", "Go to", Message::Navigate(Route::Overview)),
+        cmd("Go to Universes", "Go to", Message::Navigate(Route::UniverseList)),
+        cmd("Go to The Forge", "Go to", Message::TheForge(TheForgeMessage::Open(None))),
+        cmd("Go to Boards", "Go to", Message::Navigate(Route::PmList)),
+        cmd("Go to Trash", "Go to", Message::Navigate(Route::Trash)),
+        cmd("Go to Settings", "Go to", Message::Navigate(Route::Account)),
+        cmd("Go to Workspaces", "Go to", Message::Navigate(Route::Workspaces)),
+        cmd("Undo", "Action", Message::Undo),
+        cmd("Redo", "Action", Message::Redo),
+    ];
+
+    let theme = state.ui_theme.toggled();
+    out.push(cmd(
+        format!("Switch to {} theme", theme.to_string().to_lowercase()),
+        "Action",
+        Message::Settings(SettingsMessage::ThemeChanged(theme)),
+    ));
+
+    if !state.trash_entries.is_empty() {
+        out.push(cmd(
+            format!("Empty trash ({} items)", state.trash_entries.len()),
+            "Action",
+            Message::EmptyTrash,
+        ));
+    }
+
+    // Lo escrito sirve de nombre: "Create board “Ideas”"
+    let name = query.trim();
+    if !name.is_empty() {
+        out.push(cmd(
+            format!("Create board “{}”", name),
+            "Action",
+            Message::Pm(PmMessage::CreateBoardNamed(name.to_string())),
+        ));
+    }
+
+    for u in state.universes.iter().filter(|u| !u.archived) {
+        out.push(cmd(format!("Open universe {}", u.name), "Universe", Message::Universe(UniverseMessage::Open(u.id.clone()))));
+    }
+    for n in &state.novels {
+        out.push(cmd(
+            format!("Open novel {}", n.title),
+            "Novel",
+            Message::TheForge(TheForgeMessage::JumpToNovel {
+                universe_id: n.universe_id.clone(),
+                novel_id: n.id.clone(),
+            }),
+        ));
+    }
+    for b in &state.boards_list {
+        out.push(cmd(format!("Open board {}", b.name), "Board", Message::Pm(PmMessage::OpenBoard(b.id.clone()))));
+    }
+
+    // Destinos con la capability apagada: no se ofrecen
+    out.retain(|c| target_route(&c.message).is_none_or(|r| route_enabled(state, &r)));
+    out
+}
+
+/// Comandos que matchean `query`, mejor puntaje primero (vacío = orden natural).
+pub fn matches(state: &AppState, query: &str) -> Vec<PaletteCommand> {
+    let mut scored: Vec<(i32, PaletteCommand)> = commands(state, query)
+        .into_iter()
+        .filter_map(|c| fuzzy_score(query, &c.label).map(|s| (s, c)))
+        .collect();
+    // sort estable: a igual puntaje queda el orden de `commands`
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().take(MAX_RESULTS).map(|(_, c)| c).collect()
+}
+
+/// Subsecuencia case-insensitive. Premia letras seguidas e inicios de palabra;
+/// penaliza saltos. None = no matchea.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0i32;
+    let mut qi = 0usize;
+    let mut last_match: Option<usize> = None;
+
+    for (i, c) in chars.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if *c != query[qi] {
+            continue;
+        }
+
+        score += 1;
+        let word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+        if word_start {
+            score += 8;
+        }
+        match last_match {
+            Some(prev) if prev + 1 == i => score += 5,
+            Some(prev) => score -= ((i - prev - 1) as i32).min(3),
+            None => {}
+        }
+        last_match = Some(i);
+        qi += 1;
+    }
+
+    (qi == query.len()).then_some(score)
+}
@@ -1,12 +1,15 @@
 // src/controllers/action_done_controller.rs
 
 use iced::widget::text_editor;
+use iced::Task;
 
-use crate::app::AppState;
-use crate::state::{DbAction, DemoResetScope, ToastKind};
+use crate::app::{AppState, Message, Route};
+use crate::db::{Database, DbError};
+use crate::state::{DbAction, DemoResetScope, RestoreOpenTarget, ToastKind};
 
 fn invalidate_trash(state: &mut AppState) {
     state.trash_entries.clear();
+    state.trash_cleanup_preview.clear();
     state.trash_loaded = false;
 }
 
@@ -43,6 +46,16 @@ fn clear_universe_scoped_caches_if_match(state: &mut AppState, universe_id: &Str
         universe_id: universe_id.clone(),
     });
 
+    // --- Glossary ---
+    if state.loaded_glossary_universe.as_ref() == Some(universe_id) {
+        state.loaded_glossary_universe = None;
+        state.glossary.clear();
+    }
+    state.core_glossary_loaded_for.remove(universe_id);
+    state.core_loading_in_progress.remove(&crate::state::CoreLoadKey::Glossary {
+        universe_id: universe_id.clone(),
+    });
+
     // --- Timeline ---
     if state.loaded_timeline_universe.as_ref() == Some(universe_id) {
         state.loaded_timeline_universe = None;
@@ -114,6 +127,7 @@ fn handle_deleted_board(state: &mut AppState, deleted_id: String) {
 fn invalidate_after_restore_from_trash(state: &mut AppState) {
     // Refresh trash list
     invalidate_trash(state);
+    state.forge_orphans_loaded = false;
 
     // Invalidar caches para que se recarguen
     state.universes.clear();
@@ -128,6 +142,9 @@ fn invalidate_after_restore_from_trash(state: &mut AppState) {
     state.loaded_locations_universe = None;
     state.locations.clear();
 
+    state.loaded_glossary_universe = None;
+    state.glossary.clear();
+
     state.loaded_timeline_universe = None;
     state.timeline_events.clear();
     state.timeline_eras.clear();
@@ -138,6 +155,7 @@ fn invalidate_after_restore_from_trash(state: &mut AppState) {
     // ✅ FASE 10: abrir compuertas globales (loaded_for + in_progress)
     state.core_creatures_loaded_for.clear();
     state.core_locations_loaded_for.clear();
+    state.core_glossary_loaded_for.clear();
     state.core_timeline_loaded_for.clear();
     state.core_snapshots_loaded_for.clear();
     state.core_loading_in_progress.clear();
@@ -148,6 +166,48 @@ fn invalidate_after_restore_from_trash(state: &mut AppState) {
     state.active_chapter_scenes.clear();
 }
 
+fn apply_scene_replace_in_memory(state: &mut AppState, universe_id: &str, from: &str, to: &str) {
+    // Los caches de la Forge acumulan chapters/scenes de universos visitados antes: solo se
+    // conserva lo del universo cargado (state.novels es solo suyo); el resto se re-pide.
+    let forge_novels: std::collections::HashSet<String> = state.novels.iter().map(|n| n.id.clone()).collect();
+    let forge_chapters: std::collections::HashSet<String> = forge_novels
+        .iter()
+        .filter_map(|nid| state.chapters_by_novel_id.get(nid))
+        .flatten()
+        .map(|c| c.id.clone())
+        .collect();
+    state.chapters_by_novel_id.retain(|nid, _| forge_novels.contains(nid));
+    state.scenes_by_chapter_id.retain(|cid, _| forge_chapters.contains(cid));
+    state.forge_cache_generation = state.forge_cache_generation.wrapping_add(1);
+
+    // Forge con otro universo: nada de lo que queda en memoria es del replace
+    if state.loaded_forge_universe.as_deref() != Some(universe_id) {
+        return;
+    }
+
+    let fix = |scene: &mut crate::model::Scene| {
+        if scene.body.contains(from) || scene.title.contains(from) {
+            scene.title = scene.title.replace(from, to);
+            scene.body = scene.body.replace(from, to);
+            scene.word_count = scene.body.split_whitespace().count() as i64;
+        }
+    };
+
+    for list in state.scenes_by_chapter_id.values_mut() {
+        list.iter_mut().for_each(fix);
+    }
+    state.active_chapter_scenes.iter_mut().for_each(fix);
+
+    if state.active_scene_id.is_some() {
+        let current = state.forge_content.text();
+        if current.contains(from) {
+            state.forge_content = text_editor::Content::with_text(&current.replace(from, to));
+        }
+    }
+
+    state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+}
+
 fn apply_global_invalidate_legacy(state: &mut AppState) {
     // Preserve old behavior: if not handled explicitly, do global invalidate.
     state.data_dirty = true;
@@ -155,40 +215,145 @@ fn apply_global_invalidate_legacy(state: &mut AppState) {
     // Flags legacy
     state.loaded_creatures_universe = None;
     state.loaded_locations_universe = None;
+    state.loaded_glossary_universe = None;
     state.loaded_timeline_universe = None;
     state.loaded_snapshots_universe = None;
 
     // ✅ FASE 10: abrir compuertas (loaded_for + in_progress)
     state.core_creatures_loaded_for.clear();
     state.core_locations_loaded_for.clear();
+    state.core_glossary_loaded_for.clear();
     state.core_timeline_loaded_for.clear();
     state.core_snapshots_loaded_for.clear();
     state.core_loading_in_progress.clear();
 
     // PM data
     state.pm_data = None;
+
+    // Relaciones siguen al cache de criaturas (papelera/restore las pudo limpiar)
+    state.creature_relations_for = None;
+}
+/// ¿Quedan ArchiveCreature en cola? (lote del bestiario todavía en curso)
+fn archive_batch_pending(state: &AppState) -> bool {
+    state.db_queue.iter().any(|a| matches!(a, DbAction::ArchiveCreature(..)))
+}
+
+fn restore_batch_pending(state: &AppState) -> bool {
+    state.db_queue.iter().any(|a| matches!(a, DbAction::RestoreFromTrash(_)))
+}
+
+/// Cierra el lote de "Restore all" cuando ya no quedan restores en la cola: un solo toast.
+fn finish_restore_batch(state: &mut AppState) {
+    if restore_batch_pending(state) {
+        return;
+    }
+    if let Some((restored, skipped)) = state.trash_restore_batch.take() {
+        let msg = if skipped == 0 {
+            format!("Restored {} items from trash", restored)
+        } else {
+            format!("Restored {} items, skipped {} (missing parent or feature disabled)", restored, skipped)
+        };
+        let kind = if restored == 0 { ToastKind::Error } else { ToastKind::Success };
+        state.show_toast(msg, kind);
+    }
+}
+
+fn invalidate_creatures_cache(state: &mut AppState) {
+    state.loaded_creatures_universe = None;
+    state.creatures.clear();
+    state.creatures_index.clear(); // ✅ REFACTOR A.3
+
+    state.core_creatures_loaded_for.clear();
+    state.core_loading_in_progress.retain(|k| {
+        !matches!(k, crate::state::CoreLoadKey::Creatures { .. })
+    });
 }
-pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
+
+/// "Restore & Open": navegación post-restore. Forge necesita resolver la ruta en DB
+/// (novel/chapter de la escena); el resto navega directo por target/parent.
+fn open_restored(state: &mut AppState, db: Option<&Database>, target: RestoreOpenTarget) -> Option<Task<Message>> {
+    let route = match target.target_type.as_str() {
+        "universe" => Route::UniverseDetail { universe_id: target.target_id },
+        "board" => Route::PmBoard { board_id: target.target_id },
+        "creature" => Route::Bestiary { universe_id: target.parent_id? },
+        "location" => Route::Locations { universe_id: target.parent_id? },
+        "glossary_term" => Route::Glossary { universe_id: target.parent_id? },
+        "event" | "era" => Route::Timeline { universe_id: target.parent_id? },
+        "novel" | "chapter" | "scene" => {
+            let db = db?.clone();
+            return Some(Task::perform(
+                async move {
+                    db.forge_path_for(target.target_type, target.target_id)
+                        .await
+                        .map_err(|e| e.to_string())
+                },
+                |r| Message::TheForge(crate::messages::TheForgeMessage::OpenRestored(r)),
+            ));
+        }
+        other => {
+            crate::logger::warn(&format!("   ⚠️ Restore & Open: sin destino para '{}'", other));
+            return None;
+        }
+    };
+
+    state.creature_editor = None;
+    Some(Task::done(Message::Navigate(route)))
+}
+
+/// Devuelve una Task de seguimiento cuando la acción encadena algo (ej: Restore & Open).
+pub fn handle_action_done(state: &mut AppState, db: Option<&Database>, result: &Result<(), DbError>) -> Option<Task<Message>> {
     // O(1) y cero clones: tomamos la acción inflight y dejamos None de una vez.
     let inflight = state.db_inflight.take();
+    let mut follow_up = None;
+
+    // Undo global: la entrada entra al stack (o se descarta) según lo que dijo la DB
+    if let Some(action) = inflight.as_ref() {
+        crate::controllers::undo_controller::after_done(state, action, result.is_ok());
+    }
+
+    // Cola drenada => termina la racha de backpressure
+    if state.db_queue.is_empty() {
+        state.db_queue_rejected = 0;
+    }
 
     match result {
         Ok(_) => {
             let mut do_global_invalidate = true;
 
+            // Auto-backup: solo vale la pena si hubo escrituras desde el último
+            state.auto_backup_pending_changes = true;
+            // Cualquier escritura puede mover el total de palabras (se re-suma solo en Overview)
+            state.project_word_total_stale = true;
+
             if let Some(ref action) = inflight {
                 // Si la acción fue MoveToTrash, invalidar cache de trash SIEMPRE
                 if matches!(action, DbAction::MoveToTrash { .. }) {
                     invalidate_trash(state);
+                    // "Recent": la relectura poda el target borrado
+                    state.recent_items_loaded = false;
                 }
 
                 match action {
                     // =========================================================
                     // UNIVERSES LIST
                     // =========================================================
-                    DbAction::CreateUniverse { .. } => {
+                    DbAction::CreateUniverse { id, .. } => {
+                        do_global_invalidate = false;
+                        state.universes.clear();
+
+                        // Creado desde el picker de la Forge: queda seleccionado
+                        if state.forge_pending_universe.as_ref() == Some(id) {
+                            state.forge_pending_universe = None;
+                            follow_up = Some(Task::done(Message::TheForge(
+                                crate::messages::TheForgeMessage::UniverseChanged(id.clone()),
+                            )));
+                        }
+                    }
+
+                    DbAction::CloneUniverse { .. } => {
                         do_global_invalidate = false;
                         state.universes.clear();
+                        state.show_toast("Universe duplicated", ToastKind::Success);
                     }
 
                     DbAction::MoveToTrash { target_type, target_id, .. } if *target_type == "universe" => {
@@ -199,11 +364,17 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                     // =========================================================
                     // PM BOARDS LIST
                     // =========================================================
-                    DbAction::CreateBoard { .. } => {
+                    DbAction::CreateBoard { .. } | DbAction::CreateBoardFromTemplate { .. } => {
                         do_global_invalidate = false;
                         state.boards_list.clear();
                     }
 
+                    DbAction::SaveBoardTemplate { name, .. } => {
+                        do_global_invalidate = false;
+                        state.board_templates_loaded = false;
+                        state.show_toast(format!("Template '{}' saved", name), ToastKind::Success);
+                    }
+
                     DbAction::MoveToTrash { target_type, target_id, .. } if target_type == "board" => {
                         do_global_invalidate = false;
                         handle_deleted_board(state, target_id.clone());
@@ -236,6 +407,80 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         do_global_invalidate = false;
                     }
 
+                    DbAction::SaveOutlineExpansion { .. } => {
+                        do_global_invalidate = false;
+                    }
+
+                    DbAction::ExportNovelJson { path, .. } | DbAction::ExportNovelMarkdown { path, .. } => {
+                        do_global_invalidate = false;
+                        // Solo lectura: no hubo cambios de contenido
+                        state.project_word_total_stale = false;
+                        state.show_toast(format!("Novel exported to {}", path.display()), ToastKind::Success);
+                    }
+
+                    DbAction::ExportBestiary { path, .. } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.show_toast(format!("Bestiary exported to {}", path.display()), ToastKind::Success);
+                    }
+
+                    DbAction::ExportTimeline { path, .. } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.show_toast(format!("Timeline exported to {}", path.display()), ToastKind::Success);
+                    }
+
+                    // Caches pueden tener filas borradas: invalidación global (default)
+                    // y re-validación (el fetch lo dispara post_event en UniverseDetail)
+                    DbAction::RepairForeignKeys => {
+                        state.integrity_busy = true;
+                        state.show_toast("Foreign keys repaired (see audit log)", ToastKind::Success);
+                    }
+
+                    DbAction::ExportAuditLog { path, .. } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.show_toast(format!("Audit log exported to {}", path.display()), ToastKind::Success);
+                    }
+
+                    DbAction::ExportProject { path } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        // Prellenado: el mismo path sirve para importar en otro workspace
+                        state.settings_archive_path = path.to_string_lossy().to_string();
+                        state.show_toast(format!("Project exported to {}", path.display()), ToastKind::Success);
+                    }
+
+                    // Import: invalidación global (default) + listas que no cubre
+                    DbAction::ImportProject { .. } => {
+                        state.universes.clear();
+                        state.boards_list.clear();
+                        state.show_toast("Project archive imported", ToastKind::Success);
+                    }
+
+                    DbAction::MergeNovels { keep_id, merge_id } => {
+                        do_global_invalidate = false;
+                        crate::controllers::forge_data_controller::invalidate_novels_cache(state);
+                        crate::controllers::forge_data_controller::invalidate_chapters_cache(state, keep_id);
+                        crate::controllers::forge_data_controller::invalidate_chapters_cache(state, merge_id);
+                        state.show_toast("Novels merged", ToastKind::Success);
+                    }
+
+                    // Drag & drop entre chapters: el outline ya se movió optimista;
+                    // refetch de ambos para quedar alineados con lo persistido
+                    DbAction::MoveSceneToChapterAt { from_chapter_id, chapter_id, .. } => {
+                        do_global_invalidate = false;
+                        crate::controllers::forge_data_controller::invalidate_scenes_cache(state, from_chapter_id);
+                        crate::controllers::forge_data_controller::invalidate_scenes_cache(state, chapter_id);
+                    }
+
+                    DbAction::MoveSceneToChapter { chapter_id, .. } => {
+                        do_global_invalidate = false;
+                        crate::controllers::forge_data_controller::invalidate_scenes_cache(state, chapter_id);
+                        state.forge_orphans_loaded = false;
+                        state.show_toast("Scene moved to chapter", ToastKind::Success);
+                    }
+
                     DbAction::MoveToTrash { target_type, target_id, parent_type, parent_id, .. }
                     if target_type == "novel" => {
                         do_global_invalidate = false;
@@ -309,14 +554,77 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         do_global_invalidate = false;
                         crate::controllers::forge_data_controller::invalidate_scenes_cache(state, &scene.chapter_id);
                         crate::logger::info("✅ Scene rename confirmado por DB");
+
+                        // Puede haber una revisión nueva: re-fetch si el panel la está mostrando
+                        if state.forge_revisions_for.as_deref() == Some(scene.id.as_str()) {
+                            state.forge_revisions_for = None;
+                        }
+
+                        // Dirty solo se limpia si lo guardado coincide con lo que hay en el editor
+                        // (si el usuario siguió escribiendo durante el save, sigue sucio).
+                        if state.forge_dirty
+                            && state.active_scene_id.as_deref() == Some(scene.id.as_str())
+                            && state.forge_content.text() == scene.body
+                        {
+                            state.forge_dirty = false;
+                        }
+                    }
+
+                    // =========================================================
+                    // KANBAN: card movida a otro board → refrescar origen y destino
+                    // =========================================================
+                    DbAction::MoveCardToBoard { target_board_id, .. } => {
+                        do_global_invalidate = false;
+
+                        state.pm_board_loaded_for.remove(target_board_id);
+                        if let Some(data) = state.pm_data.take() {
+                            state.pm_board_loaded_for.remove(&data.board.id);
+                        }
+                        state.show_toast("Card moved", ToastKind::Success);
+                    }
+
+                    // KANBAN: archivar / restaurar → board activo y panel de archivadas
+                    DbAction::ArchiveCard(_) | DbAction::RestoreCard { .. } => {
+                        do_global_invalidate = false;
+
+                        if let Some(data) = state.pm_data.take() {
+                            state.pm_board_loaded_for.remove(&data.board.id);
+                        }
+                        state.pm_archived_for = None;
+                        let msg = if matches!(action, DbAction::ArchiveCard(_)) { "Card archived" } else { "Card restored" };
+                        state.show_toast(msg, ToastKind::Success);
+                    }
+
+                    // Auto-snapshot: solo refrescar la lista de snapshots de ese universo
+                    DbAction::SnapshotCreateAuto { universe_id, .. } => {
+                        do_global_invalidate = false;
+                        if state.loaded_snapshots_universe.as_ref() == Some(universe_id) {
+                            state.loaded_snapshots_universe = None;
+                        }
+                        state.core_snapshots_loaded_for.remove(universe_id);
+                    }
+
+                    // Rename de snapshot: state.snapshots ya tiene el nombre nuevo
+                    DbAction::SnapshotRename { .. } => {
+                        do_global_invalidate = false;
+                        state.show_toast("Snapshot renamed", ToastKind::Success);
+                    }
+
+                    // Reorden de columnas: pm_data ya se actualizó optimista, no recargamos
+                    DbAction::ReorderColumn { .. } => {
+                        do_global_invalidate = false;
                     }
 
                     // =========================================================
                     // TRASH OPERATIONS
                     // =========================================================
-                    DbAction::RestoreFromTrash(_) => {
+                    DbAction::RestoreFromTrash(entry_id) => {
                         do_global_invalidate = false;
                         invalidate_after_restore_from_trash(state);
+
+                        if let Some(target) = state.pending_restore_open.remove(entry_id) {
+                            follow_up = open_restored(state, db, target);
+                        }
                     }
 
                     DbAction::PermanentDelete(_) => {
@@ -329,12 +637,58 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         state.show_toast("Trash emptied", ToastKind::Success);
                     }
 
+                    DbAction::CleanupOldTrash(_) => {
+                        do_global_invalidate = false;
+                        invalidate_trash(state);
+                        state.show_toast("Old trash items purged", ToastKind::Success);
+                    }
+
+                    // No purga nada ahora: aplica en el próximo arranque o con "Clean now"
+                    DbAction::SetTrashRetention(retention) => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.trash_loaded = false;
+                        state.show_toast(
+                            format!("Trash retention set to {} (applies at next startup)", retention),
+                            ToastKind::Success,
+                        );
+                    }
+
+                    DbAction::TouchRecentItem { .. } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.recent_items_loaded = false;
+                    }
+
+                    // El cache ya quedó refrescado en DB: re-leer la copia del AppState
+                    DbAction::SetCapability { key, enabled } => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                        state.capabilities_loaded = false;
+                        let label = crate::guards::Capabilities::TOGGLES
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .map(|(_, l)| *l)
+                            .unwrap_or(key.as_str());
+                        let verb = if *enabled { "enabled" } else { "disabled" };
+                        state.show_toast(format!("{} {}", label, verb), ToastKind::Success);
+                    }
+
+                    // El tema ya se aplicó al elegirlo: solo queda persistido
+                    DbAction::SetUiTheme(_) => {
+                        do_global_invalidate = false;
+                        state.project_word_total_stale = false;
+                    }
+
                     // =========================================================
                     // BESTIARY / LOCATIONS: invalidate caches on successful writes
                     // =========================================================
                     DbAction::SaveCreature(_, universe_id) => {
                         do_global_invalidate = false;
 
+                        // El nombre pudo cambiar: re-buscar menciones la próxima vez
+                        state.creature_mentions_for = None;
+
                         state.loaded_creatures_universe = None;
                         state.creatures.clear();
                         state.creatures_index.clear(); // ✅ REFACTOR A.3
@@ -351,17 +705,18 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         });
                     }
 
-                    DbAction::ArchiveCreature(_, _) => {
+                    // Optimistas: state.creature_relations ya refleja el cambio
+                    DbAction::AddCreatureRelation(_) | DbAction::RemoveCreatureRelation(_) => {
                         do_global_invalidate = false;
+                    }
 
-                        state.loaded_creatures_universe = None;
-                        state.creatures.clear();
-                        state.creatures_index.clear(); // ✅ REFACTOR A.3
+                    DbAction::ArchiveCreature(_, _) => {
+                        do_global_invalidate = false;
 
-                        state.core_creatures_loaded_for.clear();
-                        state.core_loading_in_progress.retain(|k| {
-                            !matches!(k, crate::state::CoreLoadKey::Creatures { .. })
-                        });
+                        // Lote (bulk archive): recargar una sola vez, con el último
+                        if !archive_batch_pending(state) {
+                            invalidate_creatures_cache(state);
+                        }
                     }
 
                     DbAction::SaveLocation(l) => {
@@ -369,6 +724,8 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
 
                         state.loaded_locations_universe = None;
                         state.locations.clear();
+                        // Mapa de hijos sin locations => árbol vacío hasta el reload (no hijos fantasma)
+                        state.rebuild_locations_cache();
 
                         state.core_locations_loaded_for.remove(&l.universe_id);
                         state.core_loading_in_progress.retain(|k| {
@@ -380,6 +737,33 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         });
                     }
 
+                    DbAction::SaveGlossaryTerm(g) => {
+                        do_global_invalidate = false;
+
+                        state.loaded_glossary_universe = None;
+                        state.core_glossary_loaded_for.remove(&g.universe_id);
+                        state.core_loading_in_progress.retain(|k| {
+                            !matches!(
+                                k,
+                                crate::state::CoreLoadKey::Glossary { universe_id: uid }
+                                    if uid == &g.universe_id
+                            )
+                        });
+                    }
+
+                    // =========================================================
+                    // MAINTENANCE: find & replace
+                    // =========================================================
+                    // Core (creatures/locations/timeline) → invalidación global legacy.
+                    // Forge vive en caches propios: replicamos el replace en memoria para que
+                    // el editor no pise el cambio con su próximo autosave (solo si la Forge
+                    // tiene cargado ese universo; si no, se descartan sus caches).
+                    DbAction::BulkReplaceText { universe_id, from, to, scopes }
+                        if scopes.contains(&crate::model::ReplaceScope::Scenes) =>
+                    {
+                        apply_scene_replace_in_memory(state, universe_id, from, to);
+                    }
+
                     // --- DEFAULT: keep old behavior ---
                     _ => {}
                 }
@@ -407,8 +791,20 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         state.show_toast("Demo data injected", ToastKind::Success);
                     }
 
-                    DbAction::MoveToTrash { display_name, .. } => {
-                        state.show_toast(format!("'{}' moved to trash", display_name), ToastKind::Success);
+                    DbAction::MoveToTrash { trash_id, display_name, .. } => {
+                        state.show_toast_with_action(
+                            format!("'{}' moved to trash", display_name),
+                            ToastKind::Success,
+                            "Undo",
+                            Message::RestoreFromTrash(trash_id),
+                        );
+                    }
+
+                    DbAction::RestoreFromTrash(_) if state.trash_restore_batch.is_some() => {
+                        if let Some((restored, _)) = state.trash_restore_batch.as_mut() {
+                            *restored += 1;
+                        }
+                        finish_restore_batch(state);
                     }
 
                     DbAction::RestoreFromTrash(_) => {
@@ -419,6 +815,19 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
                         state.show_toast(format!("Creature '{}' saved", c.name), ToastKind::Success);
                     }
 
+                    DbAction::BulkReplaceText { from, to, .. } => {
+                        state.show_toast(format!("Replaced '{}' with '{}'", from, to), ToastKind::Success);
+                    }
+
+                    DbAction::ArchiveCreature(_, _) if state.bestiary_bulk_archive.is_some() => {
+                        if !archive_batch_pending(state)
+                            && let Some((count, archived)) = state.bestiary_bulk_archive.take()
+                        {
+                            let verb = if archived { "archived" } else { "restored" };
+                            state.show_toast(format!("{} creatures {}", count, verb), ToastKind::Success);
+                        }
+                    }
+
                     DbAction::ArchiveCreature(id, archived) => {
                         // Evitamos clonar name a un String intermedio. Formateamos directo con &str.
                         let name = state
@@ -445,19 +854,73 @@ pub fn handle_action_done(state: &mut AppState, result: &Result<(), String>) {
         }
 
         Err(e) => {
-            let msg = if e.contains("disabled in this project") {
-                format!(
-                    "❌ Feature disabled: {}",
-                    e.replace("Capability '", "")
-                        .replace("' is disabled in this project", "")
-                )
-            } else if e.contains("capability") || e.contains("Capability") {
-                format!("⚠️ Permission error: {}", e)
-            } else {
-                format!("Action failed: {}", e)
+            // Restore fallido => no hay a dónde navegar
+            if let Some(DbAction::RestoreFromTrash(entry_id)) = inflight.as_ref() {
+                state.pending_restore_open.remove(entry_id);
+
+                // "Restore all": padre faltante o capability apagada => se saltea, el lote sigue
+                if state.trash_restore_batch.is_some()
+                    && matches!(e, DbError::MissingParent { .. } | DbError::CapabilityDenied { .. })
+                {
+                    crate::logger::warn(&format!("⚠️ Restore all: skipped {} ({})", entry_id, e));
+                    if let Some((_, skipped)) = state.trash_restore_batch.as_mut() {
+                        *skipped += 1;
+                    }
+                    finish_restore_batch(state);
+                    return follow_up;
+                }
+                // Otro error: se muestra abajo, pero el lote igual tiene que cerrarse
+                finish_restore_batch(state);
+            }
+
+            // Lote de archivado: el flag optimista pudo quedar mal => recargar al terminar
+            if matches!(inflight, Some(DbAction::ArchiveCreature(..))) && !archive_batch_pending(state) {
+                state.bestiary_bulk_archive = None;
+                invalidate_creatures_cache(state);
+            }
+
+            // Auto-snapshot fallido: sin red de seguridad no corre la operación destructiva que venía atrás
+            if let Some(DbAction::SnapshotCreateAuto { .. }) = inflight.as_ref() {
+                let before = state.db_queue.len();
+                state
+                    .db_queue
+                    .retain(|a| !matches!(a, DbAction::ResetDemoDataScoped(..) | DbAction::EmptyTrash));
+                if state.db_queue.len() != before {
+                    crate::logger::warn("⚠️ Auto-snapshot failed: destructive action cancelled");
+                    state.show_toast("Auto-snapshot failed: operation cancelled", ToastKind::Error);
+                }
+            }
+
+            // Toggle rechazado: el optimista del AppState se descarta re-leyendo el cache
+            if matches!(inflight, Some(DbAction::SetCapability { .. })) {
+                state.capabilities_loaded = false;
+            }
+
+            // SaveLocation rechazado (ej: reparent con ciclo): descartar el parent optimista
+            if let Some(DbAction::SaveLocation(l)) = inflight.as_ref() {
+                state.loaded_locations_universe = None;
+                state.core_locations_loaded_for.remove(&l.universe_id);
+            }
+
+            // ✅ Errores tipados: sin string-matching
+            let msg = match e {
+                DbError::CapabilityDenied { capability } => {
+                    format!("❌ Feature disabled: {}", capability)
+                }
+                DbError::MissingParent { kind, id } => {
+                    format!("Cannot complete: parent {} no longer exists ({})", kind, id)
+                }
+                DbError::RestoreConflict { kind, .. } => {
+                    format!("Cannot restore: a {} with the same id already exists", kind)
+                }
+                DbError::UnknownTargetType(t) => format!("Unsupported trash item type: {}", t),
+                DbError::Invalid(m) => format!("⚠️ {}", m),
+                DbError::Codec(_) | DbError::Sql(_) => format!("Action failed: {}", e),
             };
 
             state.show_toast(msg, ToastKind::Error);
         }
     }
+
+    follow_up
 }
@@ -0,0 +1,203 @@
+// ============================================
+// CONTROLLER: AUTO-BACKUP
+// ============================================
+// Copia periódica de la DB (VACUUM INTO) a un set rotativo de archivos.
+// Reglas:
+// - solo si hubo escrituras desde el último backup
+// - nunca con DB ocupada (inflight/queue) ni con otro backup corriendo
+// - conserva los últimos K (settings.auto_backup_keep)
+// También: VACUUM manual, recálculo de word_count y stats del dev panel (misma regla de DB ociosa).
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use iced::Task;
+
+use crate::app::{AppState, Message};
+use crate::db::Database;
+use crate::state::ToastKind;
+
+pub fn try_handle(state: &mut AppState, db: Option<&Database>, message: &Message) -> Option<Task<Message>> {
+    match message {
+        Message::AutoBackupTick => {
+            if !state.settings.auto_backup_enabled || state.auto_backup_in_progress {
+                return None;
+            }
+            if !state.auto_backup_pending_changes {
+                return None;
+            }
+
+            let interval = Duration::from_secs(state.settings.auto_backup_interval_min.max(1) * 60);
+            if state.auto_backup_last_at.elapsed() < interval {
+                return None;
+            }
+
+            // DB ocupada: lo intentamos en el próximo tick
+            if state.db_inflight.is_some() || !state.db_queue.is_empty() {
+                state.debug_record_ignored("Auto-backup skipped (DB busy)");
+                return None;
+            }
+
+            let db = db?.clone();
+            let project = state.active_project.as_ref()?;
+            let db_path = PathBuf::from(&project.path);
+            let keep = state.settings.auto_backup_keep;
+
+            state.auto_backup_in_progress = true;
+
+            Some(Task::perform(
+                async move {
+                    let dir = crate::db::backup::backups_dir_for(&db_path);
+                    let file = format!("auto-{}.db", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+                    let dest = dir.join(file);
+
+                    db.backup_to(&dest).await.map_err(|e| e.to_string())?;
+                    crate::db::backup::rotate_auto_backups(&dir, keep)?;
+
+                    Ok(dest.to_string_lossy().to_string())
+                },
+                Message::AutoBackupDone,
+            ))
+        }
+
+        Message::AutoBackupDone(result) => {
+            state.auto_backup_in_progress = false;
+            state.auto_backup_last_at = Instant::now();
+
+            match result {
+                Ok(path) => {
+                    state.auto_backup_pending_changes = false;
+                    crate::logger::info(&format!("💾 Auto-backup OK: {}", path));
+                }
+                Err(e) => {
+                    // No reintentamos en loop: esperamos al próximo intervalo
+                    crate::logger::error(&format!("❌ Auto-backup failed: {}", e));
+                    state.show_toast(format!("Auto-backup failed: {}", e), ToastKind::Error);
+                }
+            }
+            None
+        }
+
+        Message::CompactDatabase => {
+            if state.db_maintenance_busy {
+                return None;
+            }
+            // VACUUM no puede correr dentro de una transacción: solo con la cola vacía
+            if state.db_inflight.is_some() || !state.db_queue.is_empty() {
+                state.show_toast("Database is busy, try again in a moment", ToastKind::Info);
+                return None;
+            }
+
+            let db = db?.clone();
+            state.db_maintenance_busy = true;
+            state.show_toast("Compacting database...", ToastKind::Info);
+
+            Some(Task::perform(
+                async move { db.vacuum().await.map_err(|e| e.to_string()) },
+                Message::DatabaseCompacted,
+            ))
+        }
+
+        Message::DatabaseCompacted(result) => {
+            state.db_maintenance_busy = false;
+
+            match result {
+                Ok((before, after)) => {
+                    state.show_toast(
+                        format!("Database compacted: {} → {}", format_bytes(*before), format_bytes(*after)),
+                        ToastKind::Success,
+                    );
+                    // Stats viejas ya no valen
+                    let db = db?.clone();
+                    Some(Task::perform(
+                        async move { db.db_stats().await.map_err(|e| e.to_string()) },
+                        Message::DbStatsFetched,
+                    ))
+                }
+                Err(e) => {
+                    crate::logger::error(&format!("❌ VACUUM failed: {}", e));
+                    state.show_toast(format!("Compact failed: {}", e), ToastKind::Error);
+                    None
+                }
+            }
+        }
+
+        Message::RecalcWordCounts => {
+            if state.db_maintenance_busy {
+                return None;
+            }
+            // Un save del body en cola/en vuelo ya trae su propio word_count: esperar a que drene
+            if state.db_inflight.is_some() || !state.db_queue.is_empty() {
+                state.show_toast("Database is busy, try again in a moment", ToastKind::Info);
+                return None;
+            }
+
+            let db = db?.clone();
+            state.db_maintenance_busy = true;
+
+            Some(Task::perform(
+                async move { db.recalc_word_counts().await.map_err(|e| e.to_string()) },
+                Message::WordCountsRecalculated,
+            ))
+        }
+
+        Message::WordCountsRecalculated(result) => {
+            state.db_maintenance_busy = false;
+
+            match result {
+                Ok(0) => state.show_toast("Word counts are up to date", ToastKind::Success),
+                Ok(updated) => {
+                    // Escenas en cache (outline / stats del novel) tienen el conteo viejo
+                    let chapter_ids: Vec<String> = state.scenes_by_chapter_id.keys().cloned().collect();
+                    for chapter_id in chapter_ids {
+                        crate::controllers::forge_data_controller::invalidate_scenes_cache(state, &chapter_id);
+                    }
+                    state.project_word_total_stale = true;
+                    state.show_toast(
+                        format!("Word counts recalculated: {} scene{} corrected", updated, if *updated == 1 { "" } else { "s" }),
+                        ToastKind::Success,
+                    );
+                }
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Word count recalculation failed: {}", e));
+                    state.show_toast(format!("Recalculate failed: {}", e), ToastKind::Error);
+                }
+            }
+            None
+        }
+
+        Message::RefreshDbStats => {
+            let db = db?.clone();
+            Some(Task::perform(
+                async move { db.db_stats().await.map_err(|e| e.to_string()) },
+                Message::DbStatsFetched,
+            ))
+        }
+
+        Message::DbStatsFetched(result) => {
+            match result {
+                Ok(stats) => state.db_stats = Some(stats.clone()),
+                Err(e) => state.show_toast(format!("Could not read DB stats: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        _ => None,
+    }
+}
+
+/// 1536 => "1.5 KB" (para toasts / dev panel)
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes.max(0) as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes.max(0), UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -7,6 +7,7 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
     match message {
         BestiaryMessage::Open(universe_id) => {
             state.creature_editor = None;
+            state.bestiary_selected.clear();
             state.route = crate::app::Route::Bestiary { universe_id };
         }
         BestiaryMessage::CardClicked(index) => {
@@ -20,6 +21,8 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
                     // (Mejora bulldozer: usar ID siempre, pero mantenemos compatibilidad de índice por ahora)
                     if let Some(c) = state.creatures.get(index) {
                         state.creature_editor = Some(CreatureEditor::from_creature(index, c, &state.locations));
+                        // Menciones siempre frescas al abrir (las escenas pudieron cambiar)
+                        state.creature_mentions_for = None;
                     }
                     state.last_bestiary_click = None;
                     return;
@@ -27,7 +30,10 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
             }
             state.last_bestiary_click = Some((index, now));
         }
-        BestiaryMessage::EditorOpenCreate => state.creature_editor = Some(CreatureEditor::create_new()),
+        BestiaryMessage::EditorOpenCreate => {
+            state.creature_editor = Some(CreatureEditor::create_new());
+            state.creature_duplicate_ack = None;
+        }
         BestiaryMessage::EditorCancel => state.creature_editor = None,
         BestiaryMessage::EditorSave => {
             if let Some(editor) = state.creature_editor.take() {
@@ -46,6 +52,33 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
                     return;
                 }
 
+                // Nombre repetido (solo altas): avisar y pedir un segundo Save.
+                // No bloquea: variantes con el mismo nombre son válidas.
+                if editor.id.is_none() {
+                    let name_key = editor.name.trim().to_lowercase();
+                    let duplicate = state
+                        .creatures
+                        .iter()
+                        .any(|c| c.name.trim().to_lowercase() == name_key);
+
+                    if duplicate && state.creature_duplicate_ack.as_deref() != Some(name_key.as_str()) {
+                        state.show_toast(
+                            format!("A creature named '{}' already exists. Save again to create it anyway.", editor.name.trim()),
+                            ToastKind::Info,
+                        );
+                        state.creature_duplicate_ack = Some(name_key);
+                        state.creature_editor = Some(editor);
+                        return;
+                    }
+                }
+                state.creature_duplicate_ack = None;
+
+                if let Err(msg) = crate::editors::image_path_for_save(&editor.image_path) {
+                    state.creature_editor = Some(editor);
+                    state.show_toast(msg, ToastKind::Error);
+                    return;
+                }
+
                 let mut f = editor.into_creature();
 
                 // ✅ REFACTOR A.3: O(1) lookup instead of O(n) iteration
@@ -61,7 +94,96 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
         BestiaryMessage::KindChanged(v) => if let Some(e) = state.creature_editor.as_mut() { e.kind = v },
         BestiaryMessage::HabitatChanged(v) => if let Some(e) = state.creature_editor.as_mut() { e.habitat = v },
         BestiaryMessage::DescriptionChanged(action) => if let Some(e) = state.creature_editor.as_mut() { e.description.perform(action); },
+        BestiaryMessage::ImagePathChanged(v) => if let Some(e) = state.creature_editor.as_mut() { e.image_path = v },
         BestiaryMessage::DangerChanged(v) => if let Some(e) = state.creature_editor.as_mut() { e.danger = v },
+        BestiaryMessage::DangerFilterToggled(level) => {
+            if !state.bestiary_danger_filter.remove(&level) {
+                state.bestiary_danger_filter.insert(level);
+            }
+        }
+        BestiaryMessage::DangerFilterCleared => state.bestiary_danger_filter.clear(),
+        BestiaryMessage::SearchChanged(query) => state.bestiary_search = query,
+        BestiaryMessage::KindFilterChanged(kind) => state.bestiary_kind_filter = kind,
+        BestiaryMessage::SortChanged(sort) => state.bestiary_sort = sort,
+        BestiaryMessage::RelationTargetChanged(id) => if let Some(e) = state.creature_editor.as_mut() { e.relation_target_id = Some(id) },
+        BestiaryMessage::RelationKindChanged(kind) => if let Some(e) = state.creature_editor.as_mut() { e.relation_kind = kind },
+        BestiaryMessage::RelationAdd => {
+            let crate::app::Route::Bestiary { universe_id } = &state.route else {
+                return;
+            };
+            let universe_id = universe_id.clone();
+            let Some(editor) = state.creature_editor.as_mut() else {
+                return;
+            };
+            // Solo criaturas ya guardadas (la relación referencia ids persistidos)
+            let (Some(from_id), Some(to_id)) = (editor.id.clone(), editor.relation_target_id.take()) else {
+                return;
+            };
+            let kind = editor.relation_kind.as_str();
+
+            if from_id == to_id {
+                return;
+            }
+            let exists = state
+                .creature_relations
+                .iter()
+                .any(|r| r.from_id == from_id && r.to_id == to_id && r.relation_kind == kind);
+            if exists {
+                state.show_toast("That relationship already exists", ToastKind::Info);
+                return;
+            }
+
+            let relation = crate::model::CreatureRelation {
+                id: format!("relation-{}", uuid::Uuid::new_v4()),
+                universe_id,
+                from_id,
+                to_id,
+                relation_kind: kind.to_string(),
+            };
+            // Optimista: la UI lo muestra ya; el INSERT OR IGNORE hace idempotente el reintento
+            state.creature_relations.push(relation.clone());
+            state.queue(DbAction::AddCreatureRelation(relation));
+        }
+        BestiaryMessage::RelationRemove(id) => {
+            state.creature_relations.retain(|r| r.id != id);
+            state.queue(DbAction::RemoveCreatureRelation(id));
+        }
+        BestiaryMessage::SelectionToggled(id) => {
+            if !state.bestiary_selected.remove(&id) {
+                state.bestiary_selected.insert(id);
+            }
+        }
+        BestiaryMessage::SelectionCleared => state.bestiary_selected.clear(),
+        BestiaryMessage::ArchiveSelected => queue_bulk_archive(state, true),
+        BestiaryMessage::RestoreSelected => queue_bulk_archive(state, false),
+        BestiaryMessage::ExportFormatChanged(format) => state.bestiary_export_format = format,
+        BestiaryMessage::ExportIncludeArchivedToggled(include) => state.bestiary_export_include_archived = include,
+        BestiaryMessage::Export => {
+            let crate::app::Route::Bestiary { universe_id } = &state.route else {
+                return;
+            };
+            let Some(project) = state.active_project.as_ref() else {
+                return;
+            };
+            let universe_id = universe_id.clone();
+            let universe_name = state
+                .universes
+                .iter()
+                .find(|u| u.id == universe_id)
+                .map(|u| u.name.as_str())
+                .unwrap_or_default();
+            let path = crate::db::bestiary_export::bestiary_export_path(
+                &std::path::PathBuf::from(&project.path),
+                universe_name,
+                state.bestiary_export_format,
+            );
+            state.queue(DbAction::ExportBestiary {
+                universe_id,
+                format: state.bestiary_export_format,
+                include_archived: state.bestiary_export_include_archived,
+                path,
+            });
+        }
         BestiaryMessage::LocationChanged(loc_id) => if let Some(e) = state.creature_editor.as_mut() { e.home_location_id = loc_id }, // ✅ C.1: ID only
         // NUEVAS ACCIONES QUE YA NO NAVEGAN
         BestiaryMessage::Delete(id) => {state.pending_confirm = Some(crate::state::ConfirmAction::DeleteCreature(id));},
@@ -73,4 +195,33 @@ pub fn update(state: &mut AppState, message: BestiaryMessage) {
         state.show_toast("Restoring creature...", ToastKind::Info);},
 
     }
-}
\ No newline at end of file
+}
+
+/// Un ArchiveCreature por criatura seleccionada que cambie de estado.
+/// El flag se aplica optimista; el cache se invalida una sola vez al vaciarse el lote (action_done).
+fn queue_bulk_archive(state: &mut AppState, archived: bool) {
+    let ids: Vec<String> = state
+        .creatures
+        .iter()
+        .filter(|c| c.archived != archived && state.bestiary_selected.contains(&c.id))
+        .map(|c| c.id.clone())
+        .collect();
+
+    if ids.is_empty() {
+        let what = if archived { "active" } else { "archived" };
+        state.show_toast(format!("No {} creatures selected", what), ToastKind::Info);
+        return;
+    }
+
+    for c in state.creatures.iter_mut().filter(|c| ids.contains(&c.id)) {
+        c.archived = archived;
+    }
+    for id in &ids {
+        state.bestiary_selected.remove(id);
+    }
+
+    state.bestiary_bulk_archive = Some((ids.len(), archived));
+    for id in ids {
+        state.queue(DbAction::ArchiveCreature(id, archived));
+    }
+}
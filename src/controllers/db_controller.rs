@@ -1,7 +1,7 @@
 use iced::Task;
 
 use crate::app::Message;
-use crate::db::Database;
+use crate::db::{Database, DbError};
 use crate::state::DbAction;
 
 /// Spawn an iced Task that executes a DbAction and reports back as Message::ActionDone.
@@ -10,6 +10,19 @@ pub fn task_execute(db: Database, action: DbAction) -> Task<Message> {
     Task::perform(async move { execute(db, action).await }, Message::ActionDone)
 }
 
+// Exports a disco (JSON / Markdown): crea la carpeta si hace falta
+async fn write_export_file(path: &std::path::Path, contents: String) -> Result<(), DbError> {
+    let write = async {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, contents).await
+    };
+    write
+        .await
+        .map_err(|e| DbError::Invalid(format!("Could not write {}: {}", path.display(), e)))
+}
+
 #[derive(Debug)]
 struct AuditSpec {
     action: &'static str,
@@ -21,10 +34,10 @@ struct AuditSpec {
 
 /// Execute a DbAction against the Database.
 /// IMPORTANT: Mantiene el comportamiento; agrega hook de auditoría post-éxito.
-pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
+pub async fn execute(db: Database, action: DbAction) -> Result<(), DbError> {
     let mut audit: Option<AuditSpec> = None;
 
-    let result: Result<(), String> = match action {
+    let result: Result<(), DbError> = match action {
         // -----------------------------
         // UNIVERSE
         // -----------------------------
@@ -38,7 +51,17 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.create_universe(id, name, desc).await.map_err(|e| e.to_string())
+            db.create_universe(id, name, desc).await
+        }
+        DbAction::CloneUniverse { source_id, include_novels } => {
+            audit = Some(AuditSpec {
+                action: "clone_universe",
+                entity_type: "universe",
+                entity_id: source_id.clone(),
+                details_json: "",
+            });
+
+            db.clone_universe(source_id, include_novels).await.map(|_| ())
         }
 
         // -----------------------------
@@ -52,7 +75,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.inject_demo_data(id).await.map_err(|e| e.to_string())
+            db.inject_demo_data(id).await
         }
 
         DbAction::ResetDemoDataScoped(id, scope) => {
@@ -64,7 +87,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.reset_demo_data_scoped(id, scope).await.map_err(|e| e.to_string())
+            db.reset_demo_data_scoped(id, scope).await
         }
 
         // -----------------------------
@@ -80,7 +103,16 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
 
             db.snapshot_create(universe_id, name)
                 .await
-                .map_err(|e| e.to_string())
+        }
+        DbAction::SnapshotCreateAuto { universe_id, name, keep } => {
+            audit = Some(AuditSpec {
+                action: "snapshot_create_auto",
+                entity_type: "universe",
+                entity_id: universe_id.clone(),
+                details_json: "",
+            });
+
+            db.snapshot_create_auto(universe_id, name, keep).await
         }
         DbAction::SnapshotDelete { snapshot_id } => {
             audit = Some(AuditSpec {
@@ -92,7 +124,17 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
 
             db.snapshot_delete(snapshot_id)
                 .await
-                .map_err(|e: sqlx::Error| e.to_string())
+        }
+
+        DbAction::SnapshotRename { snapshot_id, name } => {
+            audit = Some(AuditSpec {
+                action: "snapshot_rename",
+                entity_type: "snapshot",
+                entity_id: snapshot_id.clone(),
+                details_json: "",
+            });
+
+            db.snapshot_rename(snapshot_id, name).await
         }
 
         DbAction::SnapshotRestore { snapshot_id } => {
@@ -103,7 +145,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.snapshot_restore(snapshot_id).await.map_err(|e| e.to_string())
+            db.snapshot_restore(snapshot_id).await
         }
 
         // -----------------------------
@@ -117,7 +159,29 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.create_board(id, name).await.map_err(|e| e.to_string())
+            db.create_board(id, name).await
+        }
+
+        DbAction::CreateBoardFromTemplate { id, template_id, name } => {
+            audit = Some(AuditSpec {
+                action: "create_board_from_template",
+                entity_type: "board",
+                entity_id: id.clone(),
+                details_json: "",
+            });
+
+            db.create_board_from_template(id, template_id, name).await
+        }
+
+        DbAction::SaveBoardTemplate { template_id, board_id, name } => {
+            audit = Some(AuditSpec {
+                action: "save_board_template",
+                entity_type: "board",
+                entity_id: board_id.clone(),
+                details_json: "",
+            });
+
+            db.save_board_as_template(template_id, board_id, name).await
         }
 
         // -----------------------------
@@ -132,7 +196,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.upsert_creature(c, uid).await.map_err(|e| e.to_string())
+            db.upsert_creature(c, uid).await
         }
 
         DbAction::ArchiveCreature(id, st) => {
@@ -143,7 +207,29 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.set_creature_archived(id, st).await.map_err(|e| e.to_string())
+            db.set_creature_archived(id, st).await
+        }
+
+        DbAction::AddCreatureRelation(relation) => {
+            audit = Some(AuditSpec {
+                action: "add_creature_relation",
+                entity_type: "creature",
+                entity_id: relation.from_id.clone(),
+                details_json: "",
+            });
+
+            db.add_creature_relation(relation).await
+        }
+
+        DbAction::RemoveCreatureRelation(id) => {
+            audit = Some(AuditSpec {
+                action: "remove_creature_relation",
+                entity_type: "creature_relation",
+                entity_id: id.clone(),
+                details_json: "",
+            });
+
+            db.delete_creature_relation(id).await
         }
 
         // -----------------------------
@@ -157,7 +243,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.upsert_location(l).await.map_err(|e| e.to_string())
+            db.upsert_location(l).await
         }
 
         // -----------------------------
@@ -171,7 +257,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.upsert_timeline_event(e).await.map_err(|e| e.to_string())
+            db.upsert_timeline_event(e).await
         }
 
         DbAction::SaveEra(e) => {
@@ -182,7 +268,21 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.upsert_timeline_era(e).await.map_err(|e| e.to_string())
+            db.upsert_timeline_era(e).await
+        }
+
+        // -----------------------------
+        // GLOSSARY
+        // -----------------------------
+        DbAction::SaveGlossaryTerm(g) => {
+            audit = Some(AuditSpec {
+                action: "save_glossary_term",
+                entity_type: "glossary_term",
+                entity_id: g.id.clone(),
+                details_json: "",
+            });
+
+            db.upsert_glossary_term(g).await
         }
 
         // -----------------------------
@@ -196,7 +296,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.upsert_card(c).await.map_err(|e| e.to_string())
+            db.upsert_card(c).await
         }
 
         DbAction::MoveCard(cid, col, pos) => {
@@ -207,7 +307,40 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.move_card(cid, col, pos).await.map_err(|e| e.to_string())
+            db.move_card(cid, col, pos).await
+        }
+
+        DbAction::MoveCardToBoard { card_id, target_board_id } => {
+            audit = Some(AuditSpec {
+                action: "move_card_to_board",
+                entity_type: "card",
+                entity_id: card_id.clone(),
+                details_json: "",
+            });
+
+            db.move_card_to_board(card_id, target_board_id).await
+        }
+
+        DbAction::ArchiveCard(card_id) => {
+            audit = Some(AuditSpec {
+                action: "archive_card",
+                entity_type: "card",
+                entity_id: card_id.clone(),
+                details_json: "",
+            });
+
+            db.archive_card(card_id).await
+        }
+
+        DbAction::RestoreCard { card_id, board_id } => {
+            audit = Some(AuditSpec {
+                action: "restore_card",
+                entity_type: "card",
+                entity_id: card_id.clone(),
+                details_json: "",
+            });
+
+            db.restore_card(card_id, board_id).await
         }
 
         DbAction::RebalanceColumn(col) => {
@@ -218,7 +351,18 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.rebalance_column(col).await.map_err(|e| e.to_string())
+            db.rebalance_column(col).await
+        }
+
+        DbAction::ReorderColumn { board_id, column_id, new_index } => {
+            audit = Some(AuditSpec {
+                action: "reorder_column",
+                entity_type: "board_column",
+                entity_id: column_id.clone(),
+                details_json: "",
+            });
+
+            db.reorder_column(board_id, column_id, new_index).await
         }
 
         DbAction::DeleteCard(id) => {
@@ -229,7 +373,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.delete_card(id).await.map_err(|e| e.to_string())
+            db.delete_card(id).await
         }
 
         // -----------------------------
@@ -246,7 +390,6 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
             db.create_novel_with_id(novel_id, universe_id, title)
                 .await
                 .map(|_| ())
-                .map_err(|e| e.to_string())
         }
 
         DbAction::UpdateNovel(novel) => {
@@ -257,7 +400,133 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.update_novel(novel).await.map_err(|e| e.to_string())
+            db.update_novel(novel).await
+        }
+
+        DbAction::MergeNovels { keep_id, merge_id } => {
+            audit = Some(AuditSpec {
+                action: "merge_novels",
+                entity_type: "novel",
+                entity_id: merge_id.clone(),
+                details_json: "",
+            });
+
+            db.merge_novels(keep_id, merge_id).await
+        }
+
+        DbAction::ExportNovelJson { novel_id, path } => {
+            audit = Some(AuditSpec {
+                action: "export_novel_json",
+                entity_type: "novel",
+                entity_id: novel_id.clone(),
+                details_json: "",
+            });
+
+            match db.export_novel_json(novel_id).await {
+                Ok(json) => write_export_file(&path, json).await,
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::ExportNovelMarkdown { novel_id, path } => {
+            audit = Some(AuditSpec {
+                action: "export_novel_markdown",
+                entity_type: "novel",
+                entity_id: novel_id.clone(),
+                details_json: "",
+            });
+
+            match db.export_novel_markdown(novel_id).await {
+                Ok(md) => write_export_file(&path, md).await,
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::ExportBestiary { universe_id, format, include_archived, path } => {
+            audit = Some(AuditSpec {
+                action: "export_bestiary",
+                entity_type: "universe",
+                entity_id: universe_id.clone(),
+                details_json: "",
+            });
+
+            match db.export_bestiary(universe_id, format, include_archived).await {
+                Ok(contents) => write_export_file(&path, contents).await,
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::ExportTimeline { universe_id, format, path } => {
+            audit = Some(AuditSpec {
+                action: "export_timeline",
+                entity_type: "universe",
+                entity_id: universe_id.clone(),
+                details_json: "",
+            });
+
+            match db.export_timeline(universe_id, format).await {
+                Ok(contents) => write_export_file(&path, contents).await,
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::RepairForeignKeys => {
+            audit = Some(AuditSpec {
+                action: "repair_foreign_keys",
+                entity_type: "database",
+                entity_id: String::new(),
+                details_json: "",
+            });
+
+            db.repair_foreign_keys().await.map(|_| ())
+        }
+
+        DbAction::ExportAuditLog { filter, path } => {
+            audit = Some(AuditSpec {
+                action: "export_audit_log",
+                entity_type: "audit_log",
+                entity_id: path.to_string_lossy().to_string(),
+                details_json: "",
+            });
+
+            match db.query_audit(filter.from_ts, filter.to_ts, filter.action, filter.entity_type).await {
+                Ok(rows) => write_export_file(&path, crate::db::audit::render_audit_csv(&rows)).await,
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::ExportProject { path } => {
+            audit = Some(AuditSpec {
+                action: "export_project",
+                entity_type: "project",
+                entity_id: path.to_string_lossy().to_string(),
+                details_json: "",
+            });
+
+            match db.export_all().await {
+                Ok(archive) => match serde_json::to_string_pretty(&archive) {
+                    Ok(json) => write_export_file(&path, json).await,
+                    Err(e) => Err(DbError::from(e)),
+                },
+                Err(e) => Err(e),
+            }
+        }
+
+        DbAction::ImportProject { path } => {
+            audit = Some(AuditSpec {
+                action: "import_project",
+                entity_type: "project",
+                entity_id: path.to_string_lossy().to_string(),
+                details_json: "",
+            });
+
+            match tokio::fs::read_to_string(&path).await {
+                Ok(json) => match crate::db::project_archive::parse_archive(&json) {
+                    Ok(archive) => db.import_archive(archive).await.map(|_| ()),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(DbError::Invalid(format!("Could not read {}: {}", path.display(), e))),
+            }
         }
 
         DbAction::CreateChapter(chapter_id, novel_id, title) => {
@@ -271,7 +540,6 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
             db.create_chapter_with_id(chapter_id, novel_id, title)
                 .await
                 .map(|_| ())
-                .map_err(|e| e.to_string())
         }
 
         DbAction::UpdateChapter(chapter) => {
@@ -282,7 +550,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.update_chapter(chapter).await.map_err(|e| e.to_string())
+            db.update_chapter(chapter).await
         }
 
         DbAction::ReorderChapter(chapter_id, new_position) => {
@@ -295,7 +563,6 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
 
             db.reorder_chapter(chapter_id, new_position)
                 .await
-                .map_err(|e| e.to_string())
         }
 
         DbAction::CreateScene(scene_id, chapter_id, title) => {
@@ -309,7 +576,6 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
             db.create_scene_with_id(scene_id, chapter_id, title)
                 .await
                 .map(|_| ())
-                .map_err(|e| e.to_string())
         }
 
         DbAction::UpdateScene(scene) => {
@@ -320,7 +586,35 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
                 details_json: "",
             });
 
-            db.update_scene(scene).await.map_err(|e| e.to_string())
+            db.update_scene(scene).await
+        }
+
+        DbAction::MoveSceneToChapter { scene_id, chapter_id } => {
+            audit = Some(AuditSpec {
+                action: "move_scene_to_chapter",
+                entity_type: "scene",
+                entity_id: scene_id.clone(),
+                details_json: "",
+            });
+
+            db.move_scene_to_chapter(scene_id, chapter_id).await
+        }
+
+        DbAction::MoveSceneToChapterAt { scene_id, chapter_id, new_index, .. } => {
+            audit = Some(AuditSpec {
+                action: "move_scene_to_chapter",
+                entity_type: "scene",
+                entity_id: scene_id.clone(),
+                details_json: "",
+            });
+
+            db.move_scene_to_chapter_at(scene_id, chapter_id, new_index).await
+        }
+
+        // Preferencia de UI: sin audit (no es contenido del usuario)
+        DbAction::SaveOutlineExpansion { universe_id, novel_ids, chapter_ids } => {
+            db.save_outline_expansion(universe_id, novel_ids, chapter_ids)
+                .await
         }
 
         DbAction::ReorderScene(scene_id, new_position) => {
@@ -333,7 +627,6 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
 
             db.reorder_scene(scene_id, new_position)
                 .await
-                .map_err(|e| e.to_string())
         }
 
         // -----------------------------
@@ -343,6 +636,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
         // - Trash ya tiene lógica propia (y típicamente es transaccional).
         // - Para evitar doble-log o inconsistencias, no metemos audit aquí.
         DbAction::MoveToTrash {
+            trash_id,
             target_type,
             target_id,
             display_name,
@@ -352,6 +646,7 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
             payload_json,
         } => {
             db.move_to_trash_and_delete(
+                &trash_id,
                 &target_type,
                 &target_id,
                 &display_name,
@@ -362,24 +657,76 @@ pub async fn execute(db: Database, action: DbAction) -> Result<(), String> {
             )
                 .await
                 .map(|_| ())
-                .map_err(|e| e.to_string())
         }
 
         DbAction::RestoreFromTrash(entry_id) => {
-            db.restore_from_trash(&entry_id).await.map_err(|e| e.to_string())
+            db.restore_from_trash(&entry_id).await
         }
 
         DbAction::PermanentDelete(entry_id) => {
-            db.permanent_delete(&entry_id).await.map_err(|e| e.to_string())
+            db.permanent_delete(&entry_id).await
         }
 
-        DbAction::EmptyTrash => db.empty_trash().await.map_err(|e| e.to_string()),
+        DbAction::EmptyTrash => db.empty_trash().await,
 
-        DbAction::CleanupOldTrash => db
-            .cleanup_old_trash(14)
+        DbAction::CleanupOldTrash(days) => db
+            .cleanup_old_trash(days)
             .await
-            .map_err(|e| e.to_string())
             .map(|_| ()),
+
+        DbAction::SetTrashRetention(retention) => {
+            audit = Some(AuditSpec {
+                action: "set_trash_retention",
+                entity_type: "project",
+                entity_id: retention.0.to_string(),
+                details_json: "",
+            });
+
+            db.set_trash_retention(retention).await
+        }
+
+        DbAction::SetUiTheme(mode) => {
+            audit = Some(AuditSpec {
+                action: "set_ui_theme",
+                entity_type: "project",
+                entity_id: mode.key().to_string(),
+                details_json: "",
+            });
+
+            db.set_ui_theme(mode).await
+        }
+
+        // Preferencia de UI: sin audit (igual que la expansión del outline)
+        DbAction::TouchRecentItem { kind, target_id } => db
+            .touch_recent_item(kind, target_id)
+            .await,
+
+        DbAction::SetCapability { key, enabled } => {
+            audit = Some(AuditSpec {
+                action: if enabled { "enable_capability" } else { "disable_capability" },
+                entity_type: "project",
+                entity_id: key.clone(),
+                details_json: "",
+            });
+
+            db.set_capability(&key, enabled).await.map(|_| ())
+        }
+
+        // -----------------------------
+        // MAINTENANCE
+        // -----------------------------
+        DbAction::BulkReplaceText { universe_id, from, to, scopes } => {
+            audit = Some(AuditSpec {
+                action: "bulk_replace_text",
+                entity_type: "universe",
+                entity_id: universe_id.clone(),
+                details_json: "",
+            });
+
+            db.bulk_replace_text(universe_id, from, to, scopes)
+                .await
+                .map(|n| crate::logger::info(&format!("🔁 bulk_replace_text: {} filas", n)))
+        }
     };
 
     // Hook de auditoría SOLO si la acción fue exitosa.
@@ -0,0 +1,61 @@
+use crate::app::{AppState, GlossaryMessage, GlossaryEditor};
+use crate::model::GlossaryTerm;
+use crate::state::{DbAction, ToastKind};
+use uuid::Uuid;
+
+pub fn update(state: &mut AppState, message: GlossaryMessage) {
+    match message {
+        GlossaryMessage::Open(universe_id) => {
+            state.glossary_editor = None;
+            state.glossary_search.clear();
+            state.route = crate::app::Route::Glossary { universe_id };
+        }
+        GlossaryMessage::EditorOpenCreate => {
+            state.glossary_editor = Some(GlossaryEditor::create_new());
+        }
+        GlossaryMessage::Edit(id) => {
+            if let Some(g) = state.glossary.iter().find(|g| g.id == id) {
+                state.glossary_editor = Some(GlossaryEditor::from_term(g));
+            }
+        }
+        GlossaryMessage::EditorCancel => state.glossary_editor = None,
+
+        GlossaryMessage::EditorSave => {
+            if let Some(editor) = state.glossary_editor.take() {
+                if !editor.term.trim().is_empty() {
+                    let universe_id = match &state.route { crate::app::Route::Glossary { universe_id } => universe_id.clone(), _ => return };
+                    let term = GlossaryTerm {
+                        id: editor.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        universe_id,
+                        term: editor.term.trim().to_string(),
+                        definition: editor.definition.text(),
+                        pronunciation: editor.pronunciation.trim().to_string(),
+                    };
+
+                    // Optimistic: reflejar ya en la lista (el refetch post-save confirma)
+                    match state.glossary.iter_mut().find(|g| g.id == term.id) {
+                        Some(existing) => *existing = term.clone(),
+                        None => state.glossary.push(term.clone()),
+                    }
+
+                    state.show_toast(format!("Term '{}' saved", term.term), ToastKind::Success);
+                    state.queue(DbAction::SaveGlossaryTerm(term));
+                } else {
+                    state.glossary_editor = Some(editor);
+                    state.show_toast("Term cannot be empty", ToastKind::Error);
+                }
+            }
+        }
+
+        GlossaryMessage::Delete(id) => {
+            state.pending_confirm = Some(crate::state::ConfirmAction::DeleteGlossaryTerm(id));
+        }
+
+        GlossaryMessage::TermChanged(v) => if let Some(e) = state.glossary_editor.as_mut() { e.term = v },
+        GlossaryMessage::PronunciationChanged(v) => if let Some(e) = state.glossary_editor.as_mut() { e.pronunciation = v },
+        GlossaryMessage::DefinitionChanged(action) => if let Some(e) = state.glossary_editor.as_mut() { e.definition.perform(action) },
+
+        GlossaryMessage::SearchChanged(v) => state.glossary_search = v,
+        GlossaryMessage::ToggleSortOrder => state.glossary_sort_desc = !state.glossary_sort_desc,
+    }
+}
@@ -12,6 +12,11 @@ const DRAG_CURSOR_THROTTLE_MS: u128 = 16;
 ///
 /// Nota: ahora recibe &mut AppState porque aplicamos throttle con estado del drag.
 pub fn translate_global_event(state: &mut AppState, message: &Message) -> Option<Message> {
+    // Atajos: el modal de confirmación también existe sin proyecto abierto (ver keymap)
+    if let Message::Shortcut(shortcut) = message {
+        return crate::keymap::resolve(state, *shortcut);
+    }
+
     // Only relevant when a project is open.
     if state.active_project.is_none() {
         return None;
@@ -16,6 +16,23 @@ pub fn update(state: &mut AppState, message: LocationsMessage) {
         LocationsMessage::ToggleExpand(id) => {
             if state.expanded_locations.contains(&id) { state.expanded_locations.remove(&id); } else { state.expanded_locations.insert(id); }
         }
+        LocationsMessage::ExpandAll => {
+            // Solo nodos con hijos (expandir una hoja no cambia nada)
+            let with_children: Vec<String> = state
+                .locations_children_map
+                .iter()
+                .filter(|(_, children)| !children.is_empty())
+                .filter_map(|(parent, _)| parent.clone())
+                .collect();
+            state.expanded_locations.extend(with_children);
+        }
+        LocationsMessage::CollapseAll => {
+            state.expanded_locations.clear();
+            // La selección sigue visible
+            if let Some(selected) = state.selected_location.clone() {
+                state.expand_location_ancestors(&selected);
+            }
+        }
         LocationsMessage::Select(id) => {
             state.selected_location = Some(id.clone());
             let now = Instant::now();
@@ -39,6 +56,21 @@ pub fn update(state: &mut AppState, message: LocationsMessage) {
             if let Some(editor) = state.location_editor.take() {
                 if !editor.name.trim().is_empty() {
                     let universe_id = match &state.route { crate::app::Route::Locations { universe_id } => universe_id.clone(), _ => return };
+                    if let Some(id) = editor.id.as_deref()
+                        && state.location_would_cycle(id, editor.parent_id.as_deref())
+                    {
+                        state.location_editor = Some(editor);
+                        state.show_toast("Cannot move a location inside itself or one of its sub-locations", ToastKind::Error);
+                        return;
+                    }
+                    let image_path = match crate::editors::image_path_for_save(&editor.image_path) {
+                        Ok(p) => p,
+                        Err(msg) => {
+                            state.location_editor = Some(editor);
+                            state.show_toast(msg, ToastKind::Error);
+                            return;
+                        }
+                    };
                     let loc = Location {
                         id: editor.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
                         universe_id: universe_id.clone(),
@@ -46,9 +78,18 @@ pub fn update(state: &mut AppState, message: LocationsMessage) {
                         name: editor.name,
                         description: editor.description.text(),
                         kind: editor.kind,
+                        image_path,
                     };
                     if let Some(pid) = &loc.parent_id { state.expanded_locations.insert(pid.clone()); }
 
+                    // Reparent optimista: el árbol se re-arma ya (el reload confirma)
+                    if let Some(existing) = state.locations.iter_mut().find(|l| l.id == loc.id)
+                        && existing.parent_id != loc.parent_id
+                    {
+                        existing.parent_id = loc.parent_id.clone();
+                        state.rebuild_locations_cache();
+                    }
+
                     state.queue(DbAction::SaveLocation(loc));
                     state.show_toast("Location saved", ToastKind::Success);
                 } else {
@@ -64,6 +105,8 @@ pub fn update(state: &mut AppState, message: LocationsMessage) {
 
         LocationsMessage::NameChanged(v) => if let Some(e) = state.location_editor.as_mut() { e.name = v },
         LocationsMessage::KindChanged(v) => if let Some(e) = state.location_editor.as_mut() { e.kind = v },
+        LocationsMessage::ParentChanged(v) => if let Some(e) = state.location_editor.as_mut() { e.parent_id = v },
+        LocationsMessage::ImagePathChanged(v) => if let Some(e) = state.location_editor.as_mut() { e.image_path = v },
         LocationsMessage::DescriptionChanged(action) => if let Some(e) = state.location_editor.as_mut() { e.description.perform(action) },
     }
 }
\ No newline at end of file
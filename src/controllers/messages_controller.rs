@@ -4,8 +4,10 @@ use crate::app::{AppState, Message};
 use crate::controllers::{
     bestiary_controller,
     locations_controller,
+    glossary_controller,
     navigation_controller,
     pm_controller,
+    settings_controller,
     the_forge_controller,
     timeline_controller,
     universe_controller,
@@ -40,6 +42,18 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             return tasks;
         }
 
+        navigation_controller::NavigationResult::FeatureDisabled { attempted, capability } => {
+            crate::logger::warn(&format!(
+                "🚫 Navegación denegada: capability '{}' apagada | attempted={:?}",
+                capability, attempted
+            ));
+            state.show_toast(
+                format!("Feature disabled in this workspace: {} (enable it in Settings)", capability),
+                ToastKind::Error,
+            );
+            return tasks;
+        }
+
         navigation_controller::NavigationResult::NotHandled => {
             // Continúa al match de módulos
         }
@@ -51,19 +65,38 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
         // Delegate module-specific messages (canonical names)
         Message::Pm(msg) => pm_controller::update(state, msg),
         Message::Bestiary(msg) => bestiary_controller::update(state, msg),
-        Message::Universe(msg) => universe_controller::update(state, msg),
+        Message::Universe(msg) => {
+            let was_renaming = state.snapshot_renaming.is_some();
+            universe_controller::update(state, msg);
+            // Recién entró a rename de snapshot: foco al input inline
+            if !was_renaming && state.snapshot_renaming.is_some() {
+                tasks.push(iced::widget::operation::focus::<Message>(iced::widget::Id::new("snapshot_rename")));
+            }
+        }
         Message::Locations(msg) => locations_controller::update(state, msg),
+        Message::Glossary(msg) => glossary_controller::update(state, msg),
         Message::Timeline(msg) => timeline_controller::update(state, msg),
+        Message::Settings(msg) => settings_controller::update(state, msg),
 
         Message::TheForge(msg) => {
             if let Some(t) = the_forge_controller::update(state, msg) {
                 tasks.push(t);
             }
         }
+        Message::ImageFileDropped(path) => {
+            let path = path.to_string_lossy().to_string();
+            if let Some(e) = state.creature_editor.as_mut() {
+                e.image_path = path;
+            } else if let Some(e) = state.location_editor.as_mut() {
+                e.image_path = path;
+            }
+        }
+
         // Global Mouse Events (Delegated to controllers that need Drag&Drop)
         Message::MouseMoved(p) => {
             pm_controller::handle_mouse_moved(state, p);
-            // (Forge no necesita MouseMoved si usamos hover por mouse_area)
+            // Forge: solo umbral de activación (el destino llega por hover de mouse_area)
+            the_forge_controller::handle_mouse_moved(state, p);
         }
 
         Message::MouseReleased => {
@@ -92,6 +125,16 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             }
         }
 
+        Message::ForgeOrphanScenesFetched(result) => match result {
+            Ok(v) => state.forge_orphan_scenes = v,
+            Err(e) => crate::logger::error(&format!("❌ Fetch orphan scenes failed: {}", e)),
+        },
+
+        Message::ProjectWordTotalFetched(result) => match result {
+            Ok(total) => state.project_word_total = Some(total),
+            Err(e) => crate::logger::error(&format!("❌ Fetch project word total failed: {}", e)),
+        },
+
         // Boards list
         Message::BoardsFetched(result) => {
             state
@@ -155,9 +198,11 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             });
 
             // Guard de ruta actual (para evitar aplicar resultados viejos)
+            // Locations también las consume (badges de criaturas por nodo)
             let still_relevant = matches!(
                 &state.route,
-                crate::app::Route::Bestiary { universe_id: uid } if uid == &universe_id
+                crate::app::Route::Bestiary { universe_id: uid }
+                    | crate::app::Route::Locations { universe_id: uid } if uid == &universe_id
             );
 
             match result {
@@ -167,11 +212,14 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
 
                         // ✅ REFACTOR A.3: Rebuild index after loading
                         state.rebuild_creatures_index();
+                        state.rebuild_location_counts();
 
                         state.loaded_creatures_universe = Some(universe_id.clone());
                         state
                             .core_creatures_loaded_for
                             .insert(universe_id, std::time::Instant::now());
+
+                        apply_pending_definition_open(state);
                     } else {
                         // Out-of-order: no aplicamos data, pero NO bloqueamos recargas futuras
                         crate::logger::warn(&format!(
@@ -194,6 +242,148 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             }
         }
 
+        // Solo si sigue siendo el último pedido (el usuario pudo abrir otra criatura)
+        Message::CreatureMentionsFetched { creature_id, result } if state.creature_mentions_for.as_ref() == Some(&creature_id) => {
+            match result {
+                Ok(v) => state.creature_mentions = v,
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch creature mentions failed ({}): {}", creature_id, e));
+                    state.creature_mentions.clear();
+                }
+            }
+        }
+
+        Message::TimelineSceneRefsFetched { universe_id, result } if state.timeline_scene_refs_for.as_ref() == Some(&universe_id) => {
+            match result {
+                Ok(v) => state.timeline_scene_refs = v,
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch timeline scene refs failed ({}): {}", universe_id, e));
+                    state.timeline_scene_refs.clear();
+                }
+            }
+        }
+
+        Message::CreatureRelationsFetched { universe_id, result } if state.creature_relations_for.as_ref() == Some(&universe_id) => {
+            match result {
+                Ok(v) => state.creature_relations = v,
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch creature relations failed ({}): {}", universe_id, e));
+                    state.creature_relations.clear();
+                }
+            }
+        }
+
+        Message::ArchivedCardsFetched { board_id, result } if state.pm_archived_for.as_ref() == Some(&board_id) => {
+            match result {
+                Ok(v) => state.pm_archived_cards = v,
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch archived cards failed ({}): {}", board_id, e));
+                    state.pm_archived_cards.clear();
+                }
+            }
+        }
+
+        Message::BoardTemplatesFetched(result) => match result {
+            Ok(v) => state.board_templates = v,
+            Err(e) => {
+                crate::logger::error(&format!("❌ Fetch board templates failed: {}", e));
+                state.board_templates.clear();
+            }
+        },
+
+        Message::SceneRevisionsFetched { scene_id, result } if state.forge_revisions_for.as_ref() == Some(&scene_id) => {
+            match result {
+                Ok(v) => {
+                    // La preview se suelta si la revisión se podó
+                    if let Some(sel) = state.forge_revision_selected.as_ref()
+                        && !v.iter().any(|r| &r.id == sel)
+                    {
+                        state.forge_revision_selected = None;
+                    }
+                    state.forge_revisions = v;
+                }
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch scene revisions failed ({}): {}", scene_id, e));
+                    state.forge_revisions.clear();
+                }
+            }
+        }
+
+        Message::ForgeSearchFetched { novel_id, query, result } => match result {
+            // Si el usuario siguió tipeando, este resultado ya no aplica
+            Ok(hits) if query == state.forge_search_query.trim() => {
+                state.forge_search_results = hits;
+                state.forge_search_novel_id = Some(novel_id);
+            }
+            Ok(_) => state.debug_record_ignored("ForgeSearch stale (query changed)"),
+            Err(e) => {
+                crate::logger::error(&format!("❌ Forge search failed: {}", e));
+                state.show_toast(format!("Search failed: {}", e), ToastKind::Error);
+            }
+        },
+
+        Message::DefinitionLookedUp { word, result } => match result {
+            Ok(Some(hit)) => {
+                match &hit {
+                    crate::model::DefinitionHit::GlossaryTerm { universe_id, .. } => {
+                        glossary_controller::update(state, crate::app::GlossaryMessage::Open(universe_id.clone()));
+                    }
+                    crate::model::DefinitionHit::Creature { universe_id, .. } => {
+                        bestiary_controller::update(state, crate::app::BestiaryMessage::Open(universe_id.clone()));
+                    }
+                }
+                // Si la lista ya estaba cargada se abre ya; si no, al llegar el fetch
+                state.pending_definition_open = Some(hit);
+                apply_pending_definition_open(state);
+            }
+            Ok(None) => {
+                state.show_toast(format!("No glossary term or creature named '{}'", word), ToastKind::Info);
+            }
+            Err(e) => {
+                crate::logger::error(&format!("❌ Definition lookup failed ('{}'): {}", word, e));
+                state.show_toast(format!("Lookup failed: {}", e), ToastKind::Error);
+            }
+        },
+
+        // Glossary
+        Message::GlossaryFetched { universe_id, result } => {
+            state
+                .core_loading_in_progress
+                .remove(&crate::state::CoreLoadKey::Glossary {
+                    universe_id: universe_id.clone(),
+                });
+
+            let still_relevant = matches!(
+                &state.route,
+                crate::app::Route::Glossary { universe_id: uid } if uid == &universe_id
+            );
+
+            match result {
+                Ok(v) => {
+                    if still_relevant {
+                        state.glossary = v;
+                        state.loaded_glossary_universe = Some(universe_id.clone());
+                        state
+                            .core_glossary_loaded_for
+                            .insert(universe_id, std::time::Instant::now());
+
+                        apply_pending_definition_open(state);
+                    } else {
+                        crate::logger::warn(&format!(
+                            "⏭️ Ignorando GlossaryFetched out-of-order (uid={})",
+                            universe_id
+                        ));
+                    }
+                }
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Fetch glossary failed (uid={}): {}", universe_id, e));
+                    if still_relevant {
+                        state.show_toast(format!("Action failed: {}", e), ToastKind::Error);
+                    }
+                }
+            }
+        }
+
         // Locations
         Message::LocationsFetched { universe_id, result } => {
             // FASE 9: limpiar gating aunque cambies de ruta antes de que responda
@@ -251,9 +441,12 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                     universe_id: universe_id.clone(),
                 });
 
+            // Overview del universo también consume el timeline (resumen temporal); Locations, los badges
             let still_relevant = matches!(
                 &state.route,
-                crate::app::Route::Timeline { universe_id: uid } if uid == &universe_id
+                crate::app::Route::Timeline { universe_id: uid }
+                    | crate::app::Route::UniverseDetail { universe_id: uid }
+                    | crate::app::Route::Locations { universe_id: uid } if uid == &universe_id
             );
 
             match result {
@@ -263,8 +456,12 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         eras.sort_by_key(|e| e.start_year);
                         events.sort_by_key(|e| e.year);
 
+                        state.timeline_era_overlaps =
+                            crate::controllers::timeline_controller::era_overlap_warnings(&eras);
                         state.timeline_events = events;
                         state.timeline_eras = eras;
+                        state.rebuild_location_counts();
+                        state.rebuild_timeline_layout();
 
                         state.loaded_timeline_universe = Some(universe_id.clone());
                         state
@@ -299,7 +496,20 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
         }
 
         Message::ForgeChaptersFetched { novel_id, result } => {
-            crate::controllers::navigation_controller::handle_forge_chapters_fetched(state, novel_id, result);
+            crate::controllers::navigation_controller::handle_forge_chapters_fetched(state, novel_id.clone(), result);
+
+            // Expand-all en curso: los chapters recién llegados también se abren (y piden sus scenes)
+            if state.forge_expand_all_pending && state.expanded_novels.contains(&novel_id) {
+                let chapter_ids: Vec<String> = state
+                    .chapters_by_novel_id
+                    .get(&novel_id)
+                    .map(|v| v.iter().map(|c| c.id.clone()).collect())
+                    .unwrap_or_default();
+                for chapter_id in chapter_ids {
+                    state.expanded_chapters.insert(chapter_id.clone());
+                    tasks.push(Task::done(Message::ForgeRequestLoadScenes(chapter_id)));
+                }
+            }
         }
 
         Message::ForgeScenesFetched { chapter_id, result } => {
@@ -349,6 +559,24 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             state.integrity_issues = v;
             state.integrity_busy = false;
         }
+        Message::ReplacePreviewFetched { term, result } => {
+            state.replace_preview_busy = false;
+            match result {
+                // Si el usuario cambió el término mientras cargaba, descartamos
+                Ok(rows) if term == state.replace_find => {
+                    if rows.is_empty() {
+                        state.show_toast("No matches found", ToastKind::Info);
+                    }
+                    state.replace_preview = rows;
+                    state.replace_preview_term = Some(term);
+                }
+                Ok(_) => state.debug_record_ignored("ReplacePreview stale (term changed)"),
+                Err(e) => {
+                    crate::logger::error(&format!("❌ ReplacePreview failed: {}", e));
+                    state.show_toast(format!("Action failed: {}", e), ToastKind::Error);
+                }
+            }
+        }
         // Errors (canonical grouping) — NO incluye core fetches (ya se manejan arriba con gating release)
         Message::SchemaVersionFetched(Err(e))
         | Message::IntegrityFetched(Err(e)) => {
@@ -359,9 +587,19 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             let now = std::time::Instant::now();
             state.toasts
                 .retain(|t| now.duration_since(t.created_at).as_secs() < t.ttl_secs);
+            the_forge_controller::check_break_reminder(state);
         }
 
         Message::ToastDismiss(id) => state.toasts.retain(|t| t.id != id),
+        Message::ToastAction(id) => {
+            // Si ya expiró no hay nada que hacer (el botón desaparece con el toast)
+            if let Some(pos) = state.toasts.iter().position(|t| t.id == id) {
+                let toast = state.toasts.remove(pos);
+                if let Some((_, action)) = toast.action {
+                    tasks.push(Task::done(action));
+                }
+            }
+        }
         Message::ConfirmDelete => {
             if let Some(action) = state.pending_confirm.take() {
                 match action {
@@ -369,6 +607,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         if let Some(universe) = state.universes.iter().find(|u| u.id == id) {
                             let payload = serde_json::to_string(universe).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "universe".to_string(),
                                 target_id: id.clone(),
                                 display_name: universe.name.clone(),
@@ -383,6 +622,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         if let Some(board) = state.boards_list.iter().find(|b| b.id == id) {
                             let payload = serde_json::to_string(board).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "board".to_string(),
                                 target_id: id.clone(),
                                 display_name: board.name.clone(),
@@ -405,6 +645,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
 
                             // 1) DB: ahora sí se encola porque el novel TODAVÍA existe en state
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "novel".to_string(),
                                 target_id: id.clone(),
                                 display_name: novel.title.clone(),
@@ -474,6 +715,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
 
                             // 1) DB
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "chapter".to_string(),
                                 target_id: id.clone(),
                                 display_name: chapter.title.clone(),
@@ -560,11 +802,21 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                             }
                         }
 
+                        // Último fallback: escena huérfana (su chapter ya no existe)
+                        if found_scene.is_none()
+                            && let Some(scene) = state.forge_orphan_scenes.iter().find(|s| s.id == id)
+                        {
+                            found_scene = Some(scene.clone());
+                            found_chapter_id = Some(scene.chapter_id.clone());
+                        }
+                        state.forge_orphan_scenes.retain(|s| s.id != id);
+
                         if let (Some(scene), Some(chapter_id)) = (found_scene, found_chapter_id) {
                             let payload = serde_json::to_string(&scene).unwrap_or_default();
 
                             // 1) DB
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "scene".to_string(),
                                 target_id: id.clone(),
                                 display_name: scene.title.clone(),
@@ -621,10 +873,40 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         }
                     }
 
+                    ConfirmAction::MergeNovels { keep_id, merge_id } => {
+                        // UI optimista: chapters del origen al final del destino (mismo renumerado que DB)
+                        let mut moved = state.chapters_by_novel_id.remove(&merge_id).unwrap_or_default();
+                        moved.sort_by_key(|c| c.position);
+                        let keep_list = state.chapters_by_novel_id.entry(keep_id.clone()).or_default();
+                        let first_pos = keep_list.iter().map(|c| c.position).max().unwrap_or(-1) + 1;
+                        for (i, mut ch) in moved.into_iter().enumerate() {
+                            ch.novel_id = keep_id.clone();
+                            ch.position = first_pos + i as i64;
+                            keep_list.push(ch);
+                        }
+
+                        state.novels.retain(|n| n.id != merge_id);
+                        state.expanded_novels.remove(&merge_id);
+                        if state.active_novel_id.as_deref() == Some(merge_id.as_str()) {
+                            state.active_novel_id = Some(keep_id.clone());
+                        }
+                        if state.active_novel_id.as_deref() == Some(keep_id.as_str()) {
+                            state.active_novel_chapters = state.chapters_by_novel_id.get(&keep_id).cloned().unwrap_or_default();
+                        }
+                        state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+                        state.queue(DbAction::MergeNovels { keep_id, merge_id });
+                    }
+
+                    ConfirmAction::PurgeOldTrash(days) => {
+                        state.queue(DbAction::CleanupOldTrash(days));
+                    }
+
                     ConfirmAction::DeleteCreature(id) => {
                         if let Some(creature) = state.creatures.iter().find(|c| c.id == id) {
                             let payload = serde_json::to_string(creature).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "creature".to_string(),
                                 target_id: id.clone(),
                                 display_name: creature.name.clone(),
@@ -640,6 +922,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         if let Some(location) = state.locations.iter().find(|l| l.id == id) {
                             let payload = serde_json::to_string(location).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "location".to_string(),
                                 target_id: id.clone(),
                                 display_name: location.name.clone(),
@@ -651,10 +934,29 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         }
                     }
 
+                    ConfirmAction::DeleteGlossaryTerm(id) => {
+                        if let Some(term) = state.glossary.iter().find(|g| g.id == id) {
+                            let payload = serde_json::to_string(term).unwrap_or_default();
+                            state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
+                                target_type: "glossary_term".to_string(),
+                                target_id: id.clone(),
+                                display_name: term.term.clone(),
+                                display_info: Some(term.pronunciation.clone()).filter(|p| !p.is_empty()),
+                                parent_type: Some("universe".to_string()),
+                                parent_id: Some(term.universe_id.clone()),
+                                payload_json: payload,
+                            });
+                            // Optimistic: el fetch posterior confirma
+                            state.glossary.retain(|g| g.id != id);
+                        }
+                    }
+
                     ConfirmAction::DeleteEvent(id) => {
                         if let Some(event) = state.timeline_events.iter().find(|e| e.id == id) {
                             let payload = serde_json::to_string(event).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "event".to_string(),
                                 target_id: id.clone(),
                                 display_name: event.title.clone(),
@@ -670,6 +972,7 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
                         if let Some(era) = state.timeline_eras.iter().find(|e| e.id == id) {
                             let payload = serde_json::to_string(era).unwrap_or_default();
                             state.queue(DbAction::MoveToTrash {
+                                trash_id: uuid::Uuid::new_v4().to_string(),
                                 target_type: "era".to_string(),
                                 target_id: id.clone(),
                                 display_name: era.name.clone(),
@@ -697,11 +1000,40 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             state.queue(DbAction::RestoreFromTrash(entry_id));
         }
 
+        Message::RestoreAndOpen(entry_id) => {
+            // El destino se toma ahora (la entrada desaparece de la lista al restaurar);
+            // action_done navega solo si el restore terminó OK
+            if let Some(entry) = state.trash_entries.iter().find(|e| e.id == entry_id) {
+                state.pending_restore_open.insert(
+                    entry_id.clone(),
+                    crate::state::RestoreOpenTarget {
+                        target_type: entry.target_type.clone(),
+                        target_id: entry.target_id.clone(),
+                        parent_id: entry.parent_id.clone(),
+                    },
+                );
+            }
+            state.queue(DbAction::RestoreFromTrash(entry_id));
+        }
+
         Message::PermanentDelete(entry_id) => {
             state.queue(DbAction::PermanentDelete(entry_id));
         }
 
         Message::EmptyTrash => {
+            // Un auto-snapshot por cada universo (vivo) con cosas en la papelera
+            let mut universe_ids: Vec<String> = state
+                .trash_entries
+                .iter()
+                .filter(|e| e.parent_type.as_deref() == Some("universe"))
+                .filter_map(|e| e.parent_id.clone())
+                .filter(|uid| state.universes.iter().any(|u| u.id == *uid))
+                .collect();
+            universe_ids.sort();
+            universe_ids.dedup();
+            for uid in universe_ids {
+                state.queue_auto_snapshot(&uid, "empty-trash");
+            }
             state.queue(DbAction::EmptyTrash);
         }
 
@@ -713,6 +1045,42 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             state.trash_search_query = query;
         }
 
+        Message::PaletteToggle if state.command_palette.is_some() => state.command_palette = None,
+        Message::PaletteToggle => {
+            state.command_palette = Some(crate::command_palette::PaletteState::default());
+            tasks.push(iced::widget::operation::focus::<Message>(iced::widget::Id::new("command_palette")));
+        }
+
+        Message::PaletteClose => state.command_palette = None,
+
+        Message::PaletteQueryChanged(query) => {
+            if let Some(palette) = state.command_palette.as_mut() {
+                palette.query = query;
+                palette.selected = 0;
+            }
+        }
+
+        Message::PaletteMove(delta) => {
+            if let Some(palette) = state.command_palette.as_ref() {
+                let count = crate::command_palette::matches(state, &palette.query).len();
+                if count > 0 {
+                    let next = (palette.selected as i64 + delta as i64).rem_euclid(count as i64) as usize;
+                    if let Some(palette) = state.command_palette.as_mut() {
+                        palette.selected = next;
+                    }
+                }
+            }
+        }
+
+        Message::PaletteSubmit => tasks.extend(run_palette_command(state, None)),
+        Message::PaletteRun(index) => tasks.extend(run_palette_command(state, Some(index))),
+
+        Message::Undo => crate::controllers::undo_controller::apply(state, true),
+        Message::Redo => crate::controllers::undo_controller::apply(state, false),
+
+        Message::TrashTypeFilterChanged(kind) => state.trash_type_filter = kind,
+        Message::TrashSortChanged(sort) => state.trash_sort = sort,
+
         Message::ToggleTrashSelection(id) => {
             if state.trash_selected.contains(&id) {
                 state.trash_selected.remove(&id);
@@ -738,6 +1106,45 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
             }
         }
 
+        Message::RestoreAllOfType(kind) => {
+            let mut entries: Vec<&crate::model::TrashEntry> =
+                state.trash_entries.iter().filter(|e| e.target_type == kind).collect();
+            if entries.is_empty() {
+                return tasks;
+            }
+
+            // Padres antes que hijos: una location hija cuyo padre también está en la papelera
+            // va después de él; a igual profundidad, lo más viejo primero
+            let depth = |entry: &crate::model::TrashEntry| {
+                let mut depth = 0usize;
+                let mut parent = entry.parent_id.as_deref();
+                while let Some(pid) = parent {
+                    let Some(p) = entries.iter().find(|e| e.target_id == pid) else { break };
+                    depth += 1;
+                    if depth > entries.len() {
+                        break; // ciclo en datos viejos: no colgarse
+                    }
+                    parent = p.parent_id.as_deref();
+                }
+                depth
+            };
+            let mut ranked: Vec<(usize, &crate::model::TrashEntry)> =
+                entries.iter().map(|e| (depth(e), *e)).collect();
+            ranked.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.deleted_at.cmp(&b.1.deleted_at)));
+            entries = ranked.into_iter().map(|(_, e)| e).collect();
+
+            let ids: Vec<String> = entries.iter().map(|e| e.id.clone()).collect();
+            for id in &ids {
+                state.trash_selected.remove(id);
+            }
+
+            // Si ya había un lote en curso, se suma al mismo toast
+            state.trash_restore_batch.get_or_insert((0, 0));
+            for id in ids {
+                state.queue(DbAction::RestoreFromTrash(id));
+            }
+        }
+
         Message::DeleteSelectedForever => {
             let ids: Vec<String> = state.trash_selected.drain().collect();
             for id in ids {
@@ -746,7 +1153,117 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
         }
 
         Message::CleanupOldTrash => {
-            state.queue(DbAction::CleanupOldTrash);
+            if state.trash_retention.is_never() {
+                state.show_toast("Trash retention is set to Never", ToastKind::Info);
+            } else {
+                state.queue(DbAction::CleanupOldTrash(state.trash_retention.0));
+            }
+        }
+
+        Message::TrashRetentionFetched(result) => match result {
+            Ok(retention) => {
+                if retention != state.trash_retention {
+                    state.trash_retention = retention;
+                    // El preview se pidió con el corte por defecto
+                    state.trash_loaded = false;
+                }
+            }
+            Err(e) => crate::logger::warn(&format!("⚠️ Trash retention load failed: {}", e)),
+        },
+
+        Message::CapabilitiesFetched(caps) => {
+            state.capabilities = Some(caps);
+            // Se apagó la feature de la pantalla actual: salir a Overview
+            if !navigation_controller::route_enabled(state, &state.route) {
+                state.route = crate::app::Route::Overview;
+            }
+        }
+
+        Message::RecentItemsFetched(result) => match result {
+            Ok(items) => state.recent_items = items,
+            Err(e) => crate::logger::warn(&format!("⚠️ Recent items load failed: {}", e)),
+        },
+
+        Message::UiThemeFetched(result) => match result {
+            Ok(mode) => state.ui_theme = mode,
+            Err(e) => crate::logger::warn(&format!("⚠️ UI theme load failed: {}", e)),
+        },
+
+        Message::TrashCleanupPreviewFetched(Ok(entries)) => {
+            state.trash_cleanup_preview = entries;
+        }
+
+        Message::TrashCleanupPreviewFetched(Err(e)) => {
+            crate::logger::warn(&format!("⚠️ Trash cleanup preview failed: {}", e));
+            state.trash_cleanup_preview.clear();
+        }
+
+        Message::DraftRecoveryScanned(Ok(drafts)) => {
+            if !drafts.is_empty() {
+                crate::logger::info(&format!("🧩 {} unsaved scene draft(s) found", drafts.len()));
+            }
+            state.forge_recoverable_drafts = drafts;
+        }
+
+        Message::DraftRecoveryScanned(Err(e)) => {
+            crate::logger::warn(&format!("⚠️ Draft recovery scan failed: {}", e));
+        }
+
+        Message::ForgeManuscriptScenesFetched { novel_id, result } => match result {
+            Ok(scenes) => {
+                // Solo completa chapters que no estaban: las listas locales pueden tener edits sin guardar
+                let chapter_ids: Vec<String> = state
+                    .chapters_by_novel_id
+                    .get(&novel_id)
+                    .map(|v| v.iter().map(|c| c.id.clone()).collect())
+                    .unwrap_or_default();
+                let mut by_chapter: std::collections::HashMap<String, Vec<crate::model::Scene>> =
+                    std::collections::HashMap::new();
+                for scene in scenes {
+                    by_chapter.entry(scene.chapter_id.clone()).or_default().push(scene);
+                }
+                for chapter_id in chapter_ids {
+                    if let std::collections::hash_map::Entry::Vacant(slot) = state.scenes_by_chapter_id.entry(chapter_id) {
+                        let list = by_chapter.remove(slot.key()).unwrap_or_default();
+                        slot.insert(list);
+                    }
+                }
+                state.forge_cache_generation = state.forge_cache_generation.wrapping_add(1);
+            }
+            Err(e) => {
+                crate::logger::warn(&format!("⚠️ Manuscript scenes load failed: {}", e));
+            }
+        },
+
+        // Respuesta vieja (el usuario ya cambió de universo): la ignora
+        Message::OutlineExpansionLoaded { universe_id, result } if state.forge_expansion_universe.as_ref() == Some(&universe_id) => {
+            match result {
+                Ok((novel_ids, chapter_ids)) => {
+                    // Base para comparar: solo se reescribe si la unión difiere de lo guardado
+                    state.forge_expansion_saved = (
+                        novel_ids.iter().cloned().collect(),
+                        chapter_ids.iter().cloned().collect(),
+                    );
+
+                    // Unión: lo que el usuario ya haya abierto mientras cargaba se respeta
+                    for novel_id in novel_ids {
+                        tasks.push(Task::done(Message::ForgeRequestLoadChapters(novel_id.clone())));
+                        state.expanded_novels.insert(novel_id);
+                    }
+                    for chapter_id in chapter_ids {
+                        tasks.push(Task::done(Message::ForgeRequestLoadScenes(chapter_id.clone())));
+                        state.expanded_chapters.insert(chapter_id);
+                    }
+                }
+                Err(e) => {
+                    crate::logger::warn(&format!("⚠️ Outline expansion load failed: {}", e));
+                }
+            }
+            state.forge_expansion_ready = true;
+        }
+
+        Message::PurgeOldTrashPrompt if !state.trash_cleanup_preview.is_empty() => {
+            state.pending_confirm = Some(ConfirmAction::PurgeOldTrash(state.trash_retention.preview_days()));
         }
 
         _ => {}
@@ -754,3 +1271,41 @@ pub fn update(state: &mut AppState, message: Message) -> Vec<Task<Message>> {
 
     tasks
 }
+
+/// Abre el editor del término/criatura de un "jump to definition" si su lista ya está
+/// cargada para ese universo. Si no, queda pendiente hasta el próximo *Fetched.
+fn apply_pending_definition_open(state: &mut AppState) {
+    let Some(hit) = state.pending_definition_open.clone() else {
+        return;
+    };
+
+    match hit {
+        crate::model::DefinitionHit::GlossaryTerm { universe_id, term_id } => {
+            if state.loaded_glossary_universe.as_ref() != Some(&universe_id) {
+                return;
+            }
+            state.pending_definition_open = None;
+            glossary_controller::update(state, crate::app::GlossaryMessage::Edit(term_id));
+        }
+        crate::model::DefinitionHit::Creature { universe_id, creature_id } => {
+            if state.loaded_creatures_universe.as_ref() != Some(&universe_id) {
+                return;
+            }
+            state.pending_definition_open = None;
+            if let Some(&index) = state.creatures_index.get(&creature_id)
+                && let Some(c) = state.creatures.get(index)
+            {
+                state.creature_editor = Some(crate::app::CreatureEditor::from_creature(index, c, &state.locations));
+                state.creature_mentions_for = None;
+            }
+        }
+    }
+}
+
+// Se cierra antes de despachar: el comando corre sin la paleta encima
+fn run_palette_command(state: &mut AppState, index: Option<usize>) -> Option<Task<Message>> {
+    let palette = state.command_palette.take()?;
+    let index = index.unwrap_or(palette.selected);
+    let command = crate::command_palette::matches(state, &palette.query).into_iter().nth(index)?;
+    Some(Task::done(command.message))
+}
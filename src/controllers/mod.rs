@@ -11,8 +11,12 @@ pub mod pm_controller;
 pub mod bestiary_controller;
 pub mod universe_controller;
 pub mod locations_controller;
+pub mod glossary_controller;
 pub mod timeline_controller;
 pub mod the_forge_controller;
+pub mod settings_controller;
+pub mod backup_controller;
+pub mod undo_controller;
 
 // ✅ NUEVO (PRO): single source of truth para cargas de The Forge
 pub mod forge_data_controller;
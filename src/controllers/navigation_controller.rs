@@ -1,20 +1,78 @@
 use crate::app::{AppState, Message, Route};
+use crate::messages::{BestiaryMessage, GlossaryMessage, LocationsMessage, PmMessage, TheForgeMessage, TimelineMessage, UniverseMessage};
 use crate::model::{Chapter, Novel, Scene};
-use crate::state::ToastKind;
+use crate::state::{DbAction, ToastKind};
 
 // ✅ NUEVO
 use crate::controllers::forge_data_controller;
 
 // --- NAVIGATION ROUTING ---
 
+// Tope del historial back/forward (lo más viejo se descarta)
+const NAV_HISTORY_LIMIT: usize = 50;
+
 // Resultado explícito de navegación: evita "silencios" y centraliza UX en el dispatcher.
 pub enum NavigationResult {
     NotHandled,
     Handled,
     Denied { attempted: Route, reason: String },
+    // Capability apagada en el proyecto (la ruta existe pero no se entra)
+    FeatureDisabled { attempted: Route, capability: &'static str },
+}
+
+/// Capability que necesita una ruta (None = siempre accesible).
+/// Mismas keys que el sidebar (SidebarSection::capability) para que lo oculto y lo denegado coincidan.
+pub fn route_capability(route: &Route) -> Option<&'static str> {
+    match route {
+        Route::UniverseList | Route::UniverseDetail { .. } | Route::Glossary { .. } => Some("worldbuilding"),
+        Route::Bestiary { .. } => Some("bestiary"),
+        Route::Locations { .. } => Some("locations"),
+        Route::Timeline { .. } => Some("timeline"),
+        Route::PmList | Route::PmBoard { .. } => Some("pm"),
+        Route::Forge => Some("novel"),
+        Route::Trash => Some("trash"),
+        Route::Overview | Route::Workspaces | Route::Assets | Route::Account => None,
+    }
+}
+
+pub fn route_enabled(state: &AppState, route: &Route) -> bool {
+    route_capability(route).is_none_or(|c| state.capability_enabled(c))
+}
+
+/// Ruta a la que lleva un mensaje (Navigate o los Open de cada módulo, que setean state.route
+/// en su controller). Solo importa la variante: el universe_id va vacío.
+pub(crate) fn target_route(message: &Message) -> Option<Route> {
+    let universe_id = String::new();
+    match message {
+        Message::Navigate(route) => Some(route.clone()),
+        Message::Universe(UniverseMessage::Open(_)) => Some(Route::UniverseDetail { universe_id }),
+        Message::Bestiary(BestiaryMessage::Open(_)) => Some(Route::Bestiary { universe_id }),
+        Message::Locations(LocationsMessage::Open(_)) => Some(Route::Locations { universe_id }),
+        Message::Glossary(GlossaryMessage::Open(_)) => Some(Route::Glossary { universe_id }),
+        Message::Timeline(TimelineMessage::Open(_)) | Message::OpenTimeline(_) => Some(Route::Timeline { universe_id }),
+        Message::GoToLocation(..) => Some(Route::Locations { universe_id }),
+        Message::BackToUniverses | Message::BackToUniverse(_) => Some(Route::UniverseList),
+        Message::Pm(PmMessage::OpenBoard(_)) => Some(Route::PmList),
+        Message::TheForge(TheForgeMessage::Open(_) | TheForgeMessage::JumpToNovel { .. }) => Some(Route::Forge),
+        _ => None,
+    }
+}
+
+fn feature_disabled(state: &AppState, route: &Route) -> Option<NavigationResult> {
+    let capability = route_capability(route)?;
+    if state.capability_enabled(capability) {
+        return None;
+    }
+    Some(NavigationResult::FeatureDisabled { attempted: route.clone(), capability })
 }
 
 pub fn try_handle(state: &mut AppState, message: &Message) -> NavigationResult {
+    if let Some(route) = target_route(message)
+        && let Some(denied) = feature_disabled(state, &route)
+    {
+        return denied;
+    }
+
     match message {
         Message::Navigate(route) => {
             crate::logger::info(&format!("🧭 NAVIGATE called: {:?}", route));
@@ -54,6 +112,43 @@ pub fn try_handle(state: &mut AppState, message: &Message) -> NavigationResult {
             NavigationResult::Handled
         }
 
+        Message::NavBack => {
+            if state.nav_index > 0 {
+                // Entrada del historial cuya feature se apagó después: no se vuelve a ella
+                if let Some(denied) = state
+                    .nav_history
+                    .get(state.nav_index - 1)
+                    .and_then(|route| feature_disabled(state, route))
+                {
+                    return denied;
+                }
+                state.nav_index -= 1;
+                if let Some(route) = state.nav_history.get(state.nav_index).cloned() {
+                    crate::logger::info(&format!("🧭 Back to: {:?}", route));
+                    state.route = route;
+                }
+            }
+            NavigationResult::Handled
+        }
+
+        Message::NavForward => {
+            if state.nav_index + 1 < state.nav_history.len() {
+                if let Some(denied) = state
+                    .nav_history
+                    .get(state.nav_index + 1)
+                    .and_then(|route| feature_disabled(state, route))
+                {
+                    return denied;
+                }
+                state.nav_index += 1;
+                if let Some(route) = state.nav_history.get(state.nav_index).cloned() {
+                    crate::logger::info(&format!("🧭 Forward to: {:?}", route));
+                    state.route = route;
+                }
+            }
+            NavigationResult::Handled
+        }
+
         Message::BackToUniverses => {
             crate::logger::info("🧭 Back to Universes");
             state.route = Route::UniverseList;
@@ -88,26 +183,7 @@ pub fn try_handle(state: &mut AppState, message: &Message) -> NavigationResult {
             state.selected_location = Some(location_id.clone());
 
             // Auto-expand tree to ensure the selected location becomes visible.
-            let mut current_search = Some(location_id.clone());
-            let mut safeguard = 0;
-
-            while let Some(curr_id) = current_search {
-                if safeguard > 50 {
-                    break;
-                }
-                safeguard += 1;
-
-                if let Some(loc) = state.locations.iter().find(|l| l.id == curr_id) {
-                    if let Some(parent_id) = &loc.parent_id {
-                        state.expanded_locations.insert(parent_id.clone());
-                        current_search = Some(parent_id.clone());
-                    } else {
-                        current_search = None;
-                    }
-                } else {
-                    current_search = None;
-                }
-            }
+            state.expand_location_ancestors(location_id);
 
             NavigationResult::Handled
         }
@@ -115,6 +191,55 @@ pub fn try_handle(state: &mut AppState, message: &Message) -> NavigationResult {
         _ => NavigationResult::NotHandled,
     }
 }
+/// Registra la ruta actual en el historial si cambió desde la última entrada.
+/// Se llama una vez por update (después de todos los controllers), así cubre tanto
+/// `Navigate` como los controllers que setean `state.route` directo.
+/// Back/Forward dejan la ruta igual a la entrada actual => no se registra nada.
+pub fn record_route(state: &mut AppState) {
+    if state.nav_history.get(state.nav_index) == Some(&state.route) {
+        return;
+    }
+
+    // Navegación nueva: se descarta el "forward"
+    if !state.nav_history.is_empty() {
+        state.nav_history.truncate(state.nav_index + 1);
+    }
+    state.nav_history.push(state.route.clone());
+
+    if state.nav_history.len() > NAV_HISTORY_LIMIT {
+        let overflow = state.nav_history.len() - NAV_HISTORY_LIMIT;
+        state.nav_history.drain(..overflow);
+    }
+    state.nav_index = state.nav_history.len() - 1;
+}
+
+/// Registra en "Recent" el universo / board / novel abierto. Va junto con record_route:
+/// cubre Navigate y los Open de módulo (UniverseMessage::Open, OpenBoard, TheForgeMessage::Open...)
+/// que setean state.route directo. Volver al mismo target tras salir lo sube de nuevo.
+pub fn track_recent(state: &mut AppState) {
+    let current = match &state.route {
+        Route::UniverseDetail { universe_id } => Some(("universe", universe_id.clone())),
+        Route::PmBoard { board_id } => Some(("board", board_id.clone())),
+        Route::Forge => state.active_novel_id.clone().map(|id| ("novel", id)),
+        _ => None,
+    };
+
+    let Some((kind, target_id)) = current else {
+        state.recent_last_opened = None;
+        return;
+    };
+    if state
+        .recent_last_opened
+        .as_ref()
+        .is_some_and(|(k, id)| k == kind && *id == target_id)
+    {
+        return;
+    }
+
+    state.recent_last_opened = Some((kind.to_string(), target_id.clone()));
+    state.queue(DbAction::TouchRecentItem { kind: kind.to_string(), target_id });
+}
+
 // --- FETCH HANDLERS ---
 
 pub fn handle_forge_chapters_fetched(
@@ -125,6 +250,7 @@ pub fn handle_forge_chapters_fetched(
     match result {
         Ok(chapters) => {
             crate::controllers::forge_data_controller::mark_chapters_load_finished(state, novel_id.clone());
+            state.forge_cache_generation = state.forge_cache_generation.wrapping_add(1);
 
             crate::logger::info(&format!(
                 "✅ navigation_controller: Loaded {} chapters (novel {})",
@@ -183,6 +309,7 @@ pub fn handle_forge_chapters_fetched(
 
             // ✅ FASE 12: contrato de navegación (safe fallback)
             ensure_forge_safe_fallback(state);
+            crate::controllers::the_forge_controller::apply_pending_forge_jump(state);
         }
         Err(e) => {
             crate::controllers::forge_data_controller::mark_chapters_load_finished(state, novel_id.clone());
@@ -245,6 +372,7 @@ pub fn handle_novels_fetched(state: &mut AppState, result: Result<Vec<Novel>, St
 
             // ✅ FASE 12: contrato de navegación (safe fallback)
             ensure_forge_safe_fallback(state);
+            crate::controllers::the_forge_controller::apply_pending_forge_jump(state);
         }
         Err(e) => {
             forge_data_controller::mark_novels_load_finished(state);
@@ -459,6 +587,7 @@ pub fn handle_scenes_fetched(
     match result {
         Ok(scenes) => {
             crate::controllers::forge_data_controller::mark_scenes_load_finished(state, chapter_id.clone());
+            state.forge_cache_generation = state.forge_cache_generation.wrapping_add(1);
 
             crate::logger::info(&format!(
                 "✅ navigation_controller: Loaded {} scenes (chapter {})",
@@ -481,6 +610,17 @@ pub fn handle_scenes_fetched(
             let mut merged: Vec<Scene> = by_id.into_values().collect();
             merged.sort_by(|a, b| a.id.cmp(&b.id));
 
+            // 🩹 Self-heal: word_count con drift se corrige al cargar (y se persiste)
+            for scene in merged.iter_mut() {
+                if crate::controllers::the_forge_controller::heal_scene_word_count(scene) {
+                    crate::logger::info(&format!(
+                        "   🩹 Healed word_count for scene {} ({} words)",
+                        scene.id, scene.word_count
+                    ));
+                    state.queue(DbAction::UpdateScene(scene.clone()));
+                }
+            }
+
             state.scenes_by_chapter_id.insert(chapter_id.clone(), merged.clone());
 
             if state.active_chapter_id.as_ref() == Some(&chapter_id) {
@@ -495,6 +635,17 @@ pub fn handle_scenes_fetched(
 
             // ✅ FASE 12: contrato de navegación (safe fallback)
             ensure_forge_safe_fallback(state);
+
+            // Salto pendiente: si este era su chapter y la escena no apareció, abortar
+            if !crate::controllers::the_forge_controller::apply_pending_forge_jump(state)
+                && state
+                    .forge_pending_jump
+                    .as_ref()
+                    .is_some_and(|j| j.chapter_id.as_ref() == Some(&chapter_id))
+            {
+                state.forge_pending_jump = None;
+                state.show_toast("Scene not found (it may have been deleted)", ToastKind::Error);
+            }
         }
         Err(e) => {
             crate::controllers::forge_data_controller::mark_scenes_load_finished(state, chapter_id.clone());
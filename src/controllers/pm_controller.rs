@@ -19,48 +19,71 @@ fn open_create_editor(state: &mut AppState, col_id: &str) {
         title: String::new(),
         description: text_editor::Content::new(),
         priority: "Medium".to_string(),
+        labels: String::new(),
+        due_date: String::new(),
     };
 }
 
-// Edit cuando YA TENÉS Strings (evita to_string extra; mueve ownership)
-fn open_edit_editor_owned(
-    state: &mut AppState,
-    card_id: String,
-    column_id: String,
-    title: String,
-    description: String,
-    priority: String,
-) {
-    let content = text_editor::Content::with_text(&description);
+// Edit cuando YA TENÉS la Card owned (mueve ownership de los Strings)
+fn open_edit_editor_owned(state: &mut AppState, card: crate::model::Card) {
+    let content = text_editor::Content::with_text(&card.description);
+    let due_date = card.due().map(crate::model::format_card_due_date).unwrap_or_default();
     state.pm_state = PmState::Editing {
-        card_id: Some(card_id),
-        column_id,
-        title,
+        card_id: Some(card.id),
+        column_id: card.column_id,
+        title: card.title,
         description: content,
-        priority,
+        priority: card.priority,
+        // "bug,feature" -> "bug, feature" (más cómodo de editar)
+        labels: card.labels.replace(',', ", "),
+        due_date,
     };
 }
 pub fn update(state: &mut AppState, message: PmMessage) {
     match message {
         PmMessage::BoardNameChanged(name) => state.new_board_name = name,
 
+        PmMessage::BoardTemplatePicked(template_id) => state.new_board_template_id = template_id,
+
         PmMessage::CreateBoard => {
             if !state.new_board_name.trim().is_empty() {
                 // DbAction::CreateBoard es struct variant en tu repo actual.
                 let id = format!("board-{}", Uuid::new_v4());
                 let name = state.new_board_name.trim().to_string();
 
-                state.queue(DbAction::CreateBoard { id, name });
+                match state.new_board_template_id.take() {
+                    Some(template_id) => state.queue(DbAction::CreateBoardFromTemplate { id, template_id, name }),
+                    None => state.queue(DbAction::CreateBoard { id, name }),
+                }
                 state.new_board_name.clear();
                 state.show_toast("Creating board...", ToastKind::Info);
             }
         }
 
+        PmMessage::CreateBoardNamed(name) => {
+            state.new_board_name = name;
+            update(state, PmMessage::CreateBoard);
+        }
+
+        PmMessage::SaveBoardAsTemplate => {
+            if let Some(pm) = &state.pm_data {
+                let board_id = pm.board.id.clone();
+                let name = pm.board.name.clone();
+                let template_id = format!("board-template-{}", Uuid::new_v4());
+                state.queue(DbAction::SaveBoardTemplate { template_id, board_id, name });
+            }
+        }
+
         PmMessage::DeleteBoard(id) => {
             state.pending_confirm = Some(ConfirmAction::DeleteBoard(id));
         }
 
-        PmMessage::OpenBoard(id) => state.route = crate::app::Route::PmBoard { board_id: id },
+        PmMessage::OpenBoard(id) => {
+            // Las etiquetas son por board: el filtro no viaja entre boards
+            state.pm_label_filter = None;
+            state.pm_search.clear();
+            state.route = crate::app::Route::PmBoard { board_id: id };
+        }
 
         PmMessage::BoardLoaded(data) => {
             state.pm_data = Some(data);
@@ -117,22 +140,14 @@ pub fn update(state: &mut AppState, message: PmMessage) {
                     .pm_data
                     .as_ref()
                     .and_then(|data| data.get_card(card_id.as_ref()))
-                    .map(|card| {
-                        (
-                            card.id.clone(),
-                            card.column_id.clone(),
-                            card.title.clone(),
-                            card.description.clone(),
-                            card.priority.clone(),
-                        )
-                    });
+                    .cloned();
 
                 // ---------------------------------------------------------
                 // 2) Aquí el borrow inmutable de pm_data YA TERMINÓ
                 // ---------------------------------------------------------
 
-                if let Some((cid, col, title, desc, prio)) = payload {
-                    open_edit_editor_owned(state, cid, col, title, desc, prio);
+                if let Some(card) = payload {
+                    open_edit_editor_owned(state, card);
                 }
 
                 return;
@@ -160,6 +175,26 @@ pub fn update(state: &mut AppState, message: PmMessage) {
         PmMessage::ColumnHovered(cid) => state.hovered_column = Some(cid),
         PmMessage::CardHovered(cid) => state.hovered_card = Some(cid),
 
+        PmMessage::ColumnDragStart(cid) => {
+            // Mientras se edita una card no arrancamos drags de columna
+            if matches!(state.pm_state, PmState::Idle) {
+                state.pm_column_drag = Some(cid);
+            }
+        }
+
+        PmMessage::ColumnDragEnd => handle_column_drop(state),
+
+        PmMessage::MoveColumn(column_id, direction) => {
+            let Some(data) = state.pm_data.as_ref() else { return };
+            let Some(from) = data.columns.iter().position(|c| c.id == column_id) else { return };
+            let to = from as i64 + direction.signum() as i64;
+            // Ya está en el borde: nada que hacer
+            if to < 0 || to >= data.columns.len() as i64 {
+                return;
+            }
+            move_column(state, &column_id, to as usize);
+        }
+
         PmMessage::OpenCreate(cid) => {
             open_create_editor(state, cid.as_ref());
         }
@@ -204,7 +239,7 @@ pub fn update(state: &mut AppState, message: PmMessage) {
         }
 
         PmMessage::OpenEdit(c) => {
-            open_edit_editor_owned(state, c.id, c.column_id, c.title, c.description, c.priority);
+            open_edit_editor_owned(state, c);
         }
 
         PmMessage::TitleChanged(v) => {
@@ -225,8 +260,29 @@ pub fn update(state: &mut AppState, message: PmMessage) {
             }
         }
 
+        PmMessage::LabelsChanged(v) => {
+            if let PmState::Editing { labels, .. } = &mut state.pm_state {
+                *labels = v;
+            }
+        }
+
+        PmMessage::DueDateChanged(v) => {
+            if let PmState::Editing { due_date, .. } = &mut state.pm_state {
+                *due_date = v;
+            }
+        }
+
+        PmMessage::LabelFilterChanged(label) => state.pm_label_filter = label,
+        PmMessage::SearchChanged(query) => state.pm_search = query,
+
         PmMessage::Cancel => state.pm_state = PmState::Idle,
 
+        PmMessage::ToggleColumnCollapsed(column_id) => {
+            if !state.pm_collapsed_columns.remove(&column_id) {
+                state.pm_collapsed_columns.insert(column_id);
+            }
+        }
+
         PmMessage::Save => {
             if let PmState::Editing {
                 card_id,
@@ -234,8 +290,19 @@ pub fn update(state: &mut AppState, message: PmMessage) {
                 title,
                 description,
                 priority,
+                labels,
+                due_date,
             } = &state.pm_state
             {
+                // Fecha inválida: el editor queda abierto para corregirla
+                let due_date = match crate::model::parse_card_due_date(due_date) {
+                    Ok(due) => due,
+                    Err(e) => {
+                        state.show_toast(e, ToastKind::Error);
+                        return;
+                    }
+                };
+
                 if !title.trim().is_empty() && !column_id.is_empty() {
                     // ✅ OPTIMIZED: Use get_column_cards O(1) method
                     let next_pos = state
@@ -256,6 +323,9 @@ pub fn update(state: &mut AppState, message: PmMessage) {
                         description: description.text(),
                         position: next_pos,
                         priority: priority.clone(),
+                        labels: crate::model::normalize_card_labels(labels),
+                        due_date,
+                        archived: false, // el editor solo abre cards activas
                     };
 
                     state.queue(DbAction::SaveCard(_card));
@@ -274,6 +344,39 @@ pub fn update(state: &mut AppState, message: PmMessage) {
             }
             state.pm_state = PmState::Idle;
         }
+
+        PmMessage::Archive => {
+            if let PmState::Editing {
+                card_id: Some(id), ..
+            } = &state.pm_state
+            {
+                state.queue(DbAction::ArchiveCard(id.clone()));
+                state.show_toast("Archiving card...", ToastKind::Info);
+            }
+            state.pm_state = PmState::Idle;
+        }
+
+        PmMessage::ToggleArchivedPanel => {
+            state.pm_show_archived = !state.pm_show_archived;
+            // Al reabrir se recarga (pudo archivarse algo desde el editor)
+            state.pm_archived_for = None;
+        }
+
+        PmMessage::RestoreCard(card_id) => {
+            let crate::app::Route::PmBoard { board_id } = &state.route else {
+                return;
+            };
+            let board_id = board_id.clone();
+            // Optimista: sale del panel ya; el board se recarga al terminar
+            state.pm_archived_cards.retain(|c| c.id != card_id);
+            state.queue(DbAction::RestoreCard { card_id, board_id });
+        }
+
+        PmMessage::MoveCardToBoard { card_id, target_board_id } => {
+            state.queue(DbAction::MoveCardToBoard { card_id, target_board_id });
+            state.pm_state = PmState::Idle;
+            state.show_toast("Moving card...", ToastKind::Info);
+        }
     }
 }
 
@@ -311,6 +414,8 @@ pub fn handle_mouse_moved(state: &mut AppState, p: Point) {
 
 pub fn handle_mouse_released(state: &mut AppState) {
     let mut actions_to_queue: Vec<DbAction> = Vec::new();
+    // Undo: la card vuelve a su columna / posición de antes del drop
+    let mut move_undo: Option<(DbAction, String)> = None;
 
     // Nota: evitamos returns tempranos para GARANTIZAR reset del drag state.
     let mut should_move = false;
@@ -364,6 +469,13 @@ pub fn handle_mouse_released(state: &mut AppState) {
                                 new_pos = cards.last().map(|c| c.position + 1000).unwrap_or(1000);
                             }
 
+                            if let Some(card) = data.get_card(card_id.as_ref()) {
+                                move_undo = Some((
+                                    DbAction::MoveCard(card.id.clone(), card.column_id.clone(), card.position),
+                                    format!("Move card '{}'", card.title),
+                                ));
+                            }
+
                             // ✅ BORDE DB: aquí sí convertimos PmId -> String (1 vez).
                             actions_to_queue.push(DbAction::MoveCard(
                                 card_id.as_ref().to_string(),
@@ -386,6 +498,12 @@ pub fn handle_mouse_released(state: &mut AppState) {
     // Encolado fuera del borrow de state
     if should_move {
         for action in actions_to_queue {
+            if matches!(action, DbAction::MoveCard(..))
+                && let Some((inverse, label)) = move_undo.take()
+            {
+                state.queue_undoable(action, inverse, label);
+                continue;
+            }
             state.queue(action);
         }
     }
@@ -393,3 +511,47 @@ pub fn handle_mouse_released(state: &mut AppState) {
     // ✅ SIEMPRE reseteamos el drag state (aunque sea misma columna o no haya hovered_column)
     state.pm_state = PmState::Idle;
 }
+
+// Drop de columna: reorden optimista en pm_data (posiciones 0..n) + persistencia.
+// Soltar sobre la misma columna (o fuera del board) solo resetea el drag.
+fn handle_column_drop(state: &mut AppState) {
+    let Some(dragged) = state.pm_column_drag.take() else { return };
+    let Some(target) = state.hovered_column.clone() else { return };
+    if dragged.as_ref() == target.as_ref() {
+        return;
+    }
+
+    let Some(data) = state.pm_data.as_ref() else { return };
+    let Some(to) = data.columns.iter().position(|c| c.id.as_str() == target.as_ref()) else {
+        return;
+    };
+
+    move_column(state, dragged.as_ref(), to);
+}
+
+// Optimista + persistencia (drag y botones ‹ ›). Posiciones 0..n contiguas en memoria y en DB.
+fn move_column(state: &mut AppState, column_id: &str, to: usize) {
+    let Some(data) = state.pm_data.as_mut() else { return };
+    let Some(from) = data.columns.iter().position(|c| c.id == column_id) else {
+        return;
+    };
+    if from == to {
+        return;
+    }
+
+    // La columna movida ocupa el lugar del destino (las demás se corren)
+    let col = data.columns.remove(from);
+    data.columns.insert(to, col);
+    for (i, c) in data.columns.iter_mut().enumerate() {
+        c.position = i as i32;
+    }
+
+    let board_id = data.board.id.clone();
+    state.pm_ids.rebuild_from_pm(data);
+
+    state.queue(DbAction::ReorderColumn {
+        board_id,
+        column_id: column_id.to_string(),
+        new_index: to,
+    });
+}
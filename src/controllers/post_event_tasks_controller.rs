@@ -180,6 +180,142 @@ fn request_locations_if_needed(
     }
 }
 
+// Búsqueda inversa (creature -> scenes): una vez por creature abierta en el editor
+fn request_creature_mentions_if_needed(
+    state: &mut AppState,
+    db_base: &Database,
+    tasks: &mut Vec<Task<Message>>,
+    universe_id: &str,
+) {
+    let Some(editor) = state.creature_editor.as_ref() else {
+        return;
+    };
+    let Some(creature_id) = editor.id.clone() else {
+        return;
+    };
+    if state.creature_mentions_for.as_ref() == Some(&creature_id) {
+        return;
+    }
+
+    // El nombre guardado (no el del input) es el que aparece en el manuscrito
+    let Some(name) = state.creatures.iter().find(|c| c.id == creature_id).map(|c| c.name.clone()) else {
+        return;
+    };
+
+    state.creature_mentions_for = Some(creature_id.clone());
+    state.creature_mentions.clear();
+
+    let db = db_base.clone();
+    let uid = universe_id.to_string();
+
+    tasks.push(Task::perform(
+        async move { db.scenes_mentioning(uid, name).await.map_err(|e| e.to_string()) },
+        move |result| Message::CreatureMentionsFetched { creature_id, result },
+    ));
+}
+
+fn request_creature_relations_if_needed(
+    state: &mut AppState,
+    db_base: &Database,
+    tasks: &mut Vec<Task<Message>>,
+    universe_id: &String,
+) {
+    if state.creature_relations_for.as_ref() == Some(universe_id) {
+        return;
+    }
+
+    state.creature_relations_for = Some(universe_id.clone());
+    state.creature_relations.clear();
+
+    let db = db_base.clone();
+    let uid = universe_id.clone();
+    let uid_for_msg = universe_id.clone();
+
+    tasks.push(Task::perform(
+        async move { db.get_creature_relations(uid).await.map_err(|e| e.to_string()) },
+        move |result| Message::CreatureRelationsFetched { universe_id: uid_for_msg, result },
+    ));
+}
+
+fn request_archived_cards_if_needed(
+    state: &mut AppState,
+    db_base: &Database,
+    tasks: &mut Vec<Task<Message>>,
+    board_id: &String,
+) {
+    // Solo con el panel abierto: el board activo no las necesita
+    if !state.pm_show_archived || state.pm_archived_for.as_ref() == Some(board_id) {
+        return;
+    }
+
+    state.pm_archived_for = Some(board_id.clone());
+    state.pm_archived_cards.clear();
+
+    let db = db_base.clone();
+    let bid = board_id.clone();
+    let bid_for_msg = board_id.clone();
+
+    tasks.push(Task::perform(
+        async move { db.get_archived_cards(bid).await.map_err(|e| e.to_string()) },
+        move |result| Message::ArchivedCardsFetched { board_id: bid_for_msg, result },
+    ));
+}
+
+fn request_timeline_scene_refs_if_needed(
+    state: &mut AppState,
+    db_base: &Database,
+    tasks: &mut Vec<Task<Message>>,
+    universe_id: &String,
+) {
+    if state.timeline_scene_refs_for.as_ref() == Some(universe_id) {
+        return;
+    }
+
+    state.timeline_scene_refs_for = Some(universe_id.clone());
+    state.timeline_scene_refs.clear();
+
+    let db = db_base.clone();
+    let uid = universe_id.clone();
+    let uid_for_msg = universe_id.clone();
+
+    tasks.push(Task::perform(
+        async move { db.universe_scene_refs(uid).await.map_err(|e| e.to_string()) },
+        move |result| Message::TimelineSceneRefsFetched { universe_id: uid_for_msg, result },
+    ));
+}
+
+fn request_glossary_if_needed(
+    state: &mut AppState,
+    db_base: &Database,
+    tasks: &mut Vec<Task<Message>>,
+    universe_id: &String,
+) {
+    if state.loaded_glossary_universe.as_ref() == Some(universe_id) {
+        return;
+    }
+
+    let key = crate::state::CoreLoadKey::Glossary {
+        universe_id: universe_id.clone(),
+    };
+
+    let loaded_at = state.core_glossary_loaded_for.get(universe_id).copied();
+
+    if state.core_try_begin_scoped_load(key, loaded_at, CORE_THROTTLE_MS) {
+        let db = db_base.clone();
+
+        let uid_for_task = universe_id.clone();
+        let uid_for_msg = universe_id.clone();
+
+        tasks.push(Task::perform(
+            async move { db.get_glossary(uid_for_task).await.map_err(|e| e.to_string()) },
+            move |result| Message::GlossaryFetched {
+                universe_id: uid_for_msg.clone(),
+                result,
+            },
+        ));
+    }
+}
+
 fn request_timeline_if_needed(
     state: &mut AppState,
     db_base: &Database,
@@ -288,6 +424,46 @@ pub fn post_event_tasks(state: &mut AppState, db: &Option<Database>) -> Vec<Task
     // ========================================
     // Trash fetch (solo cuando aplica)
     // ========================================
+    // Retención del proyecto: una vez por proyecto (Trash y Settings la muestran)
+    if !state.trash_retention_loaded && state.active_project.is_some() {
+        state.trash_retention_loaded = true;
+        let db = db_base.clone();
+        tasks.push(Task::perform(
+            async move { db.get_trash_retention().await.map_err(|e| e.to_string()) },
+            Message::TrashRetentionFetched,
+        ));
+    }
+
+    // Capabilities: copia sync del cache (sidebar / Settings); se re-lee tras un toggle o import
+    if !state.capabilities_loaded && state.active_project.is_some() {
+        state.capabilities_loaded = true;
+        let db = db_base.clone();
+        tasks.push(Task::perform(
+            async move { db.capabilities.read().await.clone() },
+            Message::CapabilitiesFetched,
+        ));
+    }
+
+    // Recent (Overview): se relee tras cada apertura registrada o borrado
+    if state.route == crate::app::Route::Overview && !state.recent_items_loaded && state.active_project.is_some() {
+        state.recent_items_loaded = true;
+        let db = db_base.clone();
+        tasks.push(Task::perform(
+            async move { db.get_recent_items(crate::db::recents::RECENT_ITEMS_KEEP).await.map_err(|e| e.to_string()) },
+            Message::RecentItemsFetched,
+        ));
+    }
+
+    // Tema del proyecto: una vez por proyecto
+    if !state.ui_theme_loaded && state.active_project.is_some() {
+        state.ui_theme_loaded = true;
+        let db = db_base.clone();
+        tasks.push(Task::perform(
+            async move { db.get_ui_theme().await.map_err(|e| e.to_string()) },
+            Message::UiThemeFetched,
+        ));
+    }
+
     if state.route == crate::app::Route::Trash
         && !state.trash_loaded
         && state.db_inflight.is_none()
@@ -297,6 +473,77 @@ pub fn post_event_tasks(state: &mut AppState, db: &Option<Database>) -> Vec<Task
             async move { db.get_trash_entries().await.map_err(|e| e.to_string()) },
             Message::TrashFetched,
         ));
+
+        // Preview del auto-cleanup: lo que se purga dentro de la ventana de aviso
+        if state.trash_retention.is_never() {
+            state.trash_cleanup_preview.clear();
+        } else {
+            let db = db_base.clone();
+            let days = state.trash_retention.preview_days();
+            tasks.push(Task::perform(
+                async move { db.trash_cleanup_preview(days).await.map_err(|e| e.to_string()) },
+                Message::TrashCleanupPreviewFetched,
+            ));
+        }
+    }
+
+    // ========================================
+    // Draft recovery: una vez por proyecto abierto (drafts más nuevos que la DB)
+    // ========================================
+    if !state.forge_drafts_scanned && state.active_project.is_some() {
+        state.forge_drafts_scanned = true;
+        let db = db_base.clone();
+        tasks.push(Task::perform(
+            crate::forge_draft::scan_recoverable(db),
+            Message::DraftRecoveryScanned,
+        ));
+    }
+
+    // ========================================
+    // Manuscript: scenes de los chapters que el árbol todavía no tiene (una query por novel)
+    // ========================================
+    if state.forge_manuscript_mode && matches!(state.route, crate::app::Route::Forge)
+        && let Some(novel_id) = state.active_novel_id.clone()
+    {
+        let missing = state
+            .chapters_by_novel_id
+            .get(&novel_id)
+            .is_some_and(|chapters| chapters.iter().any(|c| !state.scenes_by_chapter_id.contains_key(&c.id)));
+        if missing && state.forge_manuscript_requested.as_ref() != Some(&novel_id) {
+            state.forge_manuscript_requested = Some(novel_id.clone());
+            let db = db_base.clone();
+            tasks.push(Task::perform(
+                {
+                    let novel_id = novel_id.clone();
+                    async move { db.get_novel_scenes(novel_id).await.map_err(|e| e.to_string()) }
+                },
+                move |result| Message::ForgeManuscriptScenesFetched { novel_id, result },
+            ));
+        }
+    }
+
+    // ========================================
+    // Outline: expansión persistida, una vez por universo cargado en la Forge
+    // ========================================
+    // (Forge descargada => se vuelve a leer al reabrir: el set en memoria ya no es confiable)
+    match state.loaded_forge_universe.clone() {
+        None => {
+            state.forge_expansion_universe = None;
+            state.forge_expansion_ready = false;
+        }
+        Some(universe_id) if state.forge_expansion_universe.as_ref() != Some(&universe_id) => {
+            state.forge_expansion_universe = Some(universe_id.clone());
+            state.forge_expansion_ready = false;
+            let db = db_base.clone();
+            tasks.push(Task::perform(
+                {
+                    let universe_id = universe_id.clone();
+                    async move { db.get_outline_expansion(universe_id).await.map_err(|e| e.to_string()) }
+                },
+                move |result| Message::OutlineExpansionLoaded { universe_id, result },
+            ));
+        }
+        Some(_) => {}
     }
 
     // ========================================
@@ -326,36 +573,108 @@ pub fn post_event_tasks(state: &mut AppState, db: &Option<Database>) -> Vec<Task
                 // Nada extra: universes ya se pide arriba si hace falta
             }
 
-            crate::app::Route::Overview => {
-                // Nada extra: universes + boards ya se pide arriba
+            crate::app::Route::Overview if state.project_word_total_stale => {
+                // universes + boards ya se pide arriba; acá solo el total de palabras
+                state.project_word_total_stale = false;
+                let db = db_base.clone();
+                tasks.push(Task::perform(
+                    async move { db.project_word_total().await.map_err(|e| e.to_string()) },
+                    Message::ProjectWordTotalFetched,
+                ));
             }
 
-            crate::app::Route::PmList => {
-                // Nada extra: boards ya se pide arriba
+            crate::app::Route::PmList if !state.board_templates_loaded => {
+                // boards ya se pide arriba; acá solo los templates (para el picker de alta)
+                state.board_templates_loaded = true;
+                let db = db_base.clone();
+                tasks.push(Task::perform(
+                    async move { db.get_board_templates().await.map_err(|e| e.to_string()) },
+                    Message::BoardTemplatesFetched,
+                ));
             }
 
             crate::app::Route::PmBoard { board_id } => {
                 request_pm_board_if_needed(state, db_base, &mut tasks, &board_id);
+                request_archived_cards_if_needed(state, db_base, &mut tasks, &board_id);
             }
 
             crate::app::Route::Bestiary { universe_id } => {
                 request_creatures_if_needed(state, db_base, &mut tasks, &universe_id);
                 request_locations_if_needed(state, db_base, &mut tasks, &universe_id);
+                request_creature_mentions_if_needed(state, db_base, &mut tasks, &universe_id);
+                request_creature_relations_if_needed(state, db_base, &mut tasks, &universe_id);
             }
 
             crate::app::Route::Locations { universe_id } => {
                 request_locations_if_needed(state, db_base, &mut tasks, &universe_id);
+                // Badges del árbol: criaturas / eventos que apuntan a cada location
+                request_creatures_if_needed(state, db_base, &mut tasks, &universe_id);
+                request_timeline_if_needed(state, db_base, &mut tasks, &universe_id);
+            }
+
+            crate::app::Route::Glossary { universe_id } => {
+                request_glossary_if_needed(state, db_base, &mut tasks, &universe_id);
             }
 
             crate::app::Route::Timeline { universe_id } => {
                 request_timeline_if_needed(state, db_base, &mut tasks, &universe_id);
                 // Locations también se usa como “dropdown cache” del editor
                 request_locations_if_needed(state, db_base, &mut tasks, &universe_id);
+                request_timeline_scene_refs_if_needed(state, db_base, &mut tasks, &universe_id);
             }
 
             crate::app::Route::Forge => {
                 // NOTE: Forge lazy-loading se maneja en navigation_controller::load_forge_data_if_needed.
                 // Mantenerlo fuera de post_event evita duplicados.
+                // Excepción: huérfanas no cuelgan del árbol (una query por proyecto, no por novel)
+                if !state.forge_orphans_loaded {
+                    state.forge_orphans_loaded = true;
+                    let db = db_base.clone();
+                    tasks.push(Task::perform(
+                        async move { db.orphan_scenes().await.map_err(|e| e.to_string()) },
+                        Message::ForgeOrphanScenesFetched,
+                    ));
+                }
+
+                // Historial de la escena activa: solo con el panel abierto
+                if state.forge_revisions_open
+                    && let Some(scene_id) = state.active_scene_id.clone()
+                    && state.forge_revisions_for.as_ref() != Some(&scene_id)
+                {
+                    if state.forge_revisions_for.is_some() {
+                        // cambio de escena (no un refresh tras guardar): soltar lista y preview
+                        state.forge_revisions.clear();
+                        state.forge_revision_selected = None;
+                    }
+                    state.forge_revisions_for = Some(scene_id.clone());
+                    let db = db_base.clone();
+                    let sid_for_msg = scene_id.clone();
+                    tasks.push(Task::perform(
+                        async move { db.get_scene_revisions(scene_id).await.map_err(|e| e.to_string()) },
+                        move |result| Message::SceneRevisionsFetched { scene_id: sid_for_msg, result },
+                    ));
+                }
+
+                // Búsqueda en el novel (ya debounced por el controller)
+                if let Some((novel_id, query)) = state.forge_search_requested.take() {
+                    let db = db_base.clone();
+                    let query_for_msg = query.clone();
+                    let novel_for_msg = novel_id.clone();
+                    tasks.push(Task::perform(
+                        async move { db.search_scenes(novel_id, query).await.map_err(|e| e.to_string()) },
+                        move |result| Message::ForgeSearchFetched { novel_id: novel_for_msg, query: query_for_msg, result },
+                    ));
+                }
+
+                // Jump to definition (una sola vez por pedido)
+                if let Some((universe_id, word)) = state.forge_definition_lookup.take() {
+                    let db = db_base.clone();
+                    let word_for_msg = word.clone();
+                    tasks.push(Task::perform(
+                        async move { db.lookup_definition(universe_id, word).await.map_err(|e| e.to_string()) },
+                        move |result| Message::DefinitionLookedUp { word: word_for_msg, result },
+                    ));
+                }
             }
 
             crate::app::Route::UniverseDetail { universe_id } => {
@@ -371,6 +690,31 @@ pub fn post_event_tasks(state: &mut AppState, db: &Option<Database>) -> Vec<Task
                 // snapshots list fetching (por universo)
                 request_snapshots_if_needed(state, &db_base, &mut tasks, &universe_id);
 
+                // timeline summary (strip del overview)
+                request_timeline_if_needed(state, db_base, &mut tasks, &universe_id);
+
+                // find & replace preview (solo lectura)
+                if state.replace_preview_requested {
+                    // Evita re-disparar en el próximo evento mientras el fetch corre
+                    state.replace_preview_requested = false;
+
+                    let db = db_base.clone();
+                    let uid = universe_id.clone();
+                    let term = state.replace_find.clone();
+                    let term_for_msg = term.clone();
+                    let mut scopes: Vec<_> = state.replace_scopes.iter().copied().collect();
+                    scopes.sort_by_key(|s| *s as u8);
+
+                    tasks.push(Task::perform(
+                        async move {
+                            db.find_text_matches(uid, term, scopes)
+                                .await
+                                .map_err(|e| e.to_string())
+                        },
+                        move |result| Message::ReplacePreviewFetched { term: term_for_msg, result },
+                    ));
+                }
+
                 // integrity issues fetching (Validate Universe)
                 if state.integrity_busy {
                     let db = db_base.clone();
@@ -0,0 +1,442 @@
+use std::path::PathBuf;
+
+use iced::Task;
+
+use crate::app::{AppState, Message, SettingsMessage};
+use crate::db::Database;
+use crate::state::ToastKind;
+
+// Persistimos en cada cambio válido: settings.json es chico y esto no corre por frame.
+pub(crate) fn persist(state: &mut AppState) {
+    if let Err(e) = state.settings.save() {
+        crate::logger::error(&format!("❌ Settings save failed: {}", e));
+        state.show_toast(format!("Could not save settings: {}", e), ToastKind::Error);
+    }
+}
+
+pub fn update(state: &mut AppState, message: SettingsMessage) {
+    match message {
+        SettingsMessage::AutoBackupToggled(enabled) => {
+            state.settings.auto_backup_enabled = enabled;
+            persist(state);
+        }
+
+        SettingsMessage::AutoBackupIntervalChanged(v) => {
+            if !v.chars().all(|c| c.is_ascii_digit()) {
+                return;
+            }
+            // Mínimo 1 minuto: VACUUM INTO no es gratis
+            if let Some(n) = v.parse::<u64>().ok().filter(|n| *n >= 1) {
+                state.settings.auto_backup_interval_min = n;
+                persist(state);
+            }
+            state.settings_backup_interval_input = v;
+        }
+
+        SettingsMessage::AutoBackupKeepChanged(v) => {
+            if !v.chars().all(|c| c.is_ascii_digit()) {
+                return;
+            }
+            if let Some(n) = v.parse::<usize>().ok().filter(|n| *n >= 1) {
+                state.settings.auto_backup_keep = n;
+                persist(state);
+            }
+            state.settings_backup_keep_input = v;
+        }
+
+        SettingsMessage::AutoSnapshotToggled(enabled) => {
+            state.settings.auto_snapshot_enabled = enabled;
+            persist(state);
+        }
+
+        SettingsMessage::AutoSnapshotKeepChanged(v) => {
+            if !v.chars().all(|c| c.is_ascii_digit()) {
+                return;
+            }
+            if let Some(n) = v.parse::<usize>().ok().filter(|n| *n >= 1) {
+                state.settings.auto_snapshot_keep = n;
+                persist(state);
+            }
+            state.settings_snapshot_keep_input = v;
+        }
+
+        SettingsMessage::BreakReminderToggled(enabled) => {
+            state.settings.break_reminder_enabled = enabled;
+            // Arrancar de cero: no contar escritura previa al opt-in
+            state.writing_session_start = None;
+            persist(state);
+        }
+
+        SettingsMessage::BreakReminderIntervalChanged(v) => {
+            if !v.chars().all(|c| c.is_ascii_digit()) {
+                return;
+            }
+            if let Some(n) = v.parse::<u64>().ok().filter(|n| *n >= 1) {
+                state.settings.break_reminder_interval_min = n;
+                persist(state);
+            }
+            state.settings_break_interval_input = v;
+        }
+
+        SettingsMessage::ImportPathChanged(v) => state.settings_import_path = v,
+        SettingsMessage::DbBackupPathChanged(v) => state.settings_db_backup_path = v,
+        SettingsMessage::ArchivePathChanged(v) => state.settings_archive_path = v,
+
+        SettingsMessage::TrashRetentionChanged(retention) => {
+            if state.active_project.is_none() || retention == state.trash_retention {
+                return;
+            }
+            state.trash_retention = retention;
+            state.queue(crate::state::DbAction::SetTrashRetention(retention));
+        }
+
+        SettingsMessage::CapabilityToggled(key, enabled) => {
+            if state.active_project.is_none() {
+                return;
+            }
+            // Optimista: la vista cambia ya; action_done re-lee el cache
+            if let Some(caps) = state.capabilities.as_mut() {
+                caps.set(&key, enabled);
+            }
+            state.queue(crate::state::DbAction::SetCapability { key, enabled });
+        }
+
+        SettingsMessage::ThemeChanged(mode) => {
+            if state.active_project.is_none() || mode == state.ui_theme {
+                return;
+            }
+            state.ui_theme = mode;
+            state.queue(crate::state::DbAction::SetUiTheme(mode));
+        }
+
+        SettingsMessage::ExportProjectArchive => {
+            let Some(project) = state.active_project.as_ref() else {
+                state.show_toast("Open a workspace to export it", ToastKind::Error);
+                return;
+            };
+            let path = crate::db::project_archive::project_archive_path(&PathBuf::from(&project.path));
+            state.queue(crate::state::DbAction::ExportProject { path });
+            state.show_toast("Exporting project...", ToastKind::Info);
+        }
+
+        SettingsMessage::AuditFromChanged(v) => state.audit_filter_from = v,
+        SettingsMessage::AuditToChanged(v) => state.audit_filter_to = v,
+        SettingsMessage::AuditActionChanged(v) => state.audit_filter_action = v,
+        SettingsMessage::AuditEntityChanged(v) => state.audit_filter_entity = v,
+
+        SettingsMessage::AuditExport => {
+            let filter = match audit_filter(state) {
+                Ok(f) => f,
+                Err(e) => {
+                    state.show_toast(e, ToastKind::Error);
+                    return;
+                }
+            };
+            let Some(project) = state.active_project.as_ref() else {
+                state.show_toast("Open a workspace to export its audit log", ToastKind::Error);
+                return;
+            };
+            let path = crate::db::audit::audit_export_path(&PathBuf::from(&project.path));
+            state.queue(crate::state::DbAction::ExportAuditLog { filter, path });
+            state.show_toast("Exporting audit log...", ToastKind::Info);
+        }
+
+        SettingsMessage::ImportProjectArchive => {
+            let path = PathBuf::from(state.settings_archive_path.trim());
+            if path.as_os_str().is_empty() {
+                state.show_toast("Enter the path of a project archive", ToastKind::Error);
+                return;
+            }
+            state.queue(crate::state::DbAction::ImportProject { path });
+            state.show_toast("Importing project...", ToastKind::Info);
+        }
+
+        // --- Sidebar: reorden en vivo mientras se arrastra; persistimos al soltar ---
+        SettingsMessage::SidebarDragStart(idx) => {
+            if idx < state.settings.sidebar_sections.len() {
+                state.settings_sidebar_drag = Some(idx);
+            }
+        }
+
+        SettingsMessage::SidebarDragOver(target) => {
+            let Some(from) = state.settings_sidebar_drag else {
+                return;
+            };
+            let sections = &mut state.settings.sidebar_sections;
+            if from != target && from < sections.len() && target < sections.len() {
+                let entry = sections.remove(from);
+                sections.insert(target, entry);
+                state.settings_sidebar_drag = Some(target);
+            }
+        }
+
+        SettingsMessage::SidebarDragEnd => {
+            if state.settings_sidebar_drag.take().is_some() {
+                persist(state);
+            }
+        }
+
+        SettingsMessage::SidebarVisibilityToggled(section, visible) => {
+            if let Some(e) = state.settings.sidebar_sections.iter_mut().find(|e| e.section == section) {
+                e.visible = visible;
+                persist(state);
+            }
+        }
+
+        SettingsMessage::ProjectWordGoalChanged(v) => {
+            if !v.chars().all(|c| c.is_ascii_digit()) {
+                return;
+            }
+            let Some(project_id) = state.active_project.as_ref().map(|p| p.id.clone()) else {
+                return;
+            };
+            // Vacío o 0 => sin meta
+            match v.parse::<u64>() {
+                Ok(n) if n > 0 => {
+                    state.settings.project_word_goals.insert(project_id, n);
+                }
+                _ => {
+                    state.settings.project_word_goals.remove(&project_id);
+                }
+            }
+            persist(state);
+            state.project_word_goal_input = v;
+        }
+
+        SettingsMessage::SidebarReset => {
+            state.settings.sidebar_sections = crate::app_settings::default_sidebar_sections();
+            state.settings_sidebar_drag = None;
+            persist(state);
+        }
+
+        // I/O (necesitan DB): ver try_handle
+        SettingsMessage::ExportProjectSettings
+        | SettingsMessage::ProjectSettingsExported(_)
+        | SettingsMessage::ImportProjectSettings
+        | SettingsMessage::ProjectSettingsImported(_)
+        | SettingsMessage::BackupDatabase
+        | SettingsMessage::DatabaseBackedUp(_)
+        | SettingsMessage::RestoreDatabase
+        | SettingsMessage::DatabaseRestoreStaged(_)
+        | SettingsMessage::AuditApply
+        | SettingsMessage::AuditFetched(_) => {}
+    }
+}
+
+fn audit_filter(state: &AppState) -> Result<crate::model::AuditFilter, String> {
+    crate::model::AuditFilter::from_inputs(
+        &state.audit_filter_from,
+        &state.audit_filter_to,
+        &state.audit_filter_action,
+        &state.audit_filter_entity,
+    )
+}
+
+/// Export/Import de configuración del proyecto (capabilities en db_meta + AppSettings).
+pub fn try_handle(state: &mut AppState, db: Option<&Database>, message: &Message) -> Option<Task<Message>> {
+    let Message::Settings(msg) = message else {
+        return None;
+    };
+
+    match msg {
+        SettingsMessage::ExportProjectSettings => {
+            if state.settings_io_busy {
+                return None;
+            }
+            let Some(db) = db.cloned() else {
+                state.show_toast("Open a workspace to export its settings", ToastKind::Error);
+                return None;
+            };
+            let project = state.active_project.as_ref()?;
+            let dest = crate::db::project_settings::settings_export_path(&PathBuf::from(&project.path));
+            let app_settings = state.settings.clone();
+
+            state.settings_io_busy = true;
+
+            Some(Task::perform(
+                async move {
+                    let json = db.export_settings(&app_settings).await.map_err(|e| e.to_string())?;
+                    if let Some(parent) = dest.parent() {
+                        tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+                    }
+                    tokio::fs::write(&dest, json).await.map_err(|e| e.to_string())?;
+                    Ok(dest.to_string_lossy().to_string())
+                },
+                |r| Message::Settings(SettingsMessage::ProjectSettingsExported(r)),
+            ))
+        }
+
+        SettingsMessage::ProjectSettingsExported(result) => {
+            state.settings_io_busy = false;
+            match result {
+                Ok(path) => {
+                    crate::logger::info(&format!("📤 Settings exported: {}", path));
+                    // Prellenamos el import con el último export (útil para copiar el path)
+                    state.settings_import_path = path.clone();
+                    state.show_toast(format!("Settings exported to {}", path), ToastKind::Success);
+                }
+                Err(e) => state.show_toast(format!("Settings export failed: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        SettingsMessage::ImportProjectSettings => {
+            if state.settings_io_busy {
+                return None;
+            }
+            let Some(db) = db.cloned() else {
+                state.show_toast("Open a workspace to import settings", ToastKind::Error);
+                return None;
+            };
+            let path = PathBuf::from(state.settings_import_path.trim());
+            if path.as_os_str().is_empty() {
+                state.show_toast("Enter the path of a settings JSON file", ToastKind::Error);
+                return None;
+            }
+
+            state.settings_io_busy = true;
+
+            Some(Task::perform(
+                async move {
+                    let json = tokio::fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+                    db.import_settings(&json).await.map_err(|e| e.to_string())
+                },
+                |r| Message::Settings(SettingsMessage::ProjectSettingsImported(r)),
+            ))
+        }
+
+        SettingsMessage::ProjectSettingsImported(result) => {
+            state.settings_io_busy = false;
+            match result {
+                Ok(imported) => {
+                    // Capabilities ya quedaron aplicadas (db_meta + cache); ahora settings globales
+                    state.capabilities_loaded = false;
+                    state.settings = imported.clone().normalized();
+                    state.settings_backup_interval_input = imported.auto_backup_interval_min.to_string();
+                    state.settings_backup_keep_input = imported.auto_backup_keep.to_string();
+                    state.settings_break_interval_input = imported.break_reminder_interval_min.to_string();
+                    state.settings_snapshot_keep_input = state.settings.auto_snapshot_keep.to_string();
+                    persist(state);
+                    state.show_toast("Settings imported", ToastKind::Success);
+                }
+                Err(e) => state.show_toast(format!("Settings import failed: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        SettingsMessage::AuditApply => {
+            let Some(db) = db.cloned() else {
+                state.show_toast("Open a workspace to see its audit log", ToastKind::Error);
+                return None;
+            };
+            let filter = match audit_filter(state) {
+                Ok(f) => f,
+                Err(e) => {
+                    state.show_toast(e, ToastKind::Error);
+                    return None;
+                }
+            };
+
+            Some(Task::perform(
+                async move {
+                    db.query_audit(filter.from_ts, filter.to_ts, filter.action, filter.entity_type)
+                        .await
+                        .map_err(|e| e.to_string())
+                },
+                |r| Message::Settings(SettingsMessage::AuditFetched(r)),
+            ))
+        }
+
+        SettingsMessage::AuditFetched(result) => {
+            match result {
+                Ok(rows) => {
+                    state.audit_rows = rows.clone();
+                    state.audit_loaded = true;
+                }
+                Err(e) => state.show_toast(format!("Audit log query failed: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        SettingsMessage::BackupDatabase => {
+            if state.settings_io_busy {
+                return None;
+            }
+            let Some(db) = db.cloned() else {
+                state.show_toast("Open a workspace to back it up", ToastKind::Error);
+                return None;
+            };
+            let project = state.active_project.as_ref()?;
+            let typed = state.settings_db_backup_path.trim();
+            let dest = if typed.is_empty() {
+                crate::db::backup::backups_dir_for(&PathBuf::from(&project.path))
+                    .join(format!("manual-{}.db", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+            } else {
+                PathBuf::from(typed)
+            };
+
+            state.settings_io_busy = true;
+
+            Some(Task::perform(
+                async move {
+                    db.backup_to(&dest).await.map_err(|e| e.to_string())?;
+                    Ok(dest.to_string_lossy().to_string())
+                },
+                |r| Message::Settings(SettingsMessage::DatabaseBackedUp(r)),
+            ))
+        }
+
+        SettingsMessage::DatabaseBackedUp(result) => {
+            state.settings_io_busy = false;
+            match result {
+                Ok(path) => {
+                    crate::logger::info(&format!("💾 Manual backup OK: {}", path));
+                    // Prellenado: el mismo path sirve para restaurar
+                    state.settings_db_backup_path = path.clone();
+                    state.show_toast(format!("Database backed up to {}", path), ToastKind::Success);
+                }
+                Err(e) => state.show_toast(format!("Backup failed: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        SettingsMessage::RestoreDatabase => {
+            if state.settings_io_busy {
+                return None;
+            }
+            let project = state.active_project.as_ref()?;
+            let db_path = PathBuf::from(&project.path);
+            let src = PathBuf::from(state.settings_db_backup_path.trim());
+            if src.as_os_str().is_empty() {
+                state.show_toast("Enter the path of a backup .db file", ToastKind::Error);
+                return None;
+            }
+
+            state.settings_io_busy = true;
+
+            Some(Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || Database::restore_from(&db_path, &src))
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .map(|p| p.to_string_lossy().to_string())
+                },
+                |r| Message::Settings(SettingsMessage::DatabaseRestoreStaged(r)),
+            ))
+        }
+
+        SettingsMessage::DatabaseRestoreStaged(result) => {
+            state.settings_io_busy = false;
+            match result {
+                Ok(path) => {
+                    crate::logger::info(&format!("♻️ Restore staged: {}", path));
+                    state.show_toast("Restore ready: reopen the workspace to apply it", ToastKind::Success);
+                }
+                Err(e) => state.show_toast(format!("Restore failed: {}", e), ToastKind::Error),
+            }
+            None
+        }
+
+        _ => None,
+    }
+}
@@ -8,15 +8,25 @@ use crate::app::{AppState, Message};
 use crate::messages::TheForgeMessage;
 use crate::state::DbAction;
 use crate::state::ConfirmAction;
+use crate::state::ToastKind;
+use crate::state::{ForgeDrag, ForgeDragItem};
 
 
-const AUTOSAVE_DELAY_MS: u64 = 800;
+// Búsqueda en el novel: espera a que el usuario deje de tipear antes de ir a la DB
+const SEARCH_DEBOUNCE_MS: u64 = 300;
+
+// Metas de palabras: tope razonable (evita overflow de porcentajes en la UI)
+const MAX_WORD_TARGET: i64 = 10_000_000;
+
+// Edición externa: si el archivo no cambia en este tiempo asumimos que el editor
+// quedó abierto/olvidado y dejamos de vigilarlo.
+const EXTERNAL_EDIT_IDLE_TIMEOUT_SECS: u64 = 30 * 60;
 
 // ============================================
 // HELPERS: lookup robusto (árbol manda)
 // ============================================
 
-fn find_chapter_anywhere(state: &AppState, chapter_id: &str) -> Option<crate::model::Chapter> {
+pub(crate) fn find_chapter_anywhere(state: &AppState, chapter_id: &str) -> Option<crate::model::Chapter> {
     // 1) Fast-path: si hay novel activa, probamos ahí primero
     if let Some(novel_id) = state.active_novel_id.as_deref() {
         if let Some(list) = state.chapters_by_novel_id.get(novel_id) {
@@ -55,7 +65,7 @@ fn find_chapter_anywhere(state: &AppState, chapter_id: &str) -> Option<crate::mo
         .cloned()
 }
 
-fn find_scene_anywhere(state: &AppState, scene_id: &str) -> Option<crate::model::Scene> {
+pub(crate) fn find_scene_anywhere(state: &AppState, scene_id: &str) -> Option<crate::model::Scene> {
     // 1) Fast-path: si hay chapter activo, probamos ahí primero
     if let Some(chapter_id) = state.active_chapter_id.as_deref() {
         if let Some(list) = state.scenes_by_chapter_id.get(chapter_id) {
@@ -119,6 +129,9 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                 state.active_chapter_scenes.clear();
                 state.active_scene_id = None;
                 state.forge_content = text_editor::Content::new();
+                // Expansión del universo nuevo: la restaura post_event (persistida por universo)
+                state.expanded_novels.clear();
+                state.expanded_chapters.clear();
 
                 crate::logger::info("   🧹 Forge state cleared");
             } else {
@@ -143,9 +156,45 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
 
         TheForgeMessage::UniverseChanged(new_id) => {
             if state.loaded_forge_universe.as_ref() != Some(&new_id) {
+                auto_save_before_switch(state);
                 state.loaded_forge_universe = Some(new_id);
                 reset_forge_state(state);
+                // Novels del universo nuevo (reset_forge_state vació la lista)
+                return Some(Task::done(Message::ForgeRequestLoadNovels));
+            }
+            None
+        }
+
+        TheForgeMessage::StartQuickUniverse => {
+            state.forge_new_universe_name = Some(String::new());
+            Some(operation::focus::<Message>(Id::new("forge_quick_universe")))
+        }
+
+        TheForgeMessage::QuickUniverseNameChanged(name) => {
+            if let Some(n) = state.forge_new_universe_name.as_mut() {
+                *n = name;
+            }
+            None
+        }
+
+        TheForgeMessage::QuickUniverseCancel => {
+            state.forge_new_universe_name = None;
+            None
+        }
+
+        TheForgeMessage::QuickUniverseCreate => {
+            let name = state.forge_new_universe_name.as_ref().map(|n| n.trim().to_string())?;
+            if name.is_empty() {
+                state.show_toast("Universe name cannot be empty", ToastKind::Error);
+                return None;
             }
+
+            // Misma acción que la lista de universos; la selección se hace en ActionDone
+            let id = format!("u-{}", uuid::Uuid::new_v4());
+            state.forge_pending_universe = Some(id.clone());
+            state.forge_new_universe_name = None;
+            state.queue(DbAction::CreateUniverse { id, name, desc: String::new() });
+            state.show_toast("Creating universe...", ToastKind::Info);
             None
         }
         TheForgeMessage::CreateNovel => {
@@ -253,6 +302,7 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             if is_double {
                 // Guardar copia temporal del novel para rename
                 if let Some(novel) = state.novels.iter().find(|n| n.id == id).cloned() {
+                    state.forge_rename_original_title = Some(novel.title.clone());
                     state.forge_renaming_novel_temp = Some(novel);
                 }
 
@@ -359,10 +409,11 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                 novel_id: novel_id.clone(),
                 title: title.clone(),
                 synopsis: String::new(),
-                status: "draft".to_string(),
+                status: crate::model::WorkStatus::Draft.as_str().to_string(),
                 position: pos,
                 created_at: now,
                 updated_at: now,
+                target_words: 0,
             };
 
             // ✅ Optimista: agregar local inmediato (contexto activo)
@@ -390,6 +441,99 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             None
         }
 
+        TheForgeMessage::DuplicateChapter(chapter_id) => {
+            // Body más reciente del editor si la escena activa es de este chapter
+            if state.active_chapter_id.as_deref() == Some(chapter_id.as_str()) {
+                sync_active_scene_body(state);
+            }
+
+            let original = state
+                .chapters_by_novel_id
+                .values()
+                .flatten()
+                .chain(state.active_novel_chapters.iter())
+                .find(|c| c.id == chapter_id)
+                .cloned()?;
+
+            // Scenes: árbol primero, fallback al panel activo. Sin data cargada no hay qué copiar.
+            let scenes: Vec<crate::model::Scene> = match state.scenes_by_chapter_id.get(&chapter_id) {
+                Some(list) => list.clone(),
+                None if state.active_chapter_id.as_deref() == Some(chapter_id.as_str()) => {
+                    state.active_chapter_scenes.clone()
+                }
+                None => {
+                    state.show_toast("Expand the chapter first so its scenes are loaded", ToastKind::Info);
+                    return None;
+                }
+            };
+
+            let novel_id = original.novel_id.clone();
+            let siblings: Vec<crate::model::Chapter> = state
+                .chapters_by_novel_id
+                .get(&novel_id)
+                .cloned()
+                .unwrap_or_else(|| state.active_novel_chapters.clone());
+            let index = siblings.iter().position(|c| c.id == chapter_id).unwrap_or(siblings.len().saturating_sub(1));
+            let now = chrono::Utc::now();
+
+            let copy = crate::model::Chapter {
+                id: format!("chapter-{}", uuid::Uuid::new_v4()),
+                title: format!("{} (copy)", original.title),
+                position: index as i64 + 1,
+                created_at: now,
+                updated_at: now,
+                ..original.clone()
+            };
+
+            // Posición relativa preservada: mismo orden, ids nuevos
+            let mut ordered = scenes;
+            ordered.sort_by_key(|s| s.position);
+            let copied_scenes: Vec<crate::model::Scene> = ordered
+                .into_iter()
+                .enumerate()
+                .map(|(pos, s)| crate::model::Scene {
+                    id: format!("scene-{}", uuid::Uuid::new_v4()),
+                    chapter_id: copy.id.clone(),
+                    word_count: count_words(&s.body),
+                    position: pos as i64,
+                    created_at: now,
+                    updated_at: now,
+                    ..s
+                })
+                .collect();
+
+            // ✅ Optimista: árbol + lista activa (copia justo después, positions corridas)
+            let insert_copy = |list: &mut Vec<crate::model::Chapter>| {
+                let Some(i) = list.iter().position(|c| c.id == chapter_id) else {
+                    return;
+                };
+                list.insert(i + 1, copy.clone());
+                for (pos, c) in list.iter_mut().enumerate() {
+                    c.position = pos as i64;
+                }
+            };
+            if let Some(list) = state.chapters_by_novel_id.get_mut(&novel_id) {
+                insert_copy(list);
+            }
+            if state.active_novel_chapters.iter().any(|c| c.novel_id == novel_id) {
+                insert_copy(&mut state.active_novel_chapters);
+            }
+            state.scenes_by_chapter_id.insert(copy.id.clone(), copied_scenes.clone());
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            // DB: crear (al final) -> mover a su lugar -> metadata; scenes se crean en orden y luego body
+            state.queue(DbAction::CreateChapter(copy.id.clone(), novel_id, copy.title.clone()));
+            state.queue(DbAction::ReorderChapter(copy.id.clone(), copy.position));
+            state.queue(DbAction::UpdateChapter(copy.clone()));
+            for scene in copied_scenes {
+                state.queue(DbAction::CreateScene(scene.id.clone(), copy.id.clone(), scene.title.clone()));
+                state.queue(DbAction::UpdateScene(scene));
+            }
+
+            state.show_toast(format!("Duplicated \"{}\"", original.title), ToastKind::Success);
+            None
+        }
+
         TheForgeMessage::DeleteChapter(id) => {
             // ✅ PRO: NO mutar state aquí.
             // Solo abrir confirm modal. La mutación real ocurre en Message::ConfirmDelete.
@@ -413,6 +557,7 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                 // Guardar copia temporal del chapter para rename (robusto: árbol primero)
                 if let Some(chapter) = find_chapter_anywhere(state, &chapter_id) {
                     crate::logger::info(&format!("   📋 Chapter encontrado con título: '{}'", chapter.title));
+                    state.forge_rename_original_title = Some(chapter.title.clone());
                     state.forge_renaming_chapter_temp = Some(chapter);
                 } else {
                     crate::logger::warn(&format!(
@@ -525,6 +670,25 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
         }
 
         // --- SCENE ACTIONS ---
+        // Atajo: mismo camino (y mismo debounce de 1s) que el "+" del chapter
+        TheForgeMessage::SaveNow => {
+            state.active_scene_id.as_ref()?;
+            // El debounce en vuelo queda huérfano (id distinto): no guarda dos veces
+            state.forge_debounce_task_id = None;
+            state.forge_history.close_burst();
+            auto_save_before_switch(state);
+            state.show_toast("Scene saved", ToastKind::Success);
+            None
+        }
+
+        TheForgeMessage::CreateSceneInActiveChapter => match state.active_chapter_id.clone() {
+            Some(chapter_id) => update(state, TheForgeMessage::CreateScene(chapter_id)),
+            None => {
+                state.show_toast("Select a chapter first to add a scene", ToastKind::Info);
+                None
+            }
+        },
+
         TheForgeMessage::CreateScene(chapter_id) => {
             // ✅ DEBOUNCING: Prevenir clicks múltiples
             let now = std::time::Instant::now();
@@ -571,10 +735,14 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                     .get(&chapter_id)
                     .map(|v| v.len() as i64)
                     .unwrap_or(0),
-                status: "draft".to_string(),
+                status: crate::model::WorkStatus::Draft.as_str().to_string(),
                 word_count: 0,
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                scene_kind: crate::model::SceneKind::Prose.as_str().to_string(),
+                include_in_compile: true,
+                target_words: 0,
+                notes: String::new(),
             };
 
             crate::logger::info(&format!(
@@ -615,6 +783,185 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             None
         }
 
+        TheForgeMessage::SplitSceneAtCursor => {
+            let scene_id = state.active_scene_id.clone()?;
+            let original = state.active_chapter_scenes.iter().find(|s| s.id == scene_id).cloned()?;
+
+            // Corte en el cursor (con selección: al inicio de la selección)
+            let body = state.forge_content.text();
+            let cursor = editor_cursor_chars(&mut state.forge_content);
+            let cut = body.char_indices().nth(cursor).map(|(i, _)| i).unwrap_or(body.len());
+            let before = body[..cut].trim_end().to_string();
+            let after = body[cut..].trim_start().to_string();
+
+            if after.is_empty() {
+                state.show_toast("Nothing below the cursor to split", ToastKind::Info);
+                return None;
+            }
+
+            let chapter_id = original.chapter_id.clone();
+            let siblings: Vec<crate::model::Scene> = state
+                .scenes_by_chapter_id
+                .get(&chapter_id)
+                .cloned()
+                .unwrap_or_else(|| state.active_chapter_scenes.clone());
+            let index = siblings.iter().position(|s| s.id == scene_id).unwrap_or(siblings.len().saturating_sub(1));
+            let now = chrono::Utc::now();
+
+            let mut first = original.clone();
+            first.body = before;
+            first.word_count = count_words(&first.body);
+            first.updated_at = now;
+
+            let title = next_scene_title(&siblings);
+            let second = crate::model::Scene {
+                id: format!("scene-{}", uuid::Uuid::new_v4()),
+                chapter_id: chapter_id.clone(),
+                title: title.clone(),
+                word_count: count_words(&after),
+                body: after,
+                position: index as i64 + 1,
+                status: crate::model::WorkStatus::Draft.as_str().to_string(),
+                created_at: now,
+                updated_at: now,
+                scene_kind: original.scene_kind.clone(),
+                include_in_compile: original.include_in_compile,
+                target_words: 0,
+                notes: String::new(),
+            };
+
+            // ✅ Optimista: árbol + panel activo (nueva escena justo después, positions corridas)
+            let insert_split = |list: &mut Vec<crate::model::Scene>| {
+                let Some(i) = list.iter().position(|s| s.id == scene_id) else {
+                    return;
+                };
+                list[i] = first.clone();
+                list.insert(i + 1, second.clone());
+                for (pos, s) in list.iter_mut().enumerate() {
+                    s.position = pos as i64;
+                }
+            };
+            if let Some(list) = state.scenes_by_chapter_id.get_mut(&chapter_id) {
+                insert_split(list);
+            }
+            insert_split(&mut state.active_chapter_scenes);
+
+            // Editor queda en la primera mitad (undo no cruza el split)
+            state.forge_content = text_editor::Content::with_text(&first.body);
+            state.forge_content.perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+            state.forge_history.reset(Some(scene_id));
+            cancel_debounce(state);
+            state.forge_dirty = true;
+            refresh_annotations(state);
+            refresh_find_total(state);
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            // Sesión: el texto se movió, no se escribió (la nueva arranca de 0)
+            state.record_session_words(&first.id, original.word_count, first.word_count);
+            state.record_session_words(&second.id, 0, second.word_count);
+
+            // DB: recortar original -> crear (al final) -> mover a su lugar -> body de la nueva
+            state.queue(DbAction::UpdateScene(first));
+            state.queue(DbAction::CreateScene(second.id.clone(), chapter_id, title));
+            state.queue(DbAction::ReorderScene(second.id.clone(), second.position));
+            state.queue(DbAction::UpdateScene(second));
+
+            None
+        }
+
+        TheForgeMessage::MergeSceneUp(scene_id) => {
+            // Body más reciente del editor antes de leer las copias en memoria
+            if state.active_scene_id.as_deref() == Some(scene_id.as_str()) {
+                sync_active_scene_body(state);
+            }
+
+            let merged = find_scene_anywhere(state, &scene_id)?;
+            let chapter_id = merged.chapter_id.clone();
+
+            // Orden del árbol; las copias del panel activo traen el body más nuevo
+            let mut siblings: Vec<crate::model::Scene> = state
+                .scenes_by_chapter_id
+                .get(&chapter_id)
+                .cloned()
+                .unwrap_or_else(|| state.active_chapter_scenes.clone());
+            for s in siblings.iter_mut() {
+                if let Some(fresh) = state.active_chapter_scenes.iter().find(|a| a.id == s.id) {
+                    *s = fresh.clone();
+                }
+            }
+
+            let index = siblings.iter().position(|s| s.id == scene_id)?;
+            if index == 0 {
+                state.show_toast("First scene of the chapter: nothing to merge into", ToastKind::Info);
+                return None;
+            }
+
+            let merged = siblings[index].clone();
+            let mut survivor = siblings[index - 1].clone();
+            let head = survivor.body.trim_end();
+            let tail = merged.body.trim_start();
+            survivor.body = match (head.is_empty(), tail.is_empty()) {
+                (_, true) => head.to_string(),
+                (true, false) => tail.to_string(),
+                (false, false) => format!("{head}\n\n{tail}"),
+            };
+            survivor.word_count = count_words(&survivor.body);
+            survivor.updated_at = chrono::Utc::now();
+
+            // ✅ Optimista: árbol + panel activo
+            let apply_merge = |list: &mut Vec<crate::model::Scene>| {
+                list.retain(|s| s.id != merged.id);
+                if let Some(s) = list.iter_mut().find(|s| s.id == survivor.id) {
+                    *s = survivor.clone();
+                }
+                for (pos, s) in list.iter_mut().enumerate() {
+                    s.position = pos as i64;
+                }
+            };
+            if let Some(list) = state.scenes_by_chapter_id.get_mut(&chapter_id) {
+                apply_merge(list);
+            }
+            if state.active_chapter_id.as_deref() == Some(chapter_id.as_str()) {
+                apply_merge(&mut state.active_chapter_scenes);
+            }
+
+            // Selección -> escena sobreviviente (editor con el texto unido)
+            state.active_chapter_id = Some(chapter_id.clone());
+            if !state.active_chapter_scenes.iter().any(|s| s.id == survivor.id) {
+                state.active_chapter_scenes = state.scenes_by_chapter_id.get(&chapter_id).cloned().unwrap_or_default();
+            }
+            state.active_scene_id = Some(survivor.id.clone());
+            state.forge_content = text_editor::Content::with_text(&survivor.body);
+            state.forge_history.reset(Some(survivor.id.clone()));
+            cancel_debounce(state);
+            state.forge_dirty = true;
+            refresh_annotations(state);
+            refresh_find_total(state);
+            if state.forge_renaming_scene_id.as_deref() == Some(merged.id.as_str()) {
+                state.forge_renaming_scene_id = None;
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            // Sesión: las palabras de la absorbida pasan a la sobreviviente (no es borrado ni escritura)
+            state.record_session_words(&survivor.id, siblings[index - 1].word_count, survivor.word_count);
+            state.record_session_words(&merged.id, merged.word_count, 0);
+
+            // DB: un UpdateScene para la sobreviviente + la absorbida a la papelera (restaurable)
+            state.queue(DbAction::UpdateScene(survivor));
+            state.queue(DbAction::MoveToTrash {
+                trash_id: uuid::Uuid::new_v4().to_string(),
+                target_type: "scene".to_string(),
+                target_id: merged.id.clone(),
+                display_name: merged.title.clone(),
+                display_info: Some(format!("{} words (merged)", merged.word_count)),
+                parent_type: Some("chapter".to_string()),
+                parent_id: Some(chapter_id),
+                payload_json: serde_json::to_string(&merged).unwrap_or_default(),
+            });
+
+            None
+        }
+
         TheForgeMessage::DeleteScene(id) => {
             // ✅ PRO: NO mutar state aquí.
             // Solo abrir confirm modal. La mutación real ocurre en Message::ConfirmDelete.
@@ -622,6 +969,90 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             None
         }
 
+        TheForgeMessage::ExportNovelJson(novel_id) => {
+            let path = novel_export_target(state, &novel_id, crate::db::novel_export::novel_export_path)?;
+
+            // Si había edición pendiente, que el dump la incluya
+            auto_save_before_switch(state);
+            state.queue(DbAction::ExportNovelJson { novel_id, path });
+            None
+        }
+
+        TheForgeMessage::ExportNovelMarkdown(novel_id) => {
+            let path = novel_export_target(state, &novel_id, crate::db::novel_export::novel_markdown_path)?;
+
+            auto_save_before_switch(state);
+            state.queue(DbAction::ExportNovelMarkdown { novel_id, path });
+            None
+        }
+
+        TheForgeMessage::LookupWordAtCursor => {
+            state.active_scene_id.as_ref()?;
+
+            // Selección explícita manda; si no hay, la palabra bajo el cursor (queda seleccionada)
+            if state.forge_content.selection().is_none() {
+                state.forge_content.perform(text_editor::Action::SelectWord);
+            }
+            let word = state
+                .forge_content
+                .selection()
+                .map(|s| s.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+                .unwrap_or_default();
+
+            if word.is_empty() {
+                state.show_toast("Place the cursor on a word to look it up", ToastKind::Info);
+                return None;
+            }
+
+            // Universo de la novela activa (fallback: el universo cargado en la Forge)
+            let universe_id = state
+                .active_novel_id
+                .as_ref()
+                .and_then(|nid| state.novels.iter().find(|n| &n.id == nid))
+                .and_then(|n| n.universe_id.clone())
+                .or_else(|| state.loaded_forge_universe.clone());
+
+            let Some(universe_id) = universe_id else {
+                state.show_toast("This novel is not linked to a universe", ToastKind::Info);
+                return None;
+            };
+
+            state.forge_definition_lookup = Some((universe_id, word));
+            None
+        }
+
+        TheForgeMessage::StartMergeNovel(id) => {
+            state.forge_merge_source = Some(id);
+            None
+        }
+
+        TheForgeMessage::PickMergeTarget(keep_id) => {
+            if let Some(merge_id) = state.forge_merge_source.take()
+                && merge_id != keep_id
+            {
+                state.pending_confirm = Some(ConfirmAction::MergeNovels { keep_id, merge_id });
+            }
+            None
+        }
+
+        TheForgeMessage::CancelMergeNovel => {
+            state.forge_merge_source = None;
+            None
+        }
+
+        TheForgeMessage::ReassignOrphanScene { scene_id, chapter_id } => {
+            // Optimistic: sale de la lista de huérfanas; el refetch del chapter la muestra en su lugar
+            state.forge_orphan_scenes.retain(|s| s.id != scene_id);
+            state.queue(DbAction::MoveSceneToChapter { scene_id, chapter_id });
+            None
+        }
+
+        TheForgeMessage::TrashOrphanScene(id) => {
+            // Mismo flujo que DeleteScene (ConfirmDelete busca también en huérfanas)
+            state.pending_confirm = Some(ConfirmAction::DeleteScene(id));
+            None
+        }
+
         TheForgeMessage::SelectScene(id) => {
             let now = Instant::now();
             let is_double = state.last_forge_scene_click.as_ref()
@@ -636,6 +1067,7 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             if is_double {
                 // Guardar copia temporal de la scene para rename (robusto: árbol primero)
                 if let Some(scene) = find_scene_anywhere(state, &id) {
+                    state.forge_rename_original_title = Some(scene.title.clone());
                     state.forge_renaming_scene_temp = Some(scene);
                 } else {
                     crate::logger::warn(&format!(
@@ -651,6 +1083,8 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                         state.forge_content = text_editor::Content::with_text(&scene.body);
                     }
                     cancel_debounce(state);
+                    state.forge_dirty = false;
+                    refresh_annotations(state);
                 }
 
                 // Entrar a modo rename
@@ -669,12 +1103,32 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                 auto_save_before_switch(state);
 
                 state.active_scene_id = Some(id.clone());
+                // Undo/redo es por escena: arranca vacío
+                state.forge_history.reset(Some(id.clone()));
 
                 // 2) Cargar desde DB/cache primero (baseline)
-                if let Some(scene) = state.active_chapter_scenes.iter().find(|s| s.id == id) {
+                let mut healed: Option<crate::model::Scene> = None;
+                if let Some(scene) = state.active_chapter_scenes.iter_mut().find(|s| s.id == id) {
                     state.forge_content = text_editor::Content::with_text(&scene.body);
+
+                    // word_count con drift => corregir en silencio al abrir
+                    if heal_scene_word_count(scene) {
+                        healed = Some(scene.clone());
+                    }
+                }
+
+                if let Some(scene) = healed {
+                    if let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                        && let Some(s) = list.iter_mut().find(|s| s.id == scene.id)
+                    {
+                        s.word_count = scene.word_count;
+                    }
+                    crate::logger::info(&format!("   🩹 Healed word_count for scene {}", scene.id));
+                    state.queue(DbAction::UpdateScene(scene));
                 }
                 cancel_debounce(state);
+                state.forge_dirty = false;
+                refresh_annotations(state);
 
                 // 3) Intentar restaurar draft local en background
                 let scene_id_for_task = id.clone();
@@ -748,25 +1202,49 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
         }
 
         TheForgeMessage::SceneBodyChanged(action) => {
+            // Solo ediciones reales ensucian (mover cursor / seleccionar no)
+            if action.is_edit() {
+                state.forge_dirty = true;
+                // Undo: snapshot del texto previo al inicio de cada ráfaga (cierra en el debounce)
+                if let Some(scene_id) = state.active_scene_id.as_deref() {
+                    state.forge_history.ensure_scene(scene_id);
+                    state.forge_history.before_edit(&state.forge_content.text());
+                }
+            }
             state.forge_content.perform(action);
 
             // Actualizar word count y body en el scene activo
-            if let Some(scene_id) = &state.active_scene_id {
-                if let Some(scene) = state.active_chapter_scenes.iter_mut().find(|s| s.id == *scene_id) {
-                    let text = state.forge_content.text();
-                    scene.body = text.clone();
-                    scene.word_count = count_words(&text);
-                }
-            }
+            sync_active_scene_body(state);
 
             // Trigger debounce
-            state.forge_last_edit = Some(Instant::now());
-            let task_id = state.forge_debounce_task_id.unwrap_or(0) + 1;
-            state.forge_debounce_task_id = Some(task_id);
+            note_writing_activity(state);
+            Some(schedule_autosave(state))
+        }
+
+        // Sin historial del body (o sin escena activa) => undo/redo global (renames, reorders, ...)
+        TheForgeMessage::UndoSceneEdit => {
+            apply_history_step(state, true).or_else(|| Some(Task::done(Message::Undo)))
+        }
+
+        TheForgeMessage::RedoSceneEdit => {
+            apply_history_step(state, false).or_else(|| Some(Task::done(Message::Redo)))
+        }
+
+        TheForgeMessage::ResetWritingSession => {
+            state.forge_session_words.clear();
+            state.show_toast("Writing session reset", ToastKind::Info);
+            None
+        }
 
-            return Some(Task::perform(debounce_save(task_id), |id| {
-                Message::TheForge(TheForgeMessage::DebounceComplete(id))
-            }));
+        TheForgeMessage::AutosaveDelayChanged(ms) => {
+            let ms = crate::app_settings::clamp_autosave_ms(ms);
+            if state.settings.forge_autosave_ms == ms {
+                return None;
+            }
+            // El debounce en vuelo se re-arma solo en DebounceComplete con el valor nuevo
+            state.settings.forge_autosave_ms = ms;
+            crate::controllers::settings_controller::persist(state);
+            None
         }
 
         TheForgeMessage::SaveCurrentScene => {
@@ -787,18 +1265,31 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                 return None;
             };
 
-            if Instant::now()
-                .duration_since(last_edit)
-                .as_millis()
-                < AUTOSAVE_DELAY_MS as u128
-            {
-                return None;
+            // El delay pudo subir mientras el timer corría: re-armar por lo que falta
+            // (mismo id, así el debounce en vuelo no queda huérfano)
+            let delay = Duration::from_millis(state.settings.forge_autosave_ms);
+            let elapsed = Instant::now().duration_since(last_edit);
+            if elapsed < delay {
+                let remaining = delay - elapsed;
+                return Some(Task::perform(
+                    async move {
+                        tokio::time::sleep(remaining).await;
+                        completed_id
+                    },
+                    |id| Message::TheForge(TheForgeMessage::DebounceComplete(id)),
+                ));
             }
 
             let Some(scene_id) = state.active_scene_id.clone() else {
                 return None;
             };
 
+            // Notas inline: re-parse en el mismo debounce del autosave
+            refresh_annotations(state);
+
+            // Límite de ráfaga para undo: la próxima edición toma snapshot nuevo
+            state.forge_history.close_burst();
+
             // Buscar scene actual (mutable o inmutable da igual aquí; vamos a persistir una copia)
             let Some(scene) = state
                 .active_chapter_scenes
@@ -840,6 +1331,9 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                         let current = state.forge_content.text();
                         if current != draft_body {
                             state.forge_content = text_editor::Content::with_text(&draft_body);
+                            // El draft difiere de lo guardado en DB
+                            state.forge_dirty = true;
+                            refresh_annotations(state);
 
                             // Mantener coherencia con el Scene en memoria (para que el siguiente autosave a DB no “rebote”)
                             if let Some(scene) = state
@@ -886,70 +1380,279 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             None
         }
 
-        TheForgeMessage::EndRename => {
-            crate::logger::info("   💾 EndRename triggered");
-
-            // --- NOVEL RENAME ---
-            if let Some(novel) = state.forge_renaming_novel_temp.take() {
-                crate::logger::info(&format!(
-                    "   ✅ Updating novel {} (title: '{}')",
-                    novel.id, novel.title
-                ));
+        TheForgeMessage::RecoverDraft(scene_id) => {
+            let index = state.forge_recoverable_drafts.iter().position(|d| d.scene.id == scene_id)?;
+            let draft = state.forge_recoverable_drafts.remove(index);
 
-                // 1) Lista global de novels
-                if let Some(local_novel) = state.novels.iter_mut().find(|n| n.id == novel.id) {
-                    local_novel.title = novel.title.clone();
-                    local_novel.updated_at = novel.updated_at;
-                    crate::logger::info("   ✅ Updated global novel title");
-                } else {
-                    crate::logger::warn(&format!(
-                        "   ⚠️ Novel {} not found in global list",
-                        novel.id
-                    ));
-                }
+            let mut scene = draft.scene;
+            scene.body = draft.draft_body;
+            scene.word_count = count_words(&scene.body);
 
-                state.queue(DbAction::UpdateNovel(novel));
+            // Copias en memoria primero: el salto abre la escena desde el cache
+            if let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                && let Some(s) = list.iter_mut().find(|s| s.id == scene_id)
+            {
+                s.body = scene.body.clone();
+                s.word_count = scene.word_count;
+            }
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                s.body = scene.body.clone();
+                s.word_count = scene.word_count;
+            }
+            if state.active_scene_id.as_deref() == Some(scene_id.as_str()) {
+                state.forge_content = text_editor::Content::with_text(&scene.body);
+                state.forge_history.reset(Some(scene_id.clone()));
+                cancel_debounce(state);
+                refresh_annotations(state);
+                state.forge_dirty = true;
             }
 
-            // --- CHAPTER RENAME ---
-            {
-                crate::logger::info("🏁 EndRename CHAPTER iniciado");
+            // El draft queda en disco hasta que la DB lo tenga (el próximo escaneo lo limpia)
+            state.queue(DbAction::UpdateScene(scene));
+            state.show_toast("Draft recovered", ToastKind::Success);
 
-                let temp = state.forge_renaming_chapter_temp.take();
+            let path = draft.path?;
+            begin_forge_jump(state, path.universe_id, path.novel_id, path.chapter_id, path.scene_id)
+        }
 
-                if let Some(temp_chapter) = &temp {
-                    crate::logger::info(&format!("   📦 Temp tiene título: '{}'", temp_chapter.title));
-                } else {
-                    crate::logger::warn("   ⚠️ Temp es None!");
+        TheForgeMessage::DiscardDraft(scene_id) => {
+            state.forge_recoverable_drafts.retain(|d| d.scene.id != scene_id);
+
+            // Borrar del disco: si no, el próximo arranque vuelve a preguntar
+            let scene_id_for_task = scene_id.clone();
+            Some(Task::perform(
+                async move { crate::forge_draft::delete_draft(&scene_id_for_task).await },
+                move |result| Message::TheForge(TheForgeMessage::DraftDiscarded { scene_id, result }),
+            ))
+        }
+
+        TheForgeMessage::DraftDiscarded { scene_id, result } => {
+            match result {
+                Ok(()) => state.show_toast("Draft discarded", ToastKind::Info),
+                Err(e) => {
+                    crate::logger::warn(&format!("⚠️ DraftDiscarded falló (scene {}): {}", scene_id, e));
+                    state.show_toast(format!("Could not delete draft: {}", e), ToastKind::Error);
                 }
+            }
+            None
+        }
 
-                if let Some(chapter_id) = state.forge_renaming_chapter_id.clone() {
-                    crate::logger::info(&format!("   🔑 Chapter ID: {}", chapter_id));
+        TheForgeMessage::OpenSceneExternally(scene_id) => {
+            // Una sola sesión a la vez: si ya hay otra, la cerramos primero
+            if let Some(prev) = state.forge_external_edit.take() {
+                crate::forge_external::cleanup(&prev.path);
+                if prev.scene_id != scene_id {
+                    state.show_toast("Previous external edit stopped", ToastKind::Info);
+                }
+            }
 
-                    if let Some(chapter_from_temp) = temp {
-                        crate::logger::info(&format!(
-                            "   ✅ Persisting chapter rename {} (title: '{}')",
-                            chapter_from_temp.id, chapter_from_temp.title
-                        ));
+            // Flush del autosave pendiente para exportar el texto más reciente
+            if state.active_scene_id.as_deref() == Some(scene_id.as_str()) {
+                auto_save_before_switch(state);
+            }
 
-                        // ✅ Actualizar el árbol (chapters_by_novel_id)
-                        let mut found_in_tree = false;
-                        for (novel_id, chapters) in state.chapters_by_novel_id.iter_mut() {
-                            if let Some(local) = chapters.iter_mut().find(|c| c.id == chapter_id) {
-                                local.title = chapter_from_temp.title.clone();
-                                local.updated_at = chapter_from_temp.updated_at;
-                                crate::logger::info(&format!(
-                                    "   ✅ Árbol actualizado en EndRename con: '{}' (novel: {})",
-                                    local.title, novel_id
-                                ));
-                                found_in_tree = true;
-                                break;
-                            }
-                        }
+            let Some(scene) = find_scene_anywhere(state, &scene_id) else {
+                state.show_toast("Scene not found", ToastKind::Error);
+                return None;
+            };
 
-                        if !found_in_tree {
-                            crate::logger::warn(&format!("   ⚠️ Chapter {} NO encontrado en árbol para actualizar", chapter_id));
-                        }
+            let body = if state.active_scene_id.as_deref() == Some(scene_id.as_str()) {
+                state.forge_content.text()
+            } else {
+                scene.body
+            };
+
+            let scene_id_for_task = scene_id.clone();
+            Some(Task::perform(
+                async move {
+                    let (path, mtime) =
+                        crate::forge_external::export_scene(&scene_id_for_task, &body).await?;
+                    crate::forge_external::launch_default_editor(&path)?;
+                    Ok((path, mtime))
+                },
+                move |res| Message::TheForge(TheForgeMessage::ExternalEditStarted {
+                    scene_id,
+                    result: res,
+                }),
+            ))
+        }
+
+        TheForgeMessage::ExternalEditStarted { scene_id, result } => {
+            match result {
+                Ok((path, last_modified)) => {
+                    state.forge_external_edit = Some(crate::state::ExternalEditSession {
+                        scene_id,
+                        path,
+                        last_modified,
+                        last_activity: Instant::now(),
+                        poll_in_flight: false,
+                    });
+                    state.show_toast(
+                        "Opened in external editor. Changes are imported on save.",
+                        ToastKind::Info,
+                    );
+                }
+                Err(e) => {
+                    crate::logger::warn(&format!("⚠️ External edit falló (scene {}): {}", scene_id, e));
+                    state.show_toast(format!("External editor failed: {}", e), ToastKind::Error);
+                }
+            }
+            None
+        }
+
+        TheForgeMessage::ExternalEditPoll => {
+            let session = state.forge_external_edit.as_mut()?;
+
+            // Evita apilar lecturas si el FS está lento
+            if session.poll_in_flight {
+                return None;
+            }
+
+            // ⚠️ El editor externo puede no volver nunca: cortamos por inactividad
+            if session.last_activity.elapsed() >= Duration::from_secs(EXTERNAL_EDIT_IDLE_TIMEOUT_SECS) {
+                crate::forge_external::cleanup(&session.path);
+                state.forge_external_edit = None;
+                state.show_toast("External edit timed out (no changes detected)", ToastKind::Info);
+                return None;
+            }
+
+            session.poll_in_flight = true;
+            let path = session.path.clone();
+            let since = session.last_modified;
+            let scene_id = session.scene_id.clone();
+
+            Some(Task::perform(
+                crate::forge_external::read_if_modified(path, since),
+                move |res| Message::TheForge(TheForgeMessage::ExternalEditPolled {
+                    scene_id,
+                    result: res,
+                }),
+            ))
+        }
+
+        TheForgeMessage::ExternalEditPolled { scene_id, result } => {
+            // La sesión puede haber cambiado/terminado mientras leíamos
+            let is_current = state
+                .forge_external_edit
+                .as_ref()
+                .map(|s| s.scene_id == scene_id)
+                .unwrap_or(false);
+            if !is_current {
+                return None;
+            }
+
+            if let Some(session) = state.forge_external_edit.as_mut() {
+                session.poll_in_flight = false;
+            }
+
+            match result {
+                Ok(Some((body, mtime))) => {
+                    if let Some(session) = state.forge_external_edit.as_mut() {
+                        session.last_modified = mtime;
+                        session.last_activity = Instant::now();
+                    }
+
+                    if !apply_external_body(state, &scene_id, body) {
+                        // La escena ya no existe (borrada / otra novel): fin de sesión
+                        state.forge_external_edit = None;
+                        state.show_toast("External edit stopped: scene no longer exists", ToastKind::Error);
+                        return None;
+                    }
+
+                    state.show_toast("Scene updated from external editor", ToastKind::Success);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    // Archivo borrado/inaccesible: no hay nada más que vigilar
+                    crate::logger::warn(&format!("⚠️ External edit poll falló (scene {}): {}", scene_id, e));
+                    state.forge_external_edit = None;
+                    state.show_toast("External edit stopped (file unavailable)", ToastKind::Error);
+                }
+            }
+            None
+        }
+
+        TheForgeMessage::StopExternalEdit => {
+            let session = state.forge_external_edit.take()?;
+            crate::forge_external::cleanup(&session.path);
+            state.show_toast("External edit stopped", ToastKind::Info);
+            None
+        }
+
+        TheForgeMessage::EndRename => {
+            crate::logger::info("   💾 EndRename triggered");
+
+            // Undo: solo si el título cambió de verdad (un solo rename activo a la vez)
+            let original_title = state.forge_rename_original_title.take();
+
+            // --- NOVEL RENAME ---
+            if let Some(novel) = state.forge_renaming_novel_temp.take() {
+                crate::logger::info(&format!(
+                    "   ✅ Updating novel {} (title: '{}')",
+                    novel.id, novel.title
+                ));
+
+                // 1) Lista global de novels
+                if let Some(local_novel) = state.novels.iter_mut().find(|n| n.id == novel.id) {
+                    local_novel.title = novel.title.clone();
+                    local_novel.updated_at = novel.updated_at;
+                    crate::logger::info("   ✅ Updated global novel title");
+                } else {
+                    crate::logger::warn(&format!(
+                        "   ⚠️ Novel {} not found in global list",
+                        novel.id
+                    ));
+                }
+
+                match original_title.as_ref().filter(|t| **t != novel.title) {
+                    Some(original) => {
+                        let label = format!("Rename novel '{}'", original);
+                        let inverse = DbAction::UpdateNovel(crate::model::Novel { title: original.clone(), ..novel.clone() });
+                        state.queue_undoable(DbAction::UpdateNovel(novel), inverse, label);
+                    }
+                    None => state.queue(DbAction::UpdateNovel(novel)),
+                }
+            }
+
+            // --- CHAPTER RENAME ---
+            {
+                crate::logger::info("🏁 EndRename CHAPTER iniciado");
+
+                let temp = state.forge_renaming_chapter_temp.take();
+
+                if let Some(temp_chapter) = &temp {
+                    crate::logger::info(&format!("   📦 Temp tiene título: '{}'", temp_chapter.title));
+                } else {
+                    crate::logger::warn("   ⚠️ Temp es None!");
+                }
+
+                if let Some(chapter_id) = state.forge_renaming_chapter_id.clone() {
+                    crate::logger::info(&format!("   🔑 Chapter ID: {}", chapter_id));
+
+                    if let Some(chapter_from_temp) = temp {
+                        crate::logger::info(&format!(
+                            "   ✅ Persisting chapter rename {} (title: '{}')",
+                            chapter_from_temp.id, chapter_from_temp.title
+                        ));
+
+                        // ✅ Actualizar el árbol (chapters_by_novel_id)
+                        let mut found_in_tree = false;
+                        for (novel_id, chapters) in state.chapters_by_novel_id.iter_mut() {
+                            if let Some(local) = chapters.iter_mut().find(|c| c.id == chapter_id) {
+                                local.title = chapter_from_temp.title.clone();
+                                local.updated_at = chapter_from_temp.updated_at;
+                                crate::logger::info(&format!(
+                                    "   ✅ Árbol actualizado en EndRename con: '{}' (novel: {})",
+                                    local.title, novel_id
+                                ));
+                                found_in_tree = true;
+                                break;
+                            }
+                        }
+
+                        if !found_in_tree {
+                            crate::logger::warn(&format!("   ⚠️ Chapter {} NO encontrado en árbol para actualizar", chapter_id));
+                        }
 
                         // 🔍 VERIFICACIÓN: Leer del árbol inmediatamente después
                         for (_novel_id, chapters) in state.chapters_by_novel_id.iter() {
@@ -972,7 +1675,17 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                             crate::logger::warn(&format!("   ⚠️ Chapter {} NO encontrado en active_novel_chapters", chapter_id));
                         }
 
-                        state.queue(DbAction::UpdateChapter(chapter_from_temp));
+                        match original_title.as_ref().filter(|t| **t != chapter_from_temp.title) {
+                            Some(original) => {
+                                let label = format!("Rename chapter '{}'", original);
+                                let inverse = DbAction::UpdateChapter(crate::model::Chapter {
+                                    title: original.clone(),
+                                    ..chapter_from_temp.clone()
+                                });
+                                state.queue_undoable(DbAction::UpdateChapter(chapter_from_temp), inverse, label);
+                            }
+                            None => state.queue(DbAction::UpdateChapter(chapter_from_temp)),
+                        }
                         crate::logger::info("   💾 DbAction::UpdateChapter encolado");
                     } else {
                         crate::logger::warn(&format!(
@@ -1027,7 +1740,14 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
                     }
                 }
 
-                state.queue(DbAction::UpdateScene(scene));
+                match original_title.as_ref().filter(|t| **t != scene.title) {
+                    Some(original) => {
+                        let label = format!("Rename scene '{}'", original);
+                        let inverse = DbAction::UpdateScene(crate::model::Scene { title: original.clone(), ..scene.clone() });
+                        state.queue_undoable(DbAction::UpdateScene(scene), inverse, label);
+                    }
+                    None => state.queue(DbAction::UpdateScene(scene)),
+                }
             }
 
             // Limpiar estado de rename
@@ -1042,7 +1762,34 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             None
         }
 
+        TheForgeMessage::ExpandAllOutline => {
+            state.forge_expand_all_pending = true;
+
+            // Pedidos vía ForgeRequestLoad*: el gating de forge_data_controller evita duplicados
+            let mut loads = Vec::new();
+            for novel in &state.novels {
+                state.expanded_novels.insert(novel.id.clone());
+                loads.push(Task::done(Message::ForgeRequestLoadChapters(novel.id.clone())));
+            }
+            for chapters in state.chapters_by_novel_id.values() {
+                for chapter in chapters {
+                    state.expanded_chapters.insert(chapter.id.clone());
+                    loads.push(Task::done(Message::ForgeRequestLoadScenes(chapter.id.clone())));
+                }
+            }
+            Some(Task::batch(loads))
+        }
+
+        TheForgeMessage::CollapseAllOutline => {
+            // Selección intacta: el editor sigue mostrando la escena actual
+            state.forge_expand_all_pending = false;
+            state.expanded_novels.clear();
+            state.expanded_chapters.clear();
+            None
+        }
+
         TheForgeMessage::ToggleNovel(novel_id) => {
+            state.forge_expand_all_pending = false;
             if state.expanded_novels.contains(&novel_id) {
                 // ✅ Colapsar: solo toggle visual
                 state.expanded_novels.remove(&novel_id);
@@ -1060,103 +1807,1190 @@ pub fn update(state: &mut AppState, message: TheForgeMessage) -> Option<Task<Mes
             }
         }
 
-        TheForgeMessage::ToggleChapter(chapter_id) => {
-            // 🛑 PRO: si estamos en modo rename, no permitimos toggles que limpien listas.
-            // Esto evita el bug de “renombré chapter y desaparecieron scenes”.
-            let renaming_any = state.forge_renaming_novel_id.is_some()
-                || state.forge_renaming_chapter_id.is_some()
-                || state.forge_renaming_scene_id.is_some();
+        TheForgeMessage::ToggleSceneNotes => {
+            state.forge_notes_open = !state.forge_notes_open;
+            None
+        }
 
-            if renaming_any {
-                // Cerramos rename de forma segura, pero NO colapsamos ni limpiamos nada.
-                return Some(Task::done(Message::TheForge(TheForgeMessage::EndRename)));
+        TheForgeMessage::ToggleSceneRevisions => {
+            state.forge_revisions_open = !state.forge_revisions_open;
+            if !state.forge_revisions_open {
+                // Al reabrir se vuelve a pedir (pudo haber saves con el panel cerrado)
+                state.forge_revisions_for = None;
+                state.forge_revisions.clear();
+                state.forge_revision_selected = None;
             }
+            None
+        }
 
-            let is_expanded = state.expanded_chapters.contains(&chapter_id);
+        TheForgeMessage::PreviewSceneRevision(revision_id) => {
+            state.forge_revision_selected = if state.forge_revision_selected.as_ref() == Some(&revision_id) {
+                None
+            } else {
+                Some(revision_id)
+            };
+            None
+        }
 
-            if is_expanded {
-                // ✅ Collapse
-                state.expanded_chapters.remove(&chapter_id);
+        TheForgeMessage::RestoreSceneRevision(revision_id) => {
+            let scene_id = state.active_scene_id.clone()?;
+            let revision = state
+                .forge_revisions
+                .iter()
+                .find(|r| r.id == revision_id && r.scene_id == scene_id)?
+                .clone();
 
-                if state.active_chapter_id.as_ref() == Some(&chapter_id) {
-                    state.active_chapter_id = None;
-                    state.active_chapter_scenes.clear();
-                    state.active_scene_id = None;
+            let current = state.forge_content.text();
+            if current == revision.body {
+                state.show_toast("The editor already matches this version", ToastKind::Info);
+                return None;
+            }
+
+            // Restore = un solo paso de undo (ráfaga propia)
+            state.forge_history.ensure_scene(&scene_id);
+            state.forge_history.close_burst();
+            state.forge_history.before_edit(&current);
+            state.forge_history.close_burst();
+
+            state.forge_content = text_editor::Content::with_text(&revision.body);
+            state.forge_dirty = true;
+            sync_active_scene_body(state);
+            refresh_find_total(state);
+            state.forge_revision_selected = None;
+            state.show_toast("Version restored (Ctrl+Z to undo)", ToastKind::Success);
+
+            // Entra por el debounce del autosave: el save genera su propia revisión
+            note_writing_activity(state);
+            Some(schedule_autosave(state))
+        }
+
+        TheForgeMessage::SceneNotesChanged(action) => {
+            let scene_id = state.active_scene_id.clone()?;
+            let (notes_scene_id, content) = state.forge_notes_editor.as_mut()?;
+            if *notes_scene_id != scene_id {
+                return None;
+            }
+
+            let is_edit = action.is_edit();
+            content.perform(action);
+            if !is_edit {
+                return None; // cursor / selección: nada que guardar
+            }
+            let notes = content.text();
+
+            // Vista activa + árbol: el UpdateScene del autosave lleva las notas
+            // (fuera del body => no cuentan para word_count)
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                s.notes = notes.clone();
+            }
+            for list in state.scenes_by_chapter_id.values_mut() {
+                if let Some(s) = list.iter_mut().find(|s| s.id == scene_id) {
+                    s.notes = notes;
+                    break;
                 }
+            }
 
-                None
-            } else {
-                // ✅ Expand
-                state.expanded_chapters.insert(chapter_id.clone());
-                state.active_chapter_id = Some(chapter_id.clone());
-                state.active_scene_id = None;
+            // Mismo debounce que el body
+            state.forge_dirty = true;
+            Some(schedule_autosave(state))
+        }
 
-                // ✅ NUEVO (FASE 2): pedir carga (sin DB aquí)
-                Some(Task::done(Message::ForgeRequestLoadScenes(chapter_id)))
+        TheForgeMessage::SceneKindChanged(kind) => {
+            let scene_id = state.active_scene_id.clone()?;
+
+            let mut updated: Option<crate::model::Scene> = None;
+
+            // Vista activa (tiene el body más reciente del editor)
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                if s.kind() == kind {
+                    return None;
+                }
+                s.scene_kind = kind.as_str().to_string();
+                updated = Some(s.clone());
+            }
+
+            // Árbol (source of truth del outline)
+            if let Some(scene) = updated.as_ref()
+                && let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                && let Some(s) = list.iter_mut().find(|s| s.id == scene_id)
+            {
+                s.scene_kind = scene.scene_kind.clone();
+            }
+
+            if let Some(scene) = updated {
+                state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+                state.queue(DbAction::UpdateScene(scene));
             }
+            None
         }
 
-        // ✅ NUEVO: Drag & Drop
-        TheForgeMessage::ChapterDragged(chapter_id, new_position) => {
-            state.queue(DbAction::ReorderChapter(chapter_id, new_position as i64));
+        TheForgeMessage::SceneIncludeInCompileToggled(include) => {
+            let scene_id = state.active_scene_id.clone()?;
+
+            // Vista activa (body más reciente) -> árbol, igual que SceneKindChanged
+            let mut updated: Option<crate::model::Scene> = None;
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                if s.include_in_compile == include {
+                    return None;
+                }
+                s.include_in_compile = include;
+                updated = Some(s.clone());
+            }
+
+            if let Some(scene) = updated {
+                if let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                    && let Some(s) = list.iter_mut().find(|s| s.id == scene_id)
+                {
+                    s.include_in_compile = include;
+                }
+                state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+                state.queue(DbAction::UpdateScene(scene));
+            }
             None
         }
 
-        TheForgeMessage::SceneDragged(scene_id, new_position) => {
-            state.queue(DbAction::ReorderScene(scene_id, new_position as i64));
+        TheForgeMessage::SceneTargetChanged(raw) => {
+            // Input no numérico: se ignora (el campo no cambia)
+            let target = parse_word_target(&raw)?;
+            let scene_id = state.active_scene_id.clone()?;
+
+            // Mismo flujo que SceneIncludeInCompileToggled: vista activa -> árbol -> DB
+            // (body sincronizado antes: el UpdateScene no debe pisar lo último tipeado)
+            sync_active_scene_body(state);
+            let mut updated: Option<crate::model::Scene> = None;
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                if s.target_words == target {
+                    return None;
+                }
+                s.target_words = target;
+                updated = Some(s.clone());
+            }
+
+            if let Some(scene) = updated {
+                if let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                    && let Some(s) = list.iter_mut().find(|s| s.id == scene_id)
+                {
+                    s.target_words = target;
+                }
+                state.queue(DbAction::UpdateScene(scene));
+            }
             None
         }
 
+        TheForgeMessage::ChapterTargetChanged(raw) => {
+            let target = parse_word_target(&raw)?;
+            let chapter_id = state.active_chapter_id.clone()?;
 
-    }
-}
+            let mut updated: Option<crate::model::Chapter> = None;
+            if let Some(c) = state.active_novel_chapters.iter_mut().find(|c| c.id == chapter_id) {
+                if c.target_words == target {
+                    return None;
+                }
+                c.target_words = target;
+                updated = Some(c.clone());
+            }
 
-// --- HELPER FUNCTIONS ---
+            if let Some(chapter) = updated {
+                if let Some(list) = state.chapters_by_novel_id.get_mut(&chapter.novel_id)
+                    && let Some(c) = list.iter_mut().find(|c| c.id == chapter_id)
+                {
+                    c.target_words = target;
+                }
+                state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+                state.queue(DbAction::UpdateChapter(chapter));
+            }
+            None
+        }
 
-fn reset_forge_state(state: &mut AppState) {
-    state.novels.clear();
-    state.active_novel_id = None;
-    // ✅ Al crear un novel nuevo: reseteamos SOLO la vista activa / selección,
-    // pero NO destruimos caches de otros novels.
-    state.active_novel_chapters.clear();
-    state.active_chapter_id = None;
-    state.active_chapter_scenes.clear();
-    state.active_scene_id = None;
-    state.expanded_chapters.clear();
-    state.expanded_novels.clear();
-    state.forge_renaming_novel_id = None;
-    state.forge_renaming_chapter_id = None;
-    state.forge_renaming_scene_id = None;
+        TheForgeMessage::CycleSceneStatus(scene_id) => {
+            // Body fresco si es la activa (el UpdateScene lleva la escena entera)
+            if state.active_scene_id.as_deref() == Some(scene_id.as_str()) {
+                sync_active_scene_body(state);
+            }
 
-    // ✅ NUEVO: Limpiar copias temporales
-    state.forge_renaming_novel_temp = None;
-    state.forge_renaming_chapter_temp = None;
-    state.forge_renaming_scene_temp = None;
+            let mut scene = state
+                .active_chapter_scenes
+                .iter()
+                .find(|s| s.id == scene_id)
+                .cloned()
+                .or_else(|| find_scene_anywhere(state, &scene_id))?;
+            scene.status = scene.work_status().next().as_str().to_string();
 
-    state.forge_content = text_editor::Content::new();
+            // Vista activa + árbol (el chapter deriva su estado de acá)
+            if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+                s.status = scene.status.clone();
+            }
+            if let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                && let Some(s) = list.iter_mut().find(|s| s.id == scene_id)
+            {
+                s.status = scene.status.clone();
+            }
 
-    cancel_debounce(state);
-}
-fn auto_save_before_switch(state: &mut AppState) {
-    if let Some(scene_id) = &state.active_scene_id {
-        if let Some(scene) = state.active_chapter_scenes.iter().find(|s| s.id == *scene_id).cloned() {
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
             state.queue(DbAction::UpdateScene(scene));
-            crate::logger::info("Auto-saved before switching");
+            None
         }
-    }
-}
+
+        TheForgeMessage::JumpToAnnotation { char_offset, char_len } => {
+            // Seleccionar el marcador para que se vea dónde está
+            select_char_range(&mut state.forge_content, char_offset, char_len);
+            None
+        }
+
+        TheForgeMessage::JumpToNovel { universe_id, novel_id } => begin_forge_jump(state, universe_id, novel_id, None, None),
+
+        TheForgeMessage::JumpToScene { universe_id, novel_id, chapter_id, scene_id } => {
+            begin_forge_jump(state, universe_id, novel_id, Some(chapter_id), Some(scene_id))
+        }
+
+        TheForgeMessage::OpenRestored(result) => match result {
+            Ok(Some(path)) => begin_forge_jump(state, path.universe_id, path.novel_id, path.chapter_id, path.scene_id),
+            Ok(None) => {
+                state.show_toast("Restored item could not be found", ToastKind::Error);
+                None
+            }
+            Err(e) => {
+                crate::logger::error(&format!("❌ Resolve restored forge item failed: {}", e));
+                state.show_toast(format!("Could not open restored item: {}", e), ToastKind::Error);
+                None
+            }
+        },
+
+        TheForgeMessage::TogglePreview => {
+            state.forge_preview_mode = !state.forge_preview_mode;
+            None
+        }
+
+        TheForgeMessage::ToggleManuscript => {
+            state.forge_manuscript_mode = !state.forge_manuscript_mode;
+            state.forge_manuscript_requested = None;
+            if !state.forge_manuscript_mode {
+                return None;
+            }
+
+            // Lo que esté en el editor entra al árbol (la vista lee scenes_by_chapter_id)
+            sync_active_scene_body(state);
+            if let Some(scene) = state.active_scene_id.as_ref().and_then(|id| {
+                state.active_chapter_scenes.iter().find(|s| s.id == *id).cloned()
+            }) && let Some(list) = state.scenes_by_chapter_id.get_mut(&scene.chapter_id)
+                && let Some(s) = list.iter_mut().find(|s| s.id == scene.id)
+            {
+                *s = scene;
+            }
+
+            // Chapters del novel activo; las scenes faltantes las pide post_event en un solo fetch
+            state
+                .active_novel_id
+                .clone()
+                .map(|novel_id| Task::done(Message::ForgeRequestLoadChapters(novel_id)))
+        }
+
+        // Solo layout: no toca active_* ni el debounce (el autosave sigue su curso)
+        TheForgeMessage::ToggleFocusMode => {
+            state.forge_focus_mode = !state.forge_focus_mode;
+            None
+        }
+
+        // =========================================================
+        // SEARCH (todas las escenas del novel activo)
+        // =========================================================
+        TheForgeMessage::SearchQueryChanged(query) => {
+            state.forge_search_query = query;
+            state.forge_search_debounce_id = state.forge_search_debounce_id.wrapping_add(1);
+
+            if state.forge_search_query.trim().is_empty() {
+                state.forge_search_results.clear();
+                state.forge_search_novel_id = None;
+                return None;
+            }
+
+            let id = state.forge_search_debounce_id;
+            Some(Task::perform(
+                async move {
+                    tokio::time::sleep(Duration::from_millis(SEARCH_DEBOUNCE_MS)).await;
+                    id
+                },
+                |id| Message::TheForge(TheForgeMessage::SearchDebounced(id)),
+            ))
+        }
+
+        TheForgeMessage::SearchDebounced(id) => {
+            // Solo el último tecleo dispara la query
+            if id != state.forge_search_debounce_id {
+                return None;
+            }
+            let query = state.forge_search_query.trim().to_string();
+            if let (Some(novel_id), false) = (state.active_novel_id.clone(), query.is_empty()) {
+                state.forge_search_requested = Some((novel_id, query));
+            }
+            None
+        }
+
+        TheForgeMessage::SearchCleared => {
+            state.forge_search_query.clear();
+            state.forge_search_results.clear();
+            state.forge_search_novel_id = None;
+            state.forge_search_debounce_id = state.forge_search_debounce_id.wrapping_add(1);
+            None
+        }
+
+        TheForgeMessage::OpenSearchHit { chapter_id, scene_id } => {
+            let novel_id = state.forge_search_novel_id.clone()?;
+            // Mismo salto que mentions/restore: expande el chapter y selecciona la escena
+            begin_forge_jump(state, None, novel_id, Some(chapter_id), Some(scene_id))
+        }
+
+        // =========================================================
+        // FIND / REPLACE (editor de la escena activa)
+        // =========================================================
+        TheForgeMessage::ToggleFindBar => {
+            state.forge_find_open = !state.forge_find_open;
+            if !state.forge_find_open {
+                return None;
+            }
+
+            // Selección de una línea => query inicial (como en cualquier editor)
+            if let Some(sel) = state.forge_content.selection()
+                && !sel.is_empty()
+                && !sel.contains('\n')
+            {
+                state.forge_find_query = sel;
+            }
+            state.forge_find_index = None;
+            refresh_find_total(state);
+            Some(operation::focus::<Message>(Id::new("forge_find_query")))
+        }
+
+        TheForgeMessage::FindQueryChanged(query) => {
+            state.forge_find_query = query;
+            state.forge_find_index = None;
+            refresh_find_total(state);
+            None
+        }
+
+        TheForgeMessage::ReplaceTextChanged(text) => {
+            state.forge_replace_text = text;
+            None
+        }
+
+        TheForgeMessage::FindCaseSensitiveToggled => {
+            state.forge_find_case_sensitive = !state.forge_find_case_sensitive;
+            state.forge_find_index = None;
+            refresh_find_total(state);
+            None
+        }
+
+        TheForgeMessage::FindNext => {
+            step_find(state, true);
+            None
+        }
+
+        TheForgeMessage::FindPrevious => {
+            step_find(state, false);
+            None
+        }
+
+        TheForgeMessage::ReplaceOne => {
+            state.active_scene_id.as_ref()?;
+
+            let body = state.forge_content.text();
+            let matches = crate::forge_find::find_matches(&body, &state.forge_find_query, state.forge_find_case_sensitive);
+            let Some(m) = state.forge_find_index.and_then(|i| matches.get(i).copied()) else {
+                step_find(state, true);
+                return None;
+            };
+
+            // Si el usuario movió el cursor, primero volvemos a mostrar el match (no reemplazamos a ciegas)
+            if state.forge_content.selection().as_deref() != Some(&body[m.start..m.end]) {
+                select_char_range(&mut state.forge_content, m.char_offset, m.char_len);
+                return None;
+            }
+
+            // Paste sobre la selección: mismo camino que tipear (dirty + word count + debounce)
+            let replacement = state.forge_replace_text.clone();
+            let task = update(
+                state,
+                TheForgeMessage::SceneBodyChanged(text_editor::Action::Edit(text_editor::Edit::Paste(
+                    std::sync::Arc::new(replacement.clone()),
+                ))),
+            );
+
+            // Siguiente match después del reemplazo (si no hay, vuelve al primero)
+            let body = state.forge_content.text();
+            let matches = crate::forge_find::find_matches(&body, &state.forge_find_query, state.forge_find_case_sensitive);
+            state.forge_find_total = matches.len();
+            let after = m.start + replacement.len();
+            state.forge_find_index = matches.iter().position(|x| x.start >= after).or((!matches.is_empty()).then_some(0));
+            if let Some(next) = state.forge_find_index.map(|i| matches[i]) {
+                select_char_range(&mut state.forge_content, next.char_offset, next.char_len);
+            }
+            task
+        }
+
+        TheForgeMessage::ReplaceAll => {
+            state.active_scene_id.as_ref()?;
+
+            let body = state.forge_content.text();
+            let matches = crate::forge_find::find_matches(&body, &state.forge_find_query, state.forge_find_case_sensitive);
+            if matches.is_empty() {
+                state.show_toast("No matches", ToastKind::Info);
+                return None;
+            }
+
+            // Replace all = un solo paso de undo (ráfaga propia)
+            if let Some(scene_id) = state.active_scene_id.as_deref() {
+                state.forge_history.ensure_scene(scene_id);
+                state.forge_history.close_burst();
+                state.forge_history.before_edit(&body);
+                state.forge_history.close_burst();
+            }
+
+            // El rebuild del Content resetea el cursor: lo medimos antes y lo reubicamos después
+            let cursor = editor_cursor_chars(&mut state.forge_content);
+            let replacement = state.forge_replace_text.clone();
+            let new_body = crate::forge_find::replace_all(&body, &matches, &replacement);
+            let new_cursor = crate::forge_find::shift_char_offset(cursor, &matches, replacement.chars().count());
+
+            state.forge_content = text_editor::Content::with_text(&new_body);
+            select_char_range(&mut state.forge_content, new_cursor, 0);
+            state.forge_dirty = true;
+            sync_active_scene_body(state);
+
+            state.forge_find_index = None;
+            refresh_find_total(state);
+            state.show_toast(format!("Replaced {} occurrence(s)", matches.len()), ToastKind::Success);
+
+            // Un solo UpdateScene: entra por el debounce del autosave
+            note_writing_activity(state);
+            Some(schedule_autosave(state))
+        }
+
+        TheForgeMessage::ToggleOutlinePanel => {
+            state.forge_outline_collapsed = !state.forge_outline_collapsed;
+            None
+        }
+
+        TheForgeMessage::ToggleChapter(chapter_id) => {
+            // 🛑 PRO: si estamos en modo rename, no permitimos toggles que limpien listas.
+            // Esto evita el bug de “renombré chapter y desaparecieron scenes”.
+            let renaming_any = state.forge_renaming_novel_id.is_some()
+                || state.forge_renaming_chapter_id.is_some()
+                || state.forge_renaming_scene_id.is_some();
+
+            if renaming_any {
+                // Cerramos rename de forma segura, pero NO colapsamos ni limpiamos nada.
+                return Some(Task::done(Message::TheForge(TheForgeMessage::EndRename)));
+            }
+
+            state.forge_expand_all_pending = false;
+            let is_expanded = state.expanded_chapters.contains(&chapter_id);
+
+            if is_expanded {
+                // ✅ Collapse
+                state.expanded_chapters.remove(&chapter_id);
+
+                if state.active_chapter_id.as_ref() == Some(&chapter_id) {
+                    state.active_chapter_id = None;
+                    state.active_chapter_scenes.clear();
+                    state.active_scene_id = None;
+                }
+
+                None
+            } else {
+                // ✅ Expand
+                state.expanded_chapters.insert(chapter_id.clone());
+                state.active_chapter_id = Some(chapter_id.clone());
+                state.active_scene_id = None;
+
+                // ✅ NUEVO (FASE 2): pedir carga (sin DB aquí)
+                Some(Task::done(Message::ForgeRequestLoadScenes(chapter_id)))
+            }
+        }
+
+        TheForgeMessage::SetActiveNodeExpanded(expand) => {
+            // Nodo activo = el más profundo con hijos (chapter > novel). Con una scene
+            // seleccionada, ← colapsa su chapter (como cualquier árbol).
+            if let Some(chapter_id) = state.active_chapter_id.clone() {
+                if state.expanded_chapters.contains(&chapter_id) == expand {
+                    return None;
+                }
+                // ToggleChapter ya redirige a EndRename si hay rename activo
+                return update(state, TheForgeMessage::ToggleChapter(chapter_id));
+            }
+
+            let novel_id = state.active_novel_id.clone()?;
+            if state.expanded_novels.contains(&novel_id) == expand {
+                return None;
+            }
+
+            // Mismo guard que ToggleChapter: no tocar el árbol con un rename abierto
+            let renaming_any = state.forge_renaming_novel_id.is_some()
+                || state.forge_renaming_chapter_id.is_some()
+                || state.forge_renaming_scene_id.is_some();
+            if renaming_any {
+                return Some(Task::done(Message::TheForge(TheForgeMessage::EndRename)));
+            }
+
+            update(state, TheForgeMessage::ToggleNovel(novel_id))
+        }
+
+        // ✅ NUEVO: Drag & Drop
+        // Reorden optimista (índice + renumerado 0..n, igual que la DB) y luego persistimos
+        TheForgeMessage::ChapterDragged(chapter_id, new_position) => {
+            let novel_id = state
+                .chapters_by_novel_id
+                .iter()
+                .find(|(_, list)| list.iter().any(|c| c.id == chapter_id))
+                .map(|(nid, _)| nid.clone());
+
+            let mut undo: Option<(i64, String)> = None;
+            if let Some(list) = novel_id.as_ref().and_then(|nid| state.chapters_by_novel_id.get_mut(nid))
+                && let Some(from) = list.iter().position(|c| c.id == chapter_id)
+            {
+                if from != new_position.min(list.len() - 1) {
+                    undo = Some((from as i64, format!("Reorder chapter '{}'", list[from].title)));
+                }
+                let ch = list.remove(from);
+                list.insert(new_position.min(list.len()), ch);
+                for (i, c) in list.iter_mut().enumerate() {
+                    c.position = i as i64;
+                }
+            }
+            if let Some(nid) = novel_id
+                && state.active_novel_id.as_deref() == Some(nid.as_str())
+            {
+                state.active_novel_chapters = state.chapters_by_novel_id.get(&nid).cloned().unwrap_or_default();
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            let action = DbAction::ReorderChapter(chapter_id.clone(), new_position as i64);
+            match undo {
+                Some((from, label)) => state.queue_undoable(action, DbAction::ReorderChapter(chapter_id, from), label),
+                None => state.queue(action),
+            }
+            None
+        }
+
+        TheForgeMessage::SceneDragged(scene_id, new_position) => {
+            let mut undo: Option<(i64, String)> = None;
+            if let Some(chapter_id) = scene_chapter_id(state, &scene_id) {
+                if let Some(list) = state.scenes_by_chapter_id.get_mut(&chapter_id)
+                    && let Some(from) = list.iter().position(|s| s.id == scene_id)
+                {
+                    if from != new_position.min(list.len() - 1) {
+                        undo = Some((from as i64, format!("Reorder scene '{}'", list[from].title)));
+                    }
+                    let sc = list.remove(from);
+                    list.insert(new_position.min(list.len()), sc);
+                    for (i, s) in list.iter_mut().enumerate() {
+                        s.position = i as i64;
+                    }
+                }
+                sync_active_chapter_scenes(state, &chapter_id);
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            let action = DbAction::ReorderScene(scene_id.clone(), new_position as i64);
+            match undo {
+                Some((from, label)) => state.queue_undoable(action, DbAction::ReorderScene(scene_id, from), label),
+                None => state.queue(action),
+            }
+            None
+        }
+
+        // ▲/▼: swap con el vecino = drag de una posición (mismo reorden optimista + undo)
+        TheForgeMessage::MoveChapterUp(chapter_id) => move_chapter_step(state, chapter_id, true),
+        TheForgeMessage::MoveChapterDown(chapter_id) => move_chapter_step(state, chapter_id, false),
+        TheForgeMessage::MoveSceneUp(scene_id) => move_scene_step(state, scene_id, true),
+        TheForgeMessage::MoveSceneDown(scene_id) => move_scene_step(state, scene_id, false),
+
+        TheForgeMessage::SceneDraggedToChapter { scene_id, chapter_id, new_position } => {
+            let from_chapter_id = scene_chapter_id(state, &scene_id)?;
+
+            let moved = state.scenes_by_chapter_id.get_mut(&from_chapter_id).and_then(|list| {
+                let from = list.iter().position(|s| s.id == scene_id)?;
+                let sc = list.remove(from);
+                for (i, s) in list.iter_mut().enumerate() {
+                    s.position = i as i64;
+                }
+                Some(sc)
+            });
+
+            // Si el destino no está cargado no inventamos una lista parcial: el refetch la trae
+            if let (Some(mut sc), Some(list)) = (moved, state.scenes_by_chapter_id.get_mut(&chapter_id)) {
+                sc.chapter_id = chapter_id.clone();
+                list.insert(new_position.min(list.len()), sc);
+                for (i, s) in list.iter_mut().enumerate() {
+                    s.position = i as i64;
+                }
+            }
+            sync_active_chapter_scenes(state, &from_chapter_id);
+            sync_active_chapter_scenes(state, &chapter_id);
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+
+            state.queue(DbAction::MoveSceneToChapterAt {
+                scene_id,
+                from_chapter_id,
+                chapter_id,
+                new_index: new_position as i64,
+            });
+            None
+        }
+
+        TheForgeMessage::DragStart(item) => {
+            // Con un rename abierto el outline no se toca
+            let renaming_any = state.forge_renaming_novel_id.is_some()
+                || state.forge_renaming_chapter_id.is_some()
+                || state.forge_renaming_scene_id.is_some();
+            if !renaming_any {
+                state.forge_drag = Some(ForgeDrag {
+                    item,
+                    drag_start: iced::Point::new(0.0, 0.0),
+                    current_cursor: iced::Point::new(0.0, 0.0),
+                    active: false,
+                });
+                state.forge_drag_hover = None;
+            }
+            None
+        }
+
+        TheForgeMessage::DragHover(target) => {
+            if state.forge_drag.is_some() {
+                state.forge_drag_hover = Some(target);
+            }
+            None
+        }
+
+
+    }
+}
+
+// --- HELPER FUNCTIONS ---
+
+// exports/<proyecto>/novel-<slug>-<ts>.<ext>; None sin proyecto abierto
+fn novel_export_target(
+    state: &AppState,
+    novel_id: &str,
+    path_for: fn(&std::path::Path, &str) -> std::path::PathBuf,
+) -> Option<std::path::PathBuf> {
+    let project = state.active_project.as_ref()?;
+    let title = state
+        .novels
+        .iter()
+        .find(|n| n.id == novel_id)
+        .map(|n| n.title.as_str())
+        .unwrap_or_default();
+    Some(path_for(&std::path::PathBuf::from(&project.path), title))
+}
+
+fn reset_forge_state(state: &mut AppState) {
+    state.novels.clear();
+    state.active_novel_id = None;
+    // ✅ Al crear un novel nuevo: reseteamos SOLO la vista activa / selección,
+    // pero NO destruimos caches de otros novels.
+    state.active_novel_chapters.clear();
+    state.active_chapter_id = None;
+    state.active_chapter_scenes.clear();
+    state.active_scene_id = None;
+    state.expanded_chapters.clear();
+    state.expanded_novels.clear();
+    state.forge_renaming_novel_id = None;
+    state.forge_renaming_chapter_id = None;
+    state.forge_renaming_scene_id = None;
+
+    // ✅ NUEVO: Limpiar copias temporales
+    state.forge_renaming_novel_temp = None;
+    state.forge_renaming_chapter_temp = None;
+    state.forge_renaming_scene_temp = None;
+
+    state.forge_content = text_editor::Content::new();
+    state.forge_dirty = false;
+    state.forge_annotations.clear();
+
+    cancel_debounce(state);
+}
+fn auto_save_before_switch(state: &mut AppState) {
+    if let Some(scene_id) = &state.active_scene_id {
+        if let Some(scene) = state.active_chapter_scenes.iter().find(|s| s.id == *scene_id).cloned() {
+            state.queue(DbAction::UpdateScene(scene));
+            crate::logger::info("Auto-saved before switching");
+        }
+    }
+}
+
+/// Pausa más larga que esto entre ediciones => la racha de escritura vuelve a empezar
+const WRITING_IDLE_RESET_SECS: u64 = 5 * 60;
+
+fn note_writing_activity(state: &mut AppState) {
+    if !state.settings.break_reminder_enabled {
+        return;
+    }
+    let now = Instant::now();
+    let idle_too_long = state
+        .writing_last_activity
+        .map(|t| now.duration_since(t).as_secs() >= WRITING_IDLE_RESET_SECS)
+        .unwrap_or(true);
+    if idle_too_long || state.writing_session_start.is_none() {
+        state.writing_session_start = Some(now);
+    }
+    state.writing_last_activity = Some(now);
+}
+
+/// Llamado en cada Tick: si la racha supera el intervalo configurado, sugerir una pausa.
+pub(crate) fn check_break_reminder(state: &mut AppState) {
+    if !state.settings.break_reminder_enabled {
+        return;
+    }
+    let (Some(start), Some(last)) = (state.writing_session_start, state.writing_last_activity) else {
+        return;
+    };
+
+    let now = Instant::now();
+    if now.duration_since(last).as_secs() >= WRITING_IDLE_RESET_SECS {
+        // Ya hizo una pausa por su cuenta
+        state.writing_session_start = None;
+        return;
+    }
+
+    let minutes = now.duration_since(start).as_secs() / 60;
+    if minutes >= state.settings.break_reminder_interval_min {
+        state.writing_session_start = None;
+        state.show_toast(
+            format!("You've been writing for {} minutes. Time for a short break?", minutes),
+            ToastKind::Info,
+        );
+    }
+}
 
 pub(crate) fn cancel_debounce(state: &mut AppState) {
     state.forge_last_edit = None;
     state.forge_debounce_task_id = None;
 }
 
-fn count_words(text: &str) -> i64 {
+// Aplica el body importado desde el editor externo a todas las copias en memoria
+// (árbol + vista activa + editor) y persiste. Devuelve false si la escena ya no existe.
+fn apply_external_body(state: &mut AppState, scene_id: &str, body: String) -> bool {
+    let Some(mut scene) = find_scene_anywhere(state, scene_id) else {
+        return false;
+    };
+
+    scene.body = body;
+    scene.word_count = count_words(&scene.body);
+    scene.updated_at = chrono::Utc::now();
+
+    if let Some(s) = state
+        .scenes_by_chapter_id
+        .get_mut(&scene.chapter_id)
+        .and_then(|list| list.iter_mut().find(|s| s.id == scene_id))
+    {
+        *s = scene.clone();
+    }
+
+    if let Some(s) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) {
+        *s = scene.clone();
+    }
+
+    if state.active_scene_id.as_deref() == Some(scene_id) {
+        // El texto externo manda: descartamos el debounce pendiente del editor interno
+        cancel_debounce(state);
+        state.forge_content = text_editor::Content::with_text(&scene.body);
+        state.forge_dirty = true;
+        refresh_annotations(state);
+    }
+
+    state.queue(DbAction::UpdateScene(scene));
+    true
+}
+
+/// Navega a novel/chapter/scene (lo más profundo que venga) aunque nada esté cargado aún.
+fn begin_forge_jump(
+    state: &mut AppState,
+    universe_id: Option<String>,
+    novel_id: String,
+    chapter_id: Option<String>,
+    scene_id: Option<String>,
+) -> Option<Task<Message>> {
+    auto_save_before_switch(state);
+
+    // Salimos de cualquier modal/ruta de origen (ej: editor de criatura)
+    state.creature_editor = None;
+    state.route = crate::app::Route::Forge;
+
+    if universe_id.is_some() && state.loaded_forge_universe != universe_id {
+        state.loaded_forge_universe = universe_id;
+        reset_forge_state(state);
+    }
+
+    state.active_novel_id = Some(novel_id.clone());
+    state.active_chapter_id = chapter_id.clone();
+    state.active_scene_id = None;
+    state.expanded_novels.insert(novel_id.clone());
+    if let Some(cid) = chapter_id.as_ref() {
+        state.expanded_chapters.insert(cid.clone());
+    }
+
+    state.forge_pending_jump = Some(crate::state::ForgeJumpTarget {
+        novel_id: novel_id.clone(),
+        chapter_id: chapter_id.clone(),
+        scene_id,
+    });
+
+    // Si ya está todo en cache, el salto es inmediato
+    if apply_pending_forge_jump(state) {
+        return None;
+    }
+
+    // Si no, pedimos la cadena de carga; cada fetch re-aplica el salto
+    let mut loads = vec![
+        Task::done(Message::ForgeRequestLoadNovels),
+        Task::done(Message::ForgeRequestLoadChapters(novel_id)),
+    ];
+    if let Some(cid) = chapter_id {
+        loads.push(Task::done(Message::ForgeRequestLoadScenes(cid)));
+    }
+    Some(Task::batch(loads))
+}
+
+/// Re-aplica `forge_pending_jump` sobre lo que haya en cache (lo llaman los saltos y
+/// los handlers de fetch de Forge, porque el safe-fallback pisa la selección mientras carga).
+/// Devuelve true si el destino quedó seleccionado (y abierto en el editor, si era una escena).
+pub(crate) fn apply_pending_forge_jump(state: &mut AppState) -> bool {
+    let Some(jump) = state.forge_pending_jump.clone() else {
+        return false;
+    };
+
+    if !matches!(state.route, crate::app::Route::Forge) {
+        state.forge_pending_jump = None;
+        return false;
+    }
+
+    // Novels ya cargados y el target no está => abortar
+    if !state.novels.is_empty() && !state.novels.iter().any(|n| n.id == jump.novel_id) {
+        state.forge_pending_jump = None;
+        state.show_toast("Scene not found: its novel no longer exists", ToastKind::Error);
+        return false;
+    }
+
+    if state.active_novel_id.as_deref() != Some(jump.novel_id.as_str()) {
+        state.active_novel_id = Some(jump.novel_id.clone());
+        state.active_novel_chapters.clear();
+    }
+    let cached_chapters = state.chapters_by_novel_id.get(&jump.novel_id).cloned();
+    if let Some(cached) = cached_chapters.as_ref() {
+        state.active_novel_chapters = cached.clone();
+    }
+    state.expanded_novels.insert(jump.novel_id.clone());
+
+    // Solo novela: listo en cuanto sus capítulos están en cache
+    let Some(chapter_id) = jump.chapter_id.clone() else {
+        if cached_chapters.is_none() {
+            return false;
+        }
+        state.active_chapter_id = None;
+        return finish_forge_jump(state);
+    };
+
+    state.active_chapter_id = Some(chapter_id.clone());
+    state.expanded_chapters.insert(chapter_id.clone());
+
+    let Some(scenes) = state.scenes_by_chapter_id.get(&chapter_id).cloned() else {
+        return false;
+    };
+
+    // Solo capítulo: mostrar sus escenas sin abrir ninguna
+    let Some(scene_id) = jump.scene_id.as_ref() else {
+        state.active_chapter_scenes = scenes;
+        return finish_forge_jump(state);
+    };
+
+    let Some(scene) = scenes.iter().find(|s| &s.id == scene_id) else {
+        return false;
+    };
+
+    state.forge_content = text_editor::Content::with_text(&scene.body);
+    state.active_scene_id = Some(scene.id.clone());
+    state.active_chapter_scenes = scenes;
+    cancel_debounce(state);
+    state.forge_dirty = false;
+    refresh_annotations(state);
+
+    finish_forge_jump(state)
+}
+
+fn finish_forge_jump(state: &mut AppState) -> bool {
+    state.forge_pending_jump = None;
+    state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+    true
+}
+
+fn refresh_annotations(state: &mut AppState) {
+    state.forge_annotations = crate::forge_annotations::parse(&state.forge_content.text());
+}
+
+pub(crate) fn count_words(text: &str) -> i64 {
     text.split_whitespace().count() as i64
 }
 
-async fn debounce_save(id: u64) -> u64 {
-    tokio::time::sleep(Duration::from_millis(AUTOSAVE_DELAY_MS)).await;
+/// Self-heal liviano: si word_count no coincide con el body, lo corrige en memoria.
+/// Devuelve true si hubo drift (el caller decide si persistir).
+pub(crate) fn heal_scene_word_count(scene: &mut crate::model::Scene) -> bool {
+    let actual = count_words(&scene.body);
+    if scene.word_count == actual {
+        return false;
+    }
+    scene.word_count = actual;
+    true
+}
+
+// Undo (true) / redo (false) del body activo: rebuild del Content + autosave con debounce
+fn apply_history_step(state: &mut AppState, undo: bool) -> Option<Task<Message>> {
+    let scene_id = state.active_scene_id.clone()?;
+    state.forge_history.ensure_scene(&scene_id);
+
+    let current = state.forge_content.text();
+    let restored = if undo {
+        state.forge_history.undo(&current)
+    } else {
+        state.forge_history.redo(&current)
+    }?;
+
+    // Cursor donde empieza la diferencia (el rebuild del Content lo manda al inicio)
+    let cursor = crate::forge_history::common_prefix_chars(&current, &restored);
+    state.forge_content = text_editor::Content::with_text(&restored);
+    select_char_range(&mut state.forge_content, cursor, 0);
+
+    // word_count + dirty: el próximo DebounceComplete lo persiste
+    state.forge_dirty = true;
+    sync_active_scene_body(state);
+    refresh_find_total(state);
+
+    note_writing_activity(state);
+    Some(schedule_autosave(state))
+}
+
+// "Scene N" siguiente al mayor sufijo numérico del chapter (mínimo "Scene 2")
+fn next_scene_title(scenes: &[crate::model::Scene]) -> String {
+    let max_suffix = scenes
+        .iter()
+        .filter_map(|s| s.title.trim().strip_prefix("Scene")?.trim().parse::<u32>().ok())
+        .max()
+        .unwrap_or(1);
+    format!("Scene {}", max_suffix.max(1) + 1)
+}
+
+/// Palabras por minuto para estimar lectura (prosa promedio)
+pub const READING_WPM: i64 = 200;
+
+/// Guarda la expansión del outline si cambió desde el último guardado (una vez cargada la
+/// del universo actual: antes de eso un set vacío pisaría lo persistido).
+pub(crate) fn persist_outline_expansion(state: &mut AppState) {
+    if !state.forge_expansion_ready {
+        return;
+    }
+    let Some(universe_id) = state.loaded_forge_universe.clone() else {
+        return;
+    };
+    if state.forge_expansion_universe.as_ref() != Some(&universe_id) {
+        return;
+    }
+    let (saved_novels, saved_chapters) = &state.forge_expansion_saved;
+    if *saved_novels == state.expanded_novels && *saved_chapters == state.expanded_chapters {
+        return;
+    }
+
+    state.forge_expansion_saved = (state.expanded_novels.clone(), state.expanded_chapters.clone());
+    state.queue(DbAction::SaveOutlineExpansion {
+        universe_id,
+        novel_ids: state.expanded_novels.iter().cloned().collect(),
+        chapter_ids: state.expanded_chapters.iter().cloned().collect(),
+    });
+}
+
+/// Recalcula las stats del novel activo solo si cambió el novel, el outline o los caches.
+pub(crate) fn refresh_novel_stats(state: &mut AppState) {
+    let Some(novel_id) = state.active_novel_id.clone() else {
+        state.forge_novel_stats = None;
+        return;
+    };
+
+    let key = (state.forge_outline_version, state.forge_cache_generation);
+    if let Some(stats) = &state.forge_novel_stats
+        && stats.novel_id == novel_id && stats.key == key
+    {
+        return;
+    }
+
+    let mut words = 0i64;
+    let mut partial = false;
+    match state.chapters_by_novel_id.get(&novel_id) {
+        Some(chapters) => {
+            for chapter in chapters {
+                if !state.forge_scenes_loaded_for.contains_key(&chapter.id)
+                    && !state.scenes_by_chapter_id.contains_key(&chapter.id)
+                {
+                    partial = true;
+                    continue;
+                }
+                words += state
+                    .scenes_by_chapter_id
+                    .get(&chapter.id)
+                    .map(|v| v.iter().filter(|s| s.kind().counts_toward_goals()).map(|s| s.word_count).sum::<i64>())
+                    .unwrap_or(0);
+            }
+        }
+        None => partial = true,
+    }
+
+    state.forge_novel_stats = Some(crate::state::NovelStats { novel_id, key, words, partial });
+}
+
+// Meta de palabras desde el input: "" = sin meta (0); no numérico = ignorar
+fn parse_word_target(raw: &str) -> Option<i64> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Some(0);
+    }
+    if !raw.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    raw.parse::<i64>().ok().map(|n| n.min(MAX_WORD_TARGET))
+}
+
+// Body + word count del scene activo desde el editor (el debounce persiste la copia)
+fn sync_active_scene_body(state: &mut AppState) {
+    let Some(scene_id) = state.active_scene_id.clone() else {
+        return;
+    };
+    let Some(scene) = state.active_chapter_scenes.iter_mut().find(|s| s.id == scene_id) else {
+        return;
+    };
+
+    let text = state.forge_content.text();
+    let old_count = scene.word_count;
+    scene.word_count = count_words(&text);
+    scene.body = text;
+
+    let new_count = scene.word_count;
+    if new_count != old_count {
+        state.record_session_words(&scene_id, old_count, new_count);
+    }
+}
+
+/// Editor de notas atado a la escena activa: se rearma cuando cambia la selección
+/// (cualquier camino que cambie active_scene_id, sin tocar cada uno).
+pub(crate) fn sync_notes_editor(state: &mut AppState) {
+    let Some(scene_id) = state.active_scene_id.as_deref() else {
+        state.forge_notes_editor = None;
+        return;
+    };
+    if state.forge_notes_editor.as_ref().is_some_and(|(id, _)| id == scene_id) {
+        return;
+    }
+    state.forge_notes_editor = state
+        .active_chapter_scenes
+        .iter()
+        .find(|s| s.id == scene_id)
+        .map(|s| (s.id.clone(), text_editor::Content::with_text(&s.notes)));
+}
+
+fn schedule_autosave(state: &mut AppState) -> Task<Message> {
+    state.forge_last_edit = Some(Instant::now());
+    let task_id = state.forge_debounce_task_id.unwrap_or(0) + 1;
+    state.forge_debounce_task_id = Some(task_id);
+
+    Task::perform(debounce_save(task_id, state.settings.forge_autosave_ms), |id| {
+        Message::TheForge(TheForgeMessage::DebounceComplete(id))
+    })
+}
+
+// Offsets en chars de content.text(): se traducen a línea/columna y se salta directo
+// (Down iría por líneas visuales y con wrap caería en otra línea).
+fn select_char_range(content: &mut text_editor::Content, char_offset: usize, char_len: usize) {
+    use text_editor::{Action, Cursor, Motion};
+
+    let start = char_position(content, char_offset);
+    let end = char_position(content, char_offset + char_len);
+
+    // DocumentStart descarta la selección previa; move_to no la limpia
+    content.perform(Action::Move(Motion::DocumentStart));
+    content.move_to(Cursor {
+        position: end,
+        selection: (char_len > 0).then_some(start),
+    });
+}
+
+// Línea + columna (en bytes, como la espera el editor) del char N del texto;
+// los saltos de línea cuentan lo mismo que en content.text().
+fn char_position(content: &text_editor::Content, char_offset: usize) -> text_editor::Position {
+    use text_editor::{LineEnding, Position};
+
+    let mut remaining = char_offset;
+    let mut last = Position { line: 0, column: 0 };
+    for (line, text) in content.lines().enumerate() {
+        let chars = text.text.chars().count();
+        if remaining <= chars {
+            let column = text.text.char_indices().nth(remaining).map_or(text.text.len(), |(i, _)| i);
+            return Position { line, column };
+        }
+        let ending = if text.ending == LineEnding::None {
+            LineEnding::default()
+        } else {
+            text.ending
+        };
+        remaining -= (chars + ending.as_str().len()).min(remaining);
+        last = Position { line, column: text.text.len() };
+    }
+    last
+}
+
+// Offset del cursor en chars (inicio de la selección si hay una): seleccionamos
+// hasta el inicio del documento y medimos. Deja el editor con esa selección.
+fn editor_cursor_chars(content: &mut text_editor::Content) -> usize {
+    use text_editor::{Action, Motion};
+
+    if content.selection().is_some() {
+        content.perform(Action::Move(Motion::Left));
+    }
+    content.perform(Action::Select(Motion::DocumentStart));
+    content.selection().map(|s| s.chars().count()).unwrap_or(0)
+}
+
+fn refresh_find_total(state: &mut AppState) {
+    state.forge_find_total = if state.forge_find_query.is_empty() {
+        0
+    } else {
+        crate::forge_find::find_matches(&state.forge_content.text(), &state.forge_find_query, state.forge_find_case_sensitive).len()
+    };
+}
+
+fn step_find(state: &mut AppState, forward: bool) {
+    if state.active_scene_id.is_none() || state.forge_find_query.is_empty() {
+        return;
+    }
+
+    let body = state.forge_content.text();
+    let matches = crate::forge_find::find_matches(&body, &state.forge_find_query, state.forge_find_case_sensitive);
+    state.forge_find_total = matches.len();
+
+    let n = matches.len();
+    if n == 0 {
+        state.forge_find_index = None;
+        state.show_toast("No matches", ToastKind::Info);
+        return;
+    }
+
+    let idx = match state.forge_find_index {
+        Some(i) if forward => (i % n + 1) % n,
+        Some(i) => (i % n + n - 1) % n,
+        None if forward => 0,
+        None => n - 1,
+    };
+    state.forge_find_index = Some(idx);
+    select_char_range(&mut state.forge_content, matches[idx].char_offset, matches[idx].char_len);
+}
+
+async fn debounce_save(id: u64, delay_ms: u64) -> u64 {
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
     id
 }
 
@@ -1164,9 +2998,145 @@ async fn debounce_save(id: u64) -> u64 {
 // MOUSE HANDLING
 // ============================================
 
-/// Maneja el evento MouseReleased para The Forge
-/// Por ahora es un stub - drag & drop se implementará después
-pub fn handle_mouse_released(_state: &mut AppState) {
-    // TODO: Implementar drag & drop para chapters y scenes
-    // Similar a pm_controller::handle_mouse_released
+// Umbral antes de considerar que un press en el handle es drag (no click)
+const FORGE_DRAG_THRESHOLD_PX: f32 = 6.0;
+
+pub fn handle_mouse_moved(state: &mut AppState, p: iced::Point) {
+    let Some(drag) = state.forge_drag.as_mut() else { return };
+
+    // Primera muestra: fija el origen (mismo criterio que pm_controller)
+    if !drag.active && drag.drag_start.x == 0.0 && drag.drag_start.y == 0.0 {
+        drag.drag_start = p;
+        drag.current_cursor = p;
+        return;
+    }
+
+    drag.current_cursor = p;
+
+    if !drag.active {
+        let dx = p.x - drag.drag_start.x;
+        let dy = p.y - drag.drag_start.y;
+        if (dx * dx + dy * dy).sqrt() > FORGE_DRAG_THRESHOLD_PX {
+            drag.active = true;
+        }
+    }
+}
+
+/// Drop del outline: resuelve destino (chapter/scene bajo el cursor) -> índice nuevo.
+/// La scene/chapter arrastrado ocupa el lugar del destino; soltar sobre un chapter
+/// manda la scene al final de ese chapter.
+pub fn handle_mouse_released(state: &mut AppState) {
+    // ✅ SIEMPRE limpiamos el drag state (aunque no haya destino válido)
+    let Some(drag) = state.forge_drag.take() else { return };
+    let target = state.forge_drag_hover.take();
+
+    if !drag.active {
+        return;
+    }
+    let Some(target) = target else { return };
+
+    match drag.item {
+        ForgeDragItem::Chapter(chapter_id) => {
+            let target_chapter = match target {
+                ForgeDragItem::Chapter(id) => Some(id),
+                ForgeDragItem::Scene(sid) => scene_chapter_id(state, &sid),
+            };
+            let Some(target_chapter) = target_chapter else { return };
+
+            // Solo dentro del mismo novel
+            let Some(list) = state
+                .chapters_by_novel_id
+                .values()
+                .find(|list| list.iter().any(|c| c.id == chapter_id))
+            else {
+                return;
+            };
+            let from = list.iter().position(|c| c.id == chapter_id);
+            let to = list.iter().position(|c| c.id == target_chapter);
+
+            if let (Some(from), Some(to)) = (from, to)
+                && from != to
+            {
+                update(state, TheForgeMessage::ChapterDragged(chapter_id, to));
+            }
+        }
+
+        ForgeDragItem::Scene(scene_id) => {
+            let Some(from_chapter) = scene_chapter_id(state, &scene_id) else { return };
+
+            let (to_chapter, to_index) = match target {
+                ForgeDragItem::Scene(sid) => {
+                    let Some(ch) = scene_chapter_id(state, &sid) else { return };
+                    let idx = state
+                        .scenes_by_chapter_id
+                        .get(&ch)
+                        .and_then(|list| list.iter().position(|s| s.id == sid))
+                        .unwrap_or(0);
+                    (ch, idx)
+                }
+                ForgeDragItem::Chapter(cid) => {
+                    let len = state.scenes_by_chapter_id.get(&cid).map(|l| l.len()).unwrap_or(0);
+                    // Mismo chapter: "al final" = último índice (la scene ya cuenta en len)
+                    let idx = if cid == from_chapter { len.saturating_sub(1) } else { len };
+                    (cid, idx)
+                }
+            };
+
+            if to_chapter == from_chapter {
+                let from = state
+                    .scenes_by_chapter_id
+                    .get(&from_chapter)
+                    .and_then(|list| list.iter().position(|s| s.id == scene_id));
+                if from.is_some_and(|f| f != to_index) {
+                    update(state, TheForgeMessage::SceneDragged(scene_id, to_index));
+                }
+            } else {
+                update(
+                    state,
+                    TheForgeMessage::SceneDraggedToChapter {
+                        scene_id,
+                        chapter_id: to_chapter,
+                        new_position: to_index,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Índice vecino dentro de una lista de `len` (None en los bordes: sin reorden no-op).
+fn step_index(from: usize, len: usize, up: bool) -> Option<usize> {
+    let to = if up { from.checked_sub(1)? } else { from + 1 };
+    (to < len).then_some(to)
+}
+
+fn move_chapter_step(state: &mut AppState, chapter_id: String, up: bool) -> Option<Task<Message>> {
+    let to = state
+        .chapters_by_novel_id
+        .values()
+        .find_map(|list| list.iter().position(|c| c.id == chapter_id).map(|from| step_index(from, list.len(), up)))??;
+    update(state, TheForgeMessage::ChapterDragged(chapter_id, to))
+}
+
+fn move_scene_step(state: &mut AppState, scene_id: String, up: bool) -> Option<Task<Message>> {
+    let chapter_id = scene_chapter_id(state, &scene_id)?;
+    let list = state.scenes_by_chapter_id.get(&chapter_id)?;
+    let from = list.iter().position(|s| s.id == scene_id)?;
+    let to = step_index(from, list.len(), up)?;
+    update(state, TheForgeMessage::SceneDragged(scene_id, to))
+}
+
+fn scene_chapter_id(state: &AppState, scene_id: &str) -> Option<String> {
+    state
+        .scenes_by_chapter_id
+        .iter()
+        .find(|(_, list)| list.iter().any(|s| s.id == scene_id))
+        .map(|(cid, _)| cid.clone())
+}
+
+// Vista del chapter activo: copia de la fuente de verdad tras un reorden
+fn sync_active_chapter_scenes(state: &mut AppState, chapter_id: &str) {
+    if state.active_chapter_id.as_deref() == Some(chapter_id) {
+        state.active_chapter_scenes = state.scenes_by_chapter_id.get(chapter_id).cloned().unwrap_or_default();
+    }
 }
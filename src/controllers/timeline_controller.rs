@@ -1,18 +1,126 @@
-use crate::app::{AppState, TimelineMessage, EventEditor, EraEditor};
+use crate::app::{AppState, Message, TimelineMessage, EventEditor, EraEditor};
 use crate::model::{TimelineEvent, TimelineEra};
 use crate::state::{DbAction, ToastKind};
+use iced::Task;
 use uuid::Uuid;
+use std::path::PathBuf;
 use std::time::Instant;
 
+/// Pares de eras cuyos rangos se pisan (índices en `eras`, el de menor start primero).
+/// Bordes iguales = se tocan, no se solapan. Eras sin fin (None / 0) no entran.
+pub fn overlapping_eras(eras: &[TimelineEra]) -> Vec<(usize, usize)> {
+    let bounded: Vec<(usize, i64, i64)> = eras
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| match e.end_year {
+            Some(end) if end != 0 => Some((i, e.start_year.min(end), e.start_year.max(end))),
+            _ => None,
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (a, &(i, start_a, end_a)) in bounded.iter().enumerate() {
+        for &(j, start_b, end_b) in &bounded[a + 1..] {
+            if start_a < end_b && start_b < end_a {
+                pairs.push(if start_a <= start_b { (i, j) } else { (j, i) });
+            }
+        }
+    }
+    pairs
+}
+
+/// Avisos legibles para la página (mismo formato de lista que integrity_issues).
+pub fn era_overlap_warnings(eras: &[TimelineEra]) -> Vec<String> {
+    overlapping_eras(eras)
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = (&eras[a], &eras[b]);
+            format!(
+                "\"{}\" ({}–{}) overlaps \"{}\" ({}–{})",
+                a.name,
+                a.start_year,
+                a.end_year.unwrap_or_default(),
+                b.name,
+                b.start_year,
+                b.end_year.unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn set_zoom(state: &mut AppState, zoom: f32) {
+    let zoom = crate::timeline_layout::clamp_zoom(zoom);
+    if zoom != state.timeline_zoom {
+        state.timeline_zoom = zoom;
+        state.rebuild_timeline_layout();
+    }
+}
 
 pub fn update(state: &mut AppState, message: TimelineMessage) {
     match message {
         TimelineMessage::Open(universe_id) => {
             state.event_editor = None;
             state.era_editor = None;
+            // Filtros por universo: una era de otro universo no aplica
+            state.timeline_era_filter = None;
+            state.timeline_importance_filter = None;
+            state.rebuild_timeline_layout();
+            // Escenas frescas al entrar (pudieron crearse / borrarse en la Forge)
+            state.timeline_scene_refs_for = None;
             state.route = crate::app::Route::Timeline { universe_id };
         }
 
+        // --- ZOOM (solo layout, sin DB) ---
+        TimelineMessage::ZoomIn => set_zoom(state, state.timeline_zoom * crate::timeline_layout::ZOOM_STEP),
+        TimelineMessage::ZoomOut => set_zoom(state, state.timeline_zoom / crate::timeline_layout::ZOOM_STEP),
+        TimelineMessage::ZoomReset => set_zoom(state, crate::timeline_layout::DEFAULT_ZOOM),
+        TimelineMessage::CtrlHeld(held) => state.timeline_ctrl_held = held,
+        TimelineMessage::StripScrolled(dy) => {
+            if state.timeline_ctrl_held && dy != 0.0 {
+                let factor = if dy > 0.0 { crate::timeline_layout::ZOOM_STEP } else { 1.0 / crate::timeline_layout::ZOOM_STEP };
+                set_zoom(state, state.timeline_zoom * factor);
+            }
+        }
+
+        // --- FILTROS (vista; la fuente sigue ordenada por año) ---
+        TimelineMessage::EraFilterChanged(era_id) => {
+            state.timeline_era_filter = era_id;
+            state.rebuild_timeline_layout();
+        }
+        TimelineMessage::ImportanceFilterChanged(importance) => {
+            state.timeline_importance_filter = importance;
+            state.rebuild_timeline_layout();
+        }
+        TimelineMessage::ClearFilters => {
+            state.timeline_era_filter = None;
+            state.timeline_importance_filter = None;
+            state.rebuild_timeline_layout();
+        }
+
+        // --- EXPORT DE DATOS (solo lectura, via DB queue) ---
+        TimelineMessage::ExportFormatChanged(format) => state.timeline_export_format = format,
+        TimelineMessage::ExportData => {
+            let crate::app::Route::Timeline { universe_id } = &state.route else {
+                return;
+            };
+            let Some(project) = state.active_project.as_ref() else {
+                return;
+            };
+            let universe_id = universe_id.clone();
+            let universe_name = state
+                .universes
+                .iter()
+                .find(|u| u.id == universe_id)
+                .map(|u| u.name.as_str())
+                .unwrap_or_default();
+            let path = crate::db::timeline_data_export::timeline_export_path(
+                &PathBuf::from(&project.path),
+                universe_name,
+                state.timeline_export_format,
+            );
+            state.queue(DbAction::ExportTimeline { universe_id, format: state.timeline_export_format, path });
+        }
+
         // --- EVENT ACTIONS ---
         TimelineMessage::EditorOpenCreateEvent(default_year) => state.event_editor = Some(EventEditor::create_new(default_year)),
         TimelineMessage::EditEvent(id) => if let Some(evt) = state.timeline_events.iter().find(|e| e.id == id) { state.event_editor = Some(EventEditor::from_event(evt, &state.locations)); },
@@ -43,6 +151,7 @@ pub fn update(state: &mut AppState, message: TimelineMessage) {
                         kind: editor.kind,
                         color: editor.color,
                         location_id: editor.location_id, // ✅ C.1: Direct ID usage
+                        scene_id: editor.scene_id,
                     };
                     state.queue(DbAction::SaveEvent(evt));
                     state.show_toast("Event saved", ToastKind::Success);
@@ -61,6 +170,7 @@ pub fn update(state: &mut AppState, message: TimelineMessage) {
         TimelineMessage::KindChanged(v) => if let Some(e) = state.event_editor.as_mut() { e.kind = v },
         TimelineMessage::ColorChanged(v) => if let Some(e) = state.event_editor.as_mut() { e.color = v },
         TimelineMessage::LocationChanged(loc_id) => if let Some(e) = state.event_editor.as_mut() { e.location_id = loc_id }, // ✅ C.1: ID only
+        TimelineMessage::SceneChanged(scene_id) => if let Some(e) = state.event_editor.as_mut() { e.scene_id = scene_id },
         TimelineMessage::DescriptionChanged(action) => if let Some(e) = state.event_editor.as_mut() { e.description.perform(action) },
 
         // --- ERA ACTIONS ---
@@ -101,4 +211,66 @@ pub fn update(state: &mut AppState, message: TimelineMessage) {
         TimelineMessage::EraColorChanged(v) => if let Some(e) = state.era_editor.as_mut() { e.color = v },
         TimelineMessage::EraDescChanged(action) => if let Some(e) = state.era_editor.as_mut() { e.description.perform(action) },
     }
-}
\ No newline at end of file
+}
+/// Export del timeline a PNG: screenshot de la ventana -> encode en blocking -> toast.
+pub fn try_handle(state: &mut AppState, message: &Message) -> Option<Task<Message>> {
+    match message {
+        Message::ExportTimelineImage(path) => {
+            if state.timeline_export_busy || !matches!(state.route, crate::app::Route::Timeline { .. }) {
+                return None;
+            }
+            let dest = match path {
+                Some(p) => p.clone(),
+                None => {
+                    let project = state.active_project.as_ref()?;
+                    crate::timeline_export::image_export_path(&PathBuf::from(&project.path))
+                }
+            };
+
+            // Cerrar modales para que la captura sea solo el timeline
+            state.event_editor = None;
+            state.era_editor = None;
+            state.timeline_export_busy = true;
+
+            Some(
+                iced::window::latest()
+                    .and_then(iced::window::screenshot)
+                    .map(move |shot| Message::TimelineScreenshotTaken(dest.clone(), shot)),
+            )
+        }
+
+        Message::TimelineScreenshotTaken(path, shot) => {
+            let path = path.clone();
+            let (w, h) = (shot.size.width, shot.size.height);
+            let rgba = shot.rgba.clone();
+
+            Some(Task::perform(
+                async move {
+                    let dest = path.clone();
+                    tokio::task::spawn_blocking(move || crate::timeline_export::write_png(&dest, w, h, &rgba))
+                        .await
+                        .map_err(|e| e.to_string())??;
+                    Ok(path.to_string_lossy().to_string())
+                },
+                Message::TimelineImageExported,
+            ))
+        }
+
+        Message::TimelineImageExported(result) => {
+            state.timeline_export_busy = false;
+            match result {
+                Ok(path) => {
+                    crate::logger::info(&format!("🖼️ Timeline exported: {}", path));
+                    state.show_toast(format!("Timeline image saved to {}", path), ToastKind::Success);
+                }
+                Err(e) => {
+                    crate::logger::error(&format!("❌ Timeline image export failed: {}", e));
+                    state.show_toast(format!("Timeline export failed: {}", e), ToastKind::Error);
+                }
+            }
+            None
+        }
+
+        _ => None,
+    }
+}
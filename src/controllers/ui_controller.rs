@@ -1,4 +1,4 @@
-use iced::{event, mouse, Element, Event, Size, Subscription, Task, Theme};
+use iced::{event, keyboard, mouse, Element, Event, Size, Subscription, Task, Theme};
 
 use crate::app::{AppState, Message, APP_ACRONYM, APP_NAME};
 use crate::db::Database;
@@ -68,8 +68,8 @@ impl App {
     fn subscription(&self) -> Subscription<Message> {
         let mut subs = Vec::new();
 
-        // 1) Dragging tracking (PM)
-        if let crate::app::PmState::Dragging { .. } = self.state.pm_state {
+        // 1) Dragging tracking (PM + outline de The Forge)
+        if matches!(self.state.pm_state, crate::app::PmState::Dragging { .. }) || self.state.forge_drag.is_some() {
             subs.push(event::listen_with(|event, _status, _window| match event {
                 Event::Mouse(mouse::Event::CursorMoved { position }) => {
                     Some(Message::GlobalEvent(Event::Mouse(
@@ -85,6 +85,26 @@ impl App {
             }));
         }
 
+        // 1b) Drag del editor de sidebar (Settings): soltar en cualquier lado termina el drag
+        if self.state.settings_sidebar_drag.is_some() {
+            subs.push(event::listen_with(|event, _status, _window| match event {
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    Some(Message::Settings(crate::app::SettingsMessage::SidebarDragEnd))
+                }
+                _ => None,
+            }));
+        }
+
+        // 1c) Drag de columna (Kanban): soltar en cualquier lado cierra el drag (drop en hovered_column)
+        if self.state.pm_column_drag.is_some() {
+            subs.push(event::listen_with(|event, _status, _window| match event {
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    Some(Message::Pm(crate::app::PmMessage::ColumnDragEnd))
+                }
+                _ => None,
+            }));
+        }
+
         // 2) Toast TTL tick (lo dejás como estaba, 1 Hz)
         if !self.state.toasts.is_empty() {
             subs.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::Tick));
@@ -96,6 +116,48 @@ impl App {
             subs.push(iced::time::every(Duration::from_millis(16)).map(|_| Message::Tick));
         }
 
+        // 4b) Recordatorio de pausas: solo mientras hay una racha de escritura abierta
+        if self.state.settings.break_reminder_enabled && self.state.writing_session_start.is_some() {
+            subs.push(iced::time::every(Duration::from_secs(30)).map(|_| Message::Tick));
+        }
+
+        // 5) Auto-backup: chequeo cada minuto (el controller decide si toca)
+        if self.state.settings.auto_backup_enabled && self.state.active_project.is_some() {
+            subs.push(iced::time::every(Duration::from_secs(60)).map(|_| Message::AutoBackupTick));
+        }
+
+        // 4) Edición externa (Forge): polling del archivo temporal mientras haya sesión
+        if self.state.forge_external_edit.is_some() {
+            subs.push(iced::time::every(Duration::from_secs(2)).map(|_| {
+                Message::TheForge(crate::messages::TheForgeMessage::ExternalEditPoll)
+            }));
+        }
+
+        // 6) Atajos de teclado: tabla central en crate::keymap (resuelve por ruta en input_controller)
+        subs.push(event::listen_with(|event, status, _window| match event {
+            Event::Keyboard(key_event) => crate::keymap::from_event(&key_event, status == event::Status::Captured)
+                .map(Message::Shortcut),
+            _ => None,
+        }));
+
+        // 9) Editor de criatura / location abierto: soltar un archivo = elegir imagen
+        if self.state.creature_editor.is_some() || self.state.location_editor.is_some() {
+            subs.push(event::listen_with(|event, _status, _window| match event {
+                Event::Window(iced::window::Event::FileDropped(path)) => Some(Message::ImageFileDropped(path)),
+                _ => None,
+            }));
+        }
+
+        // 10) Timeline: Ctrl presionado => la rueda sobre la banda cronológica hace zoom
+        if matches!(self.state.route, crate::app::Route::Timeline { .. }) {
+            subs.push(event::listen_with(|event, _status, _window| match event {
+                Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => Some(Message::Timeline(
+                    crate::app::TimelineMessage::CtrlHeld(modifiers.command()),
+                )),
+                _ => None,
+            }));
+        }
+
         Subscription::batch(subs)
     }
 
@@ -156,9 +218,38 @@ impl App {
             _ => {}
         }
 
+        // 2b) Auto-backup (necesita DB + path del proyecto)
+        if let Some(t) = crate::controllers::backup_controller::try_handle(
+            &mut self.state,
+            self.db.as_ref(),
+            &message,
+        ) {
+            tasks.push(t);
+        }
+
+        // 2c) Settings export/import (necesita DB)
+        if let Some(t) = crate::controllers::settings_controller::try_handle(
+            &mut self.state,
+            self.db.as_ref(),
+            &message,
+        ) {
+            tasks.push(t);
+        }
+
+        // 2d) Timeline -> PNG (screenshot + encode)
+        if let Some(t) = crate::controllers::timeline_controller::try_handle(&mut self.state, &message) {
+            tasks.push(t);
+        }
+
         // 3) ActionDone side effects
-        if let Message::ActionDone(result) = &message {
-            crate::controllers::action_done_controller::handle_action_done(&mut self.state, result);
+        if let Message::ActionDone(result) = &message
+            && let Some(t) = crate::controllers::action_done_controller::handle_action_done(
+                &mut self.state,
+                self.db.as_ref(),
+                result,
+            )
+        {
+            tasks.push(t);
         }
 
         // 4) Handle fetch results (delegación a navigation_controller)
@@ -225,6 +316,19 @@ impl App {
             tasks.append(&mut extra_tasks);
         }
 
+        // 6b) Historial back/forward (una entrada por cambio de ruta)
+        if self.state.active_project.is_some() {
+            crate::controllers::navigation_controller::record_route(&mut self.state);
+            crate::controllers::navigation_controller::track_recent(&mut self.state);
+        }
+
+        // 6c) Stats del novel activo (lazy: solo recalcula si cambió outline/caches)
+        if matches!(self.state.route, crate::app::Route::Forge) {
+            crate::controllers::the_forge_controller::refresh_novel_stats(&mut self.state);
+            crate::controllers::the_forge_controller::persist_outline_expansion(&mut self.state);
+            crate::controllers::the_forge_controller::sync_notes_editor(&mut self.state);
+        }
+
         // 7) Post-event scheduler
         if self.state.active_project.is_some() {
             tasks.extend(crate::controllers::post_event_tasks_controller::post_event_tasks(
@@ -0,0 +1,172 @@
+// ============================================
+// CONTROLLER: UNDO / REDO GLOBAL
+// ============================================
+// Ctrl+Z / Ctrl+Y fuera del editor de escenas: aplica la inversa (o la forward) de la última
+// acción reversible. El stack vive en crate::undo_stack; acá está lo que toca caches.
+// Reglas:
+// - renames: se rearma la acción sobre la copia actual (solo cambia el título, no pisa
+//   ediciones posteriores del body / sinopsis)
+// - reorders: el reorden optimista no recarga, así que undo/redo invalidan el outline
+// - borrar: undo = restore desde el trash; no se rehace
+
+use crate::app::AppState;
+use crate::state::{DbAction, ToastKind};
+use crate::undo_stack::UndoOrigin;
+
+pub fn apply(state: &mut AppState, undo: bool) {
+    let entry = if undo { state.undo_stack.pop_undo() } else { state.undo_stack.pop_redo() };
+    let Some(entry) = entry else {
+        let msg = if undo { "Nothing to undo" } else { "Nothing to redo" };
+        state.show_toast(msg, ToastKind::Info);
+        return;
+    };
+
+    let (action, origin) = if undo {
+        (rebase(state, &entry.inverse), UndoOrigin::Undo)
+    } else {
+        (rebase(state, &entry.forward), UndoOrigin::Redo)
+    };
+
+    crate::logger::info(&format!("↩️ {:?}: {}", origin, entry.label));
+    state.queue(action.clone());
+    // Rechazada por backpressure => la entrada se pierde (mejor que un stack desfasado)
+    if state.db_queue.contains(&action) {
+        state.undo_stack.track(action, entry, origin);
+    }
+}
+
+/// action_done: la DB confirmó (ok) o rechazó una acción. Mueve la entrada de undo si la había.
+pub fn after_done(state: &mut AppState, action: &DbAction, ok: bool) {
+    if !ok {
+        if let Some(origin) = state.undo_stack.fail(action)
+            && origin != UndoOrigin::Fresh
+        {
+            // El outline optimista del rebase puede haber quedado mal
+            refresh_after_apply(state, action);
+        }
+        return;
+    }
+
+    match state.undo_stack.complete(action) {
+        Some((entry, origin)) if origin != UndoOrigin::Fresh => {
+            refresh_after_apply(state, action);
+            // RestoreFromTrash ya tiene su propio toast
+            if !matches!(action, DbAction::RestoreFromTrash(_)) {
+                let verb = if origin == UndoOrigin::Undo { "Undone" } else { "Redone" };
+                state.show_toast(format!("{}: {}", verb, entry.label), ToastKind::Success);
+            }
+        }
+        Some(_) => {}
+        None => forget_stale(state, action),
+    }
+}
+
+// Acciones hechas por fuera del stack que dejan entradas de "Delete" sin trash entry
+fn forget_stale(state: &mut AppState, action: &DbAction) {
+    match action {
+        DbAction::RestoreFromTrash(id) | DbAction::PermanentDelete(id) => {
+            state.undo_stack.forget_where(|e| matches!(&e.inverse, DbAction::RestoreFromTrash(t) if t == id));
+        }
+        DbAction::EmptyTrash | DbAction::CleanupOldTrash(_) => {
+            state.undo_stack.forget_where(|e| matches!(e.inverse, DbAction::RestoreFromTrash(_)));
+        }
+        _ => {}
+    }
+}
+
+// La acción guardada en el stack es una foto del momento: para renames tomamos la copia
+// actual y solo le cambiamos el título (también en caches, como el rename inline).
+fn rebase(state: &mut AppState, action: &DbAction) -> DbAction {
+    let now = chrono::Utc::now();
+
+    match action {
+        DbAction::UpdateNovel(saved) => {
+            let mut novel = state.novels.iter().find(|n| n.id == saved.id).cloned().unwrap_or_else(|| saved.clone());
+            novel.title = saved.title.clone();
+            novel.updated_at = now;
+            if let Some(local) = state.novels.iter_mut().find(|n| n.id == saved.id) {
+                local.title = novel.title.clone();
+                local.updated_at = now;
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+            DbAction::UpdateNovel(novel)
+        }
+
+        DbAction::UpdateChapter(saved) => {
+            let mut chapter = crate::controllers::the_forge_controller::find_chapter_anywhere(state, &saved.id)
+                .unwrap_or_else(|| saved.clone());
+            chapter.title = saved.title.clone();
+            chapter.updated_at = now;
+            for local in state
+                .chapters_by_novel_id
+                .values_mut()
+                .flat_map(|list| list.iter_mut())
+                .chain(state.active_novel_chapters.iter_mut())
+                .filter(|c| c.id == saved.id)
+            {
+                local.title = chapter.title.clone();
+                local.updated_at = now;
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+            DbAction::UpdateChapter(chapter)
+        }
+
+        DbAction::UpdateScene(saved) => {
+            // Un save del body ya encolado es la versión más nueva de la escena
+            let queued = state.db_queue.iter().rev().find_map(|a| match a {
+                DbAction::UpdateScene(s) if s.id == saved.id => Some(s.clone()),
+                _ => None,
+            });
+            let mut scene = queued
+                .or_else(|| crate::controllers::the_forge_controller::find_scene_anywhere(state, &saved.id))
+                .unwrap_or_else(|| saved.clone());
+            scene.title = saved.title.clone();
+            scene.updated_at = now;
+            for local in state
+                .scenes_by_chapter_id
+                .values_mut()
+                .flat_map(|list| list.iter_mut())
+                .chain(state.active_chapter_scenes.iter_mut())
+                .filter(|s| s.id == saved.id)
+            {
+                local.title = scene.title.clone();
+                local.updated_at = now;
+            }
+            state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+            DbAction::UpdateScene(scene)
+        }
+
+        other => other.clone(),
+    }
+}
+
+// Reorders (y sus fallos) no recargan solos: invalidar el outline afectado
+fn refresh_after_apply(state: &mut AppState, action: &DbAction) {
+    use crate::controllers::forge_data_controller::{invalidate_chapters_cache, invalidate_novels_cache, invalidate_scenes_cache};
+
+    match action {
+        DbAction::ReorderChapter(chapter_id, _) | DbAction::UpdateChapter(crate::model::Chapter { id: chapter_id, .. }) => {
+            let novel_id = state
+                .chapters_by_novel_id
+                .iter()
+                .find(|(_, list)| list.iter().any(|c| c.id == *chapter_id))
+                .map(|(nid, _)| nid.clone());
+            if let Some(novel_id) = novel_id {
+                invalidate_chapters_cache(state, &novel_id);
+            }
+        }
+        DbAction::ReorderScene(scene_id, _) | DbAction::UpdateScene(crate::model::Scene { id: scene_id, .. }) => {
+            let chapter_id = state
+                .scenes_by_chapter_id
+                .iter()
+                .find(|(_, list)| list.iter().any(|s| s.id == *scene_id))
+                .map(|(cid, _)| cid.clone());
+            if let Some(chapter_id) = chapter_id {
+                invalidate_scenes_cache(state, &chapter_id);
+            }
+        }
+        DbAction::UpdateNovel(_) => invalidate_novels_cache(state),
+        _ => {}
+    }
+    state.forge_outline_version = state.forge_outline_version.wrapping_add(1);
+}
@@ -27,6 +27,16 @@ pub fn update(state: &mut AppState, message: UniverseMessage) {
             state.show_toast("Confirm delete universe?", ToastKind::Info);
         }
 
+        UniverseMessage::Duplicate(source_id) => {
+            let include_novels = state.universe_clone_include_novels;
+            state.queue(DbAction::CloneUniverse { source_id, include_novels });
+            state.show_toast("Duplicating universe...", ToastKind::Info);
+        }
+
+        UniverseMessage::ToggleCloneNovels => {
+            state.universe_clone_include_novels = !state.universe_clone_include_novels;
+        }
+
         UniverseMessage::Open(id) => {
             state.route = crate::app::Route::UniverseDetail { universe_id: id };
         }
@@ -37,6 +47,7 @@ pub fn update(state: &mut AppState, message: UniverseMessage) {
         }
 
         UniverseMessage::ResetDemoPrompt(uid, scope) => {
+            state.queue_auto_snapshot(&uid, "reset");
             state.queue(DbAction::ResetDemoDataScoped(uid, scope));
             state.show_toast("Resetting demo data...", ToastKind::Info);
         }
@@ -97,6 +108,7 @@ pub fn update(state: &mut AppState, message: UniverseMessage) {
             // Restore affects universe data; mark caches dirty so UI refreshes.
             state.loaded_creatures_universe = None;
             state.loaded_locations_universe = None;
+            state.loaded_glossary_universe = None;
             state.loaded_timeline_universe = None;
             state.loaded_snapshots_universe = None;
 
@@ -119,9 +131,116 @@ pub fn update(state: &mut AppState, message: UniverseMessage) {
             state.show_toast("Deleting snapshot...", ToastKind::Info);
         }
 
+        UniverseMessage::SnapshotClicked(snapshot_id) => {
+            const DOUBLE_CLICK_MS: u128 = 400;
+            let now = std::time::Instant::now();
+
+            let is_double = matches!(
+                &state.last_snapshot_click,
+                Some((last_id, at)) if *last_id == snapshot_id && now.duration_since(*at).as_millis() <= DOUBLE_CLICK_MS
+            );
+
+            if is_double {
+                state.last_snapshot_click = None;
+                if let Some(s) = state.snapshots.iter().find(|s| s.id == snapshot_id) {
+                    state.snapshot_renaming = Some((snapshot_id, s.name.clone()));
+                }
+            } else {
+                state.last_snapshot_click = Some((snapshot_id, now));
+            }
+        }
+
+        UniverseMessage::SnapshotRenameChanged(v) => {
+            if let Some((_, draft)) = state.snapshot_renaming.as_mut() {
+                *draft = v;
+            }
+        }
+
+        UniverseMessage::SnapshotRenameCommit => {
+            let Some((snapshot_id, draft)) = state.snapshot_renaming.take() else {
+                return;
+            };
+            let Some(snapshot) = state.snapshots.iter_mut().find(|s| s.id == snapshot_id) else {
+                return;
+            };
+
+            // Vacío => nombre por fecha (nunca queda un snapshot sin nombre)
+            let name = match draft.trim() {
+                "" => snapshot.default_name(),
+                trimmed => trimmed.to_string(),
+            };
+            if name == snapshot.name {
+                return;
+            }
+
+            // Optimista: la lista ya muestra el nombre nuevo
+            snapshot.name = name.clone();
+            state.queue(DbAction::SnapshotRename { snapshot_id, name });
+        }
+
         UniverseMessage::ValidateUniverse(_universe_id) => {
             // Fetch issues via root_controller task (not queued) to avoid breaking inflight clearing.
             state.integrity_busy = true;
         }
+
+        UniverseMessage::RepairForeignKeys => {
+            state.queue(DbAction::RepairForeignKeys);
+            state.show_toast("Repairing foreign keys...", ToastKind::Info);
+        }
+
+        // --- FIND & REPLACE ---
+        UniverseMessage::ReplaceFindChanged(v) => {
+            state.replace_find = v;
+            // El preview viejo ya no representa el término actual
+            state.replace_preview.clear();
+            state.replace_preview_term = None;
+        }
+        UniverseMessage::ReplaceWithChanged(v) => state.replace_with = v,
+        UniverseMessage::ReplaceToggleScope(scope) => {
+            if !state.replace_scopes.remove(&scope) {
+                state.replace_scopes.insert(scope);
+            }
+            state.replace_preview.clear();
+            state.replace_preview_term = None;
+        }
+
+        UniverseMessage::ReplacePreview(_universe_id) => {
+            if state.replace_find.is_empty() {
+                state.show_toast("Enter a term to find", ToastKind::Error);
+                return;
+            }
+            if state.replace_scopes.is_empty() {
+                state.show_toast("Select at least one scope", ToastKind::Error);
+                return;
+            }
+            // Igual que integrity: el fetch lo dispara post_event (controller puro)
+            state.replace_preview_busy = true;
+            state.replace_preview_requested = true;
+        }
+
+        UniverseMessage::ReplaceApply(universe_id) => {
+            // Solo aplicamos lo que el usuario ya vio en preview
+            if state.replace_preview_term.as_deref() != Some(state.replace_find.as_str())
+                || state.replace_preview.is_empty()
+            {
+                state.show_toast("Run a preview before replacing", ToastKind::Error);
+                return;
+            }
+
+            let mut scopes: Vec<_> = state.replace_scopes.iter().copied().collect();
+            scopes.sort_by_key(|s| *s as u8);
+
+            let rows = state.replace_preview.len();
+            state.queue(DbAction::BulkReplaceText {
+                universe_id,
+                from: state.replace_find.clone(),
+                to: state.replace_with.clone(),
+                scopes,
+            });
+
+            state.replace_preview.clear();
+            state.replace_preview_term = None;
+            state.show_toast(format!("Replacing text in {} items...", rows), ToastKind::Info);
+        }
     }
 }
@@ -39,6 +39,32 @@ pub fn try_handle(
             if let Some(proj) = state.projects.iter().find(|p| p.id == *id).cloned() {
                 state.active_project = Some(proj.clone());
 
+                // Meta de palabras: el total es por proyecto
+                state.project_word_total = None;
+                state.project_word_total_stale = true;
+                state.forge_orphan_scenes.clear();
+                state.forge_orphans_loaded = false;
+                state.board_templates.clear();
+                state.board_templates_loaded = false;
+                state.new_board_template_id = None;
+                state.trash_retention_loaded = false;
+                state.ui_theme_loaded = false;
+                state.capabilities = None;
+                state.capabilities_loaded = false;
+                state.recent_items.clear();
+                state.recent_items_loaded = false;
+                state.recent_last_opened = None;
+                state.undo_stack.clear();
+                state.command_palette = None;
+                // Historial por proyecto: las rutas del anterior no aplican
+                state.nav_history.clear();
+                state.nav_index = 0;
+                state.project_word_goal_input = state
+                    .settings
+                    .project_word_goal(&proj.id)
+                    .map(|g| g.to_string())
+                    .unwrap_or_default();
+
                 // Route by project kind (canonical: Project::get_kind()).
                 match proj.get_kind() {
                     ProjectKind::Universe => {
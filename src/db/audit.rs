@@ -1,4 +1,7 @@
-use crate::db::Database;
+use std::path::{Path, PathBuf};
+
+use crate::db::{Database, DbError};
+use crate::db::bestiary_export::csv_field;
 use crate::model::AuditLogEntry;
 use uuid::Uuid;
 
@@ -17,7 +20,7 @@ impl Database {
         entity_type: &str,
         entity_id: &str,
         details_json: &str,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<(), DbError> {
         let id = Uuid::new_v4().to_string();
 
         sqlx::query(
@@ -38,7 +41,7 @@ impl Database {
     // =========================
     // Lectura
     // =========================
-    pub async fn get_audit_log(&self, limit: i64) -> Result<Vec<AuditLogEntry>, sqlx::Error> {
+    pub async fn get_audit_log(&self, limit: i64) -> Result<Vec<AuditLogEntry>, DbError> {
         sqlx::query_as::<_, AuditLogEntry>(
             "SELECT id, ts, action, entity_type, entity_id, details_json
              FROM audit_log
@@ -48,5 +51,70 @@ impl Database {
             .bind(limit)
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
     }
+
+    /// Audit log filtrado (más nuevo primero). None => sin restricción en ese campo.
+    pub async fn query_audit(
+        &self,
+        from_ts: Option<i64>,
+        to_ts: Option<i64>,
+        action: Option<String>,
+        entity_type: Option<String>,
+    ) -> Result<Vec<AuditLogEntry>, DbError> {
+        sqlx::query_as::<_, AuditLogEntry>(
+            "SELECT id, ts, action, entity_type, entity_id, details_json
+             FROM audit_log
+             WHERE (?1 IS NULL OR ts >= ?1)
+               AND (?2 IS NULL OR ts <= ?2)
+               AND (?3 IS NULL OR action = ?3)
+               AND (?4 IS NULL OR entity_type = ?4)
+             ORDER BY ts DESC",
+        )
+            .bind(from_ts)
+            .bind(to_ts)
+            .bind(action)
+            .bind(entity_type)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+}
+
+const AUDIT_CSV_COLUMNS: [&str; 6] = ["timestamp", "action", "entity_type", "entity_id", "details", "id"];
+
+/// CSV del audit log: details_json va aplanado (una línea) en una sola columna escapada.
+pub fn render_audit_csv(rows: &[AuditLogEntry]) -> String {
+    let mut out = AUDIT_CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+
+    for r in rows {
+        let ts = chrono::DateTime::from_timestamp(r.ts, 0)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_else(|| r.ts.to_string());
+        // JSON válido => re-serializado compacto; si no, texto con saltos de línea colapsados
+        let details = serde_json::from_str::<serde_json::Value>(&r.details_json)
+            .map(|v| v.to_string())
+            .unwrap_or_else(|_| r.details_json.split_whitespace().collect::<Vec<_>>().join(" "));
+
+        let fields = [
+            ts.as_str(),
+            r.action.as_str(),
+            r.entity_type.as_str(),
+            r.entity_id.as_str(),
+            details.as_str(),
+            r.id.as_str(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+/// data_dir/exports/<stem>/audit-<timestamp>.csv
+pub fn audit_export_path(db_path: &Path) -> PathBuf {
+    crate::db::project_settings::exports_dir(db_path)
+        .join(format!("audit-{}.csv", chrono::Local::now().format("%Y%m%d-%H%M%S")))
 }
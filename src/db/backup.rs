@@ -0,0 +1,141 @@
+// ========================================
+// backup.rs - Copias completas de la base de datos
+// ========================================
+// VACUUM INTO genera una copia consistente (y compactada) sin cerrar el pool.
+// Restore: el archivo elegido se deja "pendiente" junto a la DB y se swapea en el
+// próximo connect (con el pool abierto no se puede reemplazar el archivo).
+
+use std::path::{Path, PathBuf};
+
+use crate::db::{Database, DbError};
+
+impl Database {
+    pub async fn backup_to(&self, dest: &Path) -> Result<(), DbError> {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| DbError::Invalid(format!("Cannot create backup folder: {}", e)))?;
+        }
+
+        // VACUUM INTO falla si el destino existe
+        if dest.exists() {
+            return Err(DbError::Invalid(format!(
+                "Backup target already exists: {}",
+                dest.display()
+            )));
+        }
+
+        // WAL: volcamos al archivo principal antes de copiar (TRUNCATE deja el -wal vacío)
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("VACUUM INTO ?")
+            .bind(dest.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deja `src` preparado para reemplazar la DB de `db_path` en el próximo connect.
+    /// Solo valida que sea un archivo SQLite; el schema lo alinean migrations/schema_guard.
+    pub fn restore_from(db_path: &Path, src: &Path) -> Result<PathBuf, String> {
+        let mut header = [0u8; 16];
+        let mut file = std::fs::File::open(src)
+            .map_err(|e| format!("Cannot open {}: {}", src.display(), e))?;
+        std::io::Read::read_exact(&mut file, &mut header)
+            .map_err(|_| format!("{} is not a SQLite database", src.display()))?;
+        if &header != SQLITE_HEADER {
+            return Err(format!("{} is not a SQLite database", src.display()));
+        }
+
+        let pending = pending_restore_path(db_path);
+        std::fs::copy(src, &pending).map_err(|e| format!("Cannot stage restore: {}", e))?;
+        Ok(pending)
+    }
+}
+
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// <db>.restore-pending: copia a aplicar en el próximo connect.
+pub fn pending_restore_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_os_string();
+    name.push(".restore-pending");
+    PathBuf::from(name)
+}
+
+/// Si hay un restore pendiente lo aplica antes de abrir el pool.
+/// La DB actual queda como <db>.pre-restore (por si hubo que arrepentirse).
+pub fn apply_pending_restore(db_path: &Path) -> std::io::Result<bool> {
+    let pending = pending_restore_path(db_path);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    let mut previous = db_path.as_os_str().to_os_string();
+    previous.push(".pre-restore");
+    // Un .pre-restore de un restore anterior se pisa (en Windows rename no sobreescribe)
+    for suffix in ["", "-wal", "-shm"] {
+        let mut old = previous.clone();
+        old.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(old));
+    }
+    if db_path.exists() {
+        std::fs::rename(db_path, PathBuf::from(&previous))?;
+    }
+
+    // -wal / -shm viajan con la DB vieja: no deben aplicarse sobre la restaurada
+    for suffix in ["-wal", "-shm"] {
+        let mut side = db_path.as_os_str().to_os_string();
+        side.push(suffix);
+        let side = PathBuf::from(side);
+        if side.exists() {
+            let mut moved = previous.clone();
+            moved.push(suffix);
+            std::fs::rename(&side, PathBuf::from(moved))?;
+        }
+    }
+
+    std::fs::rename(&pending, db_path)?;
+    Ok(true)
+}
+
+/// Carpeta de backups por proyecto (derivada del nombre del archivo .universe).
+pub fn backups_dir_for(db_path: &Path) -> PathBuf {
+    let base = if let Some(p) = directories::ProjectDirs::from("com", "TitanArchitects", "TAS") {
+        p.data_dir().to_path_buf()
+    } else {
+        PathBuf::from("data")
+    };
+
+    let stem = db_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+
+    base.join("backups").join(stem)
+}
+
+/// Borra los auto-backups más viejos dejando solo `keep` (orden por nombre = orden por fecha).
+pub fn rotate_auto_backups(dir: &Path, keep: usize) -> Result<usize, String> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Cannot read backups folder: {e}"))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().starts_with("auto-"))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    files.sort();
+
+    let mut removed = 0;
+    while files.len() > keep.max(1) {
+        let oldest = files.remove(0);
+        if std::fs::remove_file(&oldest).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,179 @@
+// ========================================
+// bestiary_export.rs - Dump CSV / JSON (solo lectura) del bestiario de un universo
+// ========================================
+// Shape propio (NO el struct del modelo): home_location sale como nombre legible,
+// no como id interno. Cambios incompatibles del JSON => subir versión.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::db::{Database, DbError};
+use crate::model::Creature;
+
+pub const BESTIARY_EXPORT_FORMAT: &str = "tas.bestiary";
+pub const BESTIARY_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestiaryExportFormat {
+    Csv,
+    Json,
+}
+
+impl BestiaryExportFormat {
+    pub const ALL: [BestiaryExportFormat; 2] = [BestiaryExportFormat::Csv, BestiaryExportFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BestiaryExportFormat::Csv => "csv",
+            BestiaryExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for BestiaryExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BestiaryExportFormat::Csv => "CSV",
+            BestiaryExportFormat::Json => "JSON",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BestiaryExport {
+    pub format: &'static str,
+    pub format_version: u32,
+    pub exported_at: String,
+    pub universe_id: String,
+    pub creatures: Vec<BestiaryExportCreature>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BestiaryExportCreature {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub habitat: String,
+    pub danger: String,
+    pub description: String,
+    pub home_location: Option<String>,
+    pub archived: bool,
+}
+
+const CSV_COLUMNS: [&str; 6] = ["name", "kind", "habitat", "danger", "description", "home_location"];
+
+impl Database {
+    /// Creatures del universo ordenadas por nombre (archivadas solo si se piden).
+    pub async fn export_creatures(
+        &self,
+        universe_id: String,
+        include_archived: bool,
+    ) -> Result<Vec<Creature>, DbError> {
+        let mut list = self.get_creatures(universe_id).await?;
+        if !include_archived {
+            list.retain(|c| !c.archived);
+        }
+        list.sort_by_key(|c| c.name.to_lowercase());
+        Ok(list)
+    }
+
+    pub async fn export_bestiary(
+        &self,
+        universe_id: String,
+        format: BestiaryExportFormat,
+        include_archived: bool,
+    ) -> Result<String, DbError> {
+        let locations: HashMap<String, String> =
+            sqlx::query_as::<_, (String, String)>("SELECT id, name FROM locations WHERE universe_id = ?")
+                .bind(&universe_id)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .collect();
+
+        let creatures = self.export_creatures(universe_id.clone(), include_archived).await?;
+        let rows: Vec<BestiaryExportCreature> = creatures
+            .into_iter()
+            .map(|c| BestiaryExportCreature {
+                // Location borrada: sin nombre que mostrar, queda vacío
+                home_location: c.home_location_id.as_ref().and_then(|id| locations.get(id).cloned()),
+                id: c.id,
+                name: c.name,
+                kind: c.kind,
+                habitat: c.habitat,
+                danger: c.danger,
+                description: c.description,
+                archived: c.archived,
+            })
+            .collect();
+
+        match format {
+            BestiaryExportFormat::Csv => Ok(render_csv(&rows)),
+            BestiaryExportFormat::Json => {
+                let export = BestiaryExport {
+                    format: BESTIARY_EXPORT_FORMAT,
+                    format_version: BESTIARY_EXPORT_VERSION,
+                    exported_at: chrono::Utc::now().to_rfc3339(),
+                    universe_id,
+                    creatures: rows,
+                };
+                serde_json::to_string_pretty(&export).map_err(|e| DbError::Codec(e.to_string()))
+            }
+        }
+    }
+}
+
+fn render_csv(rows: &[BestiaryExportCreature]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+
+    for c in rows {
+        let fields = [
+            c.name.as_str(),
+            c.kind.as_str(),
+            c.habitat.as_str(),
+            c.danger.as_str(),
+            c.description.as_str(),
+            c.home_location.as_deref().unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+// RFC 4180: comillas si hay coma / comilla / salto de línea; comillas internas duplicadas
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// data_dir/exports/<stem>/bestiary-<slug>-<timestamp>.<csv|json>
+pub fn bestiary_export_path(db_path: &Path, universe_name: &str, format: BestiaryExportFormat) -> PathBuf {
+    crate::db::project_settings::exports_dir(db_path).join(format!(
+        "bestiary-{}-{}.{}",
+        universe_slug(universe_name),
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ))
+}
+
+/// Nombre del universo apto para nombre de archivo ("Mi Mundo!" -> "mi-mundo").
+pub(crate) fn universe_slug(universe_name: &str) -> String {
+    let slug: String = universe_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "universe".to_string() } else { slug }
+}
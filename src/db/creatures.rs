@@ -3,40 +3,90 @@
 // ========================================
 // Este módulo maneja CRUD de criaturas (bestiary_entries)
 
-use crate::model::Creature;
-use crate::db::Database;
+use crate::model::{Creature, CreatureRelation};
+use crate::db::{Database, DbError};
 
 impl Database {
-    pub async fn get_creatures(&self, universe_id: String) -> Result<Vec<Creature>, sqlx::Error> {
+    pub async fn get_creatures(&self, universe_id: String) -> Result<Vec<Creature>, DbError> {
         sqlx::query_as::<_, Creature>(
-            "SELECT id, name, kind, habitat, description, danger, home_location_id, archived
+            "SELECT id, name, kind, habitat, description, danger, home_location_id, archived, image_path
                         FROM bestiary_entries
                         WHERE universe_id = ?"
         )
             .bind(universe_id)
             .fetch_all(&self.pool)
             .await
+            .map(|mut list| {
+                // ✅ Normalizamos al cargar (restores/snapshots pueden traer valores legacy)
+                for c in list.iter_mut() {
+                    c.danger = c.danger_level().as_str().to_string();
+                }
+                list
+            })
+            .map_err(DbError::from)
     }
 
-    pub async fn upsert_creature(&self, c: Creature, universe_id: String) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn upsert_creature(&self, c: Creature, universe_id: String) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("bestiary").await?;
 
-        sqlx::query("INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET name=excluded.name, kind=excluded.kind, habitat=excluded.habitat, description=excluded.description, danger=excluded.danger, home_location_id=excluded.home_location_id, archived=excluded.archived, updated_at=unixepoch()")
-            .bind(c.id).bind(universe_id).bind(c.name).bind(c.kind).bind(c.habitat).bind(c.description).bind(c.danger).bind(c.home_location_id).bind(c.archived)
+        let mut c = c;
+        c.danger = c.danger_level().as_str().to_string();
+
+        sqlx::query("INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived, image_path, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET name=excluded.name, kind=excluded.kind, habitat=excluded.habitat, description=excluded.description, danger=excluded.danger, home_location_id=excluded.home_location_id, archived=excluded.archived, image_path=excluded.image_path, updated_at=unixepoch()")
+            .bind(c.id).bind(universe_id).bind(c.name).bind(c.kind).bind(c.habitat).bind(c.description).bind(c.danger).bind(c.home_location_id).bind(c.archived).bind(c.image_path)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
         Ok(())
     }
 
-    pub async fn set_creature_archived(&self, id: String, archived: bool) -> Result<(), sqlx::Error> {
+    pub async fn set_creature_archived(&self, id: String, archived: bool) -> Result<(), DbError> {
         sqlx::query("UPDATE bestiary_entries SET archived = ? WHERE id = ?").bind(archived).bind(id).execute(&self.pool).await?;
         Ok(())
     }
 
-    pub async fn delete_creature(&self, id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_creature(&self, id: String) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM creature_relations WHERE from_id = ?1 OR to_id = ?1").bind(&id).execute(&self.pool).await?;
         sqlx::query("DELETE FROM bestiary_entries WHERE id = ?").bind(id).execute(&self.pool).await?;
         Ok(())
     }
+
+    // --- RELATIONS ---
+
+    /// Relaciones del universo cuyas dos puntas existen (restos viejos no aparecen).
+    pub async fn get_creature_relations(&self, universe_id: String) -> Result<Vec<CreatureRelation>, DbError> {
+        sqlx::query_as::<_, CreatureRelation>(
+            "SELECT r.id, r.universe_id, r.from_id, r.to_id, r.relation_kind
+                        FROM creature_relations r
+                        JOIN bestiary_entries a ON a.id = r.from_id
+                        JOIN bestiary_entries b ON b.id = r.to_id
+                        WHERE r.universe_id = ?
+                        ORDER BY r.created_at ASC"
+        )
+            .bind(universe_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    pub async fn add_creature_relation(&self, r: CreatureRelation) -> Result<(), DbError> {
+        self.require_capability("bestiary").await?;
+
+        if r.from_id == r.to_id {
+            return Err(DbError::Invalid("A creature cannot be related to itself".to_string()));
+        }
+
+        // Misma arista dos veces: no-op (índice único from/to/kind)
+        let kind = r.kind().as_str();
+        sqlx::query("INSERT OR IGNORE INTO creature_relations (id, universe_id, from_id, to_id, relation_kind) VALUES (?, ?, ?, ?, ?)")
+            .bind(r.id).bind(r.universe_id).bind(r.from_id).bind(r.to_id).bind(kind)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_creature_relation(&self, id: String) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM creature_relations WHERE id = ?").bind(id).execute(&self.pool).await?;
+        Ok(())
+    }
 }
\ No newline at end of file
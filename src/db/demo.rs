@@ -4,15 +4,15 @@
 // Este módulo maneja la inyección de datos de ejemplo y el reseteo selectivo por scopes
 
 use crate::state::DemoResetScope;
-use crate::db::Database;
+use crate::db::{Database, DbError};
 
 impl Database {
-    pub async fn inject_demo_data(&self, universe_id: String) -> Result<(), sqlx::Error> {
+    pub async fn inject_demo_data(&self, universe_id: String) -> Result<(), DbError> {
         crate::db_seed::run_all(&self.pool, &universe_id).await?;
         Ok(())
     }
 
-    pub async fn reset_demo_data_scoped(&self, universe_id: String, scope: DemoResetScope) -> Result<(), sqlx::Error> {
+    pub async fn reset_demo_data_scoped(&self, universe_id: String, scope: DemoResetScope) -> Result<(), DbError> {
         let mut tx = self.pool.begin().await?;
         match scope {
             DemoResetScope::All => {
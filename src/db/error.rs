@@ -0,0 +1,84 @@
+// ========================================
+// error.rs - Errores tipados de la capa DB
+// ========================================
+// Antes: errores de dominio viajaban como sqlx::Error::Protocol(String) y los
+// controllers hacían string-matching. Ahora la capa DB devuelve DbError y los
+// errores de sqlx se convierten en el borde (From).
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbError {
+    /// Capability deshabilitada en el proyecto (db_meta).
+    CapabilityDenied { capability: String },
+    /// El padre requerido no existe (ej: restaurar scene sin chapter).
+    MissingParent { kind: String, id: String },
+    /// El registro a restaurar ya existe (id duplicado).
+    RestoreConflict { kind: String, id: String },
+    /// target_type de papelera desconocido.
+    UnknownTargetType(String),
+    /// Validación de input (ej: título vacío).
+    Invalid(String),
+    /// Payload corrupto (JSON / gzip / base64).
+    Codec(String),
+    /// Cualquier otro error de SQLite/sqlx (ya como texto: sqlx::Error no es Clone).
+    Sql(String),
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::CapabilityDenied { capability } => {
+                write!(f, "Capability '{}' is disabled in this project", capability)
+            }
+            DbError::MissingParent { kind, id } => write!(f, "Missing parent {} (id={})", kind, id),
+            DbError::RestoreConflict { kind, id } => {
+                write!(f, "A {} with id={} already exists", kind, id)
+            }
+            DbError::UnknownTargetType(t) => write!(f, "Unknown trash target_type: {}", t),
+            DbError::Invalid(msg) => write!(f, "{}", msg),
+            DbError::Codec(msg) => write!(f, "Corrupt payload: {}", msg),
+            DbError::Sql(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl DbError {
+    /// True si sqlx reportó violación de UNIQUE/PRIMARY KEY (SQLite 1555 / 2067).
+    pub(crate) fn is_unique_violation(e: &sqlx::Error) -> bool {
+        match e {
+            sqlx::Error::Database(db) => {
+                matches!(db.code().as_deref(), Some("1555") | Some("2067"))
+                    || db.message().contains("UNIQUE constraint failed")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<sqlx::Error> for DbError {
+    fn from(e: sqlx::Error) -> Self {
+        DbError::Sql(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DbError {
+    fn from(e: serde_json::Error) -> Self {
+        DbError::Codec(e.to_string())
+    }
+}
+
+// Funciones legacy que devuelven Box<dyn Error> (guards, upserts)
+impl From<Box<dyn std::error::Error>> for DbError {
+    fn from(e: Box<dyn std::error::Error>) -> Self {
+        if let Some(cap) = e.downcast_ref::<crate::guards::CapabilityDisabledError>() {
+            return DbError::CapabilityDenied { capability: cap.capability.clone() };
+        }
+        if let Some(db) = e.downcast_ref::<DbError>() {
+            return db.clone();
+        }
+        DbError::Sql(e.to_string())
+    }
+}
@@ -0,0 +1,67 @@
+// ========================================
+// glossary.rs - Glosario / pronunciaciones por universo
+// ========================================
+// Lista estructurada análoga al bestiario: term + definition + pronunciation.
+// El borrado pasa por la papelera (target_type = "glossary_term").
+
+use crate::model::{DefinitionHit, GlossaryTerm};
+use crate::db::{Database, DbError};
+
+impl Database {
+    pub async fn get_glossary(&self, universe_id: String) -> Result<Vec<GlossaryTerm>, DbError> {
+        sqlx::query_as::<_, GlossaryTerm>(
+            "SELECT id, universe_id, term, definition, pronunciation FROM glossary WHERE universe_id = ? ORDER BY term COLLATE NOCASE ASC"
+        )
+            .bind(universe_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Palabra exacta (case-insensitive) -> término del glosario o, si no hay, criatura.
+    /// El glosario gana: es la referencia "oficial" de nombres inventados.
+    pub async fn lookup_definition(&self, universe_id: String, word: String) -> Result<Option<DefinitionHit>, DbError> {
+        let word = word.trim();
+        if word.is_empty() {
+            return Ok(None);
+        }
+
+        let term: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM glossary WHERE universe_id = ? AND term = ? COLLATE NOCASE LIMIT 1"
+        )
+            .bind(&universe_id)
+            .bind(word)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        if let Some((term_id,)) = term {
+            return Ok(Some(DefinitionHit::GlossaryTerm { universe_id, term_id }));
+        }
+
+        // Archivadas al final: si hay homónimos, preferimos la activa
+        let creature: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM bestiary_entries WHERE universe_id = ? AND name = ? COLLATE NOCASE ORDER BY archived ASC LIMIT 1"
+        )
+            .bind(&universe_id)
+            .bind(word)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(creature.map(|(creature_id,)| DefinitionHit::Creature { universe_id, creature_id }))
+    }
+
+    pub async fn upsert_glossary_term(&self, g: GlossaryTerm) -> Result<(), DbError> {
+        // ✅ Guard de capability (el glosario vive bajo el universo)
+        self.require_capability("universes").await?;
+
+        if g.term.trim().is_empty() {
+            return Err(DbError::Invalid("Glossary term cannot be empty".to_string()));
+        }
+
+        sqlx::query("INSERT INTO glossary (id, universe_id, term, definition, pronunciation, updated_at) VALUES (?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET term=excluded.term, definition=excluded.definition, pronunciation=excluded.pronunciation, updated_at=unixepoch()")
+            .bind(g.id).bind(g.universe_id).bind(g.term).bind(g.definition).bind(g.pronunciation)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
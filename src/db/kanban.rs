@@ -3,13 +3,13 @@
 // ========================================
 // Este módulo maneja boards, columnas y cards del sistema Kanban
 
-use crate::model::{Board, BoardColumn, Card, KanbanBoardData};
-use crate::db::Database;
+use crate::model::{Board, BoardColumn, BoardTemplate, Card, KanbanBoardData};
+use crate::db::{Database, DbError};
 use sqlx::SqlitePool;
 
 impl Database {
 
-    pub async fn load_board_full(pool: &SqlitePool, board_id: &str) -> Result<KanbanBoardData, sqlx::Error> {
+    pub async fn load_board_full(pool: &SqlitePool, board_id: &str) -> Result<KanbanBoardData, DbError> {
         let board = sqlx::query_as::<_, Board>("SELECT * FROM boards WHERE id = ?")
             .bind(board_id)
             .fetch_one(pool)
@@ -25,7 +25,7 @@ impl Database {
         let mut columns_with_cards = Vec::new();
         for col in columns {
             let cards = sqlx::query_as::<_, Card>(
-                "SELECT * FROM cards WHERE column_id = ? ORDER BY position ASC"
+                "SELECT * FROM cards WHERE column_id = ? AND archived = 0 ORDER BY position ASC"
             )
                 .bind(&col.id)
                 .fetch_all(pool)
@@ -37,7 +37,7 @@ impl Database {
         // ✅ NEW: Use optimized constructor
         Ok(KanbanBoardData::from_columns_and_cards(board, columns_with_cards))
     }
-    pub async fn get_all_boards(&self) -> Result<Vec<Board>, sqlx::Error> {
+    pub async fn get_all_boards(&self) -> Result<Vec<Board>, DbError> {
         crate::logger::info("🔍 DB: Querying boards...");
 
         let result = sqlx::query_as::<_, Board>(
@@ -57,12 +57,37 @@ impl Database {
         Ok(result)
     }
 
-    pub async fn create_board(&self, id: String, name: String) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn create_board(&self, id: String, name: String) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("boards").await?;
 
-        let mut tx = self.pool.begin().await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+        // Columnas default
+        let defaults = ["On Hold", "To Do", "In Progress", "Done"].map(String::from);
+        self.insert_board_with_columns(&id, &name, &defaults).await
+    }
+
+    /// Mismo alta que create_board, pero con las columnas guardadas en el template.
+    pub async fn create_board_from_template(&self, id: String, template_id: String, name: String) -> Result<(), DbError> {
+        self.require_capability("boards").await?;
+
+        let template = sqlx::query_as::<_, BoardTemplate>(
+            "SELECT id, name, columns_json FROM board_templates WHERE id = ?"
+        )
+            .bind(&template_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::Invalid(format!("Board template not found (id={})", template_id)))?;
+
+        let columns: Vec<String> = serde_json::from_str(&template.columns_json)?;
+        if columns.is_empty() {
+            return Err(DbError::Invalid("Board template has no columns".to_string()));
+        }
+
+        self.insert_board_with_columns(&id, &name, &columns).await
+    }
+
+    async fn insert_board_with_columns(&self, id: &str, name: &str, columns: &[String]) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
 
         // 1) Insert board
         sqlx::query(
@@ -71,22 +96,20 @@ impl Database {
                     VALUES (?, ?)
                     "#,
         )
-            .bind(&id)
-            .bind(&name)
+            .bind(id)
+            .bind(name)
             .execute(&mut *tx)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
-
-        // 2) Insert default columns
-        let defaults: [(&str, i64); 4] = [
-            ("On Hold", 0),
-            ("To Do", 1),
-            ("In Progress", 2),
-            ("Done", 3),
-        ];
+            .await?;
 
-        for (col_name, pos) in defaults {
-            let col_id = format!("col-{}-{}", &id, col_name.to_lowercase().replace(' ', "-"));
+        // 2) Insert columns (posición = orden de la lista)
+        let mut used_ids = std::collections::HashSet::new();
+        for (pos, col_name) in columns.iter().enumerate() {
+            let mut col_id = format!("col-{}-{}", id, col_name.to_lowercase().replace(' ', "-"));
+            // Templates pueden repetir nombres: el id de columna no
+            if !used_ids.insert(col_id.clone()) {
+                col_id = format!("{}-{}", col_id, pos);
+                used_ids.insert(col_id.clone());
+            }
 
             sqlx::query(
                 r#"
@@ -95,20 +118,56 @@ impl Database {
                     "#,
             )
                 .bind(col_id)
-                .bind(&id)
+                .bind(id)
                 .bind(col_name)
-                .bind(pos)
+                .bind(pos as i64)
                 .execute(&mut *tx)
-                .await
-                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_board_templates(&self) -> Result<Vec<BoardTemplate>, DbError> {
+        sqlx::query_as::<_, BoardTemplate>(
+            "SELECT id, name, columns_json FROM board_templates ORDER BY name COLLATE NOCASE ASC"
+        )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Guarda la estructura de columnas del board (nombres + orden, sin cards) como template.
+    pub async fn save_board_as_template(&self, template_id: String, board_id: String, name: String) -> Result<(), DbError> {
+        if name.trim().is_empty() {
+            return Err(DbError::Invalid("Template name cannot be empty".to_string()));
+        }
+
+        let columns: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM board_columns WHERE board_id = ? ORDER BY position ASC"
+        )
+            .bind(&board_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        if columns.is_empty() {
+            return Err(DbError::Invalid("Board has no columns to save".to_string()));
         }
 
-        tx.commit().await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+        let names: Vec<String> = columns.into_iter().map(|(n,)| n).collect();
+        let columns_json = serde_json::to_string(&names)?;
+
+        sqlx::query("INSERT INTO board_templates (id, name, columns_json) VALUES (?, ?, ?)")
+            .bind(template_id)
+            .bind(name.trim())
+            .bind(columns_json)
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
 
-    pub async fn delete_board(&self, board_id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_board(&self, board_id: String) -> Result<(), DbError> {
         let mut tx = self.pool.begin().await?;
 
         // Defensive deletes
@@ -131,7 +190,7 @@ impl Database {
         Ok(())
     }
 
-    pub async fn get_kanban_data(&self, board_id: String) -> Result<KanbanBoardData, sqlx::Error> {
+    pub async fn get_kanban_data(&self, board_id: String) -> Result<KanbanBoardData, DbError> {
         let board: Board = sqlx::query_as("SELECT id, name, kind FROM boards WHERE id = ?")
             .bind(&board_id)
             .fetch_one(&self.pool)
@@ -145,7 +204,7 @@ impl Database {
         let mut columns_with_cards: Vec<(BoardColumn, Vec<Card>)> = Vec::new();
 
         for (cid, cname, pos) in cols {
-            let cards: Vec<Card> = sqlx::query_as("SELECT id, column_id, title, description, position, priority FROM cards WHERE column_id = ? ORDER BY position ASC")
+            let cards: Vec<Card> = sqlx::query_as("SELECT id, column_id, title, description, position, priority, labels, NULLIF(due_date, 0) as due_date, archived FROM cards WHERE column_id = ? AND archived = 0 ORDER BY position ASC")
                 .bind(&cid)
                 .fetch_all(&self.pool)
                 .await?;
@@ -165,14 +224,15 @@ impl Database {
         Ok(KanbanBoardData::from_columns_and_cards(board, columns_with_cards))
     }
 
-    pub async fn upsert_card(&self, c: Card) -> Result<(), sqlx::Error> {
-        sqlx::query("INSERT INTO cards (id, column_id, title, description, position, priority, updated_at) VALUES (?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET column_id=excluded.column_id, title=excluded.title, description=excluded.description, position=excluded.position, priority=excluded.priority, updated_at=unixepoch()")
-            .bind(c.id).bind(c.column_id).bind(c.title).bind(c.description).bind(c.position).bind(c.priority)
+    pub async fn upsert_card(&self, c: Card) -> Result<(), DbError> {
+        let due_date = c.due(); // 0 => NULL
+        sqlx::query("INSERT INTO cards (id, column_id, title, description, position, priority, labels, due_date, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET column_id=excluded.column_id, title=excluded.title, description=excluded.description, position=excluded.position, priority=excluded.priority, labels=excluded.labels, due_date=excluded.due_date, updated_at=unixepoch()")
+            .bind(c.id).bind(c.column_id).bind(c.title).bind(c.description).bind(c.position).bind(c.priority).bind(c.labels).bind(due_date)
             .execute(&self.pool).await?;
         Ok(())
     }
 
-    pub async fn move_card(&self, card_id: String, column_id: String, pos: i64) -> Result<(), sqlx::Error> {
+    pub async fn move_card(&self, card_id: String, column_id: String, pos: i64) -> Result<(), DbError> {
         sqlx::query("UPDATE cards SET column_id = ?, position = ? WHERE id = ?")
             .bind(column_id)
             .bind(pos)
@@ -182,12 +242,126 @@ impl Database {
         Ok(())
     }
 
-    pub async fn delete_card(&self, card_id: String) -> Result<(), sqlx::Error> {
+    /// Mueve una card a otro board: cae en la columna "To Do" del destino
+    /// (o la primera por posición si fue renombrada/borrada), al final.
+    pub async fn move_card_to_board(&self, card_id: String, target_board_id: String) -> Result<(), DbError> {
+        let column_id: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM board_columns WHERE board_id = ? \
+             ORDER BY CASE WHEN name = 'To Do' THEN 0 ELSE 1 END, position ASC LIMIT 1",
+        )
+            .bind(&target_board_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some((column_id,)) = column_id else {
+            return Err(DbError::MissingParent { kind: "board column".to_string(), id: target_board_id });
+        };
+
+        let (max_pos,): (i64,) = sqlx::query_as(
+            "SELECT CAST(COALESCE(MAX(position), 0) AS INTEGER) FROM cards WHERE column_id = ?",
+        )
+            .bind(&column_id)
+            .fetch_one(&self.pool)
+            .await?;
+        let pos = max_pos + 1000;
+
+        let updated = sqlx::query("UPDATE cards SET column_id = ?, position = ? WHERE id = ?")
+            .bind(&column_id)
+            .bind(pos)
+            .bind(&card_id)
+            .execute(&self.pool)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid(format!("Card not found (id={})", card_id)));
+        }
+        Ok(())
+    }
+
+    pub async fn delete_card(&self, card_id: String) -> Result<(), DbError> {
         sqlx::query("DELETE FROM cards WHERE id = ?").bind(card_id).execute(&self.pool).await?;
         Ok(())
     }
 
-    pub async fn rebalance_column(&self, column_id: String) -> Result<(), sqlx::Error> {
+    pub async fn archive_card(&self, card_id: String) -> Result<(), DbError> {
+        let updated = sqlx::query("UPDATE cards SET archived = 1, updated_at = unixepoch() WHERE id = ?")
+            .bind(&card_id)
+            .execute(&self.pool)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid(format!("Card not found (id={})", card_id)));
+        }
+        Ok(())
+    }
+
+    /// Archivadas del board + huérfanas (columna ya inexistente) para poder rescatarlas.
+    pub async fn get_archived_cards(&self, board_id: String) -> Result<Vec<Card>, DbError> {
+        sqlx::query_as::<_, Card>(
+            "SELECT c.id, c.column_id, c.title, c.description, c.position, c.priority, c.labels, \
+                    NULLIF(c.due_date, 0) as due_date, c.archived \
+             FROM cards c LEFT JOIN board_columns bc ON bc.id = c.column_id \
+             WHERE c.archived = 1 AND (bc.board_id = ? OR bc.id IS NULL) \
+             ORDER BY c.updated_at DESC",
+        )
+            .bind(board_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Vuelve al final de su columna original; si la columna ya no existe, a la primera del board.
+    pub async fn restore_card(&self, card_id: String, board_id: String) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        let column_id: Option<(String,)> = sqlx::query_as(
+            "SELECT bc.id FROM cards c JOIN board_columns bc ON bc.id = c.column_id WHERE c.id = ?",
+        )
+            .bind(&card_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let column_id = match column_id {
+            Some((id,)) => id,
+            None => {
+                let first: Option<(String,)> = sqlx::query_as(
+                    "SELECT id FROM board_columns WHERE board_id = ? ORDER BY position ASC LIMIT 1",
+                )
+                    .bind(&board_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+                let Some((id,)) = first else {
+                    return Err(DbError::MissingParent { kind: "board column".to_string(), id: board_id });
+                };
+                id
+            }
+        };
+
+        let (max_pos,): (i64,) = sqlx::query_as(
+            "SELECT CAST(COALESCE(MAX(position), 0) AS INTEGER) FROM cards WHERE column_id = ? AND archived = 0",
+        )
+            .bind(&column_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let updated = sqlx::query(
+            "UPDATE cards SET archived = 0, column_id = ?, position = ?, updated_at = unixepoch() WHERE id = ?",
+        )
+            .bind(&column_id)
+            .bind(max_pos + 1000)
+            .bind(&card_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid(format!("Card not found (id={})", card_id)));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn rebalance_column(&self, column_id: String) -> Result<(), DbError> {
         let cards: Vec<(String,)> = sqlx::query_as("SELECT id FROM cards WHERE column_id = ? ORDER BY position ASC").bind(&column_id).fetch_all(&self.pool).await?;
         let mut tx = self.pool.begin().await?;
         for (i, (id,)) in cards.into_iter().enumerate() {
@@ -197,4 +371,28 @@ impl Database {
         tx.commit().await?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    // Mueve una columna a new_index y renumera todas (0..n) en una transacción.
+    pub async fn reorder_column(&self, board_id: String, column_id: String, new_index: usize) -> Result<(), DbError> {
+        let mut ids: Vec<String> = sqlx::query_as::<_, (String,)>("SELECT id FROM board_columns WHERE board_id = ? ORDER BY position ASC")
+            .bind(&board_id)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        let Some(from) = ids.iter().position(|id| *id == column_id) else {
+            return Err(DbError::MissingParent { kind: "column".to_string(), id: column_id });
+        };
+        let id = ids.remove(from);
+        ids.insert(new_index.min(ids.len()), id);
+
+        let mut tx = self.pool.begin().await?;
+        for (i, id) in ids.iter().enumerate() {
+            sqlx::query("UPDATE board_columns SET position = ? WHERE id = ?").bind(i as i64).bind(id).execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
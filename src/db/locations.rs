@@ -4,31 +4,56 @@
 // Este módulo maneja CRUD de locaciones con soporte para jerarquías (parent_id)
 
 use crate::model::Location;
-use crate::db::Database;
+use crate::db::{Database, DbError};
 
 impl Database {
-    pub async fn get_locations_flat(&self, universe_id: String) -> Result<Vec<Location>, sqlx::Error> {
+    pub async fn get_locations_flat(&self, universe_id: String) -> Result<Vec<Location>, DbError> {
         sqlx::query_as::<_, Location>(
-            "SELECT id, universe_id, parent_id, name, description, kind FROM locations WHERE universe_id = ? ORDER BY name ASC"
+            "SELECT id, universe_id, parent_id, name, description, kind, image_path FROM locations WHERE universe_id = ? ORDER BY name ASC"
         )
             .bind(universe_id)
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
     }
 
-    pub async fn upsert_location(&self, l: Location) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn upsert_location(&self, l: Location) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("locations").await?;
 
-        sqlx::query("INSERT INTO locations (id, universe_id, parent_id, name, description, kind, updated_at) VALUES (?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET parent_id=excluded.parent_id, name=excluded.name, description=excluded.description, kind=excluded.kind, updated_at=unixepoch()")
-            .bind(l.id).bind(l.universe_id).bind(l.parent_id).bind(l.name).bind(l.description).bind(l.kind)
+        // Reparent: el nuevo padre no puede descender de esta location (ciclo)
+        if let Some(parent_id) = l.parent_id.as_deref() {
+            if parent_id == l.id {
+                return Err(DbError::Invalid("A location cannot be its own parent".to_string()));
+            }
+
+            let cycle: Option<i64> = sqlx::query_scalar(
+                "WITH RECURSIVE ancestors(id, depth) AS (
+                    SELECT parent_id, 1 FROM locations WHERE id = ?1
+                    UNION ALL
+                    SELECT l.parent_id, a.depth + 1 FROM locations l JOIN ancestors a ON l.id = a.id
+                    WHERE a.id IS NOT NULL AND a.depth < 1000
+                )
+                SELECT 1 FROM ancestors WHERE id = ?2 LIMIT 1"
+            )
+                .bind(parent_id)
+                .bind(&l.id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+            if cycle.is_some() {
+                return Err(DbError::Invalid("A location cannot be moved inside its own sub-locations".to_string()));
+            }
+        }
+
+        sqlx::query("INSERT INTO locations (id, universe_id, parent_id, name, description, kind, image_path, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, unixepoch()) ON CONFLICT(id) DO UPDATE SET parent_id=excluded.parent_id, name=excluded.name, description=excluded.description, kind=excluded.kind, image_path=excluded.image_path, updated_at=unixepoch()")
+            .bind(l.id).bind(l.universe_id).bind(l.parent_id).bind(l.name).bind(l.description).bind(l.kind).bind(l.image_path)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
         Ok(())
     }
 
-    pub async fn delete_location(&self, id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_location(&self, id: String) -> Result<(), DbError> {
         sqlx::query("DELETE FROM locations WHERE id = ?").bind(id).execute(&self.pool).await?;
         Ok(())
     }
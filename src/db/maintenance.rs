@@ -0,0 +1,350 @@
+// ========================================
+// maintenance.rs - Herramientas de mantenimiento por universo
+// ========================================
+// Find & replace de texto sobre creatures/locations/events/scenes.
+// Preview y apply usan el MISMO criterio (instr/replace de SQLite, case-sensitive)
+// para que lo que se muestra sea exactamente lo que se reemplaza.
+// Reparación de FKs: resuelve lo que reporta validate_integrity (PRAGMA foreign_key_check).
+// VACUUM + stats: compactar el archivo tras muchos deletes.
+// Recalcular word_count: lo mismo que corre al arrancar, a mano (ej: tras un import masivo).
+
+use sqlx::Row;
+
+use crate::db::{Database, DbError};
+use crate::model::{DbStats, ReplaceScope, TextMatch};
+
+// (scope, tabla, columna de nombre, columnas de texto a reemplazar)
+fn scope_columns(scope: ReplaceScope) -> (&'static str, &'static str, &'static [&'static str]) {
+    match scope {
+        ReplaceScope::Creatures => ("bestiary_entries", "name", &["name", "habitat", "description"]),
+        ReplaceScope::Locations => ("locations", "name", &["name", "description"]),
+        ReplaceScope::Events => ("timeline_events", "title", &["title", "description"]),
+        ReplaceScope::Scenes => ("scenes", "title", &["title", "body"]),
+    }
+}
+
+// Scenes no tienen universe_id directo: scenes -> chapters -> novels.universe_id
+fn scope_filter(scope: ReplaceScope) -> &'static str {
+    match scope {
+        ReplaceScope::Scenes => {
+            "chapter_id IN (SELECT c.id FROM chapters c JOIN novels n ON n.id = c.novel_id WHERE n.universe_id = ?)"
+        }
+        _ => "universe_id = ?",
+    }
+}
+
+fn match_clause(cols: &[&str]) -> String {
+    cols.iter()
+        .map(|c| format!("instr({}, ?) > 0", c))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+impl Database {
+    /// Preview: filas afectadas por el término (no modifica nada).
+    pub async fn find_text_matches(
+        &self,
+        universe_id: String,
+        term: String,
+        scopes: Vec<ReplaceScope>,
+    ) -> Result<Vec<TextMatch>, DbError> {
+        let mut out = Vec::new();
+        if term.is_empty() {
+            return Ok(out);
+        }
+
+        for scope in scopes {
+            let (table, name_col, cols) = scope_columns(scope);
+            let sql = format!(
+                "SELECT id, {name_col} AS display_name, {cols} FROM {table} WHERE {filter} AND ({matches}) ORDER BY {name_col}",
+                name_col = name_col,
+                cols = cols.join(", "),
+                table = table,
+                filter = scope_filter(scope),
+                matches = match_clause(cols),
+            );
+
+            let mut q = sqlx::query(&sql).bind(&universe_id);
+            for _ in cols {
+                q = q.bind(&term);
+            }
+
+            for r in q.fetch_all(&self.pool).await? {
+                let mut occurrences = 0usize;
+                for c in cols {
+                    let v: String = r.try_get(*c)?;
+                    occurrences += v.matches(term.as_str()).count();
+                }
+
+                out.push(TextMatch {
+                    scope,
+                    entity_id: r.try_get("id")?,
+                    display_name: r.try_get("display_name")?,
+                    occurrences,
+                });
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Reemplazo masivo en una sola transacción. Devuelve filas modificadas.
+    pub async fn bulk_replace_text(
+        &self,
+        universe_id: String,
+        from: String,
+        to: String,
+        scopes: Vec<ReplaceScope>,
+    ) -> Result<u64, DbError> {
+        if from.is_empty() {
+            return Err(DbError::Invalid("Find term cannot be empty".to_string()));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut affected: u64 = 0;
+
+        for scope in scopes {
+            let (table, _name_col, cols) = scope_columns(scope);
+
+            if scope == ReplaceScope::Scenes {
+                // Scenes: word_count debe seguir al body (C8), así que lo hacemos fila por fila
+                let sql = format!(
+                    "SELECT id, title, body FROM scenes WHERE {} AND ({})",
+                    scope_filter(scope),
+                    match_clause(cols)
+                );
+                let rows = sqlx::query(&sql)
+                    .bind(&universe_id)
+                    .bind(&from)
+                    .bind(&from)
+                    .fetch_all(&mut *tx)
+                    .await?;
+
+                for r in rows {
+                    let id: String = r.try_get("id")?;
+                    let title: String = r.try_get("title")?;
+                    let body: String = r.try_get("body")?;
+
+                    let new_body = body.replace(from.as_str(), &to);
+                    let word_count = new_body.split_whitespace().count() as i64;
+
+                    sqlx::query(
+                        "UPDATE scenes SET title = ?, body = ?, word_count = ?, updated_at = unixepoch() WHERE id = ?",
+                    )
+                        .bind(title.replace(from.as_str(), &to))
+                        .bind(new_body)
+                        .bind(word_count)
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    affected += 1;
+                }
+                continue;
+            }
+
+            let sets = cols
+                .iter()
+                .map(|c| format!("{c} = replace({c}, ?, ?)", c = c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!(
+                "UPDATE {} SET {}, updated_at = unixepoch() WHERE {} AND ({})",
+                table,
+                sets,
+                scope_filter(scope),
+                match_clause(cols)
+            );
+
+            let mut q = sqlx::query(&sql);
+            for _ in cols.iter() {
+                q = q.bind(&from).bind(&to);
+            }
+            q = q.bind(&universe_id);
+            for _ in cols.iter() {
+                q = q.bind(&from);
+            }
+
+            affected += q.execute(&mut *tx).await?.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(affected)
+    }
+
+    /// Repara violaciones de PRAGMA foreign_key_check en una transacción:
+    /// FK nullable => se pone en NULL; si no => se borra la fila huérfana.
+    /// Cada reparación queda en audit_log. Devuelve cuántas filas se tocaron.
+    pub async fn repair_foreign_keys(&self) -> Result<usize, DbError> {
+        let mut tx = self.pool.begin().await?;
+        let mut repaired = 0usize;
+
+        // Borrar una fila puede dejar huérfanos a sus hijos (sin CASCADE en schemas viejos):
+        // repetimos hasta que no queden violaciones, con tope por si algo no converge.
+        for _pass in 0..8 {
+            let violations = sqlx::query("PRAGMA foreign_key_check")
+                .fetch_all(&mut *tx)
+                .await?;
+            if violations.is_empty() {
+                break;
+            }
+
+            for v in violations {
+                let table: String = v.try_get("table")?;
+                let parent: String = v.try_get("parent")?;
+                let fkid: i64 = v.try_get("fkid")?;
+                // WITHOUT ROWID => no podemos apuntar a la fila
+                let Some(rowid) = v.try_get::<Option<i64>, _>("rowid")? else {
+                    continue;
+                };
+
+                let fk_cols: Vec<String> = sqlx::query_scalar(&format!(
+                    "SELECT \"from\" FROM pragma_foreign_key_list('{table}') WHERE id = ?1"
+                ))
+                    .bind(fkid)
+                    .fetch_all(&mut *tx)
+                    .await?;
+
+                let mut nullable = !fk_cols.is_empty();
+                for col in &fk_cols {
+                    let notnull: i64 = sqlx::query_scalar(&format!(
+                        "SELECT \"notnull\" FROM pragma_table_info('{table}') WHERE name = ?1"
+                    ))
+                        .bind(col)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                        .unwrap_or(1);
+                    nullable &= notnull == 0;
+                }
+
+                // Para el audit: id legible si la tabla lo tiene
+                let entity_id: String = sqlx::query_scalar::<_, String>(&format!(
+                    "SELECT CAST(id AS TEXT) FROM {table} WHERE rowid = ?1"
+                ))
+                    .bind(rowid)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| format!("rowid:{}", rowid));
+
+                let (action, affected) = if nullable {
+                    let set = fk_cols.iter().map(|c| format!("{c} = NULL")).collect::<Vec<_>>().join(", ");
+                    let affected = sqlx::query(&format!("UPDATE {table} SET {set} WHERE rowid = ?1"))
+                        .bind(rowid)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+                    ("fk_repair_null", affected)
+                } else {
+                    let affected = sqlx::query(&format!("DELETE FROM {table} WHERE rowid = ?1"))
+                        .bind(rowid)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+                    ("fk_repair_delete", affected)
+                };
+                // La fila ya cayó por otra violación de esta misma pasada
+                if affected == 0 {
+                    continue;
+                }
+
+                let details = serde_json::json!({
+                    "table": table,
+                    "columns": fk_cols,
+                    "parent": parent,
+                })
+                    .to_string();
+
+                // Mismo INSERT que insert_audit_log, pero dentro de la tx (pool de 1 conexión)
+                sqlx::query(
+                    "INSERT INTO audit_log (id, ts, action, entity_type, entity_id, details_json)
+                     VALUES (?, unixepoch(), ?, ?, ?, ?)",
+                )
+                    .bind(uuid::Uuid::new_v4().to_string())
+                    .bind(action)
+                    .bind(&table)
+                    .bind(&entity_id)
+                    .bind(details)
+                    .execute(&mut *tx)
+                    .await?;
+
+                crate::logger::info(&format!("🩹 FK repair: {} {} ({} -> {})", action, entity_id, table, parent));
+                repaired += 1;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(repaired)
+    }
+
+    /// Tamaño lógico de la DB (page_count * page_size).
+    async fn db_size_bytes(&self) -> Result<i64, DbError> {
+        sqlx::query_scalar("SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    pub async fn db_stats(&self) -> Result<DbStats, DbError> {
+        let size_bytes = self.db_size_bytes().await?;
+        let free_bytes: i64 =
+            sqlx::query_scalar("SELECT freelist_count * page_size FROM pragma_freelist_count(), pragma_page_size()")
+                .fetch_one(&self.pool)
+                .await?;
+
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut tables = Vec::with_capacity(names.len());
+        for name in names {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")))
+                .fetch_one(&self.pool)
+                .await?;
+            tables.push((name, count));
+        }
+
+        Ok(DbStats { size_bytes, free_bytes, tables })
+    }
+
+    /// Re-cuenta palabras de todas las escenas desde el body. Devuelve cuántas se corrigieron.
+    pub async fn recalc_word_counts(&self) -> Result<usize, DbError> {
+        let updated = crate::db::schema_guard::recalc_scene_word_counts(&self.pool).await?;
+        if updated > 0 {
+            crate::logger::info(&format!("🔢 Word counts recalculated: {} scenes corrected", updated));
+        }
+        Ok(updated)
+    }
+
+    /// VACUUM con el mismo retry que connect si SQLite está ocupado.
+    /// El caller garantiza que no hay DbAction en vuelo (pool de 1 conexión => sin tx abierta).
+    /// Devuelve (bytes antes, bytes después).
+    pub async fn vacuum(&self) -> Result<(i64, i64), DbError> {
+        let before = self.db_size_bytes().await?;
+
+        for attempt in 1..=5 {
+            match sqlx::query("VACUUM").execute(&self.pool).await {
+                Ok(_) => break,
+                Err(e) if Self::is_sqlite_locked(&e) && attempt < 5 => {
+                    crate::logger::warn(&format!(
+                        "SQLite locked during VACUUM (attempt {}/5). Retrying...",
+                        attempt
+                    ));
+                    tokio::time::sleep(std::time::Duration::from_millis(250 * attempt as u64)).await;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        // En WAL el VACUUM pasa por el -wal: lo volcamos para que el archivo encoja ya
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&self.pool).await?;
+
+        let after = self.db_size_bytes().await?;
+        crate::logger::info(&format!("🧹 DB: VACUUM {} -> {} bytes", before, after));
+        Ok((before, after))
+    }
+}
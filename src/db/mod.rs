@@ -14,13 +14,25 @@ mod migrations;
 mod schema_guard;
 mod universes;
 mod locations;
+mod glossary;
 mod creatures;
 mod timeline;
 mod kanban;
 mod novels;
-mod trash;
+pub mod recents;
+pub mod trash;
 mod demo;
-mod audit;
+pub mod audit;
+mod maintenance;
+mod error;
+pub mod backup;
+pub mod project_settings;
+pub mod novel_export;
+pub mod bestiary_export;
+pub mod timeline_data_export;
+pub mod project_archive;
+
+pub use error::DbError;
 
 // Re-exportar la estructura principal
 #[derive(Debug, Clone)]
@@ -32,13 +44,20 @@ pub struct Database {
 static DB_CONNECT_LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
 
 impl Database {
-    pub async fn connect(db_path: PathBuf) -> Result<Self, sqlx::Error> {
+    pub async fn connect(db_path: PathBuf) -> Result<Self, DbError> {
         // ✅ Evita múltiples connects concurrentes (UI/tasks)
         let _guard = DB_CONNECT_LOCK
             .get_or_init(|| tokio::sync::Mutex::new(()))
             .lock()
             .await;
 
+        // Restore elegido en Settings: se aplica antes de abrir el archivo
+        match backup::apply_pending_restore(&db_path) {
+            Ok(true) => crate::logger::info(&format!("♻️ DB restored from backup: {}", db_path.display())),
+            Ok(false) => {}
+            Err(e) => crate::logger::error(&format!("❌ Pending restore could not be applied: {}", e)),
+        }
+
         let options = SqliteConnectOptions::new()
             .filename(&db_path)
             .create_if_missing(true)
@@ -72,7 +91,7 @@ impl Database {
                     tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
                     continue;
                 }
-                Err(e) => return Err(e),
+                Err(e) => return Err(e.into()),
             }
         }
 
@@ -102,8 +121,17 @@ impl Database {
 
         db.repair_integrity().await?;
 
-        // Auto-cleanup de trash (14 días)
-        match db.cleanup_old_trash(14).await {
+        // Auto-cleanup de trash (retención del proyecto; "Never" => no se toca)
+        let retention = db
+            .get_trash_retention()
+            .await
+            .unwrap_or(trash::TrashRetention(trash::TRASH_RETENTION_DAYS));
+        let cleanup = if retention.is_never() {
+            Ok(0)
+        } else {
+            db.cleanup_old_trash(retention.0).await
+        };
+        match cleanup {
             Ok(count) if count > 0 => {
                 crate::logger::info(&format!("🗑️ Auto-cleanup: {} old items removed from trash", count));
             }
@@ -117,8 +145,10 @@ impl Database {
     }
 
     /// Helper para verificar capabilities antes de operaciones
-    async fn require_capability(&self, capability: &str) -> Result<(), Box<dyn std::error::Error>> {
-        crate::guards::check_capability(&self.capabilities, capability).await
+    async fn require_capability(&self, capability: &str) -> Result<(), DbError> {
+        crate::guards::check_capability(&self.capabilities, capability)
+            .await
+            .map_err(DbError::from)
     }
 
     // Detecta "database is locked" (SQLite code 5)
@@ -130,11 +160,11 @@ impl Database {
         }
     }
 
-    pub async fn get_schema_version(&self) -> Result<i64, sqlx::Error> {
-        migrations::read_schema_version(&self.pool).await
+    pub async fn get_schema_version(&self) -> Result<i64, DbError> {
+        migrations::read_schema_version(&self.pool).await.map_err(DbError::from)
     }
 
-    pub async fn validate_integrity(&self) -> Result<Vec<String>, sqlx::Error> {
+    pub async fn validate_integrity(&self) -> Result<Vec<String>, DbError> {
         // Verificación real (SQLite): foreign keys
         // Devuelve una lista de strings amigables para UI/debug.
         use sqlx::Row;
@@ -163,7 +193,7 @@ impl Database {
     }
 
 
-    async fn repair_integrity(&self) -> Result<(), sqlx::Error> {
+    async fn repair_integrity(&self) -> Result<(), DbError> {
         // Primero asegurarse que existe el universo default
         sqlx::query(
             "INSERT OR IGNORE INTO universes (id, name, description) VALUES \
@@ -195,7 +225,7 @@ impl Database {
         Ok(())
     }
 
-    async fn repair_legacy_kanban(&self) -> Result<(), sqlx::Error> {
+    async fn repair_legacy_kanban(&self) -> Result<(), DbError> {
         let expected = vec![
             ("col-hold", "On-Hold", 0),
             ("col-todo", "To Do", 1),
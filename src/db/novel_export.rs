@@ -0,0 +1,187 @@
+// ========================================
+// novel_export.rs - Dump JSON / Markdown (solo lectura) de una novela
+// ========================================
+// Novel + chapters + scenes ordenados, para scripts de backup/transformación.
+// Shape estable y versionado: structs propios (NO los del modelo) para que cambios
+// internos de columnas no rompan tooling externo. Cambios incompatibles => subir versión.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::db::{Database, DbError};
+use crate::model::{Chapter, Novel, Scene};
+
+pub const NOVEL_EXPORT_FORMAT: &str = "tas.novel";
+pub const NOVEL_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct NovelExport {
+    pub format: &'static str,
+    pub format_version: u32,
+    pub exported_at: String,
+    pub novel: NovelExportNovel,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelExportNovel {
+    pub id: String,
+    pub universe_id: Option<String>,
+    pub title: String,
+    pub synopsis: String,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub chapters: Vec<NovelExportChapter>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelExportChapter {
+    pub id: String,
+    pub title: String,
+    pub position: i64,
+    pub synopsis: String,
+    pub status: String,
+    pub scenes: Vec<NovelExportScene>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelExportScene {
+    pub id: String,
+    pub title: String,
+    pub position: i64,
+    pub status: String,
+    pub kind: String,
+    pub word_count: i64,
+    pub body: String,
+}
+
+impl Database {
+    pub async fn export_novel_json(&self, novel_id: String) -> Result<String, DbError> {
+        let novel = sqlx::query_as::<_, Novel>("SELECT * FROM novels WHERE id = ?")
+            .bind(&novel_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::Invalid(format!("Novel not found: {}", novel_id)))?;
+
+        let chapters = sqlx::query_as::<_, Chapter>(
+            "SELECT * FROM chapters WHERE novel_id = ? ORDER BY position ASC, created_at ASC"
+        )
+            .bind(&novel_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut out_chapters = Vec::with_capacity(chapters.len());
+        for ch in chapters {
+            // get_scenes ya ordena por position
+            let scenes: Vec<Scene> = self.get_scenes(ch.id.clone()).await?;
+            out_chapters.push(NovelExportChapter {
+                id: ch.id,
+                title: ch.title,
+                position: ch.position,
+                synopsis: ch.synopsis,
+                status: ch.status,
+                // Scratch/borradores marcados fuera del compile no se exportan
+                scenes: scenes
+                    .into_iter()
+                    .filter(|s| s.include_in_compile)
+                    .map(|s| NovelExportScene {
+                        kind: s.kind().as_str().to_string(),
+                        id: s.id,
+                        title: s.title,
+                        position: s.position,
+                        status: s.status,
+                        word_count: s.word_count,
+                        body: s.body,
+                    })
+                    .collect(),
+            });
+        }
+
+        let export = NovelExport {
+            format: NOVEL_EXPORT_FORMAT,
+            format_version: NOVEL_EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            novel: NovelExportNovel {
+                id: novel.id,
+                universe_id: novel.universe_id,
+                title: novel.title,
+                synopsis: novel.synopsis,
+                status: novel.status,
+                created_at: novel.created_at.to_rfc3339(),
+                updated_at: novel.updated_at.to_rfc3339(),
+                chapters: out_chapters,
+            },
+        };
+
+        serde_json::to_string_pretty(&export).map_err(|e| DbError::Codec(e.to_string()))
+    }
+
+    /// Compila la novela a un único Markdown: # novela, ## chapter, ### scene + body.
+    /// Scenes vacías igual emiten su heading (preserva estructura); sin metadata (word counts, status).
+    pub async fn export_novel_markdown(&self, novel_id: String) -> Result<String, DbError> {
+        let novel = sqlx::query_as::<_, Novel>("SELECT * FROM novels WHERE id = ?")
+            .bind(&novel_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::Invalid(format!("Novel not found: {}", novel_id)))?;
+
+        let chapters = sqlx::query_as::<_, Chapter>(
+            "SELECT * FROM chapters WHERE novel_id = ? ORDER BY position ASC, created_at ASC"
+        )
+            .bind(&novel_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut out = format!("# {}\n", heading_text(&novel.title));
+
+        for ch in chapters {
+            out.push_str(&format!("\n## {}\n", heading_text(&ch.title)));
+
+            let scenes: Vec<Scene> = self.get_scenes(ch.id.clone()).await?;
+            for scene in scenes.into_iter().filter(|s| s.include_in_compile) {
+                out.push_str(&format!("\n### {}\n", heading_text(&scene.title)));
+
+                let body = scene.body.trim();
+                if !body.is_empty() {
+                    out.push('\n');
+                    out.push_str(body);
+                    out.push('\n');
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+// Títulos en una sola línea (un salto de línea rompería el heading)
+fn heading_text(title: &str) -> String {
+    let t = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if t.is_empty() { "Untitled".to_string() } else { t }
+}
+
+/// data_dir/exports/<stem>/novel-<slug>-<timestamp>.json
+pub fn novel_export_path(db_path: &Path, title: &str) -> PathBuf {
+    novel_export_file(db_path, title, "json")
+}
+
+/// data_dir/exports/<stem>/novel-<slug>-<timestamp>.md
+pub fn novel_markdown_path(db_path: &Path, title: &str) -> PathBuf {
+    novel_export_file(db_path, title, "md")
+}
+
+fn novel_export_file(db_path: &Path, title: &str, ext: &str) -> PathBuf {
+    let slug: String = title
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() { "novel".to_string() } else { slug };
+
+    crate::db::project_settings::exports_dir(db_path)
+        .join(format!("novel-{}-{}.{}", slug, chrono::Local::now().format("%Y%m%d-%H%M%S"), ext))
+}
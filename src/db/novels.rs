@@ -3,13 +3,16 @@
 // ========================================
 // Este módulo maneja el sistema completo de escritura: novels, chapters, scenes
 
-use crate::model::{Novel, Chapter, Scene};
-use crate::db::Database;
+use crate::model::{Novel, Chapter, Scene, SceneMention, SceneHit, ForgePath, SceneRevision};
+use crate::db::{Database, DbError};
+
+/// Revisiones del body que se conservan por escena (las más viejas se podan al guardar)
+pub const SCENE_REVISIONS_KEEP: i64 = 30;
 
 impl Database {
     // --- NOVELS ---
 
-    pub async fn get_novels(&self, universe_id: Option<String>) -> Result<Vec<Novel>, sqlx::Error> {
+    pub async fn get_novels(&self, universe_id: Option<String>) -> Result<Vec<Novel>, DbError> {
         crate::logger::info(&format!("🔍 DB: Querying novels for universe: {:?}", universe_id));
 
         let result = match universe_id {
@@ -44,12 +47,12 @@ impl Database {
         novel_id: String,
         universe_id: Option<String>,
         title: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("forge").await?;
 
         if title.trim().is_empty() {
-            return Err("Title cannot be empty".into());
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         sqlx::query("INSERT INTO novels (id, universe_id, title) VALUES (?, ?, ?)")
@@ -57,15 +60,14 @@ impl Database {
             .bind(universe_id)
             .bind(title)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         Ok(())
     }
 
-    pub async fn update_novel(&self, n: Novel) -> Result<(), sqlx::Error> {
+    pub async fn update_novel(&self, n: Novel) -> Result<(), DbError> {
         if n.title.trim().is_empty() {
-            return Err(sqlx::Error::Protocol("Title cannot be empty".into()));
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         sqlx::query(
@@ -83,7 +85,7 @@ impl Database {
         Ok(())
     }
 
-    pub async fn delete_novel(&self, novel_id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_novel(&self, novel_id: String) -> Result<(), DbError> {
         // CASCADE DELETE: Esto borrará automáticamente chapters y scenes
         crate::logger::info(&format!("Deleting novel {} (cascade to chapters and scenes)", novel_id));
 
@@ -95,11 +97,64 @@ impl Database {
         Ok(())
     }
 
+    /// Fusiona `merge_id` en `keep_id`: sus chapters pasan al final (posiciones renumeradas
+    /// a continuación de las del novel que queda) y el novel vacío se borra. Todo en una tx.
+    pub async fn merge_novels(&self, keep_id: String, merge_id: String) -> Result<(), DbError> {
+        // ✅ Guard de capability
+        self.require_capability("forge").await?;
+
+        if keep_id == merge_id {
+            return Err(DbError::Invalid("Cannot merge a novel into itself".to_string()));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for id in [&keep_id, &merge_id] {
+            let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM novels WHERE id = ?")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if exists == 0 {
+                return Err(DbError::MissingParent { kind: "novel".to_string(), id: id.clone() });
+            }
+        }
+
+        let (max_pos,): (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM chapters WHERE novel_id = ?")
+            .bind(&keep_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let mut next_pos = max_pos.unwrap_or(-1) + 1;
+
+        let moving: Vec<(String,)> = sqlx::query_as("SELECT id FROM chapters WHERE novel_id = ? ORDER BY position ASC")
+            .bind(&merge_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        for (chapter_id,) in moving {
+            sqlx::query("UPDATE chapters SET novel_id = ?, position = ?, updated_at = unixepoch() WHERE id = ?")
+                .bind(&keep_id)
+                .bind(next_pos)
+                .bind(&chapter_id)
+                .execute(&mut *tx)
+                .await?;
+            next_pos += 1;
+        }
+
+        // Ya sin chapters: el CASCADE no arrastra nada
+        sqlx::query("DELETE FROM novels WHERE id = ?")
+            .bind(&merge_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     // --- CHAPTERS ---
 
-    pub async fn get_chapters(&self, novel_id: String) -> Result<Vec<Chapter>, sqlx::Error> {
+    pub async fn get_chapters(&self, novel_id: String) -> Result<Vec<Chapter>, DbError> {
         sqlx::query_as::<_, Chapter>(
-            "SELECT id, novel_id, title, position, synopsis, status, created_at, updated_at
+            "SELECT id, novel_id, title, position, synopsis, status, created_at, updated_at, target_words
                     FROM chapters
                     WHERE novel_id = ?
                     ORDER BY position ASC"
@@ -107,6 +162,7 @@ impl Database {
             .bind(novel_id)
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
     }
 
     pub async fn create_chapter_with_id(
@@ -114,31 +170,29 @@ impl Database {
         chapter_id: String,
         novel_id: String,
         title: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("forge").await?;
 
         if title.trim().is_empty() {
-            return Err("Title cannot be empty".into());
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         // Verificar que el novel existe
         let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM novels WHERE id = ?")
             .bind(&novel_id)
             .fetch_one(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         if exists == 0 {
-            return Err(format!("Novel {} not found", novel_id).into());
+            return Err(DbError::MissingParent { kind: "novel".to_string(), id: novel_id });
         }
 
         // Obtener la siguiente posición
         let (max_pos,): (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM chapters WHERE novel_id = ?")
             .bind(&novel_id)
             .fetch_one(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         let pos = max_pos.unwrap_or(-1) + 1;
 
@@ -148,25 +202,25 @@ impl Database {
             .bind(title)
             .bind(pos)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         Ok(())
     }
 
-    pub async fn update_chapter(&self, c: Chapter) -> Result<(), sqlx::Error> {
+    pub async fn update_chapter(&self, c: Chapter) -> Result<(), DbError> {
         if c.title.trim().is_empty() {
-            return Err(sqlx::Error::Protocol("Title cannot be empty".into()));
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         sqlx::query(
             "UPDATE chapters
-                    SET title = ?, synopsis = ?, status = ?, updated_at = unixepoch()
+                    SET title = ?, synopsis = ?, status = ?, target_words = ?, updated_at = unixepoch()
                     WHERE id = ?"
         )
             .bind(c.title)
             .bind(c.synopsis)
             .bind(c.status)
+            .bind(c.target_words.max(0))
             .bind(c.id)
             .execute(&self.pool)
             .await?;
@@ -174,7 +228,7 @@ impl Database {
         Ok(())
     }
 
-    pub async fn delete_chapter(&self, chapter_id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_chapter(&self, chapter_id: String) -> Result<(), DbError> {
         // CASCADE DELETE: Esto borrará automáticamente todas las scenes del chapter
         crate::logger::info(&format!("Deleting chapter {} (cascade to scenes)", chapter_id));
 
@@ -186,21 +240,52 @@ impl Database {
         Ok(())
     }
 
-    pub async fn reorder_chapter(&self, chapter_id: String, new_position: i64) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE chapters SET position = ?, updated_at = unixepoch() WHERE id = ?")
-            .bind(new_position)
-            .bind(chapter_id)
-            .execute(&self.pool)
+    /// Mueve el chapter al índice new_position dentro de su novel y renumera 0..n
+    /// (mismo resultado que el reorden optimista del outline).
+    pub async fn reorder_chapter(&self, chapter_id: String, new_position: i64) -> Result<(), DbError> {
+        let (novel_id,): (String,) = sqlx::query_as("SELECT novel_id FROM chapters WHERE id = ?")
+            .bind(&chapter_id)
+            .fetch_one(&self.pool)
             .await?;
 
+        let mut tx = self.pool.begin().await?;
+
+        let mut ids: Vec<String> = sqlx::query_as::<_, (String,)>(
+            "SELECT id FROM chapters WHERE novel_id = ? AND id != ? ORDER BY position ASC, created_at ASC"
+        )
+            .bind(&novel_id)
+            .bind(&chapter_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        let idx = (new_position.max(0) as usize).min(ids.len());
+        ids.insert(idx, chapter_id.clone());
+
+        for (i, id) in ids.iter().enumerate() {
+            sqlx::query("UPDATE chapters SET position = ? WHERE id = ?")
+                .bind(i as i64)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query("UPDATE chapters SET updated_at = unixepoch() WHERE id = ?")
+            .bind(&chapter_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
     // --- SCENES ---
 
-    pub async fn get_scenes(&self, chapter_id: String) -> Result<Vec<Scene>, sqlx::Error> {
+    pub async fn get_scenes(&self, chapter_id: String) -> Result<Vec<Scene>, DbError> {
         sqlx::query_as::<_, Scene>(
-            "SELECT id, chapter_id, title, body, position, status, word_count, created_at, updated_at
+            "SELECT id, chapter_id, title, body, position, status, word_count, created_at, updated_at, scene_kind, include_in_compile, target_words, notes
                     FROM scenes
                     WHERE chapter_id = ?
                     ORDER BY position ASC"
@@ -208,6 +293,197 @@ impl Database {
             .bind(chapter_id)
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
+    }
+
+    /// Todas las scenes de un novel en una sola query (vista Manuscript).
+    pub async fn get_novel_scenes(&self, novel_id: String) -> Result<Vec<Scene>, DbError> {
+        sqlx::query_as::<_, Scene>(
+            "SELECT s.id, s.chapter_id, s.title, s.body, s.position, s.status, s.word_count, s.created_at, s.updated_at, s.scene_kind, s.include_in_compile, s.target_words, s.notes
+                    FROM scenes s
+                    JOIN chapters c ON c.id = s.chapter_id
+                    WHERE c.novel_id = ?
+                    ORDER BY c.position ASC, s.position ASC"
+        )
+            .bind(novel_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    pub async fn get_scene(&self, scene_id: String) -> Result<Option<Scene>, DbError> {
+        sqlx::query_as::<_, Scene>(
+            "SELECT id, chapter_id, title, body, position, status, word_count, created_at, updated_at, scene_kind, include_in_compile, target_words, notes
+                    FROM scenes
+                    WHERE id = ?"
+        )
+            .bind(scene_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    // --- OUTLINE EXPANSION ---
+
+    /// Novels/chapters expandidos del universo. Poda antes los ids que ya no existen
+    /// (borrados o en la papelera) para que no reaparezcan expansiones fantasma.
+    pub async fn get_outline_expansion(&self, universe_id: String) -> Result<(Vec<String>, Vec<String>), DbError> {
+        sqlx::query(
+            "DELETE FROM forge_outline_expansion
+                    WHERE universe_id = ?
+                      AND ((node_kind = 'novel' AND node_id NOT IN (SELECT id FROM novels))
+                        OR (node_kind = 'chapter' AND node_id NOT IN (SELECT id FROM chapters)))"
+        )
+            .bind(&universe_id)
+            .execute(&self.pool)
+            .await?;
+
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT node_kind, node_id FROM forge_outline_expansion WHERE universe_id = ?"
+        )
+            .bind(&universe_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut novels = Vec::new();
+        let mut chapters = Vec::new();
+        for (kind, id) in rows {
+            match kind.as_str() {
+                "novel" => novels.push(id),
+                "chapter" => chapters.push(id),
+                _ => {}
+            }
+        }
+        Ok((novels, chapters))
+    }
+
+    /// Reemplaza el set guardado del universo (una transacción).
+    pub async fn save_outline_expansion(
+        &self,
+        universe_id: String,
+        novel_ids: Vec<String>,
+        chapter_ids: Vec<String>,
+    ) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM forge_outline_expansion WHERE universe_id = ?")
+            .bind(&universe_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let nodes = novel_ids
+            .into_iter()
+            .map(|id| ("novel", id))
+            .chain(chapter_ids.into_iter().map(|id| ("chapter", id)));
+        for (kind, id) in nodes {
+            sqlx::query("INSERT OR IGNORE INTO forge_outline_expansion (universe_id, node_kind, node_id) VALUES (?, ?, ?)")
+                .bind(&universe_id)
+                .bind(kind)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Total de palabras del proyecto (todas las novelas). Solo prosa alcanzable desde una
+    /// novela: outline/notes no cuentan (ver SceneKind::counts_toward_goals) ni los huérfanos.
+    pub async fn project_word_total(&self) -> Result<i64, DbError> {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT CAST(COALESCE(SUM(s.word_count), 0) AS INTEGER) FROM scenes s
+                JOIN chapters c ON c.id = s.chapter_id
+                JOIN novels n ON n.id = c.novel_id
+                WHERE LOWER(TRIM(COALESCE(s.scene_kind, ''))) NOT IN ('outline', 'note', 'notes')"
+        )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Resuelve novel/chapter/scene -> ruta completa en el árbol. None si ya no existe.
+    pub async fn forge_path_for(&self, target_type: String, target_id: String) -> Result<Option<ForgePath>, DbError> {
+        let path = match target_type.as_str() {
+            "novel" => sqlx::query_as::<_, (Option<String>,)>("SELECT universe_id FROM novels WHERE id = ?")
+                .bind(&target_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|(universe_id,)| ForgePath { universe_id, novel_id: target_id, chapter_id: None, scene_id: None }),
+
+            "chapter" => sqlx::query_as::<_, (String, Option<String>)>(
+                "SELECT c.novel_id, n.universe_id FROM chapters c JOIN novels n ON n.id = c.novel_id WHERE c.id = ?"
+            )
+                .bind(&target_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|(novel_id, universe_id)| ForgePath { universe_id, novel_id, chapter_id: Some(target_id), scene_id: None }),
+
+            "scene" => sqlx::query_as::<_, (String, String, Option<String>)>(
+                "SELECT s.chapter_id, c.novel_id, n.universe_id FROM scenes s
+                    JOIN chapters c ON c.id = s.chapter_id
+                    JOIN novels n ON n.id = c.novel_id
+                    WHERE s.id = ?"
+            )
+                .bind(&target_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|(chapter_id, novel_id, universe_id)| ForgePath {
+                    universe_id,
+                    novel_id,
+                    chapter_id: Some(chapter_id),
+                    scene_id: Some(target_id),
+                }),
+
+            _ => None,
+        };
+        Ok(path)
+    }
+
+    /// Escenas de los novels del universo cuyo body menciona `needle` (LIKE, case-insensitive ASCII).
+    pub async fn scenes_mentioning(&self, universe_id: String, needle: String) -> Result<Vec<SceneMention>, DbError> {
+        let needle = needle.trim();
+        if needle.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Escapar comodines de LIKE para que "50%" no matchee todo
+        let escaped = needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+
+        sqlx::query_as::<_, SceneMention>(
+            "SELECT s.id AS scene_id, s.title AS scene_title,
+                    c.id AS chapter_id, c.title AS chapter_title,
+                    n.id AS novel_id, n.title AS novel_title, n.universe_id AS universe_id
+                    FROM scenes s
+                    JOIN chapters c ON c.id = s.chapter_id
+                    JOIN novels n ON n.id = c.novel_id
+                    WHERE n.universe_id = ? AND s.body LIKE ? ESCAPE '\\'
+                    ORDER BY n.title ASC, c.position ASC, s.position ASC"
+        )
+            .bind(universe_id)
+            .bind(pattern)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Todas las escenas de los novels del universo, en orden de lectura (picker de escena de un evento).
+    pub async fn universe_scene_refs(&self, universe_id: String) -> Result<Vec<SceneMention>, DbError> {
+        sqlx::query_as::<_, SceneMention>(
+            "SELECT s.id AS scene_id, s.title AS scene_title,
+                    c.id AS chapter_id, c.title AS chapter_title,
+                    n.id AS novel_id, n.title AS novel_title, n.universe_id AS universe_id
+                    FROM scenes s
+                    JOIN chapters c ON c.id = s.chapter_id
+                    JOIN novels n ON n.id = c.novel_id
+                    WHERE n.universe_id = ?
+                    ORDER BY n.title ASC, c.position ASC, s.position ASC"
+        )
+            .bind(universe_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
     }
 
     pub async fn create_scene_with_id(
@@ -215,31 +491,29 @@ impl Database {
         scene_id: String,
         chapter_id: String,
         title: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("forge").await?;
 
         if title.trim().is_empty() {
-            return Err("Title cannot be empty".into());
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         // Verificar que el chapter existe
         let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chapters WHERE id = ?")
             .bind(&chapter_id)
             .fetch_one(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         if exists == 0 {
-            return Err(format!("Chapter {} not found", chapter_id).into());
+            return Err(DbError::MissingParent { kind: "chapter".to_string(), id: chapter_id });
         }
 
         // Obtener la siguiente posición
         let (max_pos,): (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM scenes WHERE chapter_id = ?")
             .bind(&chapter_id)
             .fetch_one(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         let pos = max_pos.unwrap_or(-1) + 1;
 
@@ -249,15 +523,14 @@ impl Database {
             .bind(title)
             .bind(pos)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
 
         Ok(())
     }
 
-    pub async fn update_scene(&self, s: Scene) -> Result<(), sqlx::Error> {
+    pub async fn update_scene(&self, s: Scene) -> Result<(), DbError> {
         if s.title.trim().is_empty() {
-            return Err(sqlx::Error::Protocol("Title cannot be empty".into()));
+            return Err(DbError::Invalid("Title cannot be empty".to_string()));
         }
 
         // Recalcular word_count desde el body (DB como última fuente de verdad)
@@ -268,23 +541,75 @@ impl Database {
             s.id, computed_word_count
         ));
 
-        sqlx::query(
+        let kind = s.kind().as_str();
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query(
             "UPDATE scenes
-                        SET title = ?, body = ?, status = ?, word_count = ?, updated_at = unixepoch()
+                        SET title = ?, body = ?, status = ?, word_count = ?, scene_kind = ?, include_in_compile = ?, target_words = ?, notes = ?, updated_at = unixepoch()
                         WHERE id = ?"
         )
-            .bind(s.title)
-            .bind(s.body)
-            .bind(s.status)
+            .bind(&s.title)
+            .bind(&s.body)
+            .bind(&s.status)
             .bind(computed_word_count)
-            .bind(s.id)
-            .execute(&self.pool)
+            .bind(kind)
+            .bind(s.include_in_compile)
+            .bind(s.target_words.max(0))
+            .bind(&s.notes)
+            .bind(&s.id)
+            .execute(&mut *tx)
             .await?;
 
+        // Revisión del body: solo si cambió respecto a la última (renames / metadata no suman)
+        if updated.rows_affected() > 0 && !s.body.trim().is_empty() {
+            let last_body: Option<String> = sqlx::query_scalar(
+                "SELECT body FROM scene_revisions WHERE scene_id = ? ORDER BY saved_at DESC, rowid DESC LIMIT 1"
+            )
+                .bind(&s.id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            if last_body.as_deref() != Some(s.body.as_str()) {
+                sqlx::query(
+                    "INSERT INTO scene_revisions (id, scene_id, body, word_count, saved_at) VALUES (?, ?, ?, ?, unixepoch())"
+                )
+                    .bind(uuid::Uuid::new_v4().to_string())
+                    .bind(&s.id)
+                    .bind(&s.body)
+                    .bind(computed_word_count)
+                    .execute(&mut *tx)
+                    .await?;
+
+                sqlx::query(
+                    "DELETE FROM scene_revisions WHERE scene_id = ? AND id NOT IN
+                        (SELECT id FROM scene_revisions WHERE scene_id = ? ORDER BY saved_at DESC, rowid DESC LIMIT ?)"
+                )
+                    .bind(&s.id)
+                    .bind(&s.id)
+                    .bind(SCENE_REVISIONS_KEEP)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
-    pub async fn delete_scene(&self, scene_id: String) -> Result<(), sqlx::Error> {
+    /// Revisiones del body de una escena, la más nueva primero.
+    pub async fn get_scene_revisions(&self, scene_id: String) -> Result<Vec<SceneRevision>, DbError> {
+        sqlx::query_as::<_, SceneRevision>(
+            "SELECT id, scene_id, body, word_count, saved_at FROM scene_revisions
+            WHERE scene_id = ? ORDER BY saved_at DESC, rowid DESC"
+        )
+            .bind(scene_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    pub async fn delete_scene(&self, scene_id: String) -> Result<(), DbError> {
         crate::logger::info(&format!("Deleting scene {}", scene_id));
 
         sqlx::query("DELETE FROM scenes WHERE id = ?")
@@ -295,13 +620,206 @@ impl Database {
         Ok(())
     }
 
-    pub async fn reorder_scene(&self, scene_id: String, new_position: i64) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE scenes SET position = ?, updated_at = unixepoch() WHERE id = ?")
-            .bind(new_position)
-            .bind(scene_id)
+    /// Escenas cuyo chapter ya no existe (datos pre-cascade / restos de migración).
+    /// Son de todo el proyecto: sin chapter no hay forma de saber a qué novela pertenecían.
+    pub async fn orphan_scenes(&self) -> Result<Vec<Scene>, DbError> {
+        sqlx::query_as::<_, Scene>(
+            "SELECT s.id, s.chapter_id, s.title, s.body, s.position, s.status, s.word_count, s.created_at, s.updated_at, s.scene_kind, s.include_in_compile, s.target_words, s.notes
+                    FROM scenes s
+                    LEFT JOIN chapters c ON c.id = s.chapter_id
+                    WHERE c.id IS NULL
+                    ORDER BY s.updated_at DESC"
+        )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Mueve una escena a otro chapter (al final). Sirve también para rescatar huérfanas.
+    pub async fn move_scene_to_chapter(&self, scene_id: String, chapter_id: String) -> Result<(), DbError> {
+        // ✅ Guard de capability
+        self.require_capability("forge").await?;
+
+        let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chapters WHERE id = ?")
+            .bind(&chapter_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if exists == 0 {
+            return Err(DbError::MissingParent { kind: "chapter".to_string(), id: chapter_id });
+        }
+
+        let (max_pos,): (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM scenes WHERE chapter_id = ?")
+            .bind(&chapter_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let updated = sqlx::query("UPDATE scenes SET chapter_id = ?, position = ?, updated_at = unixepoch() WHERE id = ?")
+            .bind(&chapter_id)
+            .bind(max_pos.unwrap_or(-1) + 1)
+            .bind(&scene_id)
             .execute(&self.pool)
             .await?;
 
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid(format!("Scene not found: {}", scene_id)));
+        }
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Búsqueda (título + body) en todas las escenas del novel, en orden de outline.
+    /// LIKE de SQLite ya es case-insensitive (ASCII); el extracto se arma acá.
+    pub async fn search_scenes(&self, novel_id: String, query: String) -> Result<Vec<SceneHit>, DbError> {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // % y _ del usuario son literales
+        let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+
+        let rows: Vec<(String, String, String, String, String)> = sqlx::query_as(
+            "SELECT s.id, s.chapter_id, c.title, s.title, s.body
+                FROM scenes s
+                JOIN chapters c ON c.id = s.chapter_id
+                WHERE c.novel_id = ?
+                  AND (s.title LIKE ? ESCAPE '\\' OR s.body LIKE ? ESCAPE '\\')
+                ORDER BY c.position ASC, s.position ASC"
+        )
+            .bind(&novel_id)
+            .bind(&pattern)
+            .bind(&pattern)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(scene_id, chapter_id, chapter_title, title, body)| SceneHit {
+                snippet: search_snippet(&body, &query),
+                scene_id,
+                chapter_id,
+                chapter_title,
+                title,
+            })
+            .collect())
+    }
+
+    /// Reorden dentro del mismo chapter: índice new_position + renumerado 0..n
+    pub async fn reorder_scene(&self, scene_id: String, new_position: i64) -> Result<(), DbError> {
+        let (chapter_id,): (String,) = sqlx::query_as("SELECT chapter_id FROM scenes WHERE id = ?")
+            .bind(&scene_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let mut tx = self.pool.begin().await?;
+        place_scene_at(&mut tx, &scene_id, &chapter_id, new_position).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Drop en otro chapter: inserta en new_index del destino y renumera origen y destino.
+    pub async fn move_scene_to_chapter_at(&self, scene_id: String, chapter_id: String, new_index: i64) -> Result<(), DbError> {
+        self.require_capability("forge").await?;
+
+        let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chapters WHERE id = ?")
+            .bind(&chapter_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if exists == 0 {
+            return Err(DbError::MissingParent { kind: "chapter".to_string(), id: chapter_id });
+        }
+
+        let from: Option<(String,)> = sqlx::query_as("SELECT chapter_id FROM scenes WHERE id = ?")
+            .bind(&scene_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some((from_chapter_id,)) = from else {
+            return Err(DbError::Invalid(format!("Scene not found: {}", scene_id)));
+        };
+
+        let mut tx = self.pool.begin().await?;
+        place_scene_at(&mut tx, &scene_id, &chapter_id, new_index).await?;
+
+        if from_chapter_id != chapter_id {
+            // Cerrar el hueco en el origen
+            let ids: Vec<(String,)> = sqlx::query_as("SELECT id FROM scenes WHERE chapter_id = ? ORDER BY position ASC")
+                .bind(&from_chapter_id)
+                .fetch_all(&mut *tx)
+                .await?;
+            for (i, (id,)) in ids.iter().enumerate() {
+                sqlx::query("UPDATE scenes SET position = ? WHERE id = ?")
+                    .bind(i as i64)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+// Ubica la scene en chapter_id/índice y renumera ese chapter 0..n (dentro de la tx del caller)
+async fn place_scene_at(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    scene_id: &str,
+    chapter_id: &str,
+    index: i64,
+) -> Result<(), DbError> {
+    let mut ids: Vec<String> = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM scenes WHERE chapter_id = ? AND id != ? ORDER BY position ASC"
+    )
+        .bind(chapter_id)
+        .bind(scene_id)
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .map(|(id,)| id)
+        .collect();
+
+    let idx = (index.max(0) as usize).min(ids.len());
+    ids.insert(idx, scene_id.to_string());
+
+    for (i, id) in ids.iter().enumerate() {
+        sqlx::query("UPDATE scenes SET chapter_id = ?, position = ? WHERE id = ?")
+            .bind(chapter_id)
+            .bind(i as i64)
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    sqlx::query("UPDATE scenes SET updated_at = unixepoch() WHERE id = ?")
+        .bind(scene_id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+// Extracto de una línea (~40 chars de contexto por lado) alrededor del primer match.
+// Si el match fue solo en el título, el comienzo del body.
+fn search_snippet(body: &str, query: &str) -> String {
+    const CONTEXT: usize = 40;
+
+    let chars: Vec<char> = body.chars().collect();
+    let start = crate::forge_find::find_matches(body, query, false)
+        .first()
+        .map(|m| m.char_offset)
+        .unwrap_or(0);
+
+    let from = start.saturating_sub(CONTEXT);
+    let to = (start + query.chars().count() + CONTEXT).min(chars.len());
+
+    let mut out: String = chars[from..to].iter().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        out.insert(0, '…');
+    }
+    if to < chars.len() {
+        out.push('…');
+    }
+    out
+}
@@ -0,0 +1,206 @@
+// ========================================
+// project_archive.rs - Export / import del proyecto completo a un JSON versionado
+// ========================================
+// Filas genéricas (columna -> valor) por tabla: columnas nuevas del schema viajan solas
+// y en el import se ignoran las que la DB destino no conoce.
+// Cambios incompatibles => subir PROJECT_ARCHIVE_VERSION y migrar en `migrate_archive`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::{Row, TypeInfo, ValueRef};
+
+use crate::db::{Database, DbError};
+
+pub const PROJECT_ARCHIVE_FORMAT: &str = "tas.project";
+pub const PROJECT_ARCHIVE_VERSION: u32 = 1;
+
+/// (tabla, nombre legible para errores). Orden = padres antes que hijos.
+const ARCHIVE_TABLES: [(&str, &str); 13] = [
+    ("universes", "universe"),
+    ("locations", "location"),
+    ("bestiary_entries", "creature"),
+    ("timeline_eras", "era"),
+    ("timeline_events", "timeline event"),
+    ("glossary", "glossary term"),
+    ("creature_relations", "creature relation"),
+    ("boards", "board"),
+    ("board_columns", "board column"),
+    ("cards", "card"),
+    ("novels", "novel"),
+    ("chapters", "chapter"),
+    ("scenes", "scene"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectArchive {
+    pub format: String,
+    pub format_version: u32,
+    pub exported_at: String,
+    /// tabla -> filas (columna -> valor)
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+impl Database {
+    pub async fn export_all(&self) -> Result<ProjectArchive, DbError> {
+        let mut tables = BTreeMap::new();
+
+        for (table, _) in ARCHIVE_TABLES {
+            let columns = table_columns(&self.pool, table).await?;
+            // Tabla ausente en schemas viejos => no se exporta
+            if columns.is_empty() {
+                continue;
+            }
+
+            let rows = sqlx::query(&format!("SELECT {} FROM {table}", columns.join(", ")))
+                .fetch_all(&self.pool)
+                .await?;
+
+            let mut out = Vec::with_capacity(rows.len());
+            for row in rows {
+                let mut obj = Map::new();
+                for (i, col) in columns.iter().enumerate() {
+                    obj.insert(col.clone(), cell_to_json(&row, i)?);
+                }
+                out.push(obj);
+            }
+            tables.insert(table.to_string(), out);
+        }
+
+        Ok(ProjectArchive {
+            format: PROJECT_ARCHIVE_FORMAT.to_string(),
+            format_version: PROJECT_ARCHIVE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            tables,
+        })
+    }
+
+    /// Recrea el archivo dentro de una transacción: todo o nada.
+    /// Ids que ya existen abortan el import (RestoreConflict) en vez de pisar datos.
+    pub async fn import_archive(&self, archive: ProjectArchive) -> Result<usize, DbError> {
+        let archive = migrate_archive(archive)?;
+
+        let mut tx = self.pool.begin().await?;
+        // FKs se validan al commit: el orden de tablas ya ayuda, pero no dependemos de él
+        sqlx::query("PRAGMA defer_foreign_keys = ON").execute(&mut *tx).await?;
+
+        let mut imported = 0usize;
+
+        for (table, kind) in ARCHIVE_TABLES {
+            let Some(rows) = archive.tables.get(table) else {
+                continue;
+            };
+            if rows.is_empty() {
+                continue;
+            }
+
+            let columns: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{table}')"))
+                .fetch_all(&mut *tx)
+                .await?;
+            if columns.is_empty() {
+                continue;
+            }
+
+            for row in rows {
+                let id = row.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+                if id.is_empty() {
+                    return Err(DbError::Invalid(format!("Archive has a {} without id", kind)));
+                }
+
+                let exists: Option<i64> = sqlx::query_scalar(&format!("SELECT 1 FROM {table} WHERE id = ?1"))
+                    .bind(&id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+                if exists.is_some() {
+                    return Err(DbError::RestoreConflict { kind: kind.to_string(), id });
+                }
+
+                // Solo columnas que existen en esta DB (archivos de versiones más nuevas)
+                let cols: Vec<&String> = columns.iter().filter(|c| row.contains_key(c.as_str())).collect();
+                let sql = format!(
+                    "INSERT INTO {table} ({}) VALUES ({})",
+                    cols.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(", "),
+                    vec!["?"; cols.len()].join(", ")
+                );
+
+                let mut q = sqlx::query(&sql);
+                for col in cols {
+                    q = match &row[col.as_str()] {
+                        Value::Null => q.bind(None::<String>),
+                        Value::Bool(b) => q.bind(*b),
+                        Value::Number(n) => match n.as_i64() {
+                            Some(i) => q.bind(i),
+                            None => q.bind(n.as_f64()),
+                        },
+                        Value::String(s) => q.bind(s.clone()),
+                        other => q.bind(other.to_string()),
+                    };
+                }
+                q.execute(&mut *tx).await?;
+                imported += 1;
+            }
+        }
+
+        tx.commit().await?;
+        crate::logger::info(&format!("📦 DB: Project archive imported ({} rows)", imported));
+        Ok(imported)
+    }
+}
+
+/// Lleva un archivo viejo al shape actual. v1 es el único por ahora.
+fn migrate_archive(archive: ProjectArchive) -> Result<ProjectArchive, DbError> {
+    if archive.format != PROJECT_ARCHIVE_FORMAT {
+        return Err(DbError::Codec(format!("Not a project archive (format '{}')", archive.format)));
+    }
+    if archive.format_version > PROJECT_ARCHIVE_VERSION {
+        return Err(DbError::Invalid(format!(
+            "Archive version {} is newer than supported ({})",
+            archive.format_version, PROJECT_ARCHIVE_VERSION
+        )));
+    }
+    Ok(archive)
+}
+
+async fn table_columns(pool: &sqlx::SqlitePool, table: &str) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{table}')"))
+        .fetch_all(pool)
+        .await
+}
+
+/// Valor de SQLite -> JSON según el tipo almacenado (no el declarado).
+/// BLOBs viajan en base64 (hoy ninguna tabla exportada los usa).
+fn cell_to_json(row: &sqlx::sqlite::SqliteRow, i: usize) -> Result<Value, sqlx::Error> {
+    let raw = row.try_get_raw(i)?;
+    if raw.is_null() {
+        return Ok(Value::Null);
+    }
+    let type_name = raw.type_info().name().to_string();
+
+    Ok(match type_name.as_str() {
+        "INTEGER" => Value::from(row.try_get::<i64, _>(i)?),
+        "REAL" => Value::from(row.try_get::<f64, _>(i)?),
+        "BLOB" => Value::from(general_purpose::STANDARD.encode(row.try_get::<Vec<u8>, _>(i)?)),
+        _ => Value::from(row.try_get::<String, _>(i)?),
+    })
+}
+
+pub fn parse_archive(json: &str) -> Result<ProjectArchive, DbError> {
+    serde_json::from_str(json).map_err(DbError::from)
+}
+
+/// data_dir/exports/<stem>/project-<stem>-<timestamp>.json
+pub fn project_archive_path(db_path: &Path) -> PathBuf {
+    let stem = db_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+
+    crate::db::project_settings::exports_dir(db_path).join(format!(
+        "project-{}-{}.json",
+        stem,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
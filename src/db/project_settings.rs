@@ -0,0 +1,128 @@
+// ========================================
+// project_settings.rs - Export/Import de configuración
+// ========================================
+// Snapshot de capabilities (db_meta) + AppSettings a un JSON portable.
+// NO incluye contenido (universos, escenas, etc.): solo la configuración.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_settings::AppSettings;
+use crate::db::{Database, DbError};
+
+const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSettingsExport {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub capabilities: Vec<String>,
+    pub app_settings: AppSettings,
+}
+
+impl Database {
+    pub async fn export_settings(&self, app_settings: &AppSettings) -> Result<String, DbError> {
+        let capabilities = self.capabilities.read().await.enabled_keys();
+
+        let export = ProjectSettingsExport {
+            format_version: SETTINGS_EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            capabilities,
+            app_settings: app_settings.clone(),
+        };
+
+        Ok(serde_json::to_string_pretty(&export)?)
+    }
+
+    /// Aplica capabilities a db_meta y refresca el cache (gating en vivo).
+    /// Devuelve los AppSettings importados: persistirlos es responsabilidad del caller.
+    pub async fn import_settings(&self, json: &str) -> Result<AppSettings, DbError> {
+        let export: ProjectSettingsExport = serde_json::from_str(json)?;
+
+        if export.format_version > SETTINGS_EXPORT_VERSION {
+            return Err(DbError::Invalid(format!(
+                "Settings file version {} is newer than supported ({})",
+                export.format_version, SETTINGS_EXPORT_VERSION
+            )));
+        }
+
+        let caps_json = serde_json::to_string(&export.capabilities)?;
+
+        let updated = sqlx::query("UPDATE db_meta SET enabled_capabilities_json = ?")
+            .bind(caps_json)
+            .execute(&self.pool)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid("Project has no db_meta row".to_string()));
+        }
+
+        // Re-leer desde DB (mismo parser fail-closed que al conectar)
+        let caps = crate::guards::fetch_capabilities_from_db(&self.pool).await?;
+        *self.capabilities.write().await = caps;
+
+        Ok(export.app_settings)
+    }
+
+    /// Prende/apaga una capability en db_meta y refresca el cache (gating en vivo).
+    /// Parte de lo que hay en DB (parser fail-closed): un JSON corrupto queda reescrito en formato nuevo.
+    pub async fn set_capability(&self, name: &str, enabled: bool) -> Result<crate::guards::Capabilities, DbError> {
+        let mut caps = crate::guards::fetch_capabilities_from_db(&self.pool).await?;
+        if !caps.set(name, enabled) {
+            return Err(DbError::Invalid(format!("Unknown capability '{}'", name)));
+        }
+
+        let caps_json = serde_json::to_string(&caps.enabled_keys())?;
+        let updated = sqlx::query("UPDATE db_meta SET enabled_capabilities_json = ?")
+            .bind(caps_json)
+            .execute(&self.pool)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid("Project has no db_meta row".to_string()));
+        }
+
+        let caps = crate::guards::fetch_capabilities_from_db(&self.pool).await?;
+        *self.capabilities.write().await = caps.clone();
+
+        Ok(caps)
+    }
+
+    // Tema de la UI (db_meta.ui_theme): se lee al abrir el proyecto
+    pub async fn get_ui_theme(&self) -> Result<crate::ui::ThemeMode, DbError> {
+        let key: Option<String> = sqlx::query_scalar("SELECT ui_theme FROM db_meta LIMIT 1")
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(key.map(|k| crate::ui::ThemeMode::from_key(&k)).unwrap_or_default())
+    }
+
+    pub async fn set_ui_theme(&self, mode: crate::ui::ThemeMode) -> Result<(), DbError> {
+        sqlx::query("UPDATE db_meta SET ui_theme = ?")
+            .bind(mode.key())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// data_dir/exports/<stem>/ (carpeta común de todos los exports del proyecto)
+pub fn exports_dir(db_path: &Path) -> PathBuf {
+    let base = if let Some(p) = directories::ProjectDirs::from("com", "TitanArchitects", "TAS") {
+        p.data_dir().to_path_buf()
+    } else {
+        PathBuf::from("data")
+    };
+
+    let stem = db_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+
+    base.join("exports").join(stem)
+}
+
+/// data_dir/exports/<stem>/settings-<timestamp>.json
+pub fn settings_export_path(db_path: &Path) -> PathBuf {
+    exports_dir(db_path).join(format!("settings-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+}
@@ -0,0 +1,66 @@
+// ========================================
+// recents.rs - Ítems abiertos recientemente (Overview → "Recent")
+// ========================================
+// Una fila por (kind, target_id) con el último acceso. Sin FK: las filas cuyo target ya no
+// existe (borrado / en papelera) se podan al leer.
+
+use crate::model::RecentItem;
+use crate::db::{Database, DbError};
+
+/// Filas que se conservan por proyecto (el Overview muestra menos)
+pub const RECENT_ITEMS_KEEP: i64 = 30;
+
+impl Database {
+    pub async fn get_recent_items(&self, limit: i64) -> Result<Vec<RecentItem>, DbError> {
+        // Poda: targets que ya no existen
+        sqlx::query(
+            "DELETE FROM recent_items WHERE
+                (kind = 'universe' AND target_id NOT IN (SELECT id FROM universes))
+                OR (kind = 'board' AND target_id NOT IN (SELECT id FROM boards))
+                OR (kind = 'novel' AND target_id NOT IN (SELECT id FROM novels))"
+        )
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query_as::<_, RecentItem>(
+            "SELECT r.kind, r.target_id, r.opened_at,
+                    COALESCE(u.name, b.name, n.title, '') AS name,
+                    n.universe_id AS universe_id
+            FROM recent_items r
+            LEFT JOIN universes u ON r.kind = 'universe' AND u.id = r.target_id
+            LEFT JOIN boards b ON r.kind = 'board' AND b.id = r.target_id
+            LEFT JOIN novels n ON r.kind = 'novel' AND n.id = r.target_id
+            ORDER BY r.opened_at DESC
+            LIMIT ?"
+        )
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    /// Marca el target como abierto ahora (upsert) y recorta a RECENT_ITEMS_KEEP.
+    pub async fn touch_recent_item(&self, kind: String, target_id: String) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO recent_items (kind, target_id, opened_at) VALUES (?, ?, unixepoch())
+            ON CONFLICT(kind, target_id) DO UPDATE SET opened_at = excluded.opened_at"
+        )
+            .bind(&kind)
+            .bind(&target_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "DELETE FROM recent_items WHERE rowid NOT IN
+                (SELECT rowid FROM recent_items ORDER BY opened_at DESC LIMIT ?)"
+        )
+            .bind(RECENT_ITEMS_KEEP)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -7,8 +7,9 @@
 
 use sqlx::SqlitePool;
 use super::helpers::{table_exists, column_exists, ensure_column, fix_zero_ts};
+use crate::db::DbError;
 
-pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), DbError> {
 
     // --- Universes ---
     ensure_column(pool, "universes", "archived", "INTEGER NOT NULL DEFAULT 0").await?;
@@ -20,6 +21,10 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     ensure_column(pool, "trash_entry", "parent_id", "TEXT").await?;
     ensure_column(pool, "trash_entry", "display_name", "TEXT NOT NULL DEFAULT ''").await?;
     ensure_column(pool, "trash_entry", "display_info", "TEXT").await?;
+    // Retención de papelera por proyecto (días; 0 = nunca purgar)
+    ensure_column(pool, "db_meta", "trash_retention_days", "INTEGER NOT NULL DEFAULT 14").await?;
+    // Tema de la UI ('dark' | 'light')
+    ensure_column(pool, "db_meta", "ui_theme", "TEXT NOT NULL DEFAULT 'dark'").await?;
 
     // Si la tabla fue creada con las columnas viejas (entity_kind/entity_id), debemos limpiarla
     let has_entity_kind: i64 = sqlx::query_scalar(
@@ -55,6 +60,9 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     // --- PM Tools ---
     ensure_column(pool, "boards", "kind", "TEXT NOT NULL DEFAULT 'kanban'").await?;
     ensure_column(pool, "cards", "priority", "TEXT NOT NULL DEFAULT ''").await?;
+    ensure_column(pool, "cards", "labels", "TEXT NOT NULL DEFAULT ''").await?;
+    ensure_column(pool, "cards", "due_date", "INTEGER").await?;
+    ensure_column(pool, "cards", "archived", "INTEGER NOT NULL DEFAULT 0").await?;
 
     // --- The Forge ---
     ensure_column(pool, "scenes", "body", "TEXT NOT NULL DEFAULT ''").await?;
@@ -65,6 +73,47 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     // Si la DB viene legacy con scenes.story_id, reconstruimos scenes con FK a chapters.
     canonicalize_scenes_to_chapters_if_needed(pool).await?;
 
+    // Scene kind (prose/outline/note): DESPUÉS de canonicalizar (el rebuild no la conoce)
+    ensure_column(pool, "scenes", "scene_kind", "TEXT NOT NULL DEFAULT 'prose'").await?;
+    ensure_column(pool, "scenes", "include_in_compile", "INTEGER NOT NULL DEFAULT 1").await?;
+    ensure_column(pool, "scenes", "target_words", "INTEGER NOT NULL DEFAULT 0").await?;
+    ensure_column(pool, "scenes", "notes", "TEXT NOT NULL DEFAULT ''").await?;
+    ensure_column(pool, "chapters", "target_words", "INTEGER NOT NULL DEFAULT 0").await?;
+
+    // Outline: nodos expandidos por universo (preferencia de UI, sin FK: se poda al leer)
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS forge_outline_expansion (
+            universe_id TEXT NOT NULL,
+            node_kind TEXT NOT NULL,
+            node_id TEXT NOT NULL,
+            PRIMARY KEY (universe_id, node_kind, node_id)
+        )
+        "#).execute(pool).await?;
+
+    // Historial del body de cada escena (UpdateScene). Sin FK: sobrevive al trash y al restore.
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS scene_revisions (
+            id TEXT PRIMARY KEY NOT NULL,
+            scene_id TEXT NOT NULL,
+            body TEXT NOT NULL,
+            word_count INTEGER NOT NULL DEFAULT 0,
+            saved_at INTEGER NOT NULL DEFAULT (unixepoch())
+        )
+        "#).execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_scene_revisions_scene ON scene_revisions(scene_id, saved_at)")
+        .execute(pool)
+        .await?;
+
+    // Recientes (Overview): último acceso por target, sin FK (se poda al leer)
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS recent_items (
+            kind TEXT NOT NULL,
+            target_id TEXT NOT NULL,
+            opened_at INTEGER NOT NULL DEFAULT (unixepoch()),
+            PRIMARY KEY (kind, target_id)
+        )
+        "#).execute(pool).await?;
+
     // C8: DB como fuente de verdad → recalcular word_count desde body
     // (corrige data vieja / migraciones legacy / restores / snapshots)
     recalc_scene_word_counts(pool).await?;
@@ -73,6 +122,7 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
 
     ensure_column(pool, "universe_snapshots", "name", "TEXT NOT NULL DEFAULT ''").await?;
     ensure_column(pool, "universe_snapshots", "payload_json", "TEXT NOT NULL DEFAULT ''").await?;
+    ensure_column(pool, "universe_snapshots", "is_auto", "INTEGER NOT NULL DEFAULT 0").await?;
 
     // --- Timeline ---
     ensure_column(pool, "timeline_eras", "description", "TEXT NOT NULL DEFAULT ''").await?;
@@ -83,6 +133,7 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     ensure_column(pool, "timeline_events", "kind", "TEXT NOT NULL DEFAULT ''").await?;
     ensure_column(pool, "timeline_events", "color", "TEXT NOT NULL DEFAULT ''").await?;
     ensure_column(pool, "timeline_events", "location_id", "TEXT").await?;
+    ensure_column(pool, "timeline_events", "scene_id", "TEXT").await?;
 
     // --- Locations & Bestiary (evita futuros "no such column …") ---
     ensure_column(pool, "locations", "parent_id", "TEXT").await?;
@@ -95,6 +146,30 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     ensure_column(pool, "bestiary_entries", "danger", "TEXT NOT NULL DEFAULT ''").await?;
     ensure_column(pool, "bestiary_entries", "home_location_id", "TEXT").await?;
     ensure_column(pool, "bestiary_entries", "archived", "INTEGER NOT NULL DEFAULT 0").await?;
+    ensure_column(pool, "bestiary_entries", "image_path", "TEXT").await?;
+    ensure_column(pool, "locations", "image_path", "TEXT").await?;
+
+    // Relaciones entre criaturas (predator/prey, allies...). Sin FK: se limpian al mover a papelera.
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS creature_relations (
+            id TEXT PRIMARY KEY NOT NULL,
+            universe_id TEXT NOT NULL,
+            from_id TEXT NOT NULL,
+            to_id TEXT NOT NULL,
+            relation_kind TEXT NOT NULL DEFAULT '',
+            created_at INTEGER NOT NULL DEFAULT (unixepoch())
+        )
+        "#).execute(pool).await?;
+    sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_creature_relations_edge ON creature_relations(from_id, to_id, relation_kind)")
+        .execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_creature_relations_to ON creature_relations(to_id)")
+        .execute(pool).await?;
+
+    // --- Glossary (tabla nueva: no existe en migraciones, la crea el guard) ---
+    ensure_glossary_table(pool).await?;
+
+    // --- Board templates (idem: solo las crea el guard) ---
+    ensure_board_templates_table(pool).await?;
 
     // --- CORE TABLES (timestamps) ---
     // Esto evita crashes tipo: "no column named updated_at" en PM/Timeline/Bestiary/Locations.
@@ -149,6 +224,11 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
         .execute(pool).await?;
     sqlx::query("UPDATE scenes SET word_count=0 WHERE word_count IS NULL")
         .execute(pool).await?;
+    sqlx::query("UPDATE scenes SET scene_kind='prose' WHERE scene_kind IS NULL OR scene_kind=''")
+        .execute(pool).await?;
+
+    // Bestiary: danger libre → escala canónica (DangerLevel)
+    normalize_creature_danger(pool).await?;
 
     // Snapshots defaults
     sqlx::query("UPDATE universe_snapshots SET name='' WHERE name IS NULL")
@@ -172,10 +252,38 @@ pub async fn ensure_minimum_schema(pool: &SqlitePool) -> Result<(), sqlx::Error>
     Ok(())
 }
 
-async fn recalc_scene_word_counts(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+async fn normalize_creature_danger(pool: &SqlitePool) -> Result<(), DbError> {
+    use crate::model::DangerLevel;
+
+    // Solo valores distintos: son pocos, y así evitamos recorrer cada criatura
+    let values: Vec<String> = sqlx::query_scalar("SELECT DISTINCT danger FROM bestiary_entries")
+        .fetch_all(pool)
+        .await?;
+
+    let mut tx = pool.begin().await?;
+
+    for raw in values {
+        let canonical = DangerLevel::from_loose(&raw).as_str();
+        if raw != canonical {
+            sqlx::query("UPDATE bestiary_entries SET danger = ? WHERE danger = ?")
+                .bind(canonical)
+                .bind(&raw)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Devuelve cuántas escenas tenían word_count distinto al del body (y se corrigieron).
+/// También la usa el dev panel ("Recalculate word counts") vía Database::recalc_word_counts.
+pub(crate) async fn recalc_scene_word_counts(pool: &SqlitePool) -> Result<usize, DbError> {
     use sqlx::Row;
 
     let mut tx = pool.begin().await?;
+    let mut updated = 0usize;
 
     let rows = sqlx::query("SELECT id, body, word_count FROM scenes")
         .fetch_all(&mut *tx)
@@ -195,14 +303,15 @@ async fn recalc_scene_word_counts(pool: &SqlitePool) -> Result<(), sqlx::Error>
                 .bind(id)
                 .execute(&mut *tx)
                 .await?;
+            updated += 1;
         }
     }
 
     tx.commit().await?;
-    Ok(())
+    Ok(updated)
 }
 
-async fn canonicalize_scenes_to_chapters_if_needed(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+async fn canonicalize_scenes_to_chapters_if_needed(pool: &SqlitePool) -> Result<(), DbError> {
     // Si no hay scenes, nada que hacer
     if !table_exists(pool, "scenes").await? {
         return Ok(());
@@ -218,8 +327,8 @@ async fn canonicalize_scenes_to_chapters_if_needed(pool: &SqlitePool) -> Result<
 
     // chapters debe existir
     if !table_exists(pool, "chapters").await? {
-        return Err(sqlx::Error::Protocol(
-            "Cannot canonicalize scenes: missing 'chapters' table".into(),
+        return Err(DbError::Invalid(
+            "Cannot canonicalize scenes: missing 'chapters' table".to_string(),
         ));
     }
 
@@ -339,4 +448,36 @@ async fn canonicalize_scenes_to_chapters_if_needed(pool: &SqlitePool) -> Result<
 
     tx.commit().await?;
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn ensure_glossary_table(pool: &SqlitePool) -> Result<(), DbError> {
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS glossary (
+            id TEXT PRIMARY KEY NOT NULL,
+            universe_id TEXT NOT NULL REFERENCES universes(id) ON DELETE CASCADE,
+            term TEXT NOT NULL,
+            definition TEXT NOT NULL DEFAULT '',
+            pronunciation TEXT NOT NULL DEFAULT '',
+            created_at INTEGER NOT NULL DEFAULT (unixepoch()),
+            updated_at INTEGER NOT NULL DEFAULT (unixepoch())
+        )
+        "#).execute(pool).await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_glossary_universe ON glossary(universe_id)")
+        .execute(pool).await?;
+
+    Ok(())
+}
+
+async fn ensure_board_templates_table(pool: &SqlitePool) -> Result<(), DbError> {
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS board_templates (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            columns_json TEXT NOT NULL DEFAULT '[]',
+            created_at INTEGER NOT NULL DEFAULT (unixepoch())
+        )
+        "#).execute(pool).await?;
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// ========================================
+// timeline_data_export.rs - Dump CSV / JSON (solo lectura) del timeline de un universo
+// ========================================
+// El PNG vive en crate::timeline_export (captura de ventana); esto es la data.
+// CSV: una fila por evento con la era resuelta por rango de años.
+// JSON: arrays de eventos + eras tal cual. Cambios incompatibles => subir versión.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::db::bestiary_export::{csv_field, universe_slug};
+use crate::db::{Database, DbError};
+use crate::model::{TimelineEra, TimelineEvent};
+
+pub const TIMELINE_EXPORT_FORMAT: &str = "tas.timeline";
+pub const TIMELINE_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineExportFormat {
+    Csv,
+    Json,
+}
+
+impl TimelineExportFormat {
+    pub const ALL: [TimelineExportFormat; 2] = [TimelineExportFormat::Csv, TimelineExportFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TimelineExportFormat::Csv => "csv",
+            TimelineExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for TimelineExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TimelineExportFormat::Csv => "CSV",
+            TimelineExportFormat::Json => "JSON",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimelineExport {
+    pub format: &'static str,
+    pub format_version: u32,
+    pub exported_at: String,
+    pub universe_id: String,
+    pub events: Vec<TimelineEvent>,
+    pub eras: Vec<TimelineEra>,
+}
+
+const CSV_COLUMNS: [&str; 5] = ["year", "display_date", "title", "importance", "era"];
+
+impl Database {
+    pub async fn export_timeline(&self, universe_id: String, format: TimelineExportFormat) -> Result<String, DbError> {
+        // Ambos vienen ordenados por año desde la query
+        let events = self.get_timeline_events(universe_id.clone()).await?;
+        let eras = self.get_timeline_eras(universe_id.clone()).await?;
+
+        match format {
+            TimelineExportFormat::Csv => Ok(render_csv(&events, &eras)),
+            TimelineExportFormat::Json => {
+                let export = TimelineExport {
+                    format: TIMELINE_EXPORT_FORMAT,
+                    format_version: TIMELINE_EXPORT_VERSION,
+                    exported_at: chrono::Utc::now().to_rfc3339(),
+                    universe_id,
+                    events,
+                    eras,
+                };
+                serde_json::to_string_pretty(&export).map_err(|e| DbError::Codec(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Era que contiene el año (bordes inclusivos; era sin fin = abierta hacia adelante).
+/// Si hay varias (eras solapadas / que se tocan) gana la que empezó más tarde.
+/// Fuera de toda era => None (el evento se exporta igual, con la columna vacía).
+pub fn era_for_year(eras: &[TimelineEra], year: i64) -> Option<&TimelineEra> {
+    eras.iter()
+        .filter(|e| e.start_year <= year && e.end_year.is_none_or(|end| year <= end))
+        .max_by_key(|e| e.start_year)
+}
+
+fn render_csv(events: &[TimelineEvent], eras: &[TimelineEra]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+
+    for e in events {
+        let year = e.year.to_string();
+        let fields = [
+            year.as_str(),
+            e.display_date.as_str(),
+            e.title.as_str(),
+            e.importance.as_str(),
+            era_for_year(eras, e.year).map(|era| era.name.as_str()).unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+/// data_dir/exports/<stem>/timeline-<slug>-<timestamp>.<csv|json>
+pub fn timeline_export_path(db_path: &Path, universe_name: &str, format: TimelineExportFormat) -> PathBuf {
+    crate::db::project_settings::exports_dir(db_path).join(format!(
+        "timeline-{}-{}.{}",
+        universe_slug(universe_name),
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ))
+}
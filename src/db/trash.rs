@@ -5,8 +5,46 @@
 
 use sqlx::{Row, SqlitePool};
 use uuid::Uuid;
-use crate::model::{TrashEntry, Universe, Board, Novel, Chapter, Scene, Creature, Location, TimelineEvent, TimelineEra};
-use crate::db::Database;
+use crate::model::{TrashEntry, Universe, Board, Novel, Chapter, Scene, Creature, Location, TimelineEvent, TimelineEra, GlossaryTerm};
+use crate::db::{Database, DbError};
+
+// Retención del auto-cleanup al conectar (db_meta.trash_retention_days, 0 = nunca);
+// el preview de la página de Trash mira TRASH_PREVIEW_WINDOW_DAYS antes del corte.
+pub const TRASH_RETENTION_DAYS: i64 = 14;
+pub const TRASH_PREVIEW_WINDOW_DAYS: i64 = 3;
+
+/// Opciones del selector de Settings. 0 = Never.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrashRetention(pub i64);
+
+impl TrashRetention {
+    pub const ALL: [TrashRetention; 5] = [
+        TrashRetention(7),
+        TrashRetention(14),
+        TrashRetention(30),
+        TrashRetention(90),
+        TrashRetention(0),
+    ];
+
+    pub fn is_never(&self) -> bool {
+        self.0 <= 0
+    }
+
+    /// Corte del preview (entries que se purgan dentro de la ventana de aviso)
+    pub fn preview_days(&self) -> i64 {
+        (self.0 - TRASH_PREVIEW_WINDOW_DAYS).max(0)
+    }
+}
+
+impl std::fmt::Display for TrashRetention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_never() {
+            f.write_str("Never")
+        } else {
+            write!(f, "{} days", self.0)
+        }
+    }
+}
 
 impl Database {
     pub async fn move_to_trash(
@@ -18,7 +56,7 @@ impl Database {
         parent_type: Option<&str>,
         parent_id: Option<&str>,
         payload_json: &str,
-    ) -> Result<String, sqlx::Error> {
+    ) -> Result<String, DbError> {
         let id = Uuid::new_v4().to_string();
 
         sqlx::query(
@@ -42,6 +80,7 @@ impl Database {
     /// Atomically: insert trash_entry + delete the original rows in ONE transaction.
     pub async fn move_to_trash_and_delete(
         &self,
+        trash_id: &str,
         target_type: &str,
         target_id: &str,
         display_name: &str,
@@ -49,16 +88,15 @@ impl Database {
         parent_type: Option<&str>,
         parent_id: Option<&str>,
         payload_json: &str,
-    ) -> Result<String, sqlx::Error> {
+    ) -> Result<String, DbError> {
         let mut tx = self.pool.begin().await?;
-        let trash_id = Uuid::new_v4().to_string();
 
         // 1) Insert trash entry
         sqlx::query(
             "INSERT INTO trash_entry (id, deleted_at, target_type, target_id, parent_type, parent_id, display_name, display_info, payload_json)
             VALUES (?, unixepoch(), ?, ?, ?, ?, ?, ?, ?)"
         )
-            .bind(&trash_id)
+            .bind(trash_id)
             .bind(target_type)
             .bind(target_id)
             .bind(parent_type)
@@ -100,6 +138,8 @@ impl Database {
         // 2) Delete source rows
         match target_type {
             "universe" => {
+                sqlx::query("DELETE FROM creature_relations WHERE universe_id = ?1")
+                    .bind(target_id).execute(&mut *tx).await?;
                 sqlx::query("DELETE FROM bestiary_entries WHERE universe_id = ?1")
                     .bind(target_id).execute(&mut *tx).await?;
                 sqlx::query("DELETE FROM locations WHERE universe_id = ?1")
@@ -108,6 +148,8 @@ impl Database {
                     .bind(target_id).execute(&mut *tx).await?;
                 sqlx::query("DELETE FROM timeline_eras WHERE universe_id = ?1")
                     .bind(target_id).execute(&mut *tx).await?;
+                sqlx::query("DELETE FROM glossary WHERE universe_id = ?1")
+                    .bind(target_id).execute(&mut *tx).await?;
                 sqlx::query("DELETE FROM universes WHERE id = ?1")
                     .bind(target_id).execute(&mut *tx).await?;
             }
@@ -132,6 +174,9 @@ impl Database {
                     .bind(target_id).execute(&mut *tx).await?;
             }
             "creature" => {
+                // Relaciones fuera: un restore no debe traer aristas hacia criaturas que ya no están
+                sqlx::query("DELETE FROM creature_relations WHERE from_id = ?1 OR to_id = ?1")
+                    .bind(target_id).execute(&mut *tx).await?;
                 sqlx::query("DELETE FROM bestiary_entries WHERE id = ?")
                     .bind(target_id).execute(&mut *tx).await?;
             }
@@ -147,38 +192,44 @@ impl Database {
                 sqlx::query("DELETE FROM timeline_eras WHERE id = ?")
                     .bind(target_id).execute(&mut *tx).await?;
             }
+            "glossary_term" => {
+                sqlx::query("DELETE FROM glossary WHERE id = ?")
+                    .bind(target_id).execute(&mut *tx).await?;
+            }
             other => {
-                return Err(sqlx::Error::Protocol(
-                    format!("Unknown trash target_type: {}", other).into(),
-                ));
+                return Err(DbError::UnknownTargetType(other.to_string()));
             }
         }
 
         tx.commit().await?;
-        Ok(trash_id)
+        Ok(trash_id.to_string())
     }
 
-    pub async fn get_trash_entries(&self) -> Result<Vec<TrashEntry>, sqlx::Error> {
+    pub async fn get_trash_entries(&self) -> Result<Vec<TrashEntry>, DbError> {
         sqlx::query_as::<_, TrashEntry>(
             "SELECT id, deleted_at, target_type, target_id, parent_type, parent_id, display_name, display_info, payload_json
             FROM trash_entry ORDER BY deleted_at DESC"
         )
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
     }
 
-    pub async fn permanent_delete(&self, trash_entry_id: &str) -> Result<(), sqlx::Error> {
+    pub async fn permanent_delete(&self, trash_entry_id: &str) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
         let row = sqlx::query(
             "SELECT target_type, target_id, display_name FROM trash_entry WHERE id = ? LIMIT 1"
         )
             .bind(trash_entry_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *tx)
             .await?;
 
         sqlx::query("DELETE FROM trash_entry WHERE id = ?")
             .bind(trash_entry_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        Self::purge_orphan_scene_revisions(&mut tx).await?;
 
         if let Some(r) = row {
             let target_type: String = r.get("target_type");
@@ -201,21 +252,25 @@ impl Database {
                 .bind(target_type)
                 .bind(target_id)
                 .bind(details_json)
-                .execute(&self.pool)
+                .execute(&mut *tx)
                 .await?;
         }
 
+        tx.commit().await?;
         Ok(())
     }
 
-    pub async fn empty_trash(&self) -> Result<(), sqlx::Error> {
+    pub async fn empty_trash(&self) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+
         let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM trash_entry")
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
         sqlx::query("DELETE FROM trash_entry")
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        Self::purge_orphan_scene_revisions(&mut tx).await?;
 
         let audit_id = Uuid::new_v4().to_string();
         let details_json = format!(r#"{{"count":{}}}"#, count);
@@ -229,13 +284,28 @@ impl Database {
             .bind("trash")
             .bind("trash")
             .bind(details_json)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
 
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Revisiones cuyo scene ya no existe ni puede volver por restore (escenas purgadas o
+    // borradas en cascada con su chapter/novel). Va dentro de la tx de cada purga.
+    async fn purge_orphan_scene_revisions(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>) -> Result<(), DbError> {
+        sqlx::query(
+            "DELETE FROM scene_revisions
+             WHERE scene_id NOT IN (SELECT id FROM scenes)
+               AND scene_id NOT IN (SELECT target_id FROM trash_entry WHERE target_type = 'scene')"
+        )
+            .execute(&mut **tx)
+            .await?;
         Ok(())
     }
 
-    pub async fn cleanup_old_trash(&self, days: i64) -> Result<usize, sqlx::Error> {
+    // Corte compartido por cleanup y preview: entries con deleted_at < cutoff se purgan
+    fn trash_cutoff(days: i64) -> i64 {
         let days = days.max(0);
 
         let now_secs = std::time::SystemTime::now()
@@ -244,42 +314,78 @@ impl Database {
             .as_secs() as i64;
 
         let seconds = days.saturating_mul(24 * 60 * 60);
-        let cutoff = now_secs.saturating_sub(seconds);
+        now_secs.saturating_sub(seconds)
+    }
+
+    /// Lo que cleanup_old_trash(days) borraría, sin borrar (más viejo primero).
+    pub async fn trash_cleanup_preview(&self, days: i64) -> Result<Vec<TrashEntry>, DbError> {
+        sqlx::query_as::<_, TrashEntry>(
+            "SELECT id, deleted_at, target_type, target_id, parent_type, parent_id, display_name, display_info, payload_json
+            FROM trash_entry WHERE deleted_at < ? ORDER BY deleted_at ASC"
+        )
+            .bind(Self::trash_cutoff(days))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DbError::from)
+    }
+
+    pub async fn get_trash_retention(&self) -> Result<TrashRetention, DbError> {
+        let days: Option<i64> = sqlx::query_scalar("SELECT trash_retention_days FROM db_meta LIMIT 1")
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(TrashRetention(days.unwrap_or(TRASH_RETENTION_DAYS)))
+    }
+
+    /// Solo guarda: la purga con el nuevo valor la hace el próximo connect (o "Clean now").
+    pub async fn set_trash_retention(&self, retention: TrashRetention) -> Result<(), DbError> {
+        sqlx::query("UPDATE db_meta SET trash_retention_days = ?")
+            .bind(retention.0.max(0))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn cleanup_old_trash(&self, days: i64) -> Result<usize, DbError> {
+        let cutoff = Self::trash_cutoff(days);
+        let mut tx = self.pool.begin().await?;
 
         let result = sqlx::query("DELETE FROM trash_entry WHERE deleted_at < ?")
             .bind(cutoff)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        Self::purge_orphan_scene_revisions(&mut tx).await?;
 
+        tx.commit().await?;
         Ok(result.rows_affected() as usize)
     }
 
-    pub async fn restore_from_trash(&self, trash_entry_id: &str) -> Result<(), sqlx::Error> {
+    pub async fn restore_from_trash(&self, trash_entry_id: &str) -> Result<(), DbError> {
         // 1) Capability gate
-        if let Err(e) = self.require_capability("trash").await {
-            return Err(sqlx::Error::Protocol(format!("Trash restore blocked by capability: {}", e).into()));
-        }
+        self.require_capability("trash").await?;
 
         // 2) Helper para validación FK
-        async fn ensure_exists(pool: &SqlitePool, kind: &str, id: &str) -> Result<(), sqlx::Error> {
+        async fn ensure_exists(pool: &SqlitePool, kind: &str, id: &str) -> Result<(), DbError> {
             let table = match kind {
                 "universes" => "universes",
                 "novels" => "novels",
                 "chapters" => "chapters",
                 "locations" => "locations",
                 _ => {
-                    return Err(sqlx::Error::Protocol(
-                        format!("ensure_exists called with unsupported kind: {}", kind).into(),
-                    ));
+                    return Err(DbError::Invalid(format!(
+                        "ensure_exists called with unsupported kind: {}",
+                        kind
+                    )));
                 }
             };
 
             let q = format!("SELECT 1 FROM {} WHERE id = ? LIMIT 1", table);
             let row = sqlx::query(&q).bind(id).fetch_optional(pool).await?;
             if row.is_none() {
-                return Err(sqlx::Error::Protocol(
-                    format!("Cannot restore: missing {} record id={}", kind, id).into(),
-                ));
+                // "novels" -> "novel" (singular para el mensaje)
+                return Err(DbError::MissingParent {
+                    kind: kind.trim_end_matches('s').to_string(),
+                    id: id.to_string(),
+                });
             }
             Ok(())
         }
@@ -291,60 +397,42 @@ impl Database {
 
         match entry.target_type.as_str() {
             "universe" => {
-                if let Err(e) = self.require_capability("worldbuilding").await {
-                    return Err(sqlx::Error::Protocol(format!("Universe restore blocked by capability: {}", e).into()));
-                }
-                let universe: Universe = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("worldbuilding").await?;
+                let universe: Universe = serde_json::from_str(&entry.payload_json)?;
                 self.restore_universe(universe).await?;
             }
             "board" => {
-                if let Err(e) = self.require_capability("pm").await {
-                    return Err(sqlx::Error::Protocol(format!("Board restore blocked by capability: {}", e).into()));
-                }
-                let board: Board = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("pm").await?;
+                let board: Board = serde_json::from_str(&entry.payload_json)?;
                 self.restore_board(board).await?;
             }
             "novel" => {
-                if let Err(e) = self.require_capability("novel").await {
-                    return Err(sqlx::Error::Protocol(format!("Novel restore blocked by capability: {}", e).into()));
-                }
-                let novel: Novel = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("novel").await?;
+                let novel: Novel = serde_json::from_str(&entry.payload_json)?;
                 if let Some(universe_id) = novel.universe_id.as_deref() {
                     ensure_exists(&self.pool, "universes", universe_id).await?;
                 }
                 self.restore_novel(novel).await?;
             }
             "chapter" => {
-                if let Err(e) = self.require_capability("novel").await {
-                    return Err(sqlx::Error::Protocol(format!("Chapter restore blocked by capability: {}", e).into()));
-                }
-                let chapter: Chapter = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("novel").await?;
+                let chapter: Chapter = serde_json::from_str(&entry.payload_json)?;
                 ensure_exists(&self.pool, "novels", &chapter.novel_id).await?;
                 self.restore_chapter(chapter).await?;
             }
             "scene" => {
-                if let Err(e) = self.require_capability("novel").await {
-                    return Err(sqlx::Error::Protocol(format!("Scene restore blocked by capability: {}", e).into()));
-                }
-                let scene: Scene = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("novel").await?;
+                let scene: Scene = serde_json::from_str(&entry.payload_json)?;
                 ensure_exists(&self.pool, "chapters", &scene.chapter_id).await?;
                 self.restore_scene(scene).await?;
             }
             "creature" => {
-                if let Err(e) = self.require_capability("worldbuilding").await {
-                    return Err(sqlx::Error::Protocol(format!("Creature restore blocked by capability: {}", e).into()));
-                }
-                let creature: Creature = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("worldbuilding").await?;
+                let creature: Creature = serde_json::from_str(&entry.payload_json)?;
                 let universe_id = entry
                     .parent_id
                     .as_ref()
-                    .ok_or_else(|| sqlx::Error::Decode("Missing parent_id for creature".into()))?;
+                    .ok_or_else(|| DbError::Codec("Missing parent_id for creature".to_string()))?;
                 ensure_exists(&self.pool, "universes", universe_id).await?;
                 if let Some(loc_id) = creature.home_location_id.as_deref() {
                     ensure_exists(&self.pool, "locations", loc_id).await?;
@@ -352,11 +440,8 @@ impl Database {
                 self.restore_creature(creature, universe_id).await?;
             }
             "location" => {
-                if let Err(e) = self.require_capability("worldbuilding").await {
-                    return Err(sqlx::Error::Protocol(format!("Location restore blocked by capability: {}", e).into()));
-                }
-                let location: Location = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("worldbuilding").await?;
+                let location: Location = serde_json::from_str(&entry.payload_json)?;
                 ensure_exists(&self.pool, "universes", &location.universe_id).await?;
                 if let Some(parent_id) = location.parent_id.as_deref() {
                     ensure_exists(&self.pool, "locations", parent_id).await?;
@@ -364,11 +449,8 @@ impl Database {
                 self.restore_location(location).await?;
             }
             "event" => {
-                if let Err(e) = self.require_capability("timeline").await {
-                    return Err(sqlx::Error::Protocol(format!("Event restore blocked by capability: {}", e).into()));
-                }
-                let event: TimelineEvent = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("timeline").await?;
+                let event: TimelineEvent = serde_json::from_str(&entry.payload_json)?;
                 ensure_exists(&self.pool, "universes", &event.universe_id).await?;
                 if let Some(loc_id) = event.location_id.as_deref() {
                     ensure_exists(&self.pool, "locations", loc_id).await?;
@@ -376,18 +458,19 @@ impl Database {
                 self.restore_event(event).await?;
             }
             "era" => {
-                if let Err(e) = self.require_capability("timeline").await {
-                    return Err(sqlx::Error::Protocol(format!("Era restore blocked by capability: {}", e).into()));
-                }
-                let era: TimelineEra = serde_json::from_str(&entry.payload_json)
-                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                self.require_capability("timeline").await?;
+                let era: TimelineEra = serde_json::from_str(&entry.payload_json)?;
                 ensure_exists(&self.pool, "universes", &era.universe_id).await?;
                 self.restore_era(era).await?;
             }
+            "glossary_term" => {
+                self.require_capability("worldbuilding").await?;
+                let term: GlossaryTerm = serde_json::from_str(&entry.payload_json)?;
+                ensure_exists(&self.pool, "universes", &term.universe_id).await?;
+                self.restore_glossary_term(term).await?;
+            }
             other => {
-                return Err(sqlx::Error::Protocol(
-                    format!("Unsupported target_type in trash restore: {}", other).into(),
-                ));
+                return Err(DbError::UnknownTargetType(other.to_string()));
             }
         }
 
@@ -416,7 +499,7 @@ impl Database {
 
     // --- Helper functions for restore ---
 
-    async fn restore_universe(&self, universe: Universe) -> Result<(), sqlx::Error> {
+    async fn restore_universe(&self, universe: Universe) -> Result<(), DbError> {
         sqlx::query(
             "INSERT INTO universes (id, name, description, archived)
                 VALUES (?, ?, ?, ?)"
@@ -426,14 +509,15 @@ impl Database {
             .bind(&universe.description)
             .bind(&universe.archived)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("universe", &universe.id, e))?;
         Ok(())
     }
 
-    async fn restore_creature(&self, creature: Creature, universe_id: &str) -> Result<(), sqlx::Error> {
+    async fn restore_creature(&self, creature: Creature, universe_id: &str) -> Result<(), DbError> {
         sqlx::query(
-            "INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived, image_path)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
             .bind(&creature.id)
             .bind(universe_id)
@@ -444,15 +528,17 @@ impl Database {
             .bind(&creature.danger)
             .bind(&creature.home_location_id)
             .bind(&creature.archived)
+            .bind(&creature.image_path)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("creature", &creature.id, e))?;
         Ok(())
     }
 
-    async fn restore_location(&self, location: Location) -> Result<(), sqlx::Error> {
+    async fn restore_location(&self, location: Location) -> Result<(), DbError> {
         sqlx::query(
-            "INSERT INTO locations (id, universe_id, parent_id, name, description, kind)
-                VALUES (?, ?, ?, ?, ?, ?)"
+            "INSERT INTO locations (id, universe_id, parent_id, name, description, kind, image_path)
+                VALUES (?, ?, ?, ?, ?, ?, ?)"
         )
             .bind(&location.id)
             .bind(&location.universe_id)
@@ -460,15 +546,17 @@ impl Database {
             .bind(&location.name)
             .bind(&location.description)
             .bind(&location.kind)
+            .bind(&location.image_path)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("location", &location.id, e))?;
         Ok(())
     }
 
-    async fn restore_event(&self, event: TimelineEvent) -> Result<(), sqlx::Error> {
+    async fn restore_event(&self, event: TimelineEvent) -> Result<(), DbError> {
         sqlx::query(
-            "INSERT INTO timeline_events (id, universe_id, title, display_date, year, description, location_id)
-                VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO timeline_events (id, universe_id, title, display_date, year, description, location_id, scene_id)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
         )
             .bind(&event.id)
             .bind(&event.universe_id)
@@ -477,12 +565,14 @@ impl Database {
             .bind(&event.year)
             .bind(&event.description)
             .bind(&event.location_id)
+            .bind(&event.scene_id)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("event", &event.id, e))?;
         Ok(())
     }
 
-    async fn restore_era(&self, era: TimelineEra) -> Result<(), sqlx::Error> {
+    async fn restore_era(&self, era: TimelineEra) -> Result<(), DbError> {
         sqlx::query(
             "INSERT INTO timeline_eras (id, universe_id, name, start_year, end_year, description, color)
                 VALUES (?, ?, ?, ?, ?, ?, ?)"
@@ -495,11 +585,28 @@ impl Database {
             .bind(&era.description)
             .bind(&era.color)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("era", &era.id, e))?;
         Ok(())
     }
 
-    async fn restore_board(&self, board: Board) -> Result<(), sqlx::Error> {
+    async fn restore_glossary_term(&self, term: GlossaryTerm) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO glossary (id, universe_id, term, definition, pronunciation)
+                VALUES (?, ?, ?, ?, ?)"
+        )
+            .bind(&term.id)
+            .bind(&term.universe_id)
+            .bind(&term.term)
+            .bind(&term.definition)
+            .bind(&term.pronunciation)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| restore_err("glossary term", &term.id, e))?;
+        Ok(())
+    }
+
+    async fn restore_board(&self, board: Board) -> Result<(), DbError> {
         sqlx::query(
             "INSERT INTO boards (id, name, kind)
                 VALUES (?, ?, ?)"
@@ -508,11 +615,12 @@ impl Database {
             .bind(&board.name)
             .bind(&board.kind)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("board", &board.id, e))?;
         Ok(())
     }
 
-    async fn restore_novel(&self, novel: Novel) -> Result<(), sqlx::Error> {
+    async fn restore_novel(&self, novel: Novel) -> Result<(), DbError> {
         sqlx::query(
             "INSERT INTO novels (id, universe_id, title, synopsis, status)
                 VALUES (?, ?, ?, ?, ?)"
@@ -523,29 +631,27 @@ impl Database {
             .bind(&novel.synopsis)
             .bind(&novel.status)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("novel", &novel.id, e))?;
         Ok(())
     }
 
-    async fn restore_chapter(&self, chapter: Chapter) -> Result<(), sqlx::Error> {
+    async fn restore_chapter(&self, chapter: Chapter) -> Result<(), DbError> {
         let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM novels WHERE id = ?")
             .bind(&chapter.novel_id)
             .fetch_one(&self.pool)
             .await?;
 
         if exists == 0 {
-            return Err(sqlx::Error::Protocol(
-                format!(
-                    "Cannot restore chapter {}: novel {} not found",
-                    chapter.id, chapter.novel_id
-                )
-                    .into(),
-            ));
+            return Err(DbError::MissingParent {
+                kind: "novel".to_string(),
+                id: chapter.novel_id.clone(),
+            });
         }
 
         sqlx::query(
-            "INSERT INTO chapters (id, novel_id, title, position, synopsis, status)
-                    VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO chapters (id, novel_id, title, position, synopsis, status, target_words)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)",
         )
             .bind(&chapter.id)
             .bind(&chapter.novel_id)
@@ -553,31 +659,30 @@ impl Database {
             .bind(chapter.position)
             .bind(&chapter.synopsis)
             .bind(&chapter.status)
+            .bind(chapter.target_words)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("chapter", &chapter.id, e))?;
 
         Ok(())
     }
 
-    async fn restore_scene(&self, scene: Scene) -> Result<(), sqlx::Error> {
+    async fn restore_scene(&self, scene: Scene) -> Result<(), DbError> {
         let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chapters WHERE id = ?")
             .bind(&scene.chapter_id)
             .fetch_one(&self.pool)
             .await?;
 
         if exists == 0 {
-            return Err(sqlx::Error::Protocol(
-                format!(
-                    "Cannot restore scene {}: chapter {} not found",
-                    scene.id, scene.chapter_id
-                )
-                    .into(),
-            ));
+            return Err(DbError::MissingParent {
+                kind: "chapter".to_string(),
+                id: scene.chapter_id.clone(),
+            });
         }
 
         sqlx::query(
-            "INSERT INTO scenes (id, chapter_id, title, body, position, status, word_count)
-                        VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO scenes (id, chapter_id, title, body, position, status, word_count, scene_kind, include_in_compile, target_words, notes)
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
             .bind(&scene.id)
             .bind(&scene.chapter_id)
@@ -586,9 +691,23 @@ impl Database {
             .bind(scene.position)
             .bind(&scene.status)
             .bind(scene.word_count)
+            .bind(scene.kind().as_str())
+            .bind(scene.include_in_compile)
+            .bind(scene.target_words)
+            .bind(&scene.notes)
             .execute(&self.pool)
-            .await?;
+            .await
+            .map_err(|e| restore_err("scene", &scene.id, e))?;
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+// INSERT de restore: id duplicado => RestoreConflict (el resto pasa como Sql)
+fn restore_err(kind: &str, id: &str, e: sqlx::Error) -> DbError {
+    if DbError::is_unique_violation(&e) {
+        DbError::RestoreConflict { kind: kind.to_string(), id: id.to_string() }
+    } else {
+        DbError::from(e)
+    }
+}
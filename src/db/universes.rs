@@ -13,10 +13,10 @@ use base64::{engine::general_purpose, Engine as _};
 use crate::model::{
     Universe, UniverseSnapshot, UniverseSnapshotPayload, Card,
 };
-use crate::db::Database;
+use crate::db::{Database, DbError};
 
 impl Database {
-    pub async fn get_all_universes(&self) -> Result<Vec<Universe>, sqlx::Error> {
+    pub async fn get_all_universes(&self) -> Result<Vec<Universe>, DbError> {
         crate::logger::info("🔍 DB: Querying universes...");
 
         let result = sqlx::query_as::<_, Universe>(
@@ -38,7 +38,7 @@ impl Database {
         Ok(result)
     }
 
-    pub async fn create_universe(&self, id: String, name: String, desc: String) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn create_universe(&self, id: String, name: String, desc: String) -> Result<(), DbError> {
         // ✅ Guard de capability
         self.require_capability("universes").await?;
 
@@ -47,12 +47,11 @@ impl Database {
             .bind(name)
             .bind(desc)
             .execute(&self.pool)
-            .await
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            .await?;
         Ok(())
     }
 
-    pub async fn delete_universe(&self, id: String) -> Result<(), sqlx::Error> {
+    pub async fn delete_universe(&self, id: String) -> Result<(), DbError> {
         let mut tx = self.pool.begin().await?;
 
         // Bestiary (en schema viejo no tenía ON DELETE CASCADE)
@@ -78,6 +77,12 @@ impl Database {
             .execute(&mut *tx)
             .await?;
 
+        // Glossary
+        sqlx::query("DELETE FROM glossary WHERE universe_id = ?1")
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+
         // Finalmente borrar universe
         sqlx::query("DELETE FROM universes WHERE id = ?1")
             .bind(&id)
@@ -88,7 +93,97 @@ impl Database {
         Ok(())
     }
 
-    pub async fn validate_universe(&self, universe_id: String) -> Result<Vec<String>, sqlx::Error> {
+    /// Duplica un universo completo con ids nuevos (una sola transacción).
+    /// Las FKs internas (parent_id, home_location_id, location_id, ...) se remapean
+    /// vía una tabla temporal old -> new; si apuntan fuera del universo quedan en NULL.
+    /// Devuelve el id del universo nuevo.
+    pub async fn clone_universe(&self, source_id: String, include_novels: bool) -> Result<String, DbError> {
+        self.require_capability("universes").await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let exists: Option<i64> = sqlx::query_scalar("SELECT 1 FROM universes WHERE id = ?1")
+            .bind(&source_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Err(DbError::MissingParent { kind: "universe".to_string(), id: source_id });
+        }
+
+        sqlx::query("CREATE TEMP TABLE IF NOT EXISTS clone_id_map (old_id TEXT PRIMARY KEY, new_id TEXT NOT NULL)")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM clone_id_map").execute(&mut *tx).await?;
+
+        // Orden = padres antes que hijos: cada tabla remapea contra lo ya registrado
+        let mut tables: Vec<(&str, &str)> = vec![
+            ("universes", "src.id = ?1"),
+            ("locations", "src.universe_id = ?1"),
+            ("bestiary_entries", "src.universe_id = ?1"),
+        ];
+        if include_novels {
+            tables.push(("novels", "src.universe_id = ?1"));
+            tables.push(("chapters", "src.novel_id IN (SELECT id FROM novels WHERE universe_id = ?1)"));
+            tables.push((
+                "scenes",
+                "src.chapter_id IN (SELECT c.id FROM chapters c JOIN novels n ON n.id = c.novel_id WHERE n.universe_id = ?1)",
+            ));
+        }
+        tables.push(("timeline_eras", "src.universe_id = ?1"));
+        tables.push(("timeline_events", "src.universe_id = ?1"));
+        tables.push(("glossary", "src.universe_id = ?1"));
+        // Relaciones colgando hacia criaturas de otro universo no se copian (from/to son NOT NULL)
+        tables.push((
+            "creature_relations",
+            "src.universe_id = ?1 AND src.from_id IN (SELECT old_id FROM clone_id_map) AND src.to_id IN (SELECT old_id FROM clone_id_map)",
+        ));
+
+        let mut new_universe_id = String::new();
+
+        for (table, filter) in tables {
+            let columns: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{table}')"))
+                .fetch_all(&mut *tx)
+                .await?;
+            // Tabla ausente en schemas viejos => nada que copiar
+            if columns.is_empty() {
+                continue;
+            }
+
+            let old_ids: Vec<String> = sqlx::query_scalar(&format!("SELECT src.id FROM {table} src WHERE {filter}"))
+                .bind(&source_id)
+                .fetch_all(&mut *tx)
+                .await?;
+            for old in old_ids {
+                let new = if table == "universes" {
+                    new_universe_id = format!("u-{}", Uuid::new_v4());
+                    new_universe_id.clone()
+                } else {
+                    Uuid::new_v4().to_string()
+                };
+                sqlx::query("INSERT INTO clone_id_map (old_id, new_id) VALUES (?1, ?2)")
+                    .bind(old)
+                    .bind(new)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            let select: Vec<String> = columns.iter().map(|c| clone_column_expr(table, c)).collect();
+            let sql = format!(
+                "INSERT INTO {table} ({}) SELECT {} FROM {table} src WHERE {filter}",
+                columns.join(", "),
+                select.join(", ")
+            );
+            sqlx::query(&sql).bind(&source_id).execute(&mut *tx).await?;
+        }
+
+        sqlx::query("DROP TABLE clone_id_map").execute(&mut *tx).await?;
+        tx.commit().await?;
+
+        crate::logger::info(&format!("🧬 DB: Universe {} cloned as {}", source_id, new_universe_id));
+        Ok(new_universe_id)
+    }
+
+    pub async fn validate_universe(&self, universe_id: String) -> Result<Vec<String>, DbError> {
         let mut issues: Vec<String> = Vec::new();
         let rows = sqlx::query("SELECT b.id, b.name, b.home_location_id FROM bestiary_entries b WHERE b.universe_id = ? AND b.home_location_id IS NOT NULL AND NOT EXISTS (SELECT 1 FROM locations l WHERE l.id = b.home_location_id)").bind(&universe_id).fetch_all(&self.pool).await?;
         for r in rows {
@@ -104,9 +199,9 @@ impl Database {
 
     // --- SNAPSHOTS ---
 
-    pub async fn snapshot_list(&self, universe_id: String) -> Result<Vec<UniverseSnapshot>, sqlx::Error> {
+    pub async fn snapshot_list(&self, universe_id: String) -> Result<Vec<UniverseSnapshot>, DbError> {
         sqlx::query_as::<_, UniverseSnapshot>(
-            "SELECT id, universe_id, name, created_at, size_bytes
+            "SELECT id, universe_id, name, created_at, size_bytes, is_auto
          FROM universe_snapshots
          WHERE universe_id = ?
          ORDER BY created_at DESC"
@@ -114,9 +209,15 @@ impl Database {
             .bind(universe_id)
             .fetch_all(&self.pool)
             .await
+            .map_err(DbError::from)
     }
 
-    pub async fn snapshot_create(&self, universe_id: String, name: String) -> Result<(), sqlx::Error> {
+    pub async fn snapshot_create(&self, universe_id: String, name: String) -> Result<(), DbError> {
+        self.snapshot_insert(universe_id, name).await.map(|_| ())
+    }
+
+    /// Captura + inserta; devuelve el id del snapshot nuevo.
+    async fn snapshot_insert(&self, universe_id: String, name: String) -> Result<String, DbError> {
         let universe = sqlx::query_as::<_, Universe>(
             "SELECT id, name, description, archived FROM universes WHERE id = ?"
         )
@@ -128,9 +229,10 @@ impl Database {
         let locations = self.get_locations_flat(universe_id.clone()).await?;
         let eras = self.get_timeline_eras(universe_id.clone()).await?;
         let events = self.get_timeline_events(universe_id.clone()).await?;
+        let glossary = self.get_glossary(universe_id.clone()).await?;
 
         let pm_cards: Vec<Card> = sqlx::query_as::<_, Card>(
-            "SELECT id, column_id, title, description, position, priority
+            "SELECT id, column_id, title, description, position, priority, labels, NULLIF(due_date, 0) as due_date, archived
          FROM cards
          WHERE column_id IN (
              SELECT id FROM board_columns WHERE board_id='board-main'
@@ -145,19 +247,20 @@ impl Database {
             timeline_eras: eras,
             timeline_events: events,
             pm_cards,
+            glossary,
         };
 
         // ✅ APLICADO: Serializar a bytes directamente
         let json_bytes = serde_json::to_vec(&payload)
-            .map_err(|e| sqlx::Error::Protocol(format!("JSON serialize fail: {e}").into()))?;
+            .map_err(|e| DbError::Codec(format!("JSON serialize fail: {e}")))?;
 
         let mut e = GzEncoder::new(Vec::new(), Compression::default());
         use std::io::Write;
         e.write_all(&json_bytes)
-            .map_err(|e| sqlx::Error::Protocol(format!("Compress write fail: {e}").into()))?;
+            .map_err(|e| DbError::Codec(format!("Compress write fail: {e}")))?;
 
         let compressed = e.finish()
-            .map_err(|e| sqlx::Error::Protocol(format!("Compress finish fail: {e}").into()))?;
+            .map_err(|e| DbError::Codec(format!("Compress finish fail: {e}")))?;
 
         let size_bytes = compressed.len() as i64;
         let sid = format!("snap-{}", Uuid::new_v4());
@@ -176,7 +279,7 @@ impl Database {
             .await;
 
         match res {
-            Ok(_) => Ok(()),
+            Ok(_) => Ok(sid),
             Err(e) => {
                 let msg = e.to_string();
                 if msg.contains("no such column: compressed_blob") {
@@ -185,22 +288,56 @@ impl Database {
                         "INSERT INTO universe_snapshots (id, universe_id, name, size_bytes, compressed_b64)
                          VALUES (?, ?, ?, ?, ?)"
                     )
-                        .bind(sid)
+                        .bind(&sid)
                         .bind(universe_id)
                         .bind(name)
                         .bind(size_bytes)
                         .bind(compressed_b64)
                         .execute(&self.pool)
                         .await?;
-                    Ok(())
+                    Ok(sid)
                 } else {
-                    Err(e)
+                    Err(e.into())
                 }
             }
         }
     }
 
-    pub async fn snapshot_delete(&self, snapshot_id: String) -> Result<(), sqlx::Error> {
+    /// Snapshot automático (antes de una operación destructiva) + poda de los autos viejos.
+    /// Los manuales nunca se tocan.
+    pub async fn snapshot_create_auto(&self, universe_id: String, name: String, keep: usize) -> Result<(), DbError> {
+        // Universo ya borrado (p.ej. en la papelera): nada que capturar
+        let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM universes WHERE id = ?")
+            .bind(&universe_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        if exists.is_none() {
+            return Ok(());
+        }
+
+        let sid = self.snapshot_insert(universe_id.clone(), name).await?;
+
+        sqlx::query("UPDATE universe_snapshots SET is_auto = 1 WHERE id = ?")
+            .bind(&sid)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            "DELETE FROM universe_snapshots WHERE universe_id = ? AND is_auto = 1 AND id NOT IN (
+                 SELECT id FROM universe_snapshots WHERE universe_id = ? AND is_auto = 1
+                 ORDER BY created_at DESC, rowid DESC LIMIT ?
+             )",
+        )
+            .bind(&universe_id)
+            .bind(&universe_id)
+            .bind(keep.max(1) as i64)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn snapshot_delete(&self, snapshot_id: String) -> Result<(), DbError> {
         sqlx::query("DELETE FROM universe_snapshots WHERE id = ?")
             .bind(snapshot_id)
             .execute(&self.pool)
@@ -208,7 +345,20 @@ impl Database {
         Ok(())
     }
 
-    pub async fn snapshot_restore(&self, snapshot_id: String) -> Result<(), sqlx::Error> {
+    pub async fn snapshot_rename(&self, snapshot_id: String, name: String) -> Result<(), DbError> {
+        let updated = sqlx::query("UPDATE universe_snapshots SET name = ? WHERE id = ?")
+            .bind(name)
+            .bind(&snapshot_id)
+            .execute(&self.pool)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::Invalid(format!("Snapshot not found (id={})", snapshot_id)));
+        }
+        Ok(())
+    }
+
+    pub async fn snapshot_restore(&self, snapshot_id: String) -> Result<(), DbError> {
         // ✅ APLICADO: Lectura híbrida Blob/Base64
         let row_res = sqlx::query(
             "SELECT compressed_blob, compressed_b64 FROM universe_snapshots WHERE id = ?"
@@ -229,7 +379,7 @@ impl Database {
                             .await?;
                     (None, row.get("compressed_b64"))
                 } else {
-                    return Err(e);
+                    return Err(e.into());
                 }
             }
         };
@@ -239,7 +389,7 @@ impl Database {
         } else {
             general_purpose::STANDARD
                 .decode(b64)
-                .map_err(|e| sqlx::Error::Protocol(format!("Base64 decode fail: {e}").into()))?
+                .map_err(|e| DbError::Codec(format!("Base64 decode fail: {e}")))?
         };
 
         // ✅ APLICADO: Decompress a bytes
@@ -247,11 +397,11 @@ impl Database {
         let mut out = Vec::new();
         use std::io::Read;
         d.read_to_end(&mut out)
-            .map_err(|e| sqlx::Error::Protocol(format!("Decompress fail: {e}").into()))?;
+            .map_err(|e| DbError::Codec(format!("Decompress fail: {e}")))?;
 
         let payload: UniverseSnapshotPayload =
             serde_json::from_slice(&out)
-                .map_err(|e| sqlx::Error::Protocol(format!("JSON parse fail: {e}").into()))?;
+                .map_err(|e| DbError::Codec(format!("JSON parse fail: {e}")))?;
 
         let mut tx = self.pool.begin().await?;
 
@@ -267,17 +417,18 @@ impl Database {
         sqlx::query("DELETE FROM timeline_events WHERE universe_id = ?").bind(&payload.universe.id).execute(&mut *tx).await?;
         sqlx::query("DELETE FROM timeline_eras WHERE universe_id = ?").bind(&payload.universe.id).execute(&mut *tx).await?;
         sqlx::query("DELETE FROM locations WHERE universe_id = ?").bind(&payload.universe.id).execute(&mut *tx).await?;
+        sqlx::query("DELETE FROM glossary WHERE universe_id = ?").bind(&payload.universe.id).execute(&mut *tx).await?;
         sqlx::query("DELETE FROM cards WHERE column_id IN (SELECT id FROM board_columns WHERE board_id='board-main')").execute(&mut *tx).await?;
 
         for c in payload.creatures {
-            sqlx::query("INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
-                .bind(c.id).bind(&payload.universe.id).bind(c.name).bind(c.kind).bind(c.habitat).bind(c.description).bind(c.danger).bind(c.home_location_id).bind(c.archived)
+            sqlx::query("INSERT INTO bestiary_entries (id, universe_id, name, kind, habitat, description, danger, home_location_id, archived, image_path) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .bind(c.id).bind(&payload.universe.id).bind(c.name).bind(c.kind).bind(c.habitat).bind(c.description).bind(c.danger).bind(c.home_location_id).bind(c.archived).bind(c.image_path)
                 .execute(&mut *tx).await?;
         }
 
         for l in payload.locations {
-            sqlx::query("INSERT INTO locations (id, universe_id, parent_id, name, description, kind) VALUES (?, ?, ?, ?, ?, ?)")
-                .bind(l.id).bind(&payload.universe.id).bind(l.parent_id).bind(l.name).bind(l.description).bind(l.kind)
+            sqlx::query("INSERT INTO locations (id, universe_id, parent_id, name, description, kind, image_path) VALUES (?, ?, ?, ?, ?, ?, ?)")
+                .bind(l.id).bind(&payload.universe.id).bind(l.parent_id).bind(l.name).bind(l.description).bind(l.kind).bind(l.image_path)
                 .execute(&mut *tx).await?;
         }
 
@@ -288,18 +439,48 @@ impl Database {
         }
 
         for ev in payload.timeline_events {
-            sqlx::query("INSERT INTO timeline_events (id, universe_id, title, description, year, display_date, importance, kind, color, location_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
-                .bind(ev.id).bind(&payload.universe.id).bind(ev.title).bind(ev.description).bind(ev.year).bind(ev.display_date).bind(ev.importance).bind(ev.kind).bind(ev.color).bind(ev.location_id)
+            sqlx::query("INSERT INTO timeline_events (id, universe_id, title, description, year, display_date, importance, kind, color, location_id, scene_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .bind(ev.id).bind(&payload.universe.id).bind(ev.title).bind(ev.description).bind(ev.year).bind(ev.display_date).bind(ev.importance).bind(ev.kind).bind(ev.color).bind(ev.location_id).bind(ev.scene_id)
+                .execute(&mut *tx).await?;
+        }
+
+        for g in payload.glossary {
+            sqlx::query("INSERT INTO glossary (id, universe_id, term, definition, pronunciation) VALUES (?, ?, ?, ?, ?)")
+                .bind(g.id).bind(&payload.universe.id).bind(g.term).bind(g.definition).bind(g.pronunciation)
                 .execute(&mut *tx).await?;
         }
 
         for card in payload.pm_cards {
-            sqlx::query("INSERT INTO cards (id, column_id, title, description, position, priority) VALUES (?, ?, ?, ?, ?, ?)")
-                .bind(card.id).bind(card.column_id).bind(card.title).bind(card.description).bind(card.position).bind(card.priority)
+            sqlx::query("INSERT INTO cards (id, column_id, title, description, position, priority, labels, due_date, archived) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .bind(card.id).bind(card.column_id).bind(card.title).bind(card.description).bind(card.position).bind(card.priority).bind(card.labels).bind(card.due_date).bind(card.archived)
                 .execute(&mut *tx).await?;
         }
 
         tx.commit().await?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Columnas con ids del universo clonado: se traducen vía clone_id_map.
+const CLONE_ID_COLUMNS: [&str; 10] = [
+    "id", "universe_id", "parent_id", "home_location_id", "location_id",
+    "scene_id", "from_id", "to_id", "novel_id", "chapter_id",
+];
+
+/// Expresión del SELECT de clonado para una columna (alias de la fila origen: `src`).
+fn clone_column_expr(table: &str, column: &str) -> String {
+    if table == "universes" {
+        match column {
+            "name" => return "src.name || ' (copy)'".to_string(),
+            "archived" => return "0".to_string(),
+            "created_at" | "updated_at" => return "unixepoch()".to_string(),
+            _ => {}
+        }
+    }
+
+    if CLONE_ID_COLUMNS.contains(&column) {
+        format!("(SELECT new_id FROM clone_id_map WHERE old_id = src.{column})")
+    } else {
+        format!("src.{column}")
+    }
+}
@@ -13,13 +13,13 @@ pub async fn seed(pool: &SqlitePool, universe_id: &str) -> Result<(), sqlx::Erro
     }
 
     let creatures = vec![
-        ("Astral Whale", "Celestial", "Space", "Swims between stars.", "Low"),
-        ("Swamp Hag", "Humanoid", "Marsh", "Deceptive witch.", "High"),
-        ("Iron Golem", "Construct", "Mountains", "Guardian of the mines.", "Medium"),
-        ("Void Leech", "Aberration", "Void", "Drains magic.", "High"),
-        ("Crystal Spider", "Beast", "Caves", "Weaves glass webs.", "Medium"),
-        ("Phoenix Hatchling", "Elemental", "Volcano", "Reborn from ashes.", "High"),
-        ("Shadow Stalker", "Demon", "Ruins", "Hunts in darkness.", "Extreme"),
+        ("Astral Whale", "Celestial", "Space", "Swims between stars.", "Minor"),
+        ("Swamp Hag", "Humanoid", "Marsh", "Deceptive witch.", "Deadly"),
+        ("Iron Golem", "Construct", "Mountains", "Guardian of the mines.", "Moderate"),
+        ("Void Leech", "Aberration", "Void", "Drains magic.", "Deadly"),
+        ("Crystal Spider", "Beast", "Caves", "Weaves glass webs.", "Moderate"),
+        ("Phoenix Hatchling", "Elemental", "Volcano", "Reborn from ashes.", "Deadly"),
+        ("Shadow Stalker", "Demon", "Ruins", "Hunts in darkness.", "Legendary"),
     ];
 
     for (name, kind, habitat, description, danger) in creatures {
@@ -1,5 +1,5 @@
 use iced::widget::text_editor;
-use crate::model::{Creature, Location, TimelineEvent, TimelineEra};
+use crate::model::{Creature, CreatureRelationKind, DangerLevel, GlossaryTerm, Location, TimelineEvent, TimelineEra};
 
 // ============================================
 // REFACTOR C.1: Editors store IDs instead of full structs
@@ -13,8 +13,12 @@ pub struct CreatureEditor {
     pub kind: String,
     pub habitat: String,
     pub description: text_editor::Content,
-    pub danger: String,
+    pub danger: DangerLevel, // ✅ Escala canónica (se serializa con as_str al guardar)
     pub home_location_id: Option<String>, // ✅ C.1: ID only instead of Option<Location>
+    pub image_path: String, // texto libre; se valida (existe + formato) al guardar
+    // Borrador de "agregar relación" (la relación se guarda al instante, no con Save)
+    pub relation_target_id: Option<String>,
+    pub relation_kind: CreatureRelationKind,
 }
 
 impl CreatureEditor {
@@ -26,8 +30,11 @@ impl CreatureEditor {
             kind: String::new(),
             habitat: String::new(),
             description: text_editor::Content::new(),
-            danger: "Medium".to_string(),
+            danger: DangerLevel::default(),
             home_location_id: None, // ✅ C.1
+            image_path: String::new(),
+            relation_target_id: None,
+            relation_kind: CreatureRelationKind::default(),
         }
     }
 
@@ -40,21 +47,27 @@ impl CreatureEditor {
             kind: c.kind.clone(),
             habitat: c.habitat.clone(),
             description: text_editor::Content::with_text(&c.description),
-            danger: c.danger.clone(),
+            danger: c.danger_level(),
             home_location_id: c.home_location_id.clone(), // ✅ C.1: Direct copy
+            image_path: c.image_path.clone().unwrap_or_default(),
+            relation_target_id: None,
+            relation_kind: CreatureRelationKind::default(),
         }
     }
 
+    // Validar antes con image_path_for_save: acá una ruta inválida simplemente se descarta
     pub fn into_creature(self) -> Creature {
+        let image_path = image_path_for_save(&self.image_path).ok().flatten();
         Creature {
             id: self.id.clone().unwrap_or_default(),
             name: self.name.clone(),
             kind: self.kind.clone(),
             habitat: self.habitat.clone(),
             description: self.description.text(),
-            danger: self.danger.clone(),
+            danger: self.danger.as_str().to_string(),
             home_location_id: self.home_location_id, // ✅ C.1: Direct usage
             archived: false,
+            image_path,
         }
     }
 }
@@ -66,6 +79,7 @@ pub struct LocationEditor {
     pub name: String,
     pub kind: String,
     pub description: text_editor::Content,
+    pub image_path: String,
 }
 
 impl LocationEditor {
@@ -76,6 +90,7 @@ impl LocationEditor {
             name: String::new(),
             kind: "Place".to_string(),
             description: text_editor::Content::new(),
+            image_path: String::new(),
         }
     }
 
@@ -86,6 +101,52 @@ impl LocationEditor {
             name: l.name.clone(),
             kind: l.kind.clone(),
             description: text_editor::Content::with_text(&l.description),
+            image_path: l.image_path.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Vacío => sin imagen. Si hay ruta: tiene que existir y ser un formato soportado.
+pub fn image_path_for_save(raw: &str) -> Result<Option<String>, String> {
+    let raw = raw.trim().trim_matches('"');
+    if raw.is_empty() {
+        return Ok(None);
+    }
+
+    let path = std::path::Path::new(raw);
+    if !path.is_file() {
+        return Err(format!("Image not found: {}", raw));
+    }
+    if !crate::ui::is_supported_image(path) {
+        return Err(format!("Unsupported image format (use {})", crate::ui::IMAGE_EXTENSIONS.join(", ")));
+    }
+    Ok(Some(raw.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct GlossaryEditor {
+    pub id: Option<String>,
+    pub term: String,
+    pub pronunciation: String,
+    pub definition: text_editor::Content,
+}
+
+impl GlossaryEditor {
+    pub fn create_new() -> Self {
+        Self {
+            id: None,
+            term: String::new(),
+            pronunciation: String::new(),
+            definition: text_editor::Content::new(),
+        }
+    }
+
+    pub fn from_term(g: &GlossaryTerm) -> Self {
+        Self {
+            id: Some(g.id.clone()),
+            term: g.term.clone(),
+            pronunciation: g.pronunciation.clone(),
+            definition: text_editor::Content::with_text(&g.definition),
         }
     }
 }
@@ -100,6 +161,7 @@ pub struct EventEditor {
     pub kind: String,
     pub color: String,
     pub location_id: Option<String>, // ✅ C.1: ID only instead of Option<Location>
+    pub scene_id: Option<String>,
     pub description: text_editor::Content,
 }
 
@@ -114,6 +176,7 @@ impl EventEditor {
             kind: "General".to_string(),
             color: "#A1A1AA".to_string(),
             location_id: None, // ✅ C.1
+            scene_id: None,
             description: text_editor::Content::new(),
         }
     }
@@ -129,6 +192,7 @@ impl EventEditor {
             kind: e.kind.clone(),
             color: e.color.clone(),
             location_id: e.location_id.clone(), // ✅ C.1: Direct copy
+            scene_id: e.scene_id.clone(),
             description: text_editor::Content::with_text(&e.description),
         }
     }
@@ -0,0 +1,44 @@
+// src/forge_annotations.rs
+// Anotaciones inline en escenas (The Forge): `[[note: fix this]]`.
+// Viven dentro del body (sin schema); acá solo las extraemos para el panel
+// "Notes in this scene" y para saltar al lugar exacto en el editor.
+
+const OPEN: &str = "[[note:";
+const CLOSE: &str = "]]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneAnnotation {
+    pub text: String,
+    pub line: usize,        // 1-based (solo display)
+    pub char_offset: usize, // offset en chars desde el inicio del body (para el salto)
+    pub char_len: usize,    // largo del marcador completo en chars (para seleccionarlo)
+}
+
+/// Extrae todas las anotaciones `[[note: ...]]` del body. Marcadores sin cerrar se ignoran.
+pub fn parse(body: &str) -> Vec<SceneAnnotation> {
+    let mut out = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(rel_start) = body[search_from..].find(OPEN) {
+        let start = search_from + rel_start;
+        let inner_start = start + OPEN.len();
+
+        let Some(rel_end) = body[inner_start..].find(CLOSE) else {
+            break;
+        };
+        let inner_end = inner_start + rel_end;
+        let end = inner_end + CLOSE.len();
+
+        let prefix = &body[..start];
+        out.push(SceneAnnotation {
+            text: body[inner_start..inner_end].trim().to_string(),
+            line: prefix.matches('\n').count() + 1,
+            char_offset: prefix.chars().count(),
+            char_len: body[start..end].chars().count(),
+        });
+
+        search_from = end;
+    }
+
+    out
+}
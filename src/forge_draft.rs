@@ -77,4 +77,73 @@ pub async fn read_draft(scene_id: &str) -> Result<Option<String>, String> {
 
     let text = String::from_utf8(bytes).map_err(|e| format!("Draft no es UTF-8 válido: {e}"))?;
     Ok(Some(text))
-}
\ No newline at end of file
+}
+pub async fn delete_draft(scene_id: &str) -> Result<(), String> {
+    let path = draft_path(scene_id);
+    match tokio::fs::remove_file(&path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("No pude borrar draft: {e}")),
+    }
+}
+
+// ============================================
+// Recovery al abrir el proyecto
+// ============================================
+
+/// Draft en disco más nuevo que lo guardado en DB (el autosave no llegó a persistirlo).
+#[derive(Debug, Clone)]
+pub struct RecoverableDraft {
+    pub scene: crate::model::Scene, // versión en DB
+    pub draft_body: String,
+    pub path: Option<crate::model::ForgePath>,
+}
+
+/// Compara cada draft con su escena en DB. Drafts de escenas de otros proyectos
+/// (el folder es global) se ignoran; los que ya están en DB se borran (housekeeping).
+pub async fn scan_recoverable(db: crate::db::Database) -> Result<Vec<RecoverableDraft>, String> {
+    let dir = drafts_dir();
+    let mut entries = match tokio::fs::read_dir(&dir).await {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("No pude leer folder de drafts: {e}")),
+    };
+
+    let mut out = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(scene_id) = name.strip_suffix(".draft.txt") else {
+            continue;
+        };
+
+        let Some(scene) = db.get_scene(scene_id.to_string()).await.map_err(|e| e.to_string())? else {
+            continue;
+        };
+
+        let Ok(Some(draft_body)) = read_draft(scene_id).await else {
+            continue;
+        };
+
+        // Draft viejo (la DB se guardó después, ej: split/merge/edición externa) o idéntico: sobra
+        let modified = entry
+            .metadata()
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(chrono::DateTime::<chrono::Utc>::from);
+        let is_newer = modified.map(|m| m > scene.updated_at).unwrap_or(false);
+
+        if draft_body == scene.body || !is_newer {
+            let _ = delete_draft(scene_id).await;
+            continue;
+        }
+
+        let path = db
+            .forge_path_for("scene".to_string(), scene.id.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+        out.push(RecoverableDraft { scene, draft_body, path });
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,99 @@
+// src/forge_external.rs
+// Edición externa de escenas (The Forge): exporta el body a un .txt temporal,
+// abre el editor por defecto del SO y permite re-importar cuando el archivo cambia.
+// Nota: NO esperamos al proceso del editor (puede no volver nunca); el controller
+// hace polling del mtime y corta la sesión por timeout.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+fn external_dir() -> PathBuf {
+    std::env::temp_dir().join("TAS").join("forge_external")
+}
+
+fn sanitize_scene_id(scene_id: &str) -> String {
+    // Mismo criterio que forge_draft: [a-zA-Z0-9-_], lo demás => '_'
+    scene_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub fn external_path(scene_id: &str) -> PathBuf {
+    external_dir().join(format!("{}.txt", sanitize_scene_id(scene_id)))
+}
+
+async fn modified_at(path: &Path) -> Result<SystemTime, String> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("No pude leer mtime del archivo externo: {e}"))
+}
+
+/// Escribe el body al archivo temporal y devuelve (path, mtime inicial).
+pub async fn export_scene(scene_id: &str, body: &str) -> Result<(PathBuf, SystemTime), String> {
+    let path = external_path(scene_id);
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("No pude crear folder de edición externa: {e}"))?;
+    }
+
+    tokio::fs::write(&path, body.as_bytes())
+        .await
+        .map_err(|e| format!("No pude escribir archivo externo: {e}"))?;
+
+    let mtime = modified_at(&path).await?;
+    Ok((path, mtime))
+}
+
+/// Lanza el editor por defecto del SO. No bloquea ni espera a que el editor cierre.
+pub fn launch_default_editor(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let result = std::process::Command::new("cmd")
+        .args(["/C", "start", ""])
+        .arg(path)
+        .spawn();
+
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open").arg("-t").arg(path).spawn();
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = std::process::Command::new("xdg-open").arg(path).spawn();
+
+    result
+        .map(|_child| ())
+        .map_err(|e| format!("No pude abrir el editor externo: {e}"))
+}
+
+/// Si el archivo cambió desde `since`, devuelve (body nuevo, mtime nuevo).
+pub async fn read_if_modified(
+    path: PathBuf,
+    since: SystemTime,
+) -> Result<Option<(String, SystemTime)>, String> {
+    let mtime = modified_at(&path).await?;
+    if mtime <= since {
+        return Ok(None);
+    }
+
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("No pude leer archivo externo: {e}"))?;
+
+    let text = String::from_utf8(bytes)
+        .map_err(|e| format!("Archivo externo no es UTF-8 válido: {e}"))?;
+
+    Ok(Some((text, mtime)))
+}
+
+/// Best-effort: borra el archivo temporal al terminar la sesión (archivo chico, sync OK).
+pub fn cleanup(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,79 @@
+// src/forge_find.rs
+// Find / replace del editor de escenas (The Forge). Solo texto plano:
+// el controller decide cómo reflejarlo en el text_editor (selección / Paste / rebuild).
+
+/// Match en el body: rango en bytes (para cortar) + offset/largo en chars (para el editor).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FindMatch {
+    pub start: usize,
+    pub end: usize,
+    pub char_offset: usize,
+    pub char_len: usize,
+}
+
+// Largo en bytes del match de `needle` al inicio de `hay` (None si no matchea).
+// Case-insensitive compara char a char (to_lowercase puede cambiar el largo en bytes).
+fn match_len(hay: &str, needle: &str, case_sensitive: bool) -> Option<usize> {
+    if case_sensitive {
+        return hay.starts_with(needle).then_some(needle.len());
+    }
+
+    let mut hay_chars = hay.char_indices();
+    for n in needle.chars() {
+        let (_, h) = hay_chars.next()?;
+        if !h.to_lowercase().eq(n.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(hay_chars.next().map(|(i, _)| i).unwrap_or(hay.len()))
+}
+
+/// Todos los matches sin solaparse, en orden.
+pub fn find_matches(body: &str, needle: &str, case_sensitive: bool) -> Vec<FindMatch> {
+    let mut out = Vec::new();
+    if needle.is_empty() {
+        return out;
+    }
+
+    let mut skip_until = 0usize;
+
+    for (chars_seen, (i, _)) in body.char_indices().enumerate() {
+        if i >= skip_until
+            && let Some(len) = match_len(&body[i..], needle, case_sensitive)
+        {
+            let char_len = body[i..i + len].chars().count();
+            out.push(FindMatch { start: i, end: i + len, char_offset: chars_seen, char_len });
+            skip_until = i + len;
+        }
+    }
+    out
+}
+
+/// Reemplaza todos los matches (ya calculados sobre `body`).
+pub fn replace_all(body: &str, matches: &[FindMatch], replacement: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut last = 0usize;
+    for m in matches {
+        out.push_str(&body[last..m.start]);
+        out.push_str(replacement);
+        last = m.end;
+    }
+    out.push_str(&body[last..]);
+    out
+}
+
+/// Corrige un offset en chars tras replace_all: los matches previos corren el cursor;
+/// si el cursor caía dentro de un match, queda al inicio del reemplazo.
+pub fn shift_char_offset(offset: usize, matches: &[FindMatch], replacement_chars: usize) -> usize {
+    let mut shifted = offset as i64;
+    for m in matches {
+        if m.char_offset >= offset {
+            break;
+        }
+        if offset < m.char_offset + m.char_len {
+            return (m.char_offset as i64 + (shifted - offset as i64)).max(0) as usize;
+        }
+        shifted += replacement_chars as i64 - m.char_len as i64;
+    }
+    shifted.max(0) as usize
+}
@@ -0,0 +1,84 @@
+// src/forge_history.rs
+// Undo / redo del body de la escena activa (The Forge).
+// Snapshots de texto completo tomados al inicio de cada ráfaga de edición
+// (la ráfaga se cierra en el debounce del autosave), no por tecla.
+
+use std::collections::VecDeque;
+
+/// Máximo de snapshots de undo por escena (ring buffer: se descarta el más viejo).
+pub const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Default)]
+pub struct SceneHistory {
+    scene_id: Option<String>,
+    undo: VecDeque<String>,
+    redo: Vec<String>,
+    // true = ya tomamos snapshot de esta ráfaga (hasta el próximo debounce)
+    burst_open: bool,
+}
+
+impl SceneHistory {
+    /// Historial fresco si cambió la escena activa.
+    pub fn ensure_scene(&mut self, scene_id: &str) {
+        if self.scene_id.as_deref() != Some(scene_id) {
+            self.reset(Some(scene_id.to_string()));
+        }
+    }
+
+    pub fn reset(&mut self, scene_id: Option<String>) {
+        self.scene_id = scene_id;
+        self.undo.clear();
+        self.redo.clear();
+        self.burst_open = false;
+    }
+
+    /// Antes de una edición: guarda el texto previo si es el inicio de una ráfaga.
+    pub fn before_edit(&mut self, current: &str) {
+        if self.burst_open {
+            return;
+        }
+        self.burst_open = true;
+        self.redo.clear();
+        if self.undo.back().map(|s| s.as_str()) != Some(current) {
+            push_bounded(&mut self.undo, current.to_string());
+        }
+    }
+
+    /// Límite de ráfaga (debounce del autosave): la próxima edición toma snapshot nuevo.
+    pub fn close_burst(&mut self) {
+        self.burst_open = false;
+    }
+
+    /// Devuelve el texto a restaurar (None si no hay nada que deshacer).
+    pub fn undo(&mut self, current: &str) -> Option<String> {
+        // Snapshots iguales al texto actual no deshacen nada visible: saltarlos
+        let prev = loop {
+            let s = self.undo.pop_back()?;
+            if s != current {
+                break s;
+            }
+        };
+        self.redo.push(current.to_string());
+        self.burst_open = false;
+        Some(prev)
+    }
+
+    pub fn redo(&mut self, current: &str) -> Option<String> {
+        let next = self.redo.pop()?;
+        push_bounded(&mut self.undo, current.to_string());
+        self.burst_open = false;
+        Some(next)
+    }
+}
+
+fn push_bounded(buf: &mut VecDeque<String>, s: String) {
+    buf.push_back(s);
+    while buf.len() > MAX_ENTRIES {
+        buf.pop_front();
+    }
+}
+
+/// Chars en común al inicio: ahí empieza el cambio (para ubicar el cursor tras undo/redo).
+pub fn common_prefix_chars(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count()
+}
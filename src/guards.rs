@@ -7,7 +7,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 /// Capabilities disponibles en un proyecto TAS
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Capabilities {
     pub universes: bool,
     pub bestiary: bool,
@@ -20,6 +20,18 @@ pub struct Capabilities {
 }
 
 impl Capabilities {
+    /// Toggles de Settings: (key interna, label)
+    pub const TOGGLES: [(&'static str, &'static str); 8] = [
+        ("universes", "Universes"),
+        ("bestiary", "Bestiary"),
+        ("locations", "Locations"),
+        ("timeline", "Timeline"),
+        ("forge", "The Forge"),
+        ("boards", "Boards"),
+        ("snapshots", "Snapshots"),
+        ("trash", "Trash"),
+    ];
+
     /// Crea capabilities con todo habilitado (default seguro)
     pub fn all_enabled() -> Self {
         Self {
@@ -34,6 +46,24 @@ impl Capabilities {
         }
     }
 
+    /// Keys internas habilitadas (formato NUEVO de enabled_capabilities_json)
+    pub fn enabled_keys(&self) -> Vec<String> {
+        [
+            ("universes", self.universes),
+            ("bestiary", self.bestiary),
+            ("locations", self.locations),
+            ("timeline", self.timeline),
+            ("boards", self.boards),
+            ("forge", self.forge),
+            ("snapshots", self.snapshots),
+            ("trash", self.trash),
+        ]
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(k, _)| k.to_string())
+            .collect()
+    }
+
     /// Verifica si una capability está habilitada
     pub fn is_enabled(&self, capability: &str) -> bool {
         // Acepta aliases canónicos (canon v2) sin tocar el JSON almacenado en db_meta.
@@ -62,6 +92,29 @@ impl Capabilities {
             _ => false,
         }
     }
+
+    /// Prende/apaga una capability (mismos aliases que `is_enabled`). false = key desconocida.
+    pub fn set(&mut self, capability: &str, enabled: bool) -> bool {
+        let cap = capability.trim().to_ascii_lowercase();
+
+        match cap.as_str() {
+            "novel" | "the_forge" | "forge" => self.forge = enabled,
+            "pm" | "project_management" | "boards" => self.boards = enabled,
+            "worldbuilding" | "world_building" | "world-building" => {
+                self.universes = enabled;
+                self.bestiary = enabled;
+                self.locations = enabled;
+            }
+            "universes" | "universe" => self.universes = enabled,
+            "bestiary" | "creatures" => self.bestiary = enabled,
+            "locations" | "location" => self.locations = enabled,
+            "timeline" => self.timeline = enabled,
+            "trash" => self.trash = enabled,
+            "snapshots" => self.snapshots = enabled,
+            _ => return false,
+        }
+        true
+    }
 }
 
 /// Contenedor thread-safe para capabilities
@@ -0,0 +1,245 @@
+// src/keymap.rs
+// Atajos de teclado: toda la tabla vive acá.
+// 1) `shortcut_for`: tecla -> intención (puro, no mira el estado)
+// 2) `resolve`: intención -> Message según la ruta / lo que esté abierto
+// La suscripción (ui_controller) emite Message::Shortcut; input_controller lo resuelve.
+//
+// | Tecla                   | Dónde                          | Acción                                  |
+// |-------------------------|--------------------------------|-----------------------------------------|
+// | Ctrl+N                  | Forge / Bestiary / Locations / | Nuevo novel / criatura / location /     |
+// |                         | Glossary / Timeline / Board    | término / evento / card                 |
+// | Ctrl+S                  | editor abierto / Forge         | Guardar ya                              |
+// | Del                     | item seleccionado / en editor  | Confirmación de borrado                 |
+// | Ctrl+Z, Ctrl+Y          | todas (Forge: body primero)    | Undo / redo                             |
+// | Ctrl+P                  | todas                          | Paleta de comandos (↑ ↓ Enter, Esc)     |
+// | Alt+←, Alt+→            | todas                          | Historial back / forward                |
+// | Enter, Esc              | modal de confirmación          | Confirmar / cancelar                    |
+// | Ctrl+F, Ctrl+Enter      | Forge                          | Find bar / nueva escena                 |
+// | F11 (Esc sale), F12     | Forge                          | Focus mode / jump to definition         |
+// |                         |                                | (Esc sale aun con el editor con foco)   |
+// | → + / ← -               | Forge (outline)                | Expandir / colapsar nodo activo         |
+//
+// Inputs con foco: las teclas que el widget consume (Del, Enter, flechas) llegan "Captured"
+// y no disparan nada; durante un rename inline o con un editor abierto Ctrl+N / Del / undo
+// global quedan apagados para no pisar lo que se está escribiendo.
+
+use iced::keyboard::{self, key::Named, Key, Modifiers};
+
+use crate::app::{AppState, Message, PmState, Route};
+use crate::messages::{BestiaryMessage, GlossaryMessage, LocationsMessage, PmMessage, TheForgeMessage, TimelineMessage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    NewItem,
+    Save,
+    Delete,
+    Undo,
+    Redo,
+    NavBack,
+    NavForward,
+    Confirm,
+    Cancel,
+    EscapeCaptured, // Esc que ya usó un widget: solo sale de focus mode / cierra la paleta
+    Find,
+    NewScene,
+    LookupWord,
+    FocusMode,
+    Expand(bool), // true = expandir
+    Palette,
+    Up,
+    Down,
+}
+
+/// Tecla -> atajo. `captured` = un widget (input / editor) ya usó la tecla.
+pub fn shortcut_for(key: Key<&str>, modifiers: Modifiers, captured: bool) -> Option<Shortcut> {
+    // Teclas que el editor no consume: valen con foco en cualquier lado
+    match key {
+        Key::Named(Named::F12) => return Some(Shortcut::LookupWord),
+        Key::Named(Named::F11) => return Some(Shortcut::FocusMode),
+        Key::Named(Named::Escape) => return Some(if captured { Shortcut::EscapeCaptured } else { Shortcut::Cancel }),
+        _ => {}
+    }
+
+    if modifiers.command() {
+        return match key {
+            Key::Character("z") | Key::Character("Z") => {
+                Some(if modifiers.shift() { Shortcut::Redo } else { Shortcut::Undo })
+            }
+            Key::Character("y") | Key::Character("Y") => Some(Shortcut::Redo),
+            Key::Character("s") | Key::Character("S") => Some(Shortcut::Save),
+            Key::Character("n") | Key::Character("N") => Some(Shortcut::NewItem),
+            Key::Character("f") => Some(Shortcut::Find),
+            Key::Character("p") | Key::Character("P") => Some(Shortcut::Palette),
+            Key::Named(Named::Enter) => Some(Shortcut::NewScene),
+            _ => None,
+        };
+    }
+
+    if captured {
+        return None;
+    }
+
+    if modifiers.alt() {
+        return match key {
+            Key::Named(Named::ArrowLeft) => Some(Shortcut::NavBack),
+            Key::Named(Named::ArrowRight) => Some(Shortcut::NavForward),
+            _ => None,
+        };
+    }
+
+    match key {
+        Key::Named(Named::Enter) => Some(Shortcut::Confirm),
+        Key::Named(Named::Delete) => Some(Shortcut::Delete),
+        Key::Named(Named::ArrowUp) => Some(Shortcut::Up),
+        Key::Named(Named::ArrowDown) => Some(Shortcut::Down),
+        Key::Named(Named::ArrowRight) | Key::Character("+") | Key::Character("=") => Some(Shortcut::Expand(true)),
+        Key::Named(Named::ArrowLeft) | Key::Character("-") => Some(Shortcut::Expand(false)),
+        _ => None,
+    }
+}
+
+/// Para la suscripción (fn pointer: no puede capturar estado)
+pub fn from_event(event: &keyboard::Event, captured: bool) -> Option<Shortcut> {
+    match event {
+        keyboard::Event::KeyPressed { key, modifiers, .. } => shortcut_for(key.as_ref(), *modifiers, captured),
+        _ => None,
+    }
+}
+
+/// Atajo -> Message según dónde está el usuario. None = no aplica acá.
+pub fn resolve(state: &AppState, shortcut: Shortcut) -> Option<Message> {
+    let renaming = state.forge_renaming_novel_id.is_some()
+        || state.forge_renaming_chapter_id.is_some()
+        || state.forge_renaming_scene_id.is_some();
+
+    // Modal de confirmación: solo Enter / Esc (el rename inline se queda con el teclado)
+    if state.pending_confirm.is_some() {
+        return match shortcut {
+            _ if renaming => None,
+            Shortcut::Confirm => Some(Message::ConfirmDelete),
+            Shortcut::Cancel => Some(Message::CancelConfirm),
+            _ => None,
+        };
+    }
+
+    state.active_project.as_ref()?;
+
+    // Paleta abierta: el teclado es suyo (Enter lo toma el on_submit del input; Esc llega
+    // capturado por el input con foco y también la cierra)
+    if state.command_palette.is_some() {
+        return match shortcut {
+            Shortcut::Palette | Shortcut::Cancel | Shortcut::EscapeCaptured => Some(Message::PaletteClose),
+            Shortcut::Up => Some(Message::PaletteMove(-1)),
+            Shortcut::Down => Some(Message::PaletteMove(1)),
+            _ => None,
+        };
+    }
+
+    let forge = matches!(state.route, Route::Forge);
+    let forge_msg = |m: TheForgeMessage| Some(Message::TheForge(m));
+
+    match shortcut {
+        Shortcut::Palette if !renaming => Some(Message::PaletteToggle),
+        Shortcut::NavBack => Some(Message::NavBack),
+        Shortcut::NavForward => Some(Message::NavForward),
+
+        // Forge: body primero (sin historial cae al undo global)
+        Shortcut::Undo if forge => forge_msg(TheForgeMessage::UndoSceneEdit),
+        Shortcut::Redo if forge => forge_msg(TheForgeMessage::RedoSceneEdit),
+        Shortcut::Undo if !renaming && !editor_open(state) => Some(Message::Undo),
+        Shortcut::Redo if !renaming && !editor_open(state) => Some(Message::Redo),
+
+        Shortcut::LookupWord if forge => forge_msg(TheForgeMessage::LookupWordAtCursor),
+        Shortcut::FocusMode if forge => forge_msg(TheForgeMessage::ToggleFocusMode),
+        Shortcut::Cancel | Shortcut::EscapeCaptured if forge && state.forge_focus_mode => {
+            forge_msg(TheForgeMessage::ToggleFocusMode)
+        }
+        Shortcut::NewScene if forge => forge_msg(TheForgeMessage::CreateSceneInActiveChapter),
+        Shortcut::Find if forge => forge_msg(TheForgeMessage::ToggleFindBar),
+        Shortcut::Expand(expand) if forge => forge_msg(TheForgeMessage::SetActiveNodeExpanded(expand)),
+
+        Shortcut::Save => save_message(state),
+        Shortcut::NewItem if !renaming && !editor_open(state) => new_item_message(state),
+        Shortcut::Delete if !renaming => delete_message(state),
+
+        _ => None,
+    }
+}
+
+// Algún modal de edición abierto (lo que se está escribiendo no se pisa)
+fn editor_open(state: &AppState) -> bool {
+    state.creature_editor.is_some()
+        || state.location_editor.is_some()
+        || state.glossary_editor.is_some()
+        || state.event_editor.is_some()
+        || state.era_editor.is_some()
+        || matches!(state.pm_state, PmState::Editing { .. })
+}
+
+fn new_item_message(state: &AppState) -> Option<Message> {
+    match &state.route {
+        Route::Forge => Some(Message::TheForge(TheForgeMessage::CreateNovel)),
+        Route::Bestiary { .. } => Some(Message::Bestiary(BestiaryMessage::EditorOpenCreate)),
+        Route::Locations { .. } => Some(Message::Locations(LocationsMessage::EditorOpenCreate(None))),
+        Route::Glossary { .. } => Some(Message::Glossary(GlossaryMessage::EditorOpenCreate)),
+        Route::Timeline { .. } => Some(Message::Timeline(TimelineMessage::EditorOpenCreateEvent(None))),
+        Route::PmBoard { .. } => Some(Message::Pm(PmMessage::OpenGlobalCreate)),
+        _ => None,
+    }
+}
+
+fn save_message(state: &AppState) -> Option<Message> {
+    match &state.route {
+        Route::Forge => Some(Message::TheForge(TheForgeMessage::SaveNow)),
+        Route::Bestiary { .. } if state.creature_editor.is_some() => Some(Message::Bestiary(BestiaryMessage::EditorSave)),
+        Route::Locations { .. } if state.location_editor.is_some() => Some(Message::Locations(LocationsMessage::EditorSave)),
+        Route::Glossary { .. } if state.glossary_editor.is_some() => Some(Message::Glossary(GlossaryMessage::EditorSave)),
+        Route::Timeline { .. } if state.event_editor.is_some() => Some(Message::Timeline(TimelineMessage::EditorSaveEvent)),
+        Route::Timeline { .. } if state.era_editor.is_some() => Some(Message::Timeline(TimelineMessage::EditorSaveEra)),
+        Route::PmBoard { .. } if matches!(state.pm_state, PmState::Editing { .. }) => Some(Message::Pm(PmMessage::Save)),
+        _ => None,
+    }
+}
+
+// Solo donde ya hay confirmación de borrado (cards se borran sin modal: no van por teclado)
+fn delete_message(state: &AppState) -> Option<Message> {
+    match &state.route {
+        Route::Forge => {
+            if let Some(id) = &state.active_scene_id {
+                Some(Message::TheForge(TheForgeMessage::DeleteScene(id.clone())))
+            } else if let Some(id) = &state.active_chapter_id {
+                Some(Message::TheForge(TheForgeMessage::DeleteChapter(id.clone())))
+            } else {
+                state.active_novel_id.clone().map(|id| Message::TheForge(TheForgeMessage::DeleteNovel(id)))
+            }
+        }
+        Route::Bestiary { .. } => state
+            .creature_editor
+            .as_ref()
+            .and_then(|e| e.id.clone())
+            .map(|id| Message::Bestiary(BestiaryMessage::Delete(id))),
+        Route::Locations { .. } => state
+            .location_editor
+            .as_ref()
+            .and_then(|e| e.id.clone())
+            .or_else(|| state.selected_location.clone())
+            .map(|id| Message::Locations(LocationsMessage::Delete(id))),
+        Route::Glossary { .. } => state
+            .glossary_editor
+            .as_ref()
+            .and_then(|e| e.id.clone())
+            .map(|id| Message::Glossary(GlossaryMessage::Delete(id))),
+        Route::Timeline { .. } => {
+            if let Some(id) = state.event_editor.as_ref().and_then(|e| e.id.clone()) {
+                Some(Message::Timeline(TimelineMessage::DeleteEvent(id)))
+            } else {
+                state
+                    .era_editor
+                    .as_ref()
+                    .and_then(|e| e.id.clone())
+                    .map(|id| Message::Timeline(TimelineMessage::DeleteEra(id)))
+            }
+        }
+        _ => None,
+    }
+}
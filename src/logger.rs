@@ -1,11 +1,21 @@
 use chrono::Local;
 use directories::ProjectDirs;
-use std::fs::OpenOptions;
+use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
-static LOG_FILE: OnceLock<Mutex<std::fs::File>> = OnceLock::new();
+/// Tamaño a partir del cual se rota tas.log -> tas.1.log -> ... (se conservan MAX_ROTATED)
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_ROTATED: usize = 3;
+
+struct LogSink {
+    file: File,
+    path: PathBuf,
+    written: u64,
+}
+
+static LOG_FILE: OnceLock<Mutex<LogSink>> = OnceLock::new();
 
 fn data_dir() -> Option<PathBuf> {
     ProjectDirs::from("com", "TitanArchitects", "TAS").map(|p| p.data_dir().to_path_buf())
@@ -15,23 +25,47 @@ fn log_path() -> Option<PathBuf> {
     data_dir().map(|d| d.join("tas.log"))
 }
 
+/// tas.log -> tas.1.log, tas.N.log (N = índice de rotación)
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    path.with_extension(format!("{n}.log"))
+}
+
+/// Corre la cadena: el más viejo se descarta, tas.log queda libre.
+fn rotate_files(path: &Path) {
+    let _ = std::fs::remove_file(rotated_path(path, MAX_ROTATED));
+    for n in (1..MAX_ROTATED).rev() {
+        let _ = std::fs::rename(rotated_path(path, n), rotated_path(path, n + 1));
+    }
+    let _ = std::fs::rename(path, rotated_path(path, 1));
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
 /// Initialize file logging.
 ///
 /// Works even when running with `#![windows_subsystem="windows"]` where stdout/stderr are invisible.
+/// The file rotates at `MAX_LOG_BYTES` (on startup and while running).
 pub fn init() -> Result<(), String> {
     let Some(dir) = data_dir() else { return Err("No data dir (ProjectDirs)".to_string()); };
     std::fs::create_dir_all(&dir).map_err(|e| format!("create_dir_all failed: {e}"))?;
 
     let Some(path) = log_path() else { return Err("No log path".to_string()); };
 
-    let file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&path)
-        .map_err(|e| format!("open log file failed ({path:?}): {e}"))?;
+    // Arranque con un log ya grande: rotar antes de abrir
+    let existing = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let written = if existing >= MAX_LOG_BYTES {
+        rotate_files(&path);
+        0
+    } else {
+        existing
+    };
+
+    let file = open_append(&path).map_err(|e| format!("open log file failed ({path:?}): {e}"))?;
 
     LOG_FILE
-        .set(Mutex::new(file))
+        .set(Mutex::new(LogSink { file, path: path.clone(), written }))
         .map_err(|_| "logger already initialized".to_string())?;
 
     info(&format!("--- TAS log started at {} ---", Local::now().format("%Y-%m-%d %H:%M:%S")));
@@ -43,11 +77,27 @@ fn write_line(level: &str, msg: &str) {
     let ts = Local::now().format("%Y-%m-%d %H:%M:%S");
     let line = format!("[{ts}] [{level}] {msg}\n");
 
-    if let Some(lock) = LOG_FILE.get() {
-        if let Ok(mut file) = lock.lock() {
-            let _ = file.write_all(line.as_bytes());
-            let _ = file.flush();
+    // Build de consola / debug: además a stderr (en release sin consola no se vería)
+    if cfg!(any(feature = "console", debug_assertions)) {
+        eprint!("{line}");
+    }
+
+    if let Some(lock) = LOG_FILE.get()
+        && let Ok(mut sink) = lock.lock()
+    {
+        if sink.written + line.len() as u64 > MAX_LOG_BYTES {
+            rotate_files(&sink.path);
+            // Si no se puede reabrir, seguimos escribiendo en el handle viejo (renombrado)
+            if let Ok(file) = open_append(&sink.path) {
+                sink.file = file;
+                sink.written = 0;
+            }
+        }
+
+        if sink.file.write_all(line.as_bytes()).is_ok() {
+            sink.written += line.len() as u64;
         }
+        let _ = sink.file.flush();
     }
 }
 
@@ -1,4 +1,6 @@
-#![windows_subsystem = "windows"]
+// Release normal: sin consola en Windows (el log va a archivo, ver `logger`).
+// Debug con consola: `cargo run --features console`.
+#![cfg_attr(not(feature = "console"), windows_subsystem = "windows")]
 
 mod app;
 pub mod db;
@@ -13,10 +15,34 @@ mod messages;
 mod state;
 mod editors;
 mod guards;
+mod app_settings;
+mod keymap;
+mod command_palette;
 
 // ✅ Draft Recovery (Forge) - módulo a nivel de crate root
 mod forge_draft;
 
+// ✅ Edición externa de escenas (Forge)
+mod forge_external;
+
+// ✅ Anotaciones inline `[[note: ...]]` (Forge)
+mod forge_annotations;
+
+// ✅ Find / replace del editor de escenas (Forge)
+mod forge_find;
+
+// ✅ Undo / redo del body de la escena activa (Forge)
+mod forge_history;
+
+// ✅ Export del timeline como imagen (PNG)
+mod timeline_export;
+
+// ✅ Banda cronológica con zoom (layout horizontal del timeline)
+mod timeline_layout;
+
+// ✅ Undo / redo global de acciones de DB (renames, mover, reordenar, borrar)
+mod undo_stack;
+
 pub fn main() -> iced::Result {
     controllers::ui_controller::run()
 }
@@ -1,12 +1,15 @@
 use iced::widget::text_editor;
 use crate::app::{Route, PmId};
-use crate::model::{Creature, Universe, Card, KanbanBoardData, Board, Location, TimelineEvent, TimelineEra, Project, UniverseSnapshot, Novel, Chapter, Scene, TrashEntry};
-use crate::state::DemoResetScope;
+use crate::model::{Creature, Universe, Card, KanbanBoardData, Board, Location, GlossaryTerm, TimelineEvent, TimelineEra, Project, UniverseSnapshot, Novel, Chapter, Scene, SceneMention, TrashEntry, DangerLevel, ReplaceScope, TextMatch};
+use crate::state::{DemoResetScope, ForgeDragItem};
 
 #[derive(Debug, Clone)]
 pub enum PmMessage {
     BoardNameChanged(String),
+    BoardTemplatePicked(Option<String>), // None = columnas default
     CreateBoard,
+    CreateBoardNamed(String), // paleta de comandos: nombre ya escrito
+    SaveBoardAsTemplate,
     DeleteBoard(String),
     OpenBoard(String),
 
@@ -19,22 +22,48 @@ pub enum PmMessage {
     DragStart(PmId),
     ColumnHovered(PmId),
     CardHovered(PmId),
+    ColumnDragStart(PmId),
+    ColumnDragEnd,
+    MoveColumn(String, i32), // (column_id, -1 = izquierda / +1 = derecha)
+
+    // View-only: columnas colapsadas (tira angosta)
+    ToggleColumnCollapsed(String), // column_id
 
     OpenGlobalCreate,
     TitleChanged(String),
     DescChanged(text_editor::Action),
     PriorityChanged(String),
+    LabelsChanged(String),
+    DueDateChanged(String),
+    LabelFilterChanged(Option<String>), // None = todas (solo vista)
+    SearchChanged(String),              // vacío = todas (solo vista, sin refetch)
     Save,
     Delete,
     Cancel,
+    MoveCardToBoard { card_id: String, target_board_id: String },
+
+    // Archivo: sale del board activo pero queda en DB
+    Archive, // card del editor abierto
+    ToggleArchivedPanel,
+    RestoreCard(String), // card_id
 }
 
 #[derive(Debug, Clone)]
 pub enum BestiaryMessage {
     Open(String), CardClicked(usize), EditorOpenCreate, EditorCancel, EditorSave,
     NameChanged(String), KindChanged(String), HabitatChanged(String),
-    DescriptionChanged(text_editor::Action), DangerChanged(String), LocationChanged(Option<String>), // ✅ C.1: ID instead of full struct
+    DescriptionChanged(text_editor::Action), DangerChanged(DangerLevel), LocationChanged(Option<String>), ImagePathChanged(String), // ✅ C.1: ID instead of full struct
     Delete(String), Archive(String), Restore(String),
+    DangerFilterToggled(DangerLevel), DangerFilterCleared, // leyenda/filtro del grid
+    SearchChanged(String), KindFilterChanged(Option<String>), // búsqueda + kind (solo vista)
+    SortChanged(crate::model::CreatureSort),
+    SelectionToggled(String), SelectionCleared, ArchiveSelected, RestoreSelected, // lote
+    // Relaciones (editor de una criatura existente): se guardan al instante
+    RelationTargetChanged(String), RelationKindChanged(crate::model::CreatureRelationKind),
+    RelationAdd, RelationRemove(String), // relation_id
+    ExportFormatChanged(crate::db::bestiary_export::BestiaryExportFormat),
+    ExportIncludeArchivedToggled(bool),
+    Export, // exports/<proyecto>/bestiary-*.csv|json
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +73,8 @@ pub enum UniverseMessage {
     Create,
     Delete(String),
     Open(String),
+    Duplicate(String),
+    ToggleCloneNovels,
 
     InjectDemoData(String),
     ResetDemoPrompt(String, DemoResetScope),
@@ -55,8 +86,19 @@ pub enum UniverseMessage {
     SnapshotRefresh(String), // ✅ usado por universe_controller.rs
     SnapshotRestore(String),
     SnapshotDelete(String),
+    SnapshotClicked(String), // doble click => rename inline
+    SnapshotRenameChanged(String),
+    SnapshotRenameCommit,
 
     ValidateUniverse(String),
+    RepairForeignKeys,
+
+    // --- FIND & REPLACE ---
+    ReplaceFindChanged(String),
+    ReplaceWithChanged(String),
+    ReplaceToggleScope(ReplaceScope),
+    ReplacePreview(String), // universe_id
+    ReplaceApply(String),   // universe_id
 }
 
 #[derive(Debug, Clone)]
@@ -74,11 +116,32 @@ pub enum LocationsMessage {
     NameChanged(String),
     KindChanged(String),
     DescriptionChanged(text_editor::Action),
+    ImagePathChanged(String),
+    ParentChanged(Option<String>), // reparent (None = raíz)
 
     ToggleExpand(String),
+    ExpandAll, CollapseAll, // solo estado de UI
     Select(String),
 }
 
+#[derive(Debug, Clone)]
+pub enum GlossaryMessage {
+    Open(String), // universe_id
+    EditorOpenCreate,
+    Edit(String), // term id
+
+    EditorCancel,
+    EditorSave,
+    Delete(String),
+
+    TermChanged(String),
+    PronunciationChanged(String),
+    DefinitionChanged(text_editor::Action),
+
+    SearchChanged(String),
+    ToggleSortOrder,
+}
+
 #[derive(Debug, Clone)]
 pub enum TimelineMessage {
     Open(String), // ✅ usado por timeline_controller.rs
@@ -107,6 +170,7 @@ pub enum TimelineMessage {
     KindChanged(String),
     ColorChanged(String),
     LocationChanged(Option<String>),
+    SceneChanged(Option<String>), // escena donde se narra el evento
     DescriptionChanged(text_editor::Action),
 
     EraNameChanged(String),
@@ -114,6 +178,20 @@ pub enum TimelineMessage {
     EraEndChanged(String),
     EraColorChanged(String),
     EraDescChanged(text_editor::Action),
+
+    // Banda cronológica: zoom (px por año)
+    ZoomIn, ZoomOut, ZoomReset,
+    CtrlHeld(bool),
+    StripScrolled(f32), // delta vertical de la rueda; solo zoomea con Ctrl
+
+    // Filtros de vista (None = "All")
+    EraFilterChanged(Option<String>),
+    ImportanceFilterChanged(Option<String>),
+    ClearFilters,
+
+    // Export de datos (CSV / JSON) -> exports/<proyecto>/
+    ExportFormatChanged(crate::db::timeline_data_export::TimelineExportFormat),
+    ExportData,
 }
 
 #[derive(Debug, Clone)]
@@ -123,11 +201,78 @@ pub enum WorkspaceMessage {
     Delete(String),
 }
 
+#[derive(Debug, Clone)]
+pub enum SettingsMessage {
+    AutoBackupToggled(bool),
+    AutoBackupIntervalChanged(String),
+    AutoBackupKeepChanged(String),
+
+    // Snapshots automáticos antes de operaciones destructivas
+    AutoSnapshotToggled(bool),
+    AutoSnapshotKeepChanged(String),
+
+    // Recordatorio de pausas
+    BreakReminderToggled(bool),
+    BreakReminderIntervalChanged(String),
+
+    // Export/Import de configuración (capabilities + settings)
+    ExportProjectSettings,
+    ProjectSettingsExported(Result<String, String>), // path del JSON
+    ImportPathChanged(String),
+    ImportProjectSettings,
+    ProjectSettingsImported(Result<crate::app_settings::AppSettings, String>),
+
+    // Retención de papelera del proyecto (db_meta)
+    TrashRetentionChanged(crate::db::trash::TrashRetention),
+
+    // Tema de la UI (db_meta, se aplica al instante)
+    ThemeChanged(crate::ui::ThemeMode),
+
+    // Capabilities del proyecto (key interna, prendida)
+    CapabilityToggled(String, bool),
+
+    // Backup / restore completo de la DB (path vacío => backups/ del proyecto)
+    DbBackupPathChanged(String),
+    BackupDatabase,
+    DatabaseBackedUp(Result<String, String>), // path del .db
+    RestoreDatabase,
+    DatabaseRestoreStaged(Result<String, String>),
+
+    // Archivo JSON del proyecto completo (contenido, no preferencias)
+    ExportProjectArchive,
+    ArchivePathChanged(String),
+    ImportProjectArchive,
+
+    // Visor de audit log: filtros (vacío = sin restricción) + export CSV
+    AuditFromChanged(String),
+    AuditToChanged(String),
+    AuditActionChanged(String),
+    AuditEntityChanged(String),
+    AuditApply,
+    AuditFetched(Result<Vec<crate::model::AuditLogEntry>, String>),
+    AuditExport,
+
+    // Sidebar: orden (drag) + visibilidad
+    SidebarDragStart(usize),
+    SidebarDragOver(usize),
+    SidebarDragEnd,
+    SidebarVisibilityToggled(crate::app_settings::SidebarSection, bool),
+    SidebarReset,
+
+    // Meta de palabras del proyecto activo
+    ProjectWordGoalChanged(String),
+}
+
 #[derive(Debug, Clone)]
 pub enum TheForgeMessage {
     // --- NAVIGATION ---
     Open(Option<String>),        // universe_id opcional
     UniverseChanged(String),
+    // Quick-create de universo desde el picker de la Forge (solo nombre)
+    StartQuickUniverse,
+    QuickUniverseNameChanged(String),
+    QuickUniverseCreate,
+    QuickUniverseCancel,
 
     // --- NOVEL ACTIONS ---
     CreateNovel,
@@ -135,21 +280,70 @@ pub enum TheForgeMessage {
     SelectNovel(String),
     NovelTitleChanged(String),
 
+    ExpandAllOutline,   // novels + chapters (pide la data que falte)
+    CollapseAllOutline, // solo visual: no toca active_*
+
     // --- CHAPTER ACTIONS ---
     CreateChapter(String),       // novel_id
     DeleteChapter(String),       // chapter_id
+    DuplicateChapter(String),    // chapter_id: copia con sus scenes, justo después del original
     SelectChapter(String),
     ChapterTitleChanged(String),
 
     // --- SCENE ACTIONS ---
     CreateScene(String),         // chapter_id
+    CreateSceneInActiveChapter,  // Ctrl+Enter
+    SaveNow,                     // Ctrl+S: persiste la escena activa sin esperar el debounce
     DeleteScene(String),         // scene_id
     SelectScene(String),
     SceneTitleChanged(String),
     SceneBodyChanged(text_editor::Action),
+    SceneNotesChanged(text_editor::Action), // notas de la escena activa (no cuentan palabras)
+    ToggleSceneNotes,
+    ToggleSceneRevisions,
+    PreviewSceneRevision(String), // revision_id
+    RestoreSceneRevision(String), // revision_id -> body al editor (pasa por autosave)
+    UndoSceneEdit, // Ctrl+Z
+    SplitSceneAtCursor, // escena activa: el texto bajo el cursor pasa a una escena nueva
+    MergeSceneUp(String), // scene_id: se une al final de la escena anterior del chapter
+    MoveChapterUp(String),   // ▲/▼ del outline: swap con el chapter vecino
+    MoveChapterDown(String),
+    MoveSceneUp(String),     // ▲/▼ del outline: swap con la escena vecina
+    MoveSceneDown(String),
+    RedoSceneEdit, // Ctrl+Y / Ctrl+Shift+Z
+    SceneKindChanged(crate::model::SceneKind), // escena activa
+    SceneIncludeInCompileToggled(bool),        // escena activa
+    CycleSceneStatus(String),                  // scene_id: draft → revised → final → draft
+    SceneTargetChanged(String),                // escena activa (meta de palabras, "" = sin meta)
+    ChapterTargetChanged(String),              // chapter de la escena activa
+    JumpToAnnotation { char_offset: usize, char_len: usize },
+    JumpToNovel { universe_id: Option<String>, novel_id: String }, // paleta de comandos
+    JumpToScene {
+        universe_id: Option<String>,
+        novel_id: String,
+        chapter_id: String,
+        scene_id: String,
+    },
+    ExportNovelJson(String), // novel_id -> exports/<proyecto>/novel-*.json
+    ExportNovelMarkdown(String), // novel_id -> exports/<proyecto>/novel-*.md
+    LookupWordAtCursor,      // F12: palabra bajo el cursor -> glosario / bestiario
+
+    // Merge: origen (se elimina) -> destino (se queda con sus chapters)
+    StartMergeNovel(String),
+    PickMergeTarget(String),
+    CancelMergeNovel,
+
+    // Escenas huérfanas: reasignar a un chapter o mandar a la papelera
+    ReassignOrphanScene { scene_id: String, chapter_id: String },
+    TrashOrphanScene(String),
+
+    // Tras "Restore & Open": ruta resuelta en DB del novel/chapter/scene restaurado
+    OpenRestored(Result<Option<crate::model::ForgePath>, String>),
 
     // --- AUTO-SAVE ---
     SaveCurrentScene,
+    AutosaveDelayChanged(u64), // ms (se acota en el controller)
+    ResetWritingSession,       // "+N words this session" vuelve a 0
     DebounceComplete(u64),
 
     // --- DRAFT RECOVERY (LOCAL) ---
@@ -161,6 +355,25 @@ pub enum TheForgeMessage {
         scene_id: String,
         result: Result<(), String>,
     },
+    RecoverDraft(String), // scene_id: aplicar el draft del banner de recovery
+    DiscardDraft(String), // scene_id: descartar y borrar el draft del disco
+    DraftDiscarded {
+        scene_id: String,
+        result: Result<(), String>,
+    },
+
+    // --- EXTERNAL EDITOR (round-trip) ---
+    OpenSceneExternally(String), // scene_id
+    ExternalEditStarted {
+        scene_id: String,
+        result: Result<(std::path::PathBuf, std::time::SystemTime), String>,
+    },
+    ExternalEditPoll,
+    ExternalEditPolled {
+        scene_id: String,
+        result: Result<Option<(String, std::time::SystemTime)>, String>,
+    },
+    StopExternalEdit,
 
     // --- INLINE RENAME ---
     EndRename,
@@ -168,15 +381,40 @@ pub enum TheForgeMessage {
     // NUEVO: EXPAND/COLLAPSE
     ToggleNovel(String),      // novel_id - expande/colapsa chapters
     ToggleChapter(String),    // chapter_id - expande/colapsa scenes
+    SetActiveNodeExpanded(bool), // teclado: → / + expande, ← / - colapsa el nodo activo
+    ToggleOutlinePanel,       // colapsa el panel entero (editor a ancho completo)
+    TogglePreview,            // preview Markdown al lado del editor
+    ToggleManuscript,         // lectura continua del novel activo (solo lectura)
+    ToggleFocusMode,          // F11 (Esc sale): solo el editor, columna de texto centrada
+
+    // Búsqueda en el novel activo
+    SearchQueryChanged(String),
+    SearchDebounced(u64),
+    SearchCleared,
+    OpenSearchHit { chapter_id: String, scene_id: String },
+
+    // Find / replace (Ctrl+F)
+    ToggleFindBar,
+    FindQueryChanged(String),
+    ReplaceTextChanged(String),
+    FindCaseSensitiveToggled,
+    FindNext,
+    FindPrevious,
+    ReplaceOne,
+    ReplaceAll,
 
     // NUEVO: DRAG & DROP
     ChapterDragged(String, usize),  // chapter_id, new_position
     SceneDragged(String, usize),    // scene_id, new_position
+    SceneDraggedToChapter { scene_id: String, chapter_id: String, new_position: usize },
+    DragStart(ForgeDragItem),
+    DragHover(ForgeDragItem),
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Navigate(Route), MouseMoved(iced::Point), MouseReleased,
+    NavBack, NavForward, // historial de rutas (Alt+←/→)
     Tick,
     ToastDismiss(u64),
 
@@ -186,9 +424,32 @@ pub enum Message {
     ForgeRequestLoadScenes(String),   // chapter_id
 
     Pm(PmMessage), Bestiary(BestiaryMessage), Universe(UniverseMessage), Locations(LocationsMessage),
+    Glossary(GlossaryMessage),
     Timeline(TimelineMessage), Workspace(WorkspaceMessage), TheForge(TheForgeMessage),
+    Settings(SettingsMessage),
+
+    // Timeline -> PNG (captura de ventana). None => path por defecto en exports/
+    // Archivo soltado sobre la ventana con el editor de criatura / location abierto => ruta de imagen
+    ImageFileDropped(std::path::PathBuf),
+    ExportTimelineImage(Option<std::path::PathBuf>),
+    TimelineScreenshotTaken(std::path::PathBuf, iced::window::Screenshot),
+    TimelineImageExported(Result<String, String>), // path del PNG
+
+    // ✅ Auto-backup (VACUUM INTO + rotación)
+    AutoBackupTick,
+    AutoBackupDone(Result<String, String>), // path del backup
+
+    // Mantenimiento (dev panel): VACUUM + resumen de tamaño/filas
+    CompactDatabase,
+    DatabaseCompacted(Result<(i64, i64), String>), // bytes antes/después
+    RefreshDbStats,
+    DbStatsFetched(Result<crate::model::DbStats, String>),
+    RecalcWordCounts,
+    WordCountsRecalculated(Result<usize, String>), // escenas corregidas
 
     BoardsFetched(Result<Vec<Board>, String>),
+    ProjectWordTotalFetched(Result<i64, String>),
+    ForgeOrphanScenesFetched(Result<Vec<Scene>, String>),
 
     UniversesFetched(Result<Vec<Universe>, String>),
 
@@ -208,6 +469,42 @@ pub enum Message {
         result: Result<Vec<Location>, String>,
     },
 
+    GlossaryFetched {
+        universe_id: String,
+        result: Result<Vec<GlossaryTerm>, String>,
+    },
+
+    BoardTemplatesFetched(Result<Vec<crate::model::BoardTemplate>, String>),
+    CreatureMentionsFetched {
+        creature_id: String,
+        result: Result<Vec<SceneMention>, String>,
+    },
+    TimelineSceneRefsFetched {
+        universe_id: String,
+        result: Result<Vec<SceneMention>, String>,
+    },
+    CreatureRelationsFetched {
+        universe_id: String,
+        result: Result<Vec<crate::model::CreatureRelation>, String>,
+    },
+    ArchivedCardsFetched {
+        board_id: String,
+        result: Result<Vec<Card>, String>,
+    },
+    DefinitionLookedUp {
+        word: String,
+        result: Result<Option<crate::model::DefinitionHit>, String>,
+    },
+    SceneRevisionsFetched {
+        scene_id: String,
+        result: Result<Vec<crate::model::SceneRevision>, String>,
+    },
+    ForgeSearchFetched {
+        novel_id: String,
+        query: String,
+        result: Result<Vec<crate::model::SceneHit>, String>,
+    },
+
     TimelineFetched {
         universe_id: String,
         result: Result<(Vec<TimelineEvent>, Vec<TimelineEra>), String>,
@@ -234,14 +531,19 @@ pub enum Message {
     },
     SchemaVersionFetched(Result<i64, String>),
     IntegrityFetched(Result<Vec<String>, String>),
+    ReplacePreviewFetched {
+        term: String,
+        result: Result<Vec<TextMatch>, String>,
+    },
 
     ProjectsLoaded(Vec<Project>),
     ProjectCreated(Result<Project, String>),
     DbLoaded(Result<crate::db::Database, String>),
 
-    ActionDone(Result<(), String>),
+    ActionDone(Result<(), crate::db::DbError>),
 
     GlobalEvent(iced::Event),
+    Shortcut(crate::keymap::Shortcut), // tecla ya traducida; input_controller la resuelve por ruta
 
     BackToUniverses, BackToUniverse(String), OpenTimeline(String), GoToLocation(String, String),
     ConfirmDelete,
@@ -249,13 +551,42 @@ pub enum Message {
 
     TrashFetched(Result<Vec<TrashEntry>, String>),
     RestoreFromTrash(String),
+    RestoreAndOpen(String), // restaura y navega al item restaurado
     PermanentDelete(String),
     EmptyTrash,
     TrashSearchChanged(String),
+    ToastAction(u64), // botón del toast: dispara su mensaje y lo cierra
+    Undo, // Ctrl+Z: deshace la última acción reversible (crate::undo_stack)
+    // Paleta de comandos (Ctrl+P, crate::command_palette)
+    PaletteToggle,
+    PaletteClose,
+    PaletteQueryChanged(String),
+    PaletteMove(i32), // ↑ = -1 / ↓ = +1
+    PaletteSubmit,    // Enter: ejecuta el seleccionado
+    PaletteRun(usize), // click en un resultado
+    Redo, // Ctrl+Y / Ctrl+Shift+Z
+    TrashTypeFilterChanged(Option<String>),
+    TrashSortChanged(crate::model::TrashSort),
     ToggleTrashSelection(String),      // Toggle un item
     SelectAllTrash,                     // Seleccionar todos
     DeselectAllTrash,                   // Deseleccionar todos
     RestoreSelected,                    // Restaurar seleccionados
+    RestoreAllOfType(String),           // Restaurar todo un target_type (en orden de dependencias)
     DeleteSelectedForever,
     CleanupOldTrash,
+    TrashCleanupPreviewFetched(Result<Vec<TrashEntry>, String>),
+    TrashRetentionFetched(Result<crate::db::trash::TrashRetention, String>),
+    PurgeOldTrashPrompt, // "Purge now" del preview (con confirm)
+
+    // Tema guardado del proyecto (db_meta.ui_theme)
+    UiThemeFetched(Result<crate::ui::ThemeMode, String>),
+    // Copia del CapabilitiesCache para la vista
+    CapabilitiesFetched(crate::guards::Capabilities),
+    // Overview → "Recent"
+    RecentItemsFetched(Result<Vec<crate::model::RecentItem>, String>),
+
+    // Draft recovery (Forge): escaneo al abrir el proyecto
+    DraftRecoveryScanned(Result<Vec<crate::forge_draft::RecoverableDraft>, String>),
+    ForgeManuscriptScenesFetched { novel_id: String, result: Result<Vec<crate::model::Scene>, String> },
+    OutlineExpansionLoaded { universe_id: String, result: Result<(Vec<String>, Vec<String>), String> }, // (novel_ids, chapter_ids)
 }
\ No newline at end of file
@@ -66,6 +66,197 @@ pub struct Creature {
     pub home_location_id: Option<String>,
     #[sqlx(default)]
     pub archived: bool,
+    // Ruta local a un retrato (no se copia el archivo; puede quedar rota si se mueve)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub image_path: Option<String>,
+}
+
+impl Creature {
+    pub fn danger_level(&self) -> DangerLevel {
+        DangerLevel::from_loose(&self.danger)
+    }
+}
+
+// ✅ Escala de peligro normalizada (ordenada: Harmless < ... < Legendary).
+// En DB se sigue guardando como TEXT (as_str), pero siempre con el valor canónico.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+pub enum DangerLevel {
+    Harmless,
+    Minor,
+    #[default]
+    Moderate,
+    Deadly,
+    Legendary,
+}
+
+impl DangerLevel {
+    pub const ALL: &'static [DangerLevel] = &[
+        DangerLevel::Harmless,
+        DangerLevel::Minor,
+        DangerLevel::Moderate,
+        DangerLevel::Deadly,
+        DangerLevel::Legendary,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DangerLevel::Harmless => "Harmless",
+            DangerLevel::Minor => "Minor",
+            DangerLevel::Moderate => "Moderate",
+            DangerLevel::Deadly => "Deadly",
+            DangerLevel::Legendary => "Legendary",
+        }
+    }
+
+    /// Mapea strings legacy / libres ("High", "high", "deadly", "") a la escala canónica.
+    /// Valores desconocidos caen en el default (Moderate) para no perder la criatura.
+    pub fn from_loose(raw: &str) -> Self {
+        Self::try_from_loose(raw).unwrap_or_default()
+    }
+
+    /// Igual que from_loose pero sin default: None = valor desconocido (ej: ordenar al final).
+    pub fn try_from_loose(raw: &str) -> Option<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "harmless" | "none" | "safe" | "trivial" => Some(DangerLevel::Harmless),
+            "minor" | "low" | "weak" => Some(DangerLevel::Minor),
+            "moderate" | "medium" | "normal" => Some(DangerLevel::Moderate),
+            "deadly" | "high" | "dangerous" | "lethal" => Some(DangerLevel::Deadly),
+            "legendary" | "extreme" | "catastrophic" | "mythic" => Some(DangerLevel::Legendary),
+            _ => None,
+        }
+    }
+}
+
+// Orden del grid del bestiario (solo vista: state.creatures conserva su orden / índices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreatureSort {
+    #[default]
+    Default, // orden de carga
+    Name,    // A–Z
+    Danger,  // Harmless → Legendary (desconocidos al final)
+}
+
+impl CreatureSort {
+    pub const ALL: [CreatureSort; 3] = [CreatureSort::Default, CreatureSort::Name, CreatureSort::Danger];
+
+    /// Ordena pares (índice original, creature); empates por nombre.
+    pub fn apply(&self, list: &mut [(usize, &Creature)]) {
+        let name_key = |c: &Creature| c.name.to_lowercase();
+        match self {
+            CreatureSort::Default => {}
+            CreatureSort::Name => list.sort_by_cached_key(|(_, c)| name_key(c)),
+            CreatureSort::Danger => list.sort_by_cached_key(|(_, c)| {
+                let level = DangerLevel::try_from_loose(&c.danger);
+                (level.is_none(), level, name_key(c))
+            }),
+        }
+    }
+}
+
+impl fmt::Display for CreatureSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CreatureSort::Default => "Sort: default",
+            CreatureSort::Name => "Sort: name (A–Z)",
+            CreatureSort::Danger => "Sort: danger",
+        })
+    }
+}
+
+impl fmt::Display for DangerLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// --- CREATURE RELATIONS ---
+// Arista dirigida from -> to (ej: lobo "preys_on" ciervo). relation_kind es TEXT canónico.
+#[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
+pub struct CreatureRelation {
+    pub id: String,
+    pub universe_id: String,
+    pub from_id: String,
+    pub to_id: String,
+    pub relation_kind: String,
+}
+
+impl CreatureRelation {
+    pub fn kind(&self) -> CreatureRelationKind {
+        CreatureRelationKind::from_str_loose(&self.relation_kind)
+    }
+
+    /// La otra punta vista desde `creature_id` + etiqueta en ese sentido
+    /// (None si la relación no toca a esa criatura).
+    pub fn seen_from(&self, creature_id: &str) -> Option<(&str, &'static str)> {
+        if self.from_id == creature_id {
+            Some((self.to_id.as_str(), self.kind().label()))
+        } else if self.to_id == creature_id {
+            Some((self.from_id.as_str(), self.kind().inverse_label()))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CreatureRelationKind {
+    #[default]
+    PreysOn,
+    Ally,
+    Rival,
+    Symbiont,
+}
+
+impl CreatureRelationKind {
+    pub const ALL: [CreatureRelationKind; 4] = [
+        CreatureRelationKind::PreysOn,
+        CreatureRelationKind::Ally,
+        CreatureRelationKind::Rival,
+        CreatureRelationKind::Symbiont,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CreatureRelationKind::PreysOn => "preys_on",
+            CreatureRelationKind::Ally => "ally",
+            CreatureRelationKind::Rival => "rival",
+            CreatureRelationKind::Symbiont => "symbiont",
+        }
+    }
+
+    pub fn from_str_loose(raw: &str) -> Self {
+        match raw.trim().to_lowercase().as_str() {
+            "ally" | "allies" | "friend" => CreatureRelationKind::Ally,
+            "rival" | "enemy" | "competitor" => CreatureRelationKind::Rival,
+            "symbiont" | "symbiosis" | "mutualist" => CreatureRelationKind::Symbiont,
+            _ => CreatureRelationKind::PreysOn,
+        }
+    }
+
+    /// Etiqueta desde el origen ("Wolf preys on Deer")
+    pub fn label(&self) -> &'static str {
+        match self {
+            CreatureRelationKind::PreysOn => "Preys on",
+            CreatureRelationKind::Ally => "Ally of",
+            CreatureRelationKind::Rival => "Rival of",
+            CreatureRelationKind::Symbiont => "Symbiont of",
+        }
+    }
+
+    /// Etiqueta desde el destino ("Deer: prey of Wolf"); las simétricas no cambian
+    pub fn inverse_label(&self) -> &'static str {
+        match self {
+            CreatureRelationKind::PreysOn => "Prey of",
+            other => other.label(),
+        }
+    }
+}
+
+impl fmt::Display for CreatureRelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
 }
 
 // --- LOCATIONS ---
@@ -77,6 +268,9 @@ pub struct Location {
     pub name: String,
     pub description: String,
     pub kind: String,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub image_path: Option<String>,
 }
 
 impl fmt::Display for Location {
@@ -85,6 +279,46 @@ impl fmt::Display for Location {
     }
 }
 
+// Ubicación de un nodo de Forge en el árbol (para navegar a él, ej: tras restaurar de la papelera)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgePath {
+    pub universe_id: Option<String>,
+    pub novel_id: String,
+    pub chapter_id: Option<String>,
+    pub scene_id: Option<String>,
+}
+
+// Resultado de búsqueda inversa: escena que menciona algo del universo (ej: una criatura).
+// También sirve como referencia "novel › chapter › scene" (picker de escena de un evento).
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct SceneMention {
+    pub scene_id: String,
+    pub scene_title: String,
+    pub chapter_id: String,
+    pub chapter_title: String,
+    pub novel_id: String,
+    pub novel_title: String,
+    pub universe_id: Option<String>,
+}
+
+// "Jump to definition" desde el editor: palabra del manuscrito -> entidad del universo
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefinitionHit {
+    GlossaryTerm { universe_id: String, term_id: String },
+    Creature { universe_id: String, creature_id: String },
+}
+
+// --- GLOSSARY ---
+// Términos inventados del universo (nombres, idiomas, jerga) + cómo se pronuncian.
+#[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub id: String,
+    pub universe_id: String,
+    pub term: String,
+    pub definition: String,
+    pub pronunciation: String,
+}
+
 // --- TIMELINE ---
 #[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
 pub struct TimelineEra {
@@ -109,6 +343,10 @@ pub struct TimelineEvent {
     pub kind: String,
     pub color: String,
     pub location_id: Option<String>,
+    // Escena donde se narra el evento (puede apuntar a una escena ya borrada: resolver al mostrar)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub scene_id: Option<String>,
 }
 
 // --- PM TOOLS (KANBAN) ---
@@ -120,6 +358,12 @@ pub struct Board {
     pub kind: String,
 }
 
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 #[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
 pub struct BoardColumn {
     pub id: String,
@@ -129,6 +373,48 @@ pub struct BoardColumn {
     pub position: i32,
 }
 
+// Versión guardada del body de una escena (historial de The Forge)
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct SceneRevision {
+    pub id: String,
+    pub scene_id: String,
+    pub body: String,
+    pub word_count: i64,
+    pub saved_at: i64, // unix seconds
+}
+
+// Último acceso a un universo / board / novel (Overview → "Recent").
+// name y universe_id salen del JOIN al leer (no se guardan en recent_items).
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct RecentItem {
+    pub kind: String, // "universe" | "board" | "novel"
+    pub target_id: String,
+    pub opened_at: i64, // unix seconds
+    pub name: String,
+    pub universe_id: Option<String>, // solo novels (JumpToNovel)
+}
+
+// Estructura reutilizable de un board: solo columnas (nombre + orden), sin cards.
+// columns_json = ["To Do", "Doing", ...] (la posición es el índice)
+#[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
+pub struct BoardTemplate {
+    pub id: String,
+    pub name: String,
+    pub columns_json: String,
+}
+
+impl BoardTemplate {
+    pub fn column_names(&self) -> Vec<String> {
+        serde_json::from_str(&self.columns_json).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for BoardTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} columns)", self.name, self.column_names().len())
+    }
+}
+
 #[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
 pub struct Card {
     pub id: String,
@@ -138,6 +424,77 @@ pub struct Card {
     pub position: i64,
     #[sqlx(default)]
     pub priority: String,
+    // Etiquetas separadas por coma ("bug,feature"); normalizadas al guardar
+    #[sqlx(default)]
+    #[serde(default)]
+    pub labels: String,
+    // Unix ts de la medianoche (UTC) del día de entrega; None / 0 = sin fecha
+    #[sqlx(default)]
+    #[serde(default)]
+    pub due_date: Option<i64>,
+    // Archivada: fuera del board activo, se restaura desde "Archived cards"
+    #[sqlx(default)]
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl Card {
+    pub fn label_list(&self) -> impl Iterator<Item = &str> {
+        self.labels.split(',').map(str::trim).filter(|l| !l.is_empty())
+    }
+
+    pub fn has_label(&self, label: &str) -> bool {
+        self.label_list().any(|l| l.eq_ignore_ascii_case(label))
+    }
+
+    /// `query_lower` ya viene en minúsculas (se calcula una vez por pasada de view).
+    pub fn matches_query(&self, query_lower: &str) -> bool {
+        query_lower.is_empty()
+            || self.title.to_lowercase().contains(query_lower)
+            || self.description.to_lowercase().contains(query_lower)
+    }
+
+    pub fn due(&self) -> Option<i64> {
+        self.due_date.filter(|ts| *ts != 0)
+    }
+
+    /// `today` = card_due_today() calculado una vez por pasada de view.
+    pub fn is_overdue(&self, today: i64) -> bool {
+        self.due().is_some_and(|due| due < today)
+    }
+}
+
+/// "YYYY-MM-DD" -> ts de esa fecha (vacío = sin fecha).
+pub fn parse_card_due_date(raw: &str) -> Result<Option<i64>, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map(|d| Some(d.and_time(chrono::NaiveTime::MIN).and_utc().timestamp()))
+        .map_err(|_| format!("Invalid due date \"{}\" (expected YYYY-MM-DD)", raw))
+}
+
+pub fn format_card_due_date(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Hoy (fecha local) con la misma codificación que Card::due_date.
+pub fn card_due_today() -> i64 {
+    chrono::Local::now().date_naive().and_time(chrono::NaiveTime::MIN).and_utc().timestamp()
+}
+
+/// Input libre del editor -> "bug,feature" (sin vacíos ni duplicados case-insensitive).
+pub fn normalize_card_labels(raw: &str) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    for label in raw.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+        if !out.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            out.push(label);
+        }
+    }
+    out.join(",")
 }
 
 // ✅ OPTIMIZED: O(1) card lookups instead of O(n)
@@ -192,6 +549,20 @@ impl KanbanBoardData {
         self.cards_by_id.get(card_id)
     }
 
+    /// Etiquetas usadas en el board (orden alfabético, sin duplicados) para el filtro
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = Vec::new();
+        for card in self.cards_by_id.values() {
+            for label in card.label_list() {
+                if !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                    labels.push(label.to_string());
+                }
+            }
+        }
+        labels.sort_by_key(|l| l.to_lowercase());
+        labels
+    }
+
     /// Get cards in a column - O(1) + O(k) where k = cards in column
     pub fn get_column_cards(&self, column_id: &str) -> Vec<&Card> {
         self.cards_by_column
@@ -247,6 +618,141 @@ pub struct Scene {
     pub word_count: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // prose / outline / note (TEXT canónico, ver SceneKind)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub scene_kind: String,
+    // false = borrador/scratch: se conserva pero no sale en los exports/compile
+    #[serde(default = "default_true")]
+    pub include_in_compile: bool,
+    // Meta de palabras (0 = sin meta)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub target_words: i64,
+    // Notas del autor (intenciones, pendientes): fuera del manuscrito y del word_count
+    #[sqlx(default)]
+    #[serde(default)]
+    pub notes: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Scene {
+    pub fn kind(&self) -> SceneKind {
+        SceneKind::from_str_loose(&self.scene_kind)
+    }
+
+    pub fn work_status(&self) -> WorkStatus {
+        WorkStatus::from_str_loose(&self.status)
+    }
+}
+
+/// Estado de trabajo (draft → revised → final). Único lugar que define el ciclo:
+/// cualquier módulo con "status" editorial debe usar este enum (TEXT canónico en DB).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum WorkStatus {
+    #[default]
+    Draft,
+    Revised,
+    Final,
+}
+
+impl WorkStatus {
+    pub const CYCLE: &'static [WorkStatus] = &[WorkStatus::Draft, WorkStatus::Revised, WorkStatus::Final];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkStatus::Draft => "draft",
+            WorkStatus::Revised => "revised",
+            WorkStatus::Final => "final",
+        }
+    }
+
+    // "" / desconocido => draft (data vieja)
+    pub fn from_str_loose(raw: &str) -> Self {
+        match raw.trim().to_lowercase().as_str() {
+            "revised" | "revision" => WorkStatus::Revised,
+            "final" | "done" => WorkStatus::Final,
+            _ => WorkStatus::Draft,
+        }
+    }
+
+    /// Siguiente en el ciclo (final vuelve a draft)
+    pub fn next(&self) -> Self {
+        let i = Self::CYCLE.iter().position(|s| s == self).unwrap_or(0);
+        Self::CYCLE[(i + 1) % Self::CYCLE.len()]
+    }
+
+    /// Estado de un contenedor (ej: chapter): el menos avanzado de sus hijos.
+    /// "final" solo si todos son final; None si no hay hijos.
+    pub fn aggregate(items: impl IntoIterator<Item = WorkStatus>) -> Option<WorkStatus> {
+        items.into_iter().min()
+    }
+}
+
+impl fmt::Display for WorkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            WorkStatus::Draft => "Draft",
+            WorkStatus::Revised => "Revised",
+            WorkStatus::Final => "Final",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SceneKind {
+    #[default]
+    Prose,
+    Outline,
+    Note,
+}
+
+impl SceneKind {
+    pub const ALL: &'static [SceneKind] = &[SceneKind::Prose, SceneKind::Outline, SceneKind::Note];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SceneKind::Prose => "prose",
+            SceneKind::Outline => "outline",
+            SceneKind::Note => "note",
+        }
+    }
+
+    pub fn from_str_loose(raw: &str) -> Self {
+        match raw.trim().to_lowercase().as_str() {
+            "outline" => SceneKind::Outline,
+            "note" | "notes" => SceneKind::Note,
+            _ => SceneKind::Prose,
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            SceneKind::Prose => "¶",
+            SceneKind::Outline => "☰",
+            SceneKind::Note => "✎",
+        }
+    }
+
+    /// Outline/notes no son manuscrito: no suman a metas de palabras.
+    pub fn counts_toward_goals(&self) -> bool {
+        matches!(self, SceneKind::Prose)
+    }
+}
+
+impl fmt::Display for SceneKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SceneKind::Prose => "Prose",
+            SceneKind::Outline => "Outline",
+            SceneKind::Note => "Note",
+        };
+        write!(f, "{}", label)
+    }
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
@@ -274,6 +780,10 @@ pub struct Chapter {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // Meta de palabras del chapter (0 = sin meta)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub target_words: i64,
 }
 
 // --- SNAPSHOTS ---
@@ -284,6 +794,18 @@ pub struct UniverseSnapshot {
     pub name: String,
     pub created_at: i64,   // epoch seconds (sqlite unixepoch)
     pub size_bytes: i64,
+    // Creado solo antes de una operación destructiva (se poda aparte de los manuales)
+    #[sqlx(default)]
+    pub is_auto: bool,
+}
+
+impl UniverseSnapshot {
+    /// Nombre por defecto (rename vacío): basado en la fecha de creación.
+    pub fn default_name(&self) -> String {
+        chrono::DateTime::from_timestamp(self.created_at, 0)
+            .map(|d| format!("Snapshot {}", d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")))
+            .unwrap_or_else(|| "Snapshot".to_string())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -294,6 +816,9 @@ pub struct UniverseSnapshotPayload {
     pub timeline_eras: Vec<TimelineEra>,
     pub timeline_events: Vec<TimelineEvent>,
     pub pm_cards: Vec<Card>,
+    // Snapshots viejos no lo traen: default = vacío
+    #[serde(default)]
+    pub glossary: Vec<GlossaryTerm>,
 }
 
 // --- TRASH SYSTEM ---
@@ -310,6 +835,37 @@ pub struct TrashEntry {
     pub payload_json: String,
 }
 
+// Orden de la página de Trash (solo vista sobre state.trash_entries)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrashSort {
+    #[default]
+    Newest,
+    Oldest,
+    Name, // A–Z
+}
+
+impl TrashSort {
+    pub const ALL: [TrashSort; 3] = [TrashSort::Newest, TrashSort::Oldest, TrashSort::Name];
+
+    pub fn apply(&self, list: &mut [&TrashEntry]) {
+        match self {
+            TrashSort::Newest => list.sort_by_key(|e| std::cmp::Reverse(e.deleted_at)),
+            TrashSort::Oldest => list.sort_by_key(|e| e.deleted_at),
+            TrashSort::Name => list.sort_by_cached_key(|e| e.display_name.to_lowercase()),
+        }
+    }
+}
+
+impl fmt::Display for TrashSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TrashSort::Newest => "Sort: newest first",
+            TrashSort::Oldest => "Sort: oldest first",
+            TrashSort::Name => "Sort: name (A–Z)",
+        })
+    }
+}
+
 impl TrashEntry {
     pub fn deleted_at_formatted(&self) -> String {
         self.deleted_at.format("%Y-%m-%d %H:%M").to_string()
@@ -326,4 +882,90 @@ pub struct AuditLogEntry {
     pub entity_id: String,
     #[sqlx(default)]
     pub details_json: String,
-}
\ No newline at end of file
+}
+
+/// Filtros del visor de audit log. None / vacío => sin restricción.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    pub from_ts: Option<i64>,
+    pub to_ts: Option<i64>, // inclusivo (fin del día)
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+}
+
+impl AuditFilter {
+    /// Inputs de texto (fechas YYYY-MM-DD, UTC como las due dates) -> filtro.
+    pub fn from_inputs(from: &str, to: &str, action: &str, entity_type: &str) -> Result<Self, String> {
+        let day = |raw: &str, label: &str| -> Result<Option<i64>, String> {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                return Ok(None);
+            }
+            chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .map(|d| Some(d.and_time(chrono::NaiveTime::MIN).and_utc().timestamp()))
+                .map_err(|_| format!("Invalid {} date \"{}\" (expected YYYY-MM-DD)", label, raw))
+        };
+        let text = |raw: &str| Some(raw.trim().to_string()).filter(|s| !s.is_empty());
+
+        Ok(Self {
+            from_ts: day(from, "from")?,
+            to_ts: day(to, "to")?.map(|ts| ts + 86_399),
+            action: text(action),
+            entity_type: text(entity_type),
+        })
+    }
+}
+
+// --- MAINTENANCE (VACUUM / stats) ---
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DbStats {
+    pub size_bytes: i64,     // page_count * page_size (sin -wal)
+    pub free_bytes: i64,     // páginas libres que VACUUM recuperaría
+    pub tables: Vec<(String, i64)>, // (tabla, filas)
+}
+
+// --- MAINTENANCE (find & replace) ---
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReplaceScope {
+    Creatures,
+    Locations,
+    Events,
+    Scenes,
+}
+
+impl ReplaceScope {
+    pub const ALL: &'static [ReplaceScope] = &[
+        ReplaceScope::Creatures,
+        ReplaceScope::Locations,
+        ReplaceScope::Events,
+        ReplaceScope::Scenes,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReplaceScope::Creatures => "Creatures",
+            ReplaceScope::Locations => "Locations",
+            ReplaceScope::Events => "Events",
+            ReplaceScope::Scenes => "Scenes",
+        }
+    }
+}
+
+// Resultado de búsqueda en The Forge: escena + extracto alrededor del primer match
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneHit {
+    pub scene_id: String,
+    pub chapter_id: String,
+    pub chapter_title: String,
+    pub title: String,
+    pub snippet: String,
+}
+
+// Fila de preview: una entidad afectada + cuántas ocurrencias se reemplazarían
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    pub scope: ReplaceScope,
+    pub entity_id: String,
+    pub display_name: String,
+    pub occurrences: usize,
+}
@@ -1,33 +1,62 @@
 use iced::{Alignment, Color, Length, Vector};
-use iced::widget::{button, container, mouse_area, text, text_input, text_editor, pick_list, Column, Row};
+use iced::widget::{button, container, mouse_area, scrollable, text, text_input, text_editor, pick_list, Column, Row};
 use iced::Theme;
 use crate::app::{AppState, Message, BestiaryMessage};
-use crate::model::{Creature, Location};
+use crate::messages::TheForgeMessage;
+use crate::model::{Creature, CreatureRelation, CreatureRelationKind, DangerLevel, Location, SceneMention};
 use crate::{pages::E, ui};
 
 pub fn bestiary<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -> E<'a> {
     let universe_name = state.universes.iter().find(|u| u.id == universe_id).map(|u| u.name.as_str()).unwrap_or(universe_id);
 
-    // C12: no alocar Vecs por render; iteramos una sola vez por sección
-    let (active_grid, active_count) = creatures_grid_counted(
-        t,
-        state.creatures.iter().enumerate().filter(|(_, c)| !c.archived),
-        &state.locations,
-        universe_id,
-    );
-
-    let (archived_grid, archived_count) = creatures_grid_counted(
-        t,
-        state.creatures.iter().enumerate().filter(|(_, c)| c.archived),
-        &state.locations,
-        universe_id,
-    );
+    // Filtros de vista (peligro, búsqueda por nombre, kind). El índice se toma ANTES de filtrar:
+    // CardClicked usa state.creatures[idx], así que state.creatures nunca se toca.
+    let filter = &state.bestiary_danger_filter;
+    let query_owned = state.bestiary_search.trim().to_lowercase();
+    let query = query_owned.as_str(); // por referencia: passes sigue siendo Copy (se usa en ambas secciones)
+    let kind_filter = state.bestiary_kind_filter.as_deref();
+    let passes = move |c: &Creature| {
+        (filter.is_empty() || filter.contains(&c.danger_level()))
+            && (query.is_empty() || c.name.to_lowercase().contains(query))
+            && kind_filter.is_none_or(|k| c.kind == k)
+    };
+
+    // Orden de vista: Vec de referencias (índice original intacto para CardClicked)
+    let mut active: Vec<(usize, &Creature)> =
+        state.creatures.iter().enumerate().filter(move |(_, c)| !c.archived && passes(c)).collect();
+    let mut archived: Vec<(usize, &Creature)> =
+        state.creatures.iter().enumerate().filter(move |(_, c)| c.archived && passes(c)).collect();
+    state.bestiary_sort.apply(&mut active);
+    state.bestiary_sort.apply(&mut archived);
+
+    let (active_grid, active_count) = creatures_grid_counted(t, active, state, universe_id);
+    let (archived_grid, archived_count) = creatures_grid_counted(t, archived, state, universe_id);
 
     let header_left = Column::new().spacing(4)
         .push(text(format!("Bestiary — {}", universe_name)).size(26).color(t.foreground))
         .push(text("Creatures, entities and beings that inhabit this universe.").size(12).color(t.muted_fg));
 
-    let header_right = Row::new().spacing(10)
+    // Export: formato + archivadas (por defecto no) -> exports/<proyecto>/
+    let include_archived = state.bestiary_export_include_archived;
+    let export_row = Row::new().spacing(6).align_y(Alignment::Center)
+        .push(
+            pick_list(
+                &crate::db::bestiary_export::BestiaryExportFormat::ALL[..],
+                Some(state.bestiary_export_format),
+                |f| Message::Bestiary(BestiaryMessage::ExportFormatChanged(f)),
+            )
+                .text_size(12)
+                .padding([4, 8]),
+        )
+        .push(ui::ghost_button(
+            t,
+            if include_archived { "✓ Include archived".to_string() } else { "Include archived".to_string() },
+            Message::Bestiary(BestiaryMessage::ExportIncludeArchivedToggled(!include_archived)),
+        ))
+        .push(ui::outline_button(t, "Export".to_string(), Message::Bestiary(BestiaryMessage::Export)));
+
+    let header_right = Row::new().spacing(10).align_y(Alignment::Center)
+        .push(export_row)
         .push(ui::outline_button(t, "Back to universe".to_string(), Message::BackToUniverse(universe_id.to_string())))
         .push(ui::outline_button(t, "All universes".to_string(), Message::BackToUniverses))
         .push(ui::primary_button(t, "Create creature".to_string(), Message::Bestiary(BestiaryMessage::EditorOpenCreate)));
@@ -49,6 +78,15 @@ pub fn bestiary<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
 
     let body = Column::new().spacing(14)
         .push(header)
+        .push(search_bar(t, state))
+        .push(danger_legend(t, state));
+
+    let body = match selection_bar(t, state) {
+        Some(bar) => body.push(bar),
+        None => body,
+    };
+
+    let body = body
         .push(active_header)
         .push(active_grid)
         .push(ui::h_divider(t))
@@ -59,9 +97,133 @@ pub fn bestiary<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
     ui::page_padding(body.into())
 }
 
-// ... (render_creature_modal y danger_pill se mantienen IGUALES)
+// Barra de lote: solo visible con selección (cuenta solo ids que siguen existiendo)
+fn selection_bar<'a>(t: ui::Tokens, state: &'a AppState) -> Option<E<'a>> {
+    let count = state.creatures.iter().filter(|c| state.bestiary_selected.contains(&c.id)).count();
+    if count == 0 {
+        return None;
+    }
+
+    Some(
+        Row::new().spacing(8).align_y(Alignment::Center)
+            .push(text(format!("{} selected", count)).size(12).color(t.muted_fg))
+            .push(ui::outline_button(t, "Archive selected".to_string(), Message::Bestiary(BestiaryMessage::ArchiveSelected)))
+            .push(ui::outline_button(t, "Restore selected".to_string(), Message::Bestiary(BestiaryMessage::RestoreSelected)))
+            .push(ui::ghost_button(t, "Clear selection".to_string(), Message::Bestiary(BestiaryMessage::SelectionCleared)))
+            .into(),
+    )
+}
+
+/// Color de cada nivel de peligro (chips del grid, leyenda y editor usan el mismo mapeo).
+pub fn danger_color(level: DangerLevel) -> Color {
+    match level {
+        DangerLevel::Harmless => Color::from_rgb8(34, 197, 94),
+        DangerLevel::Minor => Color::from_rgb8(132, 204, 22),
+        DangerLevel::Moderate => Color::from_rgb8(234, 179, 8),
+        DangerLevel::Deadly => Color::from_rgb8(239, 68, 68),
+        DangerLevel::Legendary => Color::from_rgb8(168, 85, 247),
+    }
+}
+
+fn danger_chip<'a>(t: ui::Tokens, level: DangerLevel, label: String, active: bool) -> E<'a> {
+    let color = danger_color(level);
+    let dot = container(text("")).width(Length::Fixed(8.0)).height(Length::Fixed(8.0)).style(move |_: &Theme| {
+        let mut s = ui::container_style(color, t.foreground);
+        s.border = iced::Border { color, width: 0.0, radius: 999.0.into() };
+        s
+    });
+
+    container(Row::new().spacing(6).align_y(Alignment::Center).push(dot).push(text(label).size(11).color(t.foreground)))
+        .padding([3, 8])
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(ui::alpha(color, if active { 0.22 } else { 0.08 }), t.foreground);
+            s.border = iced::Border { color: ui::alpha(color, if active { 0.8 } else { 0.3 }), width: 1.0, radius: 999.0.into() };
+            s
+        })
+        .into()
+}
 
-pub fn render_creature_modal<'a>(t: ui::Tokens, editor: &'a crate::app::CreatureEditor, locations: &'a [Location]) -> E<'a> {
+// Búsqueda por nombre + kind (dropdown con los kinds presentes). Solo vista: sin DB.
+fn search_bar<'a>(t: ui::Tokens, state: &'a AppState) -> E<'a> {
+    let mut kinds: Vec<String> = state
+        .creatures
+        .iter()
+        .map(|c| c.kind.trim())
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect();
+    kinds.sort_by_key(|k| k.to_lowercase());
+    kinds.dedup();
+
+    let mut row = Row::new().spacing(8).align_y(Alignment::Center)
+        .push(
+            text_input("Search creatures by name…", &state.bestiary_search)
+                .on_input(|q| Message::Bestiary(BestiaryMessage::SearchChanged(q)))
+                .padding(8)
+                .size(13)
+                .style(ui::input_style(t))
+                .width(Length::Fixed(280.0)),
+        )
+        .push(
+            pick_list(kinds, state.bestiary_kind_filter.clone(), |k| {
+                Message::Bestiary(BestiaryMessage::KindFilterChanged(Some(k)))
+            })
+                .placeholder("All kinds")
+                .text_size(12)
+                .padding([6, 8]),
+        )
+        .push(
+            pick_list(&crate::model::CreatureSort::ALL[..], Some(state.bestiary_sort), |s| {
+                Message::Bestiary(BestiaryMessage::SortChanged(s))
+            })
+                .text_size(12)
+                .padding([6, 8]),
+        );
+
+    if state.bestiary_kind_filter.is_some() {
+        row = row.push(ui::ghost_button(t, "All kinds".to_string(), Message::Bestiary(BestiaryMessage::KindFilterChanged(None))));
+    }
+    if !state.bestiary_search.is_empty() {
+        row = row.push(ui::ghost_button(t, "Clear search".to_string(), Message::Bestiary(BestiaryMessage::SearchChanged(String::new()))));
+    }
+
+    row.into()
+}
+
+// Leyenda = filtro: click en un nivel lo agrega/saca del filtro (ninguno activo = todos)
+fn danger_legend<'a>(t: ui::Tokens, state: &'a AppState) -> E<'a> {
+    let filter = &state.bestiary_danger_filter;
+    let mut row = Row::new().spacing(8).align_y(Alignment::Center)
+        .push(text("Danger:").size(12).color(t.muted_fg));
+
+    for &level in DangerLevel::ALL {
+        let count = state.creatures.iter().filter(|c| c.danger_level() == level).count();
+        let chip = danger_chip(t, level, format!("{} ({})", level.as_str(), count), filter.contains(&level));
+        row = row.push(
+            button(chip)
+                .padding(0)
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::Bestiary(BestiaryMessage::DangerFilterToggled(level))),
+        );
+    }
+
+    if !filter.is_empty() {
+        row = row.push(ui::ghost_button(t, "Clear filter".to_string(), Message::Bestiary(BestiaryMessage::DangerFilterCleared)));
+    }
+
+    row.into()
+}
+
+// ... (render_creature_modal: danger ahora es un pick_list sobre DangerLevel::ALL)
+
+pub fn render_creature_modal<'a>(
+    t: ui::Tokens,
+    editor: &'a crate::app::CreatureEditor,
+    locations: &'a [Location],
+    mentions: &'a [SceneMention],
+    creatures: &'a [Creature],
+    relations: &'a [CreatureRelation],
+) -> E<'a> {
     let is_new = editor.index.is_none();
     let title = if is_new { "Create Creature" } else { "Edit Creature" };
 
@@ -103,27 +265,169 @@ pub fn render_creature_modal<'a>(t: ui::Tokens, editor: &'a crate::app::Creature
 
     let desc_input = text_editor(&editor.description).on_action(|v| Message::Bestiary(BestiaryMessage::DescriptionChanged(v))).padding(10).height(Length::Fixed(150.0)).style(ui::text_editor_style(t));
 
-    let danger_pills = Row::new().spacing(8).push(text("Danger Level:").size(12).color(t.muted_fg)).push(danger_pill(t, "Low", &editor.danger)).push(danger_pill(t, "Medium", &editor.danger)).push(danger_pill(t, "High", &editor.danger)).push(danger_pill(t, "Extreme", &editor.danger));
+    let danger_picker = Row::new().spacing(8).align_y(Alignment::Center)
+        .push(text("Danger Level:").size(12).color(t.muted_fg))
+        .push(danger_chip(t, editor.danger, editor.danger.as_str().to_string(), true))
+        .push(pick_list(
+            DangerLevel::ALL,
+            Some(editor.danger),
+            |d| Message::Bestiary(BestiaryMessage::DangerChanged(d))
+        )
+            .width(Length::Fixed(180.0))
+            .padding(8)
+            .style(move |_, status| {
+                let base = iced::widget::pick_list::Style {
+                    text_color: t.foreground,
+                    placeholder_color: t.muted_fg,
+                    handle_color: t.muted_fg,
+                    background: iced::Background::Color(t.input_border),
+                    border: iced::Border { color: t.border, width: 1.0, radius: 6.0.into() }
+                };
+                match status {
+                    iced::widget::pick_list::Status::Opened { .. } => iced::widget::pick_list::Style {
+                        border: iced::Border { color: t.accent, ..base.border },
+                        ..base
+                    },
+                    _ => base
+                }
+            }));
 
     let actions = Row::new().spacing(10).align_y(Alignment::Center).push(ui::primary_button(t, "Save Creature".to_string(), Message::Bestiary(BestiaryMessage::EditorSave))).push(ui::ghost_button(t, "Cancel".to_string(), Message::Bestiary(BestiaryMessage::EditorCancel)));
 
-    let form = Column::new().spacing(16).push(text(title).size(20).color(t.foreground)).push(Column::new().spacing(6).push(text("Name").size(12).color(t.muted_fg)).push(name_input)).push(Row::new().spacing(10).push(Column::new().spacing(6).push(text("Kind").size(12).color(t.muted_fg)).push(kind_input).width(Length::FillPortion(1))).push(location_picker.width(Length::FillPortion(1)))).push(Column::new().spacing(6).push(text("Habitat Details").size(12).color(t.muted_fg)).push(habitat_input)).push(Column::new().spacing(6).push(text("Description").size(12).color(t.muted_fg)).push(desc_input)).push(danger_pills).push(actions);
+    let form = Column::new().spacing(16).push(text(title).size(20).color(t.foreground)).push(Column::new().spacing(6).push(text("Name").size(12).color(t.muted_fg)).push(name_input)).push(Row::new().spacing(10).push(Column::new().spacing(6).push(text("Kind").size(12).color(t.muted_fg)).push(kind_input).width(Length::FillPortion(1))).push(location_picker.width(Length::FillPortion(1)))).push(Column::new().spacing(6).push(text("Habitat Details").size(12).color(t.muted_fg)).push(habitat_input)).push(Column::new().spacing(6).push(text("Description").size(12).color(t.muted_fg)).push(desc_input)).push(danger_picker)
+        .push(ui::image_path_field(t, "Portrait (optional)", &editor.image_path, |v| Message::Bestiary(BestiaryMessage::ImagePathChanged(v))));
+
+    // Solo para criaturas existentes (la búsqueda usa el nombre guardado)
+    let form = match editor.id.as_deref() {
+        Some(id) => form.push(relations_section(t, editor, id, creatures, relations)).push(mentions_section(t, mentions)),
+        None => form,
+    };
+    let form = form.push(actions);
 
     container(container(form).width(Length::Fixed(550.0)).padding(24).style(move |_: &Theme| { let mut s = ui::container_style(t.popover, t.foreground); s.border.color = t.border; s.border.width = 1.0; s.border.radius = 12.0.into(); s.shadow = iced::Shadow { color: Color::BLACK, offset: Vector::new(0.0, 10.0), blur_radius: 40.0 }; s })).width(Length::Fill).height(Length::Fill).center_x(Length::Fill).center_y(Length::Fill).style(move |_: &Theme| ui::container_style(Color::from_rgba8(0,0,0, 0.7), t.foreground)).into()
 }
 
-fn danger_pill<'a>(t: ui::Tokens, label: &'a str, current: &str) -> E<'a> {
-    let is_selected = current == label;
-    let mut btn = button(text(label).size(12).color(if is_selected { t.background } else { t.muted_fg })).padding([6, 12]).on_press(Message::Bestiary(BestiaryMessage::DangerChanged(label.to_string())));
-    if is_selected { btn = btn.style(ui::primary_button_style(t)); } else { btn = btn.style(ui::ghost_button_style(t)); }
-    btn.into()
+// Opción del pick_list de destino (pick_list necesita Display + PartialEq)
+#[derive(Debug, Clone, PartialEq)]
+struct RelationTarget<'a> {
+    id: &'a str,
+    name: &'a str,
+}
+
+impl std::fmt::Display for RelationTarget<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+fn creature_name<'a>(creatures: &'a [Creature], id: &str) -> &'a str {
+    creatures.iter().find(|c| c.id == id).map(|c| c.name.as_str()).unwrap_or("Unknown")
+}
+
+// Relaciones con otras criaturas (ambos sentidos); alta/baja se guardan al instante
+fn relations_section<'a>(
+    t: ui::Tokens,
+    editor: &'a crate::app::CreatureEditor,
+    creature_id: &'a str,
+    creatures: &'a [Creature],
+    relations: &'a [CreatureRelation],
+) -> E<'a> {
+    let mut col = Column::new().spacing(4)
+        .push(text("Relationships").size(12).color(t.muted_fg));
+
+    let mut any = false;
+    for r in relations {
+        let Some((other_id, label)) = r.seen_from(creature_id) else { continue };
+        any = true;
+        col = col.push(
+            Row::new().spacing(8).align_y(Alignment::Center)
+                .push(text(format!("{} {}", label, creature_name(creatures, other_id))).size(12).color(t.foreground).width(Length::Fill))
+                .push(
+                    button(text("×").size(12).color(t.muted_fg))
+                        .padding([2, 8])
+                        .style(ui::ghost_button_style(t))
+                        .on_press(Message::Bestiary(BestiaryMessage::RelationRemove(r.id.clone()))),
+                ),
+        );
+    }
+    if !any {
+        col = col.push(text("No relationships yet.").size(12).color(t.muted_fg));
+    }
+
+    let targets: Vec<RelationTarget<'a>> = creatures
+        .iter()
+        .filter(|c| c.id != creature_id)
+        .map(|c| RelationTarget { id: c.id.as_str(), name: c.name.as_str() })
+        .collect();
+    let selected = editor
+        .relation_target_id
+        .as_deref()
+        .and_then(|id| targets.iter().find(|o| o.id == id).cloned());
+
+    let add_btn = button(text("Add").size(12))
+        .padding([4, 10])
+        .style(ui::ghost_button_style(t))
+        .on_press_maybe(
+            selected.is_some().then_some(Message::Bestiary(BestiaryMessage::RelationAdd)),
+        );
+
+    let add_row = Row::new().spacing(6).align_y(Alignment::Center)
+        .push(
+            pick_list(
+                &CreatureRelationKind::ALL[..],
+                Some(editor.relation_kind),
+                |k| Message::Bestiary(BestiaryMessage::RelationKindChanged(k)),
+            )
+                .text_size(12)
+                .padding([4, 8]),
+        )
+        .push(
+            pick_list(targets, selected, |o| {
+                Message::Bestiary(BestiaryMessage::RelationTargetChanged(o.id.to_string()))
+            })
+                .placeholder("Select creature...")
+                .text_size(12)
+                .padding([4, 8])
+                .width(Length::Fill),
+        )
+        .push(add_btn);
+
+    col.push(add_row).into()
+}
+
+// "Appears in": escenas que mencionan a la criatura; click => salta a Forge
+fn mentions_section<'a>(t: ui::Tokens, mentions: &'a [SceneMention]) -> E<'a> {
+    let mut col = Column::new().spacing(4)
+        .push(text(format!("Appears in ({} scenes)", mentions.len())).size(12).color(t.muted_fg));
+
+    if mentions.is_empty() {
+        col = col.push(text("Not mentioned in any scene yet.").size(12).color(t.muted_fg));
+    } else {
+        for m in mentions {
+            let label = format!("{} › {} › {}", m.novel_title, m.chapter_title, m.scene_title);
+            col = col.push(
+                button(text(label).size(12).color(t.foreground))
+                    .padding([4, 8])
+                    .width(Length::Fill)
+                    .style(ui::ghost_button_style(t))
+                    .on_press(Message::TheForge(TheForgeMessage::JumpToScene {
+                        universe_id: m.universe_id.clone(),
+                        novel_id: m.novel_id.clone(),
+                        chapter_id: m.chapter_id.clone(),
+                        scene_id: m.scene_id.clone(),
+                    })),
+            );
+        }
+    }
+
+    container(scrollable(col)).max_height(140.0).into()
 }
 
 // FIX: Firma cambiada a Vec<&'a Creature> para evitar E0515
 fn creatures_grid_counted<'a, I>(
     t: ui::Tokens,
     creatures: I,
-    locations: &'a [Location],
+    state: &'a AppState,
     universe_id: &'a str,
 ) -> (E<'a>, usize)
 where
@@ -136,7 +440,7 @@ where
     let mut total: usize = 0;
 
     for (idx, c) in creatures.into_iter() {
-        row = row.push(container(creature_card(t, idx, c, locations, universe_id)).width(Length::Fill));
+        row = row.push(container(creature_card(t, idx, c, state, universe_id)).width(Length::Fill));
         in_row += 1;
         total += 1;
 
@@ -154,7 +458,10 @@ where
     (col.into(), total)
 }
 
-fn creature_card<'a>(t: ui::Tokens, index: usize, c: &'a Creature, locations: &'a [Location], universe_id: &'a str) -> E<'a> {
+// Listas de apoyo (locations, relaciones, selección) salen del state: la card solo lee
+fn creature_card<'a>(t: ui::Tokens, index: usize, c: &'a Creature, state: &'a AppState, universe_id: &'a str) -> E<'a> {
+    let locations = &state.locations;
+    let is_selected = state.bestiary_selected.contains(&c.id);
     let location_info = if let Some(lid) = &c.home_location_id {
         let name = locations.iter().find(|l| l.id == *lid).map(|l| l.name.as_str()).unwrap_or("Unknown");
         button(text(format!("📍 {}", name)).size(12).color(t.accent)).padding(0).style(crate::ui::ghost_button_style(t)).on_press(Message::GoToLocation(universe_id.to_string(), lid.clone()))
@@ -172,12 +479,35 @@ fn creature_card<'a>(t: ui::Tokens, index: usize, c: &'a Creature, locations: &'
             .push(ui::danger_button(t, "Delete".to_string(), Message::Bestiary(BestiaryMessage::Delete(c.id.clone()))))
     };
 
+    // Multi-select para archivar/restaurar en lote
+    let select_toggle = button(text(if is_selected { "✓" } else { "○" }).size(12).color(if is_selected { t.accent } else { t.muted_fg }))
+        .padding([2, 6])
+        .style(ui::ghost_button_style(t))
+        .on_press(Message::Bestiary(BestiaryMessage::SelectionToggled(c.id.clone())));
+
     let body = Column::new().spacing(6)
-        .push(text(&c.name).size(16).color(t.foreground))
+        .push(Row::new().spacing(8).align_y(Alignment::Center)
+            .push(text(&c.name).size(16).color(t.foreground).width(Length::Fill))
+            .push(select_toggle))
         .push(text(&c.kind).size(12).color(t.muted_fg))
         .push(location_info)
         .push(text(&c.description).size(12).color(t.muted_fg))
-        .push(text(format!("Danger: {}", c.danger)).size(12).color(t.foreground))
+        .push(danger_chip(t, c.danger_level(), c.danger_level().as_str().to_string(), false));
+
+    // Resumen de relaciones (solo lectura; se editan en el modal)
+    let related: Vec<String> = state
+        .creature_relations
+        .iter()
+        .filter_map(|r| r.seen_from(&c.id))
+        .map(|(other_id, label)| format!("{} {}", label, creature_name(&state.creatures, other_id)))
+        .collect();
+    let body = if related.is_empty() {
+        body
+    } else {
+        body.push(text(format!("🔗 {}", related.join(" · "))).size(11).color(t.muted_fg))
+    };
+
+    let body = body
         .push(actions)
         .push(text("Double-click card to edit creature.").size(10).color(Color::from_rgba8(0xA1, 0xA1, 0xA1, 0.55)));
 
@@ -0,0 +1,133 @@
+use iced::{Alignment, Color, Length, Vector};
+use iced::widget::{container, text, text_input, text_editor, Column, Row, Space};
+use iced::Theme;
+use crate::app::{AppState, Message, GlossaryMessage};
+use crate::model::GlossaryTerm;
+use crate::{pages::E, ui};
+
+pub fn glossary<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -> E<'a> {
+    let universe_name = state.universes.iter().find(|u| u.id == universe_id)
+        .map(|u| u.name.as_str()).unwrap_or("Unknown");
+
+    let header = Row::new().align_y(Alignment::Center).spacing(10)
+        .push(
+            Column::new()
+                .push(text(format!("Glossary — {}", universe_name)).size(26).color(t.foreground))
+                .push(text("Invented terms, names and how to pronounce them.").size(12).color(t.muted_fg))
+        )
+        .push(Space::new().width(Length::Fill))
+        .push(ui::outline_button(t, "Back".to_string(), Message::BackToUniverse(universe_id.to_string())))
+        .push(ui::primary_button(t, "Add Term".to_string(), Message::Glossary(GlossaryMessage::EditorOpenCreate)));
+
+    let sort_label = if state.glossary_sort_desc { "Z → A" } else { "A → Z" };
+    let toolbar = Row::new().spacing(10).align_y(Alignment::Center)
+        .push(
+            text_input("Search terms, pronunciations or definitions...", &state.glossary_search)
+                .on_input(|v| Message::Glossary(GlossaryMessage::SearchChanged(v)))
+                .padding(8)
+                .width(Length::Fill)
+                .style(ui::input_style(t)),
+        )
+        .push(ui::ghost_button(t, sort_label.to_string(), Message::Glossary(GlossaryMessage::ToggleSortOrder)));
+
+    let terms = visible_terms(&state.glossary, &state.glossary_search, state.glossary_sort_desc);
+    let shown = terms.len();
+
+    let mut list = Column::new().spacing(6);
+    if terms.is_empty() {
+        let msg = if state.glossary.is_empty() { "No terms yet." } else { "No terms match your search." };
+        list = list.push(ui::card(t, text(msg).size(14).color(t.muted_fg).into()));
+    } else {
+        for g in terms {
+            list = list.push(term_row(t, g));
+        }
+    }
+
+    let count = text(format!("{} of {} terms", shown, state.glossary.len()))
+        .size(11)
+        .color(t.muted_fg);
+
+    let content = Column::new().spacing(16).push(header).push(toolbar).push(count).push(list);
+    ui::page_padding(content.into())
+}
+
+// Filtro case-insensitive sobre term/pronunciation/definition + orden alfabético
+fn visible_terms<'a>(all: &'a [GlossaryTerm], query: &str, desc: bool) -> Vec<&'a GlossaryTerm> {
+    let q = query.trim().to_lowercase();
+    let mut out: Vec<&GlossaryTerm> = all
+        .iter()
+        .filter(|g| {
+            q.is_empty()
+                || g.term.to_lowercase().contains(&q)
+                || g.pronunciation.to_lowercase().contains(&q)
+                || g.definition.to_lowercase().contains(&q)
+        })
+        .collect();
+
+    out.sort_by_key(|g| g.term.to_lowercase());
+    if desc {
+        out.reverse();
+    }
+    out
+}
+
+fn term_row<'a>(t: ui::Tokens, g: &'a GlossaryTerm) -> E<'a> {
+    let mut title = Row::new().spacing(10).align_y(Alignment::Center)
+        .push(text(&g.term).size(16).color(t.foreground));
+    if !g.pronunciation.is_empty() {
+        title = title.push(text(format!("/{}/", g.pronunciation)).size(12).color(t.accent));
+    }
+
+    let mut info = Column::new().spacing(4).width(Length::Fill).push(title);
+    if !g.definition.is_empty() {
+        info = info.push(text(&g.definition).size(12).color(t.muted_fg));
+    }
+
+    let row = Row::new().spacing(8).align_y(Alignment::Center)
+        .push(info)
+        .push(ui::ghost_button(t, "Edit".to_string(), Message::Glossary(GlossaryMessage::Edit(g.id.clone()))))
+        .push(ui::danger_button(t, "×".to_string(), Message::Glossary(GlossaryMessage::Delete(g.id.clone()))));
+
+    container(row)
+        .padding([8, 12])
+        .width(Length::Fill)
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(t.card, t.foreground);
+            s.border.color = t.border;
+            s.border.width = 1.0;
+            s.border.radius = 6.0.into();
+            s
+        })
+        .into()
+}
+
+pub fn render_glossary_modal<'a>(t: ui::Tokens, editor: &'a crate::app::GlossaryEditor) -> E<'a> {
+    let title = if editor.id.is_some() { "Edit Term" } else { "Add Term" };
+
+    let term_input = text_input("Term", &editor.term)
+        .on_input(|v| Message::Glossary(GlossaryMessage::TermChanged(v)))
+        .padding(10).style(ui::input_style(t));
+
+    let pron_input = text_input("Pronunciation (e.g. ar-HEL-is)", &editor.pronunciation)
+        .on_input(|v| Message::Glossary(GlossaryMessage::PronunciationChanged(v)))
+        .padding(10).style(ui::input_style(t));
+
+    let def_input = text_editor(&editor.definition)
+        .on_action(|v| Message::Glossary(GlossaryMessage::DefinitionChanged(v)))
+        .padding(10).height(Length::Fixed(150.0)).style(ui::text_editor_style(t));
+
+    let form = Column::new().spacing(16)
+        .push(text(title).size(20).color(t.foreground))
+        .push(Column::new().spacing(6).push(text("Term").size(12).color(t.muted_fg)).push(term_input))
+        .push(Column::new().spacing(6).push(text("Pronunciation").size(12).color(t.muted_fg)).push(pron_input))
+        .push(Column::new().spacing(6).push(text("Definition").size(12).color(t.muted_fg)).push(def_input))
+        .push(Row::new().spacing(10).push(ui::primary_button(t, "Save".to_string(), Message::Glossary(GlossaryMessage::EditorSave))).push(ui::ghost_button(t, "Cancel".to_string(), Message::Glossary(GlossaryMessage::EditorCancel))));
+
+    container(container(form).width(Length::Fixed(550.0)).padding(24).style(move |_: &Theme| {
+        let mut s = ui::container_style(t.popover, t.foreground);
+        s.border.color = t.border; s.border.width = 1.0; s.border.radius = 12.0.into();
+        s.shadow = iced::Shadow { color: Color::BLACK, offset: Vector::new(0.0, 10.0), blur_radius: 40.0 };
+        s
+    })).width(Length::Fill).height(Length::Fill).center_x(Length::Fill).center_y(Length::Fill)
+        .style(move |_: &Theme| ui::container_style(Color::from_rgba8(0,0,0, 0.7), t.foreground)).into()
+}
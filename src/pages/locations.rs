@@ -1,5 +1,5 @@
 use iced::{Alignment, Color, Length, Vector};
-use iced::widget::{button, container, mouse_area, text, text_input, text_editor, Column, Row, Space};
+use iced::widget::{button, container, mouse_area, pick_list, text, text_input, text_editor, Column, Row, Space};
 use iced::Theme;
 use crate::app::{AppState, Message, LocationsMessage};
 use crate::model::Location;
@@ -16,6 +16,8 @@ pub fn locations<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -
                 .push(text("Manage locations, maps, and points of interest.").size(12).color(t.muted_fg))
         )
         .push(Space::new().width(Length::Fill))
+        .push(ui::ghost_button(t, "Expand all".to_string(), Message::Locations(LocationsMessage::ExpandAll)))
+        .push(ui::ghost_button(t, "Collapse all".to_string(), Message::Locations(LocationsMessage::CollapseAll)))
         .push(ui::outline_button(t, "Back".to_string(), Message::BackToUniverse(universe_id.to_string())))
         .push(ui::primary_button(t, "Add New Location".to_string(), Message::Locations(LocationsMessage::EditorOpenCreate(None))));
 
@@ -33,7 +35,9 @@ pub fn locations<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -
             let is_expanded = state.expanded_locations.contains(&loc.id);
             let is_selected = state.selected_location.as_ref() == Some(&loc.id);
 
-            list = list.push(location_node(t, loc, depth, has_children, is_expanded, is_selected));
+            let counts = state.location_counts.get(&loc.id).copied().unwrap_or_default();
+
+            list = list.push(location_node(t, loc, depth, has_children, is_expanded, is_selected, counts));
         }
     }
 
@@ -89,7 +93,8 @@ fn location_node<'a>(
     depth: usize,
     has_children: bool,
     is_expanded: bool,
-    is_selected: bool
+    is_selected: bool,
+    counts: crate::state::LocationCounts,
 ) -> E<'a> {
     // Solo clonamos lo que NECESITA ownership para mensajes
     let id = loc.id.clone();
@@ -115,12 +120,24 @@ fn location_node<'a>(
             s
         });
 
-    let main_info = Row::new()
+    let mut main_info = Row::new()
         .spacing(10)
         .align_y(Alignment::Center)
         .push(text(&loc.name).size(16).color(t.foreground))
         .push(kind_pill);
 
+    // Badges: ceros ocultos para no ensuciar el árbol
+    for (count, singular, plural) in [
+        (counts.children, "sub-location", "sub-locations"),
+        (counts.creatures, "creature", "creatures"),
+        (counts.events, "event", "events"),
+    ] {
+        if count > 0 {
+            let label = format!("{} {}", count, if count == 1 { singular } else { plural });
+            main_info = main_info.push(text(label).size(10).color(t.muted_fg));
+        }
+    }
+
     let actions = Row::new()
         .spacing(4)
         .push(ui::ghost_button(
@@ -190,7 +207,7 @@ fn location_node<'a>(
 }
 
 
-pub fn render_location_modal<'a>(t: ui::Tokens, editor: &'a crate::app::LocationEditor) -> E<'a> {
+pub fn render_location_modal<'a>(t: ui::Tokens, editor: &'a crate::app::LocationEditor, locations: &'a [Location]) -> E<'a> {
     let title = if editor.id.is_some() { "Edit Location" } else if editor.parent_id.is_some() { "Add Sub-Location" } else { "Add New Location" };
 
     let name_input = text_input("Location Name", &editor.name)
@@ -209,7 +226,9 @@ pub fn render_location_modal<'a>(t: ui::Tokens, editor: &'a crate::app::Location
         .push(text(title).size(20).color(t.foreground))
         .push(Column::new().spacing(6).push(text("Name").size(12).color(t.muted_fg)).push(name_input))
         .push(Column::new().spacing(6).push(text("Type").size(12).color(t.muted_fg)).push(kind_input))
+        .push(Column::new().spacing(6).push(text("Parent").size(12).color(t.muted_fg)).push(parent_picker(editor, locations)))
         .push(Column::new().spacing(6).push(text("Description").size(12).color(t.muted_fg)).push(desc_input))
+        .push(ui::image_path_field(t, "Image (optional)", &editor.image_path, |v| Message::Locations(LocationsMessage::ImagePathChanged(v))))
         .push(Row::new().spacing(10).push(ui::primary_button(t, "Save".to_string(), Message::Locations(LocationsMessage::EditorSave))).push(ui::ghost_button(t, "Cancel".to_string(), Message::Locations(LocationsMessage::EditorCancel))));
 
     container(container(form).width(Length::Fixed(550.0)).padding(24).style(move |_: &Theme| {
@@ -219,4 +238,51 @@ pub fn render_location_modal<'a>(t: ui::Tokens, editor: &'a crate::app::Location
         s
     })).width(Length::Fill).height(Length::Fill).center_x(Length::Fill).center_y(Length::Fill)
         .style(move |_: &Theme| ui::container_style(Color::from_rgba8(0,0,0, 0.7), t.foreground)).into()
-}
\ No newline at end of file
+}
+
+// Opción del dropdown de padre (None = nivel raíz)
+#[derive(Debug, Clone, PartialEq)]
+struct ParentOption<'a> {
+    id: Option<&'a str>,
+    name: &'a str,
+}
+
+impl std::fmt::Display for ParentOption<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+// ¿`candidate` es `location_id` o cuelga de ella? (subiendo por parent_id, con tope)
+fn is_self_or_descendant(locations: &[Location], candidate: &str, location_id: &str) -> bool {
+    let mut current = Some(candidate);
+    let mut safeguard = 0;
+    while let Some(curr_id) = current {
+        if curr_id == location_id || safeguard > locations.len() {
+            return true;
+        }
+        safeguard += 1;
+        current = locations.iter().find(|l| l.id == curr_id).and_then(|l| l.parent_id.as_deref());
+    }
+    false
+}
+
+// Reparent: solo destinos válidos (sin la location ni sus descendientes => sin ciclos)
+fn parent_picker<'a>(editor: &'a crate::app::LocationEditor, locations: &'a [Location]) -> E<'a> {
+    let mut options = vec![ParentOption { id: None, name: "(Top level)" }];
+    options.extend(
+        locations
+            .iter()
+            .filter(|l| editor.id.as_deref().is_none_or(|id| !is_self_or_descendant(locations, &l.id, id)))
+            .map(|l| ParentOption { id: Some(l.id.as_str()), name: l.name.as_str() }),
+    );
+
+    let selected = options.iter().find(|o| o.id == editor.parent_id.as_deref()).cloned();
+
+    pick_list(options, selected, |o| {
+        Message::Locations(LocationsMessage::ParentChanged(o.id.map(str::to_string)))
+    })
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
@@ -0,0 +1,155 @@
+// ============================================
+// UI: MARKDOWN PREVIEW (mínimo, sin crates extra)
+// ============================================
+// Soporta lo básico que usan los escritores:
+// - # / ## / ### headings
+// - listas "- ", "* ", "+ " y "1. "
+// - "> " citas, "---" separador
+// - inline: **bold**, *italic* / _italic_, `code`
+// Solo lectura: el editor (forge_content) sigue siendo la fuente de verdad.
+
+use iced::font::{Style as FontStyle, Weight};
+use iced::widget::text::Span;
+use iced::widget::{container, rich_text, row, span, text, Column, Space};
+use iced::{Font, Length};
+
+use crate::pages::E;
+use crate::ui::{self, Tokens};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Inline {
+    Plain,
+    Bold,
+    Italic,
+    Code,
+}
+
+// Parte una línea en segmentos con estilo. Marcadores sin cerrar quedan como texto.
+fn parse_inline(line: &str) -> Vec<(Inline, String)> {
+    let mut out: Vec<(Inline, String)> = Vec::new();
+    let mut plain = String::new();
+    let mut rest = line;
+
+    let flush = |plain: &mut String, out: &mut Vec<(Inline, String)>| {
+        if !plain.is_empty() {
+            out.push((Inline::Plain, std::mem::take(plain)));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let (marker, kind) = if rest.starts_with("**") {
+            ("**", Inline::Bold)
+        } else if c == '*' || c == '_' {
+            (&rest[..1], Inline::Italic)
+        } else if c == '`' {
+            ("`", Inline::Code)
+        } else {
+            plain.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        let after = &rest[marker.len()..];
+        match after.find(marker) {
+            Some(end) if end > 0 => {
+                flush(&mut plain, &mut out);
+                out.push((kind, after[..end].to_string()));
+                rest = &after[end + marker.len()..];
+            }
+            _ => {
+                plain.push_str(marker);
+                rest = after;
+            }
+        }
+    }
+
+    flush(&mut plain, &mut out);
+    out
+}
+
+fn inline_line<'a>(t: Tokens, line: &str, size: f32, base: Font) -> E<'a> {
+    let spans: Vec<Span<'a, (), Font>> = parse_inline(line)
+        .into_iter()
+        .map(|(kind, s)| {
+            let font = match kind {
+                Inline::Plain => base,
+                Inline::Bold => Font { weight: Weight::Bold, ..base },
+                Inline::Italic => Font { style: FontStyle::Italic, ..base },
+                Inline::Code => Font::MONOSPACE,
+            };
+            let sp = span(s).font(font).size(size).color(t.foreground);
+            if kind == Inline::Code {
+                sp.color(t.accent)
+            } else {
+                sp
+            }
+        })
+        .collect();
+
+    rich_text(spans).width(Length::Fill).into()
+}
+
+// "1. foo" => Some(("1.", "foo"))
+fn numbered_item(line: &str) -> Option<(&str, &str)> {
+    let dot = line.find(". ")?;
+    let num = &line[..dot];
+    if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) {
+        Some((&line[..dot + 1], &line[dot + 2..]))
+    } else {
+        None
+    }
+}
+
+pub fn render<'a>(t: Tokens, body: &str) -> E<'a> {
+    let mut col = Column::new().spacing(6).width(Length::Fill);
+    let bold = Font { weight: Weight::Bold, ..Font::default() };
+
+    for raw in body.lines() {
+        let line = raw.trim_end();
+        let trimmed = line.trim_start();
+
+        let el: E<'a> = if trimmed.is_empty() {
+            Space::new().height(Length::Fixed(6.0)).into()
+        } else if let Some(h) = trimmed.strip_prefix("### ") {
+            inline_line(t, h, 16.0, bold)
+        } else if let Some(h) = trimmed.strip_prefix("## ") {
+            inline_line(t, h, 19.0, bold)
+        } else if let Some(h) = trimmed.strip_prefix("# ") {
+            inline_line(t, h, 23.0, bold)
+        } else if trimmed == "---" || trimmed == "***" {
+            ui::h_divider(t)
+        } else if let Some(q) = trimmed.strip_prefix("> ") {
+            container(inline_line(t, q, 14.0, Font { style: FontStyle::Italic, ..Font::default() }))
+                .padding([2, 12])
+                .style(move |_| {
+                    let mut s = ui::container_style(ui::alpha(t.accent, 0.05), t.foreground);
+                    s.border.width = 1.0;
+                    s.border.color = ui::alpha(t.accent, 0.3);
+                    s
+                })
+                .into()
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            row![
+                text("•").size(14).color(t.muted_fg).width(Length::Fixed(18.0)),
+                inline_line(t, item, 14.0, Font::default()),
+            ]
+                .into()
+        } else if let Some((num, item)) = numbered_item(trimmed) {
+            row![
+                text(num.to_string()).size(14).color(t.muted_fg).width(Length::Fixed(28.0)),
+                inline_line(t, item, 14.0, Font::default()),
+            ]
+                .into()
+        } else {
+            inline_line(t, line, 14.0, Font::default())
+        };
+
+        col = col.push(el);
+    }
+
+    col.into()
+}
@@ -4,6 +4,7 @@ pub mod universe_list;
 pub mod universe_detail;
 pub mod bestiary;
 pub mod locations;
+pub mod glossary;
 pub mod timeline;
 pub mod pm_list;
 pub mod pm_board;
@@ -11,6 +12,8 @@ pub mod launcher;
 pub mod stubs;        // ✅ requerido por ui_shell.rs
 pub mod the_forge;
 pub mod trash;
+pub mod settings;
+pub mod markdown_preview;
 
 // --- RE-EXPORTS ---
 pub use overview::overview;
@@ -19,8 +22,9 @@ pub use universe_detail::universe_detail;
 pub use bestiary::bestiary;
 pub use the_forge::the_forge;
 
-pub use stubs::{account_stub, assets_stub}; // ✅ requerido por ui_shell.rs
+pub use stubs::assets_stub; // ✅ requerido por ui_shell.rs
 
 pub use trash::trash_page;
+pub use settings::settings;
 
 pub type E<'a> = iced::Element<'a, crate::messages::Message>;
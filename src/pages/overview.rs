@@ -1,10 +1,13 @@
 use iced::{Alignment, Length};
-use iced::widget::{container, text, Column, Row, Space};
+use iced::widget::{container, text, text_input, Column, Row, Space};
+use iced::Theme;
 
-use crate::app::{AppState, Message, Route};
+use crate::app::{AppState, Message, PmMessage, Route, SettingsMessage, UniverseMessage};
+use crate::messages::TheForgeMessage;
+use crate::model::RecentItem;
 use crate::{pages::E, ui};
 
-pub fn overview<'a>(_state: &'a AppState, t: ui::Tokens) -> E<'a> {
+pub fn overview<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
     // Header row
     let head = Row::new()
         .align_y(Alignment::Center)
@@ -25,13 +28,6 @@ pub fn overview<'a>(_state: &'a AppState, t: ui::Tokens) -> E<'a> {
             .into(),
     );
 
-    // Grids layout
-    let grid_top = Row::new()
-        .spacing(14)
-        .push(ui::recents_card_universe(t))
-        .push(ui::recents_card_forge(t))
-        .width(Length::Fill);
-
     let grid_bottom = Row::new()
         .spacing(14)
         .push(recent_activity_list(t))
@@ -42,13 +38,176 @@ pub fn overview<'a>(_state: &'a AppState, t: ui::Tokens) -> E<'a> {
         .spacing(20)
         .push(head)
         .push(welcome)
-        .push(grid_top)
+        .push(word_goal_card(state, t))
+        .push(recent_items_card(state, t))
         .push(grid_bottom)
         .width(Length::Fill);
 
     ui::page_padding(body.into())
 }
 
+// Meta de palabras del proyecto: total de prosa de todas las novelas vs meta (settings)
+fn word_goal_card<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let total = state.project_word_total.unwrap_or(0).max(0) as u64;
+    let goal = state
+        .active_project
+        .as_ref()
+        .and_then(|p| state.settings.project_word_goal(&p.id));
+
+    let summary = match (state.project_word_total, goal) {
+        (None, _) => "Counting words…".to_string(),
+        (Some(_), Some(g)) => {
+            let pct = (total as f64 / g as f64 * 100.0).min(999.0);
+            format!("{} / {} words ({:.0}%)", total, g, pct)
+        }
+        (Some(_), None) => format!("{} words written", total),
+    };
+
+    let goal_input = Row::new()
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .push(text("Goal").size(12).color(t.muted_fg))
+        .push(
+            container(
+                text_input("e.g. 90000", &state.project_word_goal_input)
+                    .on_input(|v| Message::Settings(SettingsMessage::ProjectWordGoalChanged(v)))
+                    .padding(6)
+                    .style(ui::input_style(t)),
+            )
+                .width(Length::Fixed(110.0)),
+        );
+
+    let head = Row::new()
+        .align_y(Alignment::Center)
+        .push(
+            Column::new()
+                .spacing(2)
+                .push(text("Project word goal").size(14).color(t.foreground))
+                .push(text(summary).size(12).color(t.muted_fg)),
+        )
+        .push(Space::new().width(Length::Fill))
+        .push(goal_input);
+
+    let mut content = Column::new().spacing(10).push(head);
+    if let Some(g) = goal {
+        content = content.push(progress_bar(t, total as f64 / g as f64));
+    }
+
+    ui::card(t, content.into())
+}
+
+// Filas visibles en "Recent" (la tabla guarda más)
+const RECENT_ITEMS_SHOWN: usize = 8;
+
+// Último acceso a universos / boards / novels: click = abrir directo
+fn recent_items_card<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let now = chrono::Utc::now().timestamp();
+
+    let mut list = Column::new().spacing(4);
+    let mut shown = 0usize;
+    for item in state.recent_items.iter().filter(|i| !is_stale(state, i)) {
+        let Some((tag, msg)) = recent_target(item) else {
+            continue;
+        };
+        if crate::controllers::navigation_controller::target_route(&msg)
+            .is_some_and(|r| !crate::controllers::navigation_controller::route_enabled(state, &r))
+        {
+            continue;
+        }
+
+        let row = Row::new()
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .push(text(item.name.as_str()).size(13).color(t.foreground).width(Length::Fill))
+            .push(text(tag).size(11).color(t.muted_fg))
+            .push(text(time_ago(now - item.opened_at)).size(11).color(ui::alpha(t.muted_fg, 0.7)));
+        list = list.push(
+            iced::widget::button(row)
+                .width(Length::Fill)
+                .padding([6, 8])
+                .style(ui::ghost_button_style(t))
+                .on_press(msg),
+        );
+
+        shown += 1;
+        if shown == RECENT_ITEMS_SHOWN {
+            break;
+        }
+    }
+
+    let mut content = Column::new().spacing(10).push(text("Recent").size(14).color(t.foreground));
+    content = if shown == 0 {
+        content.push(text("Universes, boards and novels you open show up here.").size(12).color(t.muted_fg))
+    } else {
+        content.push(list)
+    };
+
+    ui::card(t, content.into())
+}
+
+// Poda en render: el target ya no está en la lista cargada (borrado desde que se leyó "Recent")
+fn is_stale(state: &AppState, item: &RecentItem) -> bool {
+    match item.kind.as_str() {
+        "universe" => !state.universes.is_empty() && !state.universes.iter().any(|u| u.id == item.target_id),
+        "board" => !state.boards_list.is_empty() && !state.boards_list.iter().any(|b| b.id == item.target_id),
+        _ => false,
+    }
+}
+
+fn recent_target(item: &RecentItem) -> Option<(&'static str, Message)> {
+    let id = item.target_id.clone();
+    match item.kind.as_str() {
+        "universe" => Some(("Universe", Message::Universe(UniverseMessage::Open(id)))),
+        "board" => Some(("Board", Message::Pm(PmMessage::OpenBoard(id)))),
+        "novel" => Some((
+            "Novel",
+            Message::TheForge(TheForgeMessage::JumpToNovel { universe_id: item.universe_id.clone(), novel_id: id }),
+        )),
+        _ => None,
+    }
+}
+
+fn time_ago(secs: i64) -> String {
+    match secs.max(0) {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+// Barra con FillPortion (por mil) para no depender del estilo del widget nativo
+fn progress_bar<'a>(t: ui::Tokens, ratio: f64) -> E<'a> {
+    let done = (ratio.clamp(0.0, 1.0) * 1000.0).round() as u16;
+    let fill = if ratio >= 1.0 { iced::Color::from_rgb8(34, 197, 94) } else { t.accent };
+
+    let mut bar = Row::new().height(Length::Fixed(8.0)).width(Length::Fill);
+    if done > 0 {
+        bar = bar.push(
+            container(Space::new())
+                .width(Length::FillPortion(done))
+                .height(Length::Fill)
+                .style(move |_: &Theme| {
+                    let mut s = ui::container_style(fill, t.foreground);
+                    s.border.radius = 4.0.into();
+                    s
+                }),
+        );
+    }
+    if done < 1000 {
+        bar = bar.push(container(Space::new()).width(Length::FillPortion(1000 - done)).height(Length::Fill));
+    }
+
+    container(bar)
+        .width(Length::Fill)
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(t.hover_bg, t.foreground);
+            s.border.radius = 4.0.into();
+            s
+        })
+        .into()
+}
+
 fn recent_activity_list(t: ui::Tokens) -> iced::Element<'static, Message> {
     let title = "Recent activity";
     let items = vec![
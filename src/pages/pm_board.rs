@@ -1,13 +1,13 @@
 use iced::{
     widget::{
-        button, container, mouse_area, scrollable, text, text_editor, text_input, Column, Row,
-        Space,
+        button, container, mouse_area, pick_list, scrollable, text, text_editor, text_input, Column,
+        Row, Space,
     },
     Alignment, Color, Element, Length, Padding, Theme, Vector,
 };
 
 use crate::app::{Message, PmMessage, PmState};
-use crate::model::{BoardColumn, Card, KanbanBoardData};
+use crate::model::{Board, BoardColumn, Card, KanbanBoardData};
 use crate::ui;
 
 pub fn pm_board<'a>(
@@ -19,7 +19,33 @@ pub fn pm_board<'a>(
     let header = Row::new()
         .align_y(Alignment::Center)
         .push(text("Project Board").size(24).color(t.foreground))
+        .push(Space::new().width(Length::Fixed(20.0)))
+        .push(
+            text_input("Search cards…", &state.pm_search)
+                .on_input(|q| Message::Pm(PmMessage::SearchChanged(q)))
+                .padding(8)
+                .size(13)
+                .style(ui::input_style(t))
+                .width(Length::Fixed(260.0)),
+        )
+        .push(if state.pm_search.is_empty() {
+            Space::new().into()
+        } else {
+            ui::ghost_button(t, "Clear".to_string(), Message::Pm(PmMessage::SearchChanged(String::new())))
+        })
         .push(Space::new().width(Length::Fill))
+        .push(ui::ghost_button(
+            t,
+            if state.pm_show_archived { "✓ Archived cards".to_string() } else { "Archived cards".to_string() },
+            Message::Pm(PmMessage::ToggleArchivedPanel),
+        ))
+        .push(Space::new().width(Length::Fixed(8.0)))
+        .push(ui::ghost_button(
+            t,
+            "Save as template".to_string(),
+            Message::Pm(PmMessage::SaveBoardAsTemplate),
+        ))
+        .push(Space::new().width(Length::Fixed(8.0)))
         .push(ui::primary_button(
             t,
             "Create Task".to_string(),
@@ -42,12 +68,33 @@ pub fn pm_board<'a>(
     };
 
     let mut columns_row = Row::new().spacing(16);
+    // "Vencida" se evalúa contra hoy UNA vez por pasada (no por card)
+    let today = crate::model::card_due_today();
+    let query = state.pm_search.trim().to_lowercase();
 
     // ✅ OPTIMIZED: Iterar solo columnas, cards por lookup O(1) + collect O(k)
-    for col in &board_data.columns {
-        let cards = board_data.get_column_cards(&col.id);
+    let last_index = board_data.columns.len().saturating_sub(1);
+    for (index, col) in board_data.columns.iter().enumerate() {
+        let mut cards = board_data.get_column_cards(&col.id);
+        // Filtros (etiqueta + búsqueda): ocultan cards, las columnas quedan (layout estable)
+        if let Some(label) = state.pm_label_filter.as_deref() {
+            cards.retain(|c| c.has_label(label));
+        }
+        if !query.is_empty() {
+            cards.retain(|c| c.matches_query(&query));
+        }
+
+        if state.pm_collapsed_columns.contains(&col.id) {
+            columns_row = columns_row.push(
+                container(render_collapsed_column(t, col, cards.len(), state))
+                    .width(Length::Fixed(44.0))
+                    .height(Length::Fill),
+            );
+            continue;
+        }
+
         columns_row = columns_row.push(
-            container(render_column(t, col, &cards, state))
+            container(render_column(t, col, &cards, state, today, (index > 0, index < last_index)))
                 .width(Length::Fixed(320.0))
                 .height(Length::Fill),
         );
@@ -73,6 +120,12 @@ pub fn pm_board<'a>(
     ui::page_padding(
         Column::new()
             .push(header)
+            .push(label_filter_bar(state, t, board_data.labels()))
+            .push(if state.pm_show_archived {
+                archived_panel(state, t, board_data)
+            } else {
+                Space::new().into()
+            })
             .push(content)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -80,11 +133,144 @@ pub fn pm_board<'a>(
     )
 }
 
+// Chips de etiquetas del board ("All" + una por etiqueta). Sin etiquetas => nada.
+fn label_filter_bar<'a>(
+    state: &'a crate::app::AppState,
+    t: ui::Tokens,
+    labels: Vec<String>,
+) -> Element<'a, Message> {
+    let active = state.pm_label_filter.as_deref();
+    // Filtro colgado (se borró la última card con esa etiqueta): se sigue mostrando para poder limpiarlo
+    if labels.is_empty() && active.is_none() {
+        return Space::new().into();
+    }
+
+    let mut row = Row::new()
+        .spacing(6)
+        .align_y(Alignment::Center)
+        .push(text("Labels:").size(12).color(t.muted_fg))
+        .push(ui::ghost_button(
+            t,
+            if active.is_none() { "✓ All".to_string() } else { "All".to_string() },
+            Message::Pm(PmMessage::LabelFilterChanged(None)),
+        ));
+
+    for label in labels {
+        let selected = active.is_some_and(|a| a.eq_ignore_ascii_case(&label));
+        let color = label_color(&label);
+        let msg = Message::Pm(PmMessage::LabelFilterChanged(if selected { None } else { Some(label.clone()) }));
+        row = row.push(
+            button(text(label).size(11).color(if selected { t.background } else { color }))
+                .padding([3, 10])
+                .style(move |_: &Theme, _| iced::widget::button::Style {
+                    background: Some(if selected { color } else { ui::alpha(color, 0.12) }.into()),
+                    border: iced::Border { radius: 99.0.into(), width: 1.0, color: ui::alpha(color, 0.5) },
+                    ..Default::default()
+                })
+                .on_press(msg),
+        );
+    }
+
+    container(row)
+        .padding(Padding { top: 0.0, right: 0.0, bottom: 14.0, left: 0.0 })
+        .into()
+}
+
+// Panel "Archived cards": listado + Restore (vuelve al final de su columna)
+fn archived_panel<'a>(
+    state: &'a crate::app::AppState,
+    t: ui::Tokens,
+    board_data: &'a KanbanBoardData,
+) -> Element<'a, Message> {
+    let mut list = Column::new().spacing(6);
+
+    if state.pm_archived_cards.is_empty() {
+        list = list.push(text("No archived cards on this board.").size(12).color(t.muted_fg));
+    }
+
+    for card in &state.pm_archived_cards {
+        let column_name = board_data
+            .columns
+            .iter()
+            .find(|c| c.id == card.column_id)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| "(column removed → first column)".to_string());
+
+        list = list.push(
+            Row::new()
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .push(text(&card.title).size(13).color(t.foreground).width(Length::Fill))
+                .push(text(column_name).size(11).color(t.muted_fg))
+                .push(ui::outline_button(
+                    t,
+                    "Restore".to_string(),
+                    Message::Pm(PmMessage::RestoreCard(card.id.clone())),
+                )),
+        );
+    }
+
+    let panel = container(
+        Column::new()
+            .spacing(10)
+            .push(text(format!("🗄 Archived cards ({})", state.pm_archived_cards.len())).size(14).color(t.foreground))
+            .push(scrollable(list).height(Length::Shrink)),
+    )
+        .padding(12)
+        .width(Length::Fill)
+        .max_height(240.0)
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(ui::alpha(t.shell_a, 0.5), t.foreground);
+            s.border.radius = 12.0.into();
+            s.border.width = 1.0;
+            s.border.color = t.border;
+            s
+        });
+
+    container(panel)
+        .padding(Padding { top: 0.0, right: 0.0, bottom: 14.0, left: 0.0 })
+        .into()
+}
+
+// Color estable por etiqueta (mismo texto => mismo color en todos los boards)
+fn label_color(label: &str) -> Color {
+    const PALETTE: [(u8, u8, u8); 8] = [
+        (239, 68, 68),
+        (249, 115, 22),
+        (234, 179, 8),
+        (34, 197, 94),
+        (20, 184, 166),
+        (59, 130, 246),
+        (139, 92, 246),
+        (236, 72, 153),
+    ];
+    let hash = label
+        .to_lowercase()
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    let (r, g, b) = PALETTE[hash as usize % PALETTE.len()];
+    Color::from_rgb8(r, g, b)
+}
+
+fn label_chip(label: &str, alpha_mul: f32) -> Element<'_, Message> {
+    let color = label_color(label);
+    container(text(label).size(10).color(ui::alpha(color, alpha_mul)))
+        .padding([1, 7])
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(ui::alpha(color, 0.15 * alpha_mul), color);
+            s.border.radius = 99.0.into();
+            s
+        })
+        .into()
+}
+
 fn render_column<'a>(
     t: ui::Tokens,
     col: &'a BoardColumn,
     cards: &Vec<&'a Card>,
     state: &'a crate::app::AppState,
+    today: i64,
+    (can_left, can_right): (bool, bool),
 ) -> Element<'a, Message> {
     let mut cards_col = Column::new().spacing(10);
 
@@ -111,7 +297,7 @@ fn render_column<'a>(
 
         if !is_dragging_active {
             // Movemos el PmId al render_card (sin Strings por frame)
-            cards_col = cards_col.push(render_card(t, *card, pm_card_id, 1.0, is_hovered));
+            cards_col = cards_col.push(render_card(t, card, pm_card_id, 1.0, is_hovered, today));
         } else {
             // Placeholder visual cuando se arrastra
             cards_col = cards_col.push(
@@ -130,14 +316,29 @@ fn render_column<'a>(
         }
     }
 
+    // Título = handle de drag de la columna (drop en la columna bajo el cursor)
+    let title = Row::new()
+        .spacing(6)
+        .align_y(Alignment::Center)
+        .push(text("⠿").size(12).color(t.muted_fg))
+        .push(text(&col.name).size(14).color(t.foreground));
+
+    // Reorden sin drag: ← → (deshabilitados en los bordes)
+    let move_btn = |label: &'static str, enabled: bool, direction: i32| {
+        button(text(label).size(12).color(if enabled { t.muted_fg } else { ui::alpha(t.muted_fg, 0.3) }))
+            .padding([2, 6])
+            .style(ui::ghost_button_style(t))
+            .on_press_maybe(enabled.then(|| Message::Pm(PmMessage::MoveColumn(col.id.clone(), direction))))
+    };
+
     let header = Row::new()
         .align_y(Alignment::Center)
         .push(
-            text(&col.name)
-                .size(14)
-                .color(t.foreground)
-                .width(Length::Fill),
+            mouse_area(container(title).width(Length::Fill))
+                .on_press(Message::Pm(PmMessage::ColumnDragStart(col_id.clone()))),
         )
+        .push(move_btn("←", can_left, -1))
+        .push(move_btn("→", can_right, 1))
         .push(
             container(text(format!("{}", cards.len())).size(10).color(t.muted_fg))
                 .padding([2, 6])
@@ -146,6 +347,12 @@ fn render_column<'a>(
                     s.border.radius = 99.0.into();
                     s
                 }),
+        )
+        .push(
+            button(text("‹").size(14).color(t.muted_fg))
+                .padding([2, 8])
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::Pm(PmMessage::ToggleColumnCollapsed(col.id.clone()))),
         );
 
     // Scroll vertical dentro de la columna
@@ -174,6 +381,69 @@ fn render_column<'a>(
                     s.border.color = ui::alpha(t.accent, 0.4);
                 }
             }
+
+            // Columna arrastrada: atenuada + borde más grueso en el destino
+            if let Some(dragged) = &state.pm_column_drag {
+                if dragged.as_ref() == col_id_for_style.as_ref() {
+                    s.background = Some(ui::alpha(t.accent, 0.10).into());
+                    s.border.color = ui::alpha(t.accent, 0.6);
+                } else if state.hovered_column.as_ref().map(|h| h.as_ref()) == Some(col_id_for_style.as_ref()) {
+                    s.border.width = 2.0;
+                }
+            }
+            s
+        });
+
+    mouse_area(content)
+        .on_enter(Message::Pm(PmMessage::ColumnHovered(col_id)))
+        .into()
+}
+
+// Columna colapsada: tira angosta (nombre vertical + count).
+// Sigue registrando hover => un drop acá cae al final de la columna.
+fn render_collapsed_column<'a>(
+    t: ui::Tokens,
+    col: &'a BoardColumn,
+    count: usize,
+    state: &'a crate::app::AppState,
+) -> Element<'a, Message> {
+    let col_id = state.pm_ids.get(col.id.as_str());
+    let col_id_for_style = col_id.clone();
+
+    // Nombre "vertical": una letra por línea (sin rotación de texto en iced)
+    let mut name_col = Column::new().spacing(0).align_x(Alignment::Center);
+    for ch in col.name.chars().take(14) {
+        name_col = name_col.push(text(ch.to_string()).size(12).color(t.foreground));
+    }
+
+    let body = Column::new()
+        .spacing(10)
+        .align_x(Alignment::Center)
+        .push(
+            button(text("›").size(14).color(t.muted_fg))
+                .padding([2, 8])
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::Pm(PmMessage::ToggleColumnCollapsed(col.id.clone()))),
+        )
+        .push(text(format!("{}", count)).size(10).color(t.muted_fg))
+        .push(name_col);
+
+    let content = container(body)
+        .padding([12, 4])
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(ui::alpha(t.shell_a, 0.5), t.foreground);
+            s.border.radius = 12.0.into();
+            s.border.width = 1.0;
+            s.border.color = t.border;
+
+            if let Some(hovered) = &state.hovered_column
+                && hovered.as_ref() == col_id_for_style.as_ref()
+            {
+                s.background = Some(ui::alpha(t.accent, 0.06).into());
+                s.border.color = ui::alpha(t.accent, 0.4);
+            }
             s
         });
 
@@ -188,6 +458,7 @@ fn render_card(
     card_id: crate::app::PmId,
     alpha_mul: f32,
     is_hovered: bool,
+    today: i64,
 ) -> Element<'_, Message> {
     let title_color = ui::alpha(t.foreground, alpha_mul);
     let bg_color = if alpha_mul < 1.0 {
@@ -220,6 +491,30 @@ fn render_card(
                 ),
         );
 
+    let content = if card.labels.trim().is_empty() {
+        content
+    } else {
+        let mut chips = Row::new().spacing(4);
+        for label in card.label_list() {
+            chips = chips.push(label_chip(label, alpha_mul));
+        }
+        content.push(chips.wrap())
+    };
+
+    let content = match card.due() {
+        Some(due) => {
+            let overdue = card.is_overdue(today);
+            let color = if overdue { Color::from_rgb8(239, 68, 68) } else { t.muted_fg };
+            let label = if overdue {
+                format!("⏰ {} · overdue", crate::model::format_card_due_date(due))
+            } else {
+                format!("📅 {}", crate::model::format_card_due_date(due))
+            };
+            content.push(text(label).size(11).color(ui::alpha(color, alpha_mul)))
+        }
+        None => content,
+    };
+
     let has_desc = !card.description.trim().is_empty();
     let final_content = if has_desc {
         content.push(
@@ -267,13 +562,19 @@ fn render_card(
 }
 
 // render_modal se mantiene igual (UI de crear/editar)
-pub fn render_modal<'a>(
-    t: ui::Tokens,
-    title: &'a str,
-    desc: &'a text_editor::Content,
-    priority: &'a str,
-    is_new: bool,
-) -> Element<'a, Message> {
+// Campos del form, prestados de PmState::Editing
+pub struct CardForm<'a> {
+    pub title: &'a str,
+    pub desc: &'a text_editor::Content,
+    pub priority: &'a str,
+    pub labels: &'a str,
+    pub due_date: &'a str,
+    pub card_id: Option<&'a str>,
+}
+
+pub fn render_modal<'a>(t: ui::Tokens, form: CardForm<'a>, move_targets: Vec<Board>) -> Element<'a, Message> {
+    let CardForm { title, desc, priority, labels, due_date, card_id } = form;
+    let is_new = card_id.is_none();
     let header_text = if is_new { "Create Task" } else { "Edit Task" };
 
     let p_btn = |label: &str, val: &str| {
@@ -337,6 +638,63 @@ pub fn render_modal<'a>(
                 ),
         )
         .push(priority_row)
+        .push(
+            Column::new()
+                .spacing(6)
+                .push(text("Labels").size(12).style(move |_| iced::widget::text::Style {
+                    color: Some(t.muted_fg),
+                }))
+                .push(
+                    text_input("bug, feature", labels)
+                        .on_input(|v| Message::Pm(PmMessage::LabelsChanged(v)))
+                        .padding(10)
+                        .style(ui::input_style(t)),
+                ),
+        )
+        .push(
+            Column::new()
+                .spacing(6)
+                .push(text("Due date").size(12).style(move |_| iced::widget::text::Style {
+                    color: Some(t.muted_fg),
+                }))
+                .push(
+                    text_input("YYYY-MM-DD (optional)", due_date)
+                        .on_input(|v| Message::Pm(PmMessage::DueDateChanged(v)))
+                        .padding(10)
+                        .width(Length::Fixed(220.0))
+                        .style(ui::input_style(t)),
+                ),
+        );
+
+    // Mover a otro board (solo cards existentes y si hay otro board)
+    let form = match card_id {
+        Some(cid) if !move_targets.is_empty() => {
+            let cid = cid.to_string();
+            form.push(
+                Row::new()
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(text("Move to board:").size(12).style(move |_| iced::widget::text::Style {
+                        color: Some(t.muted_fg),
+                    }))
+                    .push(
+                        pick_list(move_targets, None::<Board>, move |b: Board| {
+                            Message::Pm(PmMessage::MoveCardToBoard {
+                                card_id: cid.clone(),
+                                target_board_id: b.id,
+                            })
+                        })
+                            .placeholder("Select board...")
+                            .width(Length::Fixed(220.0))
+                            .padding(6)
+                            .text_size(12),
+                    ),
+            )
+        }
+        _ => form,
+    };
+
+    let form = form
         .push(
             Row::new()
                 .spacing(10)
@@ -352,6 +710,11 @@ pub fn render_modal<'a>(
                     Message::Pm(PmMessage::Cancel),
                 ))
                 .push(Space::new().width(Length::Fill))
+                .push(if !is_new {
+                    ui::ghost_button(t, "Archive".to_string(), Message::Pm(PmMessage::Archive))
+                } else {
+                    Space::new().into()
+                })
                 .push(if !is_new {
                     ui::danger_button(t, "Delete".to_string(), Message::Pm(PmMessage::Delete))
                 } else {
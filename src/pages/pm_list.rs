@@ -1,5 +1,5 @@
 use iced::{Alignment, Length};
-use iced::widget::{container, text, text_input, Column, Row};
+use iced::widget::{container, pick_list, text, text_input, Column, Row};
 
 use crate::app::{AppState, Message, PmMessage};
 use crate::model::Board;
@@ -18,11 +18,39 @@ pub fn pm_list<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
 
     let create_btn = ui::primary_button(t, "Create Board".to_string(), Message::Pm(PmMessage::CreateBoard));
 
-    let form = Row::new()
+    let mut form = Row::new()
         .spacing(10)
         .align_y(Alignment::Center)
-        .push(container(name_input).width(Length::Fixed(300.0)))
-        .push(create_btn);
+        .push(container(name_input).width(Length::Fixed(300.0)));
+
+    // Template picker: solo si hay templates guardados ("Save as template" en un board)
+    if !state.board_templates.is_empty() {
+        let selected = state
+            .new_board_template_id
+            .as_ref()
+            .and_then(|id| state.board_templates.iter().find(|tpl| &tpl.id == id))
+            .cloned();
+
+        form = form
+            .push(
+                pick_list(state.board_templates.clone(), selected, |tpl| {
+                    Message::Pm(PmMessage::BoardTemplatePicked(Some(tpl.id)))
+                })
+                    .placeholder("Default columns")
+                    .text_size(12)
+                    .padding([8, 10]),
+            );
+
+        if state.new_board_template_id.is_some() {
+            form = form.push(ui::ghost_button(
+                t,
+                "×".to_string(),
+                Message::Pm(PmMessage::BoardTemplatePicked(None)),
+            ));
+        }
+    }
+
+    let form = form.push(create_btn);
 
     let header = Row::new()
         .spacing(20)
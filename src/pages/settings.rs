@@ -0,0 +1,550 @@
+use iced::{Alignment, Length};
+use iced::widget::{container, mouse_area, pick_list, text, text_input, Column, Row, Space};
+use iced::Theme;
+
+use crate::app::{AppState, Message, SettingsMessage};
+use crate::{ui, pages::E};
+
+pub fn settings<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let header = Column::new()
+        .spacing(4)
+        .push(text("Settings").size(26).color(t.foreground))
+        .push(text("Application preferences (shared by all workspaces).").size(12).color(t.muted_fg));
+
+    let body = Column::new()
+        .spacing(20)
+        .push(header)
+        .push(ui::h_divider(t))
+        .push(auto_backup_section(state, t))
+        .push(auto_snapshot_section(state, t))
+        .push(break_reminder_section(state, t))
+        .push(sidebar_section(state, t))
+        .push(theme_section(state, t))
+        .push(capabilities_section(state, t))
+        .push(project_settings_io_section(state, t))
+        .push(trash_retention_section(state, t))
+        .push(database_backup_section(state, t))
+        .push(project_archive_section(state, t))
+        .push(audit_log_section(state, t))
+        .width(Length::Fill);
+
+    ui::page_padding(body.into())
+}
+
+fn labeled_input<'a>(
+    t: ui::Tokens,
+    label: &'a str,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+) -> E<'a> {
+    Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text(label).size(13).color(t.muted_fg).width(Length::Fixed(220.0)))
+        .push(
+            container(
+                text_input("", value)
+                    .on_input(on_input)
+                    .padding(8)
+                    .style(ui::input_style(t)),
+            )
+                .width(Length::Fixed(120.0)),
+        )
+        .into()
+}
+
+fn auto_backup_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let s = &state.settings;
+
+    // Toggle con el mismo patrón de botones que el resto de la app
+    let toggle_msg = Message::Settings(SettingsMessage::AutoBackupToggled(!s.auto_backup_enabled));
+    let toggle = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text("Automatic backups").size(13).color(t.muted_fg).width(Length::Fixed(220.0)))
+        .push(if s.auto_backup_enabled {
+            ui::primary_button(t, "✓ Enabled".to_string(), toggle_msg)
+        } else {
+            ui::outline_button(t, "Disabled".to_string(), toggle_msg)
+        });
+
+    let last = if state.auto_backup_in_progress {
+        "Backing up…".to_string()
+    } else if state.auto_backup_pending_changes {
+        "Changes pending since last backup".to_string()
+    } else {
+        "Up to date".to_string()
+    };
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Auto-backup").size(16).color(t.foreground))
+        .push(
+            text("Copies the open workspace to a rotating set of files after N minutes with changes.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(toggle)
+        .push(labeled_input(
+            t,
+            "Interval (minutes)",
+            &state.settings_backup_interval_input,
+            |v| Message::Settings(SettingsMessage::AutoBackupIntervalChanged(v)),
+        ))
+        .push(labeled_input(
+            t,
+            "Backups to keep",
+            &state.settings_backup_keep_input,
+            |v| Message::Settings(SettingsMessage::AutoBackupKeepChanged(v)),
+        ))
+        .push(text(format!("Status: {}", last)).size(12).color(t.muted_fg));
+
+    ui::card(t, content.into())
+}
+
+fn auto_snapshot_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let s = &state.settings;
+
+    let toggle_msg = Message::Settings(SettingsMessage::AutoSnapshotToggled(!s.auto_snapshot_enabled));
+    let toggle = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text("Snapshot before risky actions").size(13).color(t.muted_fg).width(Length::Fixed(220.0)))
+        .push(if s.auto_snapshot_enabled {
+            ui::primary_button(t, "✓ Enabled".to_string(), toggle_msg)
+        } else {
+            ui::outline_button(t, "Disabled".to_string(), toggle_msg)
+        });
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Auto-snapshots").size(16).color(t.foreground))
+        .push(
+            text("Takes an \"auto-before-…\" snapshot of the affected universes before resetting demo data or emptying the trash. Manual snapshots are never pruned.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(toggle)
+        .push(labeled_input(
+            t,
+            "Auto-snapshots to keep",
+            &state.settings_snapshot_keep_input,
+            |v| Message::Settings(SettingsMessage::AutoSnapshotKeepChanged(v)),
+        ));
+
+    ui::card(t, content.into())
+}
+
+fn break_reminder_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let s = &state.settings;
+
+    let toggle_msg = Message::Settings(SettingsMessage::BreakReminderToggled(!s.break_reminder_enabled));
+    let toggle = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text("Break reminders").size(13).color(t.muted_fg).width(Length::Fixed(220.0)))
+        .push(if s.break_reminder_enabled {
+            ui::primary_button(t, "✓ Enabled".to_string(), toggle_msg)
+        } else {
+            ui::outline_button(t, "Disabled".to_string(), toggle_msg)
+        });
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Writing breaks").size(16).color(t.foreground))
+        .push(
+            text("Suggests a short break after continuous writing in The Forge. Pausing 5+ minutes resets the count.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(toggle)
+        .push(labeled_input(
+            t,
+            "Remind after (minutes)",
+            &state.settings_break_interval_input,
+            |v| Message::Settings(SettingsMessage::BreakReminderIntervalChanged(v)),
+        ));
+
+    ui::card(t, content.into())
+}
+
+// Editor del sidebar: arrastrar filas para reordenar, botón para ocultar/mostrar
+fn sidebar_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let dragging = state.settings_sidebar_drag;
+
+    let mut rows = Column::new().spacing(4);
+    for (idx, entry) in state.settings.sidebar_sections.iter().enumerate() {
+        let is_dragged = dragging == Some(idx);
+        let label_color = if entry.visible { t.foreground } else { t.muted_fg };
+
+        let toggle_msg = Message::Settings(SettingsMessage::SidebarVisibilityToggled(entry.section, !entry.visible));
+        let toggle = if entry.visible {
+            ui::ghost_button(t, "✓ Visible".to_string(), toggle_msg)
+        } else {
+            ui::outline_button(t, "Hidden".to_string(), toggle_msg)
+        };
+
+        let row = Row::new()
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .push(text("⠿").size(14).color(t.muted_fg))
+            .push(text(entry.section.label()).size(13).color(label_color))
+            .push(Space::new().width(Length::Fill))
+            .push(toggle);
+
+        let row = container(row)
+            .padding([4, 10])
+            .width(Length::Fill)
+            .style(move |_: &Theme| {
+                let bg = if is_dragged { t.active_bg } else { t.card };
+                let mut s = ui::container_style(bg, t.foreground);
+                s.border.color = if is_dragged { t.accent } else { t.border };
+                s.border.width = 1.0;
+                s.border.radius = 6.0.into();
+                s
+            });
+
+        rows = rows.push(
+            mouse_area(row)
+                .on_press(Message::Settings(SettingsMessage::SidebarDragStart(idx)))
+                .on_enter(Message::Settings(SettingsMessage::SidebarDragOver(idx))),
+        );
+    }
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Sidebar").size(16).color(t.foreground))
+        .push(
+            text("Drag to reorder the modules; hidden ones stay reachable from their links. Settings is always shown.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(rows)
+        .push(ui::ghost_button(t, "Reset to default".to_string(), Message::Settings(SettingsMessage::SidebarReset)));
+
+    ui::card(t, content.into())
+}
+
+fn project_settings_io_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let busy = state.settings_io_busy;
+    let has_project = state.active_project.is_some();
+
+    let export_btn: E<'a> = if busy || !has_project {
+        ui::card(t, text("Export settings").size(12).color(t.muted_fg).into())
+    } else {
+        ui::outline_button(
+            t,
+            "Export settings".to_string(),
+            Message::Settings(SettingsMessage::ExportProjectSettings),
+        )
+    };
+
+    let import_btn: E<'a> = if busy || !has_project || state.settings_import_path.trim().is_empty() {
+        ui::card(t, text("Import").size(12).color(t.muted_fg).into())
+    } else {
+        ui::primary_button(
+            t,
+            "Import".to_string(),
+            Message::Settings(SettingsMessage::ImportProjectSettings),
+        )
+    };
+
+    let import_row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(
+            text_input("Path to settings JSON", &state.settings_import_path)
+                .on_input(|v| Message::Settings(SettingsMessage::ImportPathChanged(v)))
+                .padding(8)
+                .style(ui::input_style(t)),
+        )
+        .push(import_btn);
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Project configuration").size(16).color(t.foreground))
+        .push(
+            text("Export or import the open workspace's enabled features plus these preferences (no content).")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(export_btn)
+        .push(import_row);
+
+    ui::card(t, content.into())
+}
+
+fn database_backup_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let busy = state.settings_io_busy;
+    let has_project = state.active_project.is_some();
+    let has_path = !state.settings_db_backup_path.trim().is_empty();
+
+    let backup_btn: E<'a> = if busy || !has_project {
+        ui::card(t, text("Back up now").size(12).color(t.muted_fg).into())
+    } else {
+        ui::outline_button(
+            t,
+            "Back up now".to_string(),
+            Message::Settings(SettingsMessage::BackupDatabase),
+        )
+    };
+
+    let restore_btn: E<'a> = if busy || !has_project || !has_path {
+        ui::card(t, text("Restore").size(12).color(t.muted_fg).into())
+    } else {
+        ui::danger_button(
+            t,
+            "Restore".to_string(),
+            Message::Settings(SettingsMessage::RestoreDatabase),
+        )
+    };
+
+    let path_row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(
+            text_input("Backup file (.db) — empty = backups folder", &state.settings_db_backup_path)
+                .on_input(|v| Message::Settings(SettingsMessage::DbBackupPathChanged(v)))
+                .padding(8)
+                .style(ui::input_style(t)),
+        )
+        .push(backup_btn)
+        .push(restore_btn);
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Database backup").size(16).color(t.foreground))
+        .push(
+            text("Full copy of the open workspace. A restore replaces it the next time the workspace is opened; the current file is kept as .pre-restore.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(path_row);
+
+    ui::card(t, content.into())
+}
+
+fn project_archive_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let has_project = state.active_project.is_some();
+
+    let export_btn: E<'a> = if !has_project {
+        ui::card(t, text("Export project").size(12).color(t.muted_fg).into())
+    } else {
+        ui::outline_button(
+            t,
+            "Export project".to_string(),
+            Message::Settings(SettingsMessage::ExportProjectArchive),
+        )
+    };
+
+    let import_btn: E<'a> = if !has_project || state.settings_archive_path.trim().is_empty() {
+        ui::card(t, text("Import").size(12).color(t.muted_fg).into())
+    } else {
+        ui::primary_button(
+            t,
+            "Import".to_string(),
+            Message::Settings(SettingsMessage::ImportProjectArchive),
+        )
+    };
+
+    let import_row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(
+            text_input("Path to project archive JSON", &state.settings_archive_path)
+                .on_input(|v| Message::Settings(SettingsMessage::ArchivePathChanged(v)))
+                .padding(8)
+                .style(ui::input_style(t)),
+        )
+        .push(import_btn);
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Project archive").size(16).color(t.foreground))
+        .push(
+            text("All content (universes, bestiary, locations, timeline, boards, novels) in one JSON file. Import aborts if any id already exists.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(export_btn)
+        .push(import_row);
+
+    ui::card(t, content.into())
+}
+
+fn audit_log_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let has_project = state.active_project.is_some();
+
+    let filter_input = |placeholder: &'static str, value: &'a str, on: fn(String) -> SettingsMessage| {
+        text_input(placeholder, value)
+            .on_input(move |v| Message::Settings(on(v)))
+            .on_submit(Message::Settings(SettingsMessage::AuditApply))
+            .padding(8)
+            .width(Length::Fixed(150.0))
+            .style(ui::input_style(t))
+    };
+
+    let mut filters = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(filter_input("From (YYYY-MM-DD)", &state.audit_filter_from, SettingsMessage::AuditFromChanged))
+        .push(filter_input("To (YYYY-MM-DD)", &state.audit_filter_to, SettingsMessage::AuditToChanged))
+        .push(filter_input("Action", &state.audit_filter_action, SettingsMessage::AuditActionChanged))
+        .push(filter_input("Entity type", &state.audit_filter_entity, SettingsMessage::AuditEntityChanged));
+
+    filters = if has_project {
+        filters
+            .push(ui::outline_button(t, "Apply".to_string(), Message::Settings(SettingsMessage::AuditApply)))
+            .push(ui::ghost_button(t, "Export CSV".to_string(), Message::Settings(SettingsMessage::AuditExport)))
+    } else {
+        filters.push(ui::card(t, text("Apply").size(12).color(t.muted_fg).into()))
+    };
+
+    let mut list = Column::new().spacing(4);
+    if !state.audit_loaded {
+        list = list.push(text("Apply filters to load entries.").size(12).color(t.muted_fg));
+    } else if state.audit_rows.is_empty() {
+        list = list.push(text("No entries match these filters.").size(12).color(t.muted_fg));
+    } else {
+        for r in state.audit_rows.iter().take(50) {
+            let when = chrono::DateTime::from_timestamp(r.ts, 0)
+                .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            list = list.push(
+                text(format!("{}  ·  {}  ·  {} {}", when, r.action, r.entity_type, r.entity_id))
+                    .size(12)
+                    .color(t.foreground),
+            );
+        }
+        if state.audit_rows.len() > 50 {
+            list = list.push(
+                text(format!("…and {} more (export to see all)", state.audit_rows.len() - 50))
+                    .size(12)
+                    .color(t.muted_fg),
+            );
+        }
+    }
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Audit log").size(16).color(t.foreground))
+        .push(
+            text("Every write in the open workspace. Empty filters mean no constraint; dates are UTC.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(filters)
+        .push(list);
+
+    ui::card(t, content.into())
+}
+
+fn capabilities_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    use crate::guards::Capabilities;
+
+    let mut content = Column::new()
+        .spacing(12)
+        .push(text("Features").size(16).color(t.foreground))
+        .push(
+            text("Per workspace. A disabled feature is hidden from the sidebar and its writes are refused; existing data is kept.")
+                .size(12)
+                .color(t.muted_fg),
+        );
+
+    match (&state.active_project, &state.capabilities) {
+        (None, _) => {
+            content = content.push(ui::card(t, text("Open a workspace").size(12).color(t.muted_fg).into()));
+        }
+        (Some(_), None) => {
+            content = content.push(text("Loading…").size(12).color(t.muted_fg));
+        }
+        (Some(_), Some(caps)) => {
+            for (key, label) in Capabilities::TOGGLES {
+                let enabled = caps.is_enabled(key);
+                let toggle_msg = Message::Settings(SettingsMessage::CapabilityToggled(key.to_string(), !enabled));
+                content = content.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_y(Alignment::Center)
+                        .push(text(label).size(13).color(t.muted_fg).width(Length::Fixed(220.0)))
+                        .push(if enabled {
+                            ui::primary_button(t, "✓ Enabled".to_string(), toggle_msg)
+                        } else {
+                            ui::outline_button(t, "Disabled".to_string(), toggle_msg)
+                        }),
+                );
+            }
+        }
+    }
+
+    ui::card(t, content.into())
+}
+
+fn theme_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    use crate::ui::ThemeMode;
+
+    let selector: E<'a> = if state.active_project.is_some() {
+        pick_list(&ThemeMode::ALL[..], Some(state.ui_theme), |m| {
+            Message::Settings(SettingsMessage::ThemeChanged(m))
+        })
+            .padding(8)
+            .into()
+    } else {
+        ui::card(t, text("Open a workspace").size(12).color(t.muted_fg).into())
+    };
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Theme").size(16).color(t.foreground))
+        .push(
+            text("Per workspace. Applies right away and is remembered the next time the workspace is opened.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .push(text("Appearance").size(13).color(t.foreground))
+                .push(selector),
+        );
+
+    ui::card(t, content.into())
+}
+
+fn trash_retention_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    use crate::db::trash::TrashRetention;
+
+    let has_project = state.active_project.is_some();
+
+    let selector: E<'a> = if has_project {
+        pick_list(&TrashRetention::ALL[..], Some(state.trash_retention), |r| {
+            Message::Settings(SettingsMessage::TrashRetentionChanged(r))
+        })
+            .padding(8)
+            .into()
+    } else {
+        ui::card(t, text("Open a workspace").size(12).color(t.muted_fg).into())
+    };
+
+    let mut row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text("Keep deleted items for").size(13).color(t.foreground))
+        .push(selector);
+    if has_project && !state.trash_retention.is_never() {
+        row = row.push(ui::ghost_button(t, "Clean now".to_string(), Message::CleanupOldTrash));
+    }
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text("Trash retention").size(16).color(t.foreground))
+        .push(
+            text("Per workspace. Older items are purged when the workspace is opened; changing this never purges right away.")
+                .size(12)
+                .color(t.muted_fg),
+        )
+        .push(row);
+
+    ui::card(t, content.into())
+}
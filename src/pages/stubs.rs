@@ -11,10 +11,3 @@ pub fn assets_stub<'a>(_state: &'a AppState, t: ui::Tokens) -> E<'a> {
         text("Assets (stub)").size(14).color(t.muted_fg).into(),
     ))
 }
-
-pub fn account_stub<'a>(_state: &'a AppState, t: ui::Tokens) -> E<'a> {
-    ui::page_padding(ui::card(
-        t,
-        text("Account (stub)").size(14).color(t.muted_fg).into(),
-    ))
-}
\ No newline at end of file
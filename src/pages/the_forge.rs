@@ -11,13 +11,14 @@
 // ============================================
 
 use iced::{Alignment, Background, Border, Color, Element, Length, Theme};
-use iced::widget::{button, column, container, row, scrollable, text, text_editor, text_input, Column, Id, Row,
+use iced::widget::{button, column, container, mouse_area, pick_list, row, scrollable, text, text_editor, text_input, Column, Id, Row,
     Space,
 };
 
 use crate::app::{AppState, Message};
 use crate::messages::TheForgeMessage;
-use crate::model::{Chapter, Scene};
+use crate::model::{Chapter, Scene, SceneKind, WorkStatus};
+use crate::state::ForgeDragItem;
 use crate::ui::{self, Tokens};
 
 // --- CONSTANTS ---
@@ -28,6 +29,7 @@ const PILL_HEIGHT: f32 = 36.0;
 // Premium alignment constants
 const STATUS_COL_W: f32 = 64.0; // "Draft" column width
 const METRIC_COL_W: f32 = 64.0; // Word count / metric column width (match Draft)
+const FOCUS_TEXT_MAX_W: f32 = 820.0; // Focus mode: ancho máximo de la columna de texto
 
 #[inline]
 fn stable_key(tag: u64, id: &str) -> u64 {
@@ -72,6 +74,166 @@ fn divider(t: Tokens) -> Element<'static, Message> {
         .into()
 }
 
+// Delay del autosave: presets del pick_list del header del editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AutosaveDelay(u64);
+
+impl std::fmt::Display for AutosaveDelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Autosave {} ms", self.0)
+    }
+}
+
+const AUTOSAVE_PRESETS: [AutosaveDelay; 6] = [
+    AutosaveDelay(300),
+    AutosaveDelay(500),
+    AutosaveDelay(800),
+    AutosaveDelay(1500),
+    AutosaveDelay(3000),
+    AutosaveDelay(5000),
+];
+
+// "12345 words · ~62 min read" del novel activo (cacheado en state.forge_novel_stats)
+fn novel_stats_line(state: &AppState, t: Tokens) -> Option<Element<'static, Message>> {
+    let stats = state.forge_novel_stats.as_ref()?;
+    if state.active_novel_id.as_deref() != Some(stats.novel_id.as_str()) {
+        return None;
+    }
+
+    let minutes = (stats.words + crate::controllers::the_forge_controller::READING_WPM - 1)
+        / crate::controllers::the_forge_controller::READING_WPM;
+    let mut label = format!("{} words · ~{} min read", stats.words, minutes);
+    if stats.partial {
+        label.push_str(" (partial)");
+    }
+    Some(text(label).size(11).color(ui::alpha(t.muted_fg, 0.7)).into())
+}
+
+// Estado del chapter: derivado de sus scenes cargadas (el menos avanzado); sin scenes => el propio
+fn chapter_status_label<'a>(state: &'a AppState, chapter: &'a Chapter) -> &'a str {
+    state
+        .scenes_by_chapter_id
+        .get(&chapter.id)
+        .and_then(|scenes| WorkStatus::aggregate(scenes.iter().map(|s| s.work_status())))
+        .map(|s| s.as_str())
+        .unwrap_or(chapter.status.as_str())
+}
+
+fn status_color(t: Tokens, status: WorkStatus) -> Color {
+    match status {
+        WorkStatus::Draft => ui::alpha(t.muted_fg, 0.7),
+        WorkStatus::Revised => Color::from_rgba8(0xF5, 0x9E, 0x0B, 1.0),
+        WorkStatus::Final => Color::from_rgba8(0x22, 0xC5, 0x5E, 1.0),
+    }
+}
+
+// Chip clickeable del estado de la escena (cicla draft → revised → final)
+fn status_chip<'a>(t: Tokens, status: WorkStatus, scene_id: String) -> Element<'a, Message> {
+    let color = status_color(t, status);
+    button(text(status.as_str()).size(10).color(color))
+        .padding([2, 6])
+        .style(move |_: &Theme, st: button::Status| button::Style {
+            background: Some(Background::Color(ui::alpha(
+                color,
+                if matches!(st, button::Status::Hovered) { 0.22 } else { 0.12 },
+            ))),
+            text_color: color,
+            border: Border { color: ui::alpha(color, 0.4), width: 1.0, radius: 8.0.into() },
+            shadow: iced::Shadow::default(),
+            snap: false,
+        })
+        .on_press(Message::TheForge(TheForgeMessage::CycleSceneStatus(scene_id)))
+        .into()
+}
+
+// Barra de progreso de meta de palabras (solo si hay target > 0)
+const TARGET_BAR_W: f32 = 40.0;
+
+fn target_bar(t: Tokens, words: i64, target: i64) -> Option<Element<'static, Message>> {
+    if target <= 0 {
+        return None;
+    }
+    let ratio = (words.max(0) as f32 / target as f32).min(1.0);
+    // Meta cumplida => verde; en curso => accent
+    let fill = if words >= target { Color::from_rgba8(0x22, 0xC5, 0x5E, 1.0) } else { t.accent };
+
+    let inner = container(Space::new().width(Length::Fixed(TARGET_BAR_W * ratio)).height(Length::Fixed(4.0)))
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(fill, t.foreground);
+            s.border.radius = 2.0.into();
+            s
+        });
+
+    Some(
+        container(inner)
+            .width(Length::Fixed(TARGET_BAR_W))
+            .height(Length::Fixed(4.0))
+            .style(move |_: &Theme| {
+                let mut s = ui::container_style(ui::alpha(t.muted_fg, 0.15), t.foreground);
+                s.border.radius = 2.0.into();
+                s
+            })
+            .into(),
+    )
+}
+
+// Palabras del chapter para su meta: suma del árbol (solo scenes que cuentan para metas)
+fn chapter_words(state: &AppState, chapter_id: &str) -> i64 {
+    state
+        .scenes_by_chapter_id
+        .get(chapter_id)
+        .map(|v| v.iter().filter(|s| s.kind().counts_toward_goals()).map(|s| s.word_count).sum())
+        .unwrap_or(0)
+}
+
+// Metas de la escena activa y su chapter: input numérico (vacío = sin meta) + progreso
+fn targets_bar<'a>(state: &'a AppState, t: Tokens, scene: &'a Scene) -> Element<'a, Message> {
+    let target_input = |value: i64, on_input: fn(String) -> TheForgeMessage| {
+        text_input("none", &if value > 0 { value.to_string() } else { String::new() })
+            .on_input(move |s| Message::TheForge(on_input(s)))
+            .size(12)
+            .padding([4, 8])
+            .width(Length::Fixed(72.0))
+            .style(ui::input_style(t))
+    };
+    let progress_label = |words: i64, target: i64| {
+        let label = if target > 0 {
+            format!("{} / {} ({}%)", words, target, words.max(0) * 100 / target)
+        } else {
+            format!("{} words", words)
+        };
+        text(label).size(11).color(ui::alpha(t.muted_fg, 0.7))
+    };
+
+    let mut r = Row::new()
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .push(text("Scene target").size(11).color(t.muted_fg))
+        .push(target_input(scene.target_words, TheForgeMessage::SceneTargetChanged))
+        .push(progress_label(scene.word_count, scene.target_words));
+    if let Some(pb) = target_bar(t, scene.word_count, scene.target_words) {
+        r = r.push(pb);
+    }
+
+    let chapter = state
+        .active_novel_chapters
+        .iter()
+        .find(|c| c.id == scene.chapter_id);
+    if let Some(chapter) = chapter {
+        let words = chapter_words(state, &chapter.id);
+        r = r
+            .push(Space::new().width(Length::Fixed(16.0)))
+            .push(text("Chapter target").size(11).color(t.muted_fg))
+            .push(target_input(chapter.target_words, TheForgeMessage::ChapterTargetChanged))
+            .push(progress_label(words, chapter.target_words));
+        if let Some(pb) = target_bar(t, words, chapter.target_words) {
+            r = r.push(pb);
+        }
+    }
+
+    r.into()
+}
+
 fn selection_bar(_t: Tokens, _is_active: bool) -> Element<'static, Message> {
     // Barra invisible (limpio/premium)
     Space::new()
@@ -85,9 +247,9 @@ fn outline_item_style(t: Tokens, active: bool) -> impl Fn(&Theme, button::Status
         let mut s = button::Style::default();
 
         // Fondos sutiles
-        let hover = ui::alpha(Color::WHITE, 0.028);
-        let active_bg = ui::alpha(Color::WHITE, 0.050);
-        let pressed = ui::alpha(Color::WHITE, 0.070);
+        let hover = ui::alpha(t.ink, 0.028);
+        let active_bg = ui::alpha(t.ink, 0.050);
+        let pressed = ui::alpha(t.ink, 0.070);
 
         let bg = if active {
             match status {
@@ -124,6 +286,16 @@ fn icon_btn<'a>(t: Tokens, label: &'a str, on_press: Message) -> Element<'a, Mes
         .into()
 }
 
+// ▲/▼ del outline: sin on_press (y atenuado) en el borde de la lista
+fn step_btn<'a>(t: Tokens, label: &'a str, on_press: Option<Message>) -> Element<'a, Message> {
+    let enabled = on_press.is_some();
+    button(text(label).size(10).color(ui::alpha(t.muted_fg, if enabled { 0.8 } else { 0.25 })))
+        .padding([2, 4])
+        .style(ui::ghost_button_style(t))
+        .on_press_maybe(on_press)
+        .into()
+}
+
 fn danger_icon_btn<'a>(t: Tokens, label: &'a str, on_press: Message) -> Element<'a, Message> {
     let t_danger = Tokens {
         accent: Color::from_rgb(0.95, 0.4, 0.4),
@@ -146,11 +318,11 @@ fn rename_input_style(
 
         let focused = matches!(status, text_input::Status::Focused { .. });
 
-        let bg = ui::alpha(Color::WHITE, if focused { 0.06 } else { 0.03 });
+        let bg = ui::alpha(t.ink, if focused { 0.06 } else { 0.03 });
         let border = if focused {
             ui::alpha(t.accent, 0.35)
         } else {
-            ui::alpha(Color::WHITE, 0.10)
+            ui::alpha(t.ink, 0.10)
         };
 
         text_input::Style {
@@ -235,7 +407,7 @@ fn novel_row<'a>(
             .padding([6, 10])
             .width(Length::Fill)
             .height(Length::Fixed(PILL_HEIGHT))
-            .style(move |_| ui::container_style(ui::alpha(Color::WHITE, 0.04), t.foreground))
+            .style(move |_| ui::container_style(ui::alpha(t.ink, 0.04), t.foreground))
             .into()
     } else {
         button(content_row)
@@ -256,6 +428,9 @@ fn novel_row<'a>(
             "+",
             Message::TheForge(TheForgeMessage::CreateChapter(novel_id)),
         ),
+        icon_btn(t, "⤓", Message::TheForge(TheForgeMessage::ExportNovelJson(nid.clone()))),
+        icon_btn(t, "M↓", Message::TheForge(TheForgeMessage::ExportNovelMarkdown(nid.clone()))),
+        icon_btn(t, "⇲", Message::TheForge(TheForgeMessage::StartMergeNovel(nid.clone()))),
         danger_icon_btn(t, "×", Message::TheForge(TheForgeMessage::DeleteNovel(nid))),
     ]
         .spacing(6)
@@ -267,15 +442,17 @@ fn novel_row<'a>(
         .into()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn chapter_row<'a>(
     t: Tokens,
     title: &'a str,
     status: &'a str,
+    progress: Option<(i64, i64)>, // (words, target) si el chapter tiene meta
     chapter_id: String,
     is_active: bool,
     is_expanded: bool,
     is_renaming: bool,
-    outline_nonce: u32, // CANARIO + NUDGE: fuerza cambio real en shaping/cache
+    can_move: (bool, bool), // (▲, ▼): false en el primer / último chapter
 ) -> Element<'a, Message> {
     let bar = selection_bar(t, is_active);
 
@@ -314,11 +491,6 @@ fn chapter_row<'a>(
             .align_y(Alignment::Center)
             .width(Length::Fill);
 
-        // Nudge invisible como widget aparte (cero heap) para forzar re-shaping entre frames
-        // sin ensuciar el título real.
-        let prefix = if (outline_nonce & 1) == 0 { "\u{200B}" } else { "\u{200C}" };
-        r = r.push(text(prefix).size(1).color(Color::TRANSPARENT)); // invisible (zero-width)
-
         // Partición segura por límites UTF-8 (char boundary)
         let mut start_byte = 0usize;
         let mut count_chars = 0usize;
@@ -350,9 +522,13 @@ fn chapter_row<'a>(
         .align_x(Alignment::End)
         .into();
 
-    let content_row = row![bar, expand_btn, title_widget, status_text]
+    let mut content_row = row![bar, expand_btn, title_widget]
         .spacing(10)
         .align_y(Alignment::Center);
+    if let Some(pb) = progress.and_then(|(words, target)| target_bar(t, words, target)) {
+        content_row = content_row.push(pb);
+    }
+    let content_row = content_row.push(status_text);
 
     // MAIN siempre button (mismo tipo de widget)
     let mut main_btn = button(content_row)
@@ -394,7 +570,26 @@ fn chapter_row<'a>(
         )
     };
 
-    let buttons_row = row![create_btn, delete_btn]
+    let duplicate_btn: Element<Message> = if is_renaming {
+        button(text("⧉").size(14))
+            .padding([4, 8])
+            .style(ui::ghost_button_style(t))
+            .into()
+    } else {
+        icon_btn(
+            t,
+            "⧉",
+            Message::TheForge(TheForgeMessage::DuplicateChapter(chapter_id.clone())),
+        )
+    };
+
+    let (can_up, can_down) = if is_renaming { (false, false) } else { can_move };
+    let move_btns = column![
+        step_btn(t, "▲", can_up.then(|| Message::TheForge(TheForgeMessage::MoveChapterUp(chapter_id.clone())))),
+        step_btn(t, "▼", can_down.then(|| Message::TheForge(TheForgeMessage::MoveChapterDown(chapter_id.clone())))),
+    ];
+
+    let buttons_row = row![move_btns, create_btn, duplicate_btn, delete_btn]
         .spacing(6)
         .align_y(Alignment::Center);
 
@@ -411,16 +606,268 @@ fn chapter_row<'a>(
         .into()
 }
 
+// Notas inline `[[note: ...]]` de la escena activa (click = saltar al marcador)
+// Manuscript: todo el novel activo como texto corrido (solo lectura).
+// Solo se materializa lo cargado; chapters sin scenes todavía muestran un placeholder.
+fn manuscript_view<'a>(state: &'a AppState, t: Tokens) -> Column<'a, Message> {
+    let novel = state
+        .active_novel_id
+        .as_ref()
+        .and_then(|id| state.novels.iter().find(|n| n.id == *id));
+
+    let mut header = Row::new()
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .push(
+            text(match novel {
+                Some(n) => format!("Manuscript · {}", n.title),
+                None => "Manuscript".to_string(),
+            })
+                .size(12)
+                .color(t.muted_fg)
+                .width(Length::Fill),
+        );
+    if let Some(stats) = novel_stats_line(state, t) {
+        header = header.push(stats);
+    }
+    header = header.push(ui::ghost_button(
+        t,
+        "Back to editor".to_string(),
+        Message::TheForge(TheForgeMessage::ToggleManuscript),
+    ));
+
+    let mut chapters: Vec<&Chapter> = novel
+        .and_then(|n| state.chapters_by_novel_id.get(&n.id))
+        .map(|v| v.iter().collect())
+        .unwrap_or_default();
+    chapters.sort_by_key(|c| c.position);
+
+    let mut body = Column::new().spacing(12).padding([8, 24]).max_width(FOCUS_TEXT_MAX_W);
+    if chapters.is_empty() {
+        body = body.push(text("Loading chapters…").size(13).color(ui::alpha(t.muted_fg, 0.6)));
+    }
+
+    for chapter in chapters {
+        body = body.push(
+            container(text(chapter.title.as_str()).size(20).color(t.foreground)).padding(iced::Padding {
+                top: 16.0,
+                ..iced::Padding::ZERO
+            }),
+        );
+
+        let Some(scenes) = state.scenes_by_chapter_id.get(&chapter.id) else {
+            body = body.push(text("Loading…").size(13).color(ui::alpha(t.muted_fg, 0.6)));
+            continue;
+        };
+
+        // Prosa en orden; outline/notes no son manuscrito
+        let mut scenes: Vec<&Scene> = scenes.iter().filter(|s| s.kind().counts_toward_goals()).collect();
+        scenes.sort_by_key(|s| s.position);
+
+        for (i, scene) in scenes.into_iter().enumerate() {
+            if i > 0 {
+                body = body.push(
+                    container(text("*  *  *").size(12).color(ui::alpha(t.muted_fg, 0.5))).center_x(Length::Fill),
+                );
+            }
+            // La escena activa se lee del editor (puede tener texto sin guardar)
+            let text_body: &str = if state.active_scene_id.as_deref() == Some(scene.id.as_str()) {
+                state
+                    .active_chapter_scenes
+                    .iter()
+                    .find(|s| s.id == scene.id)
+                    .map(|s| s.body.as_str())
+                    .unwrap_or(scene.body.as_str())
+            } else {
+                scene.body.as_str()
+            };
+            body = body.push(text(text_body).size(15).line_height(1.6).color(t.foreground));
+        }
+    }
+
+    column![
+        header,
+        divider(t),
+        scrollable(container(body).center_x(Length::Fill)).height(Length::Fill),
+    ]
+        .spacing(8)
+}
+
+// Notas de la escena (colapsable): intenciones / pendientes fuera del manuscrito
+fn scene_notes_panel<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    let Some((_, content)) = state
+        .forge_notes_editor
+        .as_ref()
+        .filter(|(id, _)| state.active_scene_id.as_deref() == Some(id.as_str()))
+    else {
+        return Space::new().height(Length::Fixed(0.0)).into();
+    };
+
+    let has_notes = !content.text().trim().is_empty();
+    let label = match (state.forge_notes_open, has_notes) {
+        (true, _) => "▾ Scene notes",
+        (false, true) => "▸ Scene notes •",
+        (false, false) => "▸ Scene notes",
+    };
+    let header = button(text(label).size(12).color(t.muted_fg))
+        .padding([2, 4])
+        .style(ui::ghost_button_style(t))
+        .on_press(Message::TheForge(TheForgeMessage::ToggleSceneNotes));
+
+    if !state.forge_notes_open {
+        return header.into();
+    }
+
+    let editor = text_editor(content)
+        .placeholder("Intentions, reminders, loose ends… (not part of the manuscript)")
+        .on_action(|a| Message::TheForge(TheForgeMessage::SceneNotesChanged(a)))
+        .padding(10)
+        .size(13)
+        .height(Length::Fixed(120.0))
+        .style(move |theme: &Theme, status| {
+            let mut s = ui::text_editor_style(t)(theme, status);
+            s.background = Background::Color(ui::alpha(t.shell_a, 0.5));
+            s
+        });
+
+    column![header, editor].spacing(4).into()
+}
+
+// Historial de la escena (colapsable): versiones guardadas, preview y restore
+fn scene_revisions_panel<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    let Some(scene_id) = state.active_scene_id.as_deref() else {
+        return Space::new().height(Length::Fixed(0.0)).into();
+    };
+
+    let header = button(
+        text(if state.forge_revisions_open { "▾ History" } else { "▸ History" })
+            .size(12)
+            .color(t.muted_fg),
+    )
+        .padding([2, 4])
+        .style(ui::ghost_button_style(t))
+        .on_press(Message::TheForge(TheForgeMessage::ToggleSceneRevisions));
+
+    if !state.forge_revisions_open {
+        return header.into();
+    }
+
+    let revisions: Vec<_> = state.forge_revisions.iter().filter(|r| r.scene_id == scene_id).collect();
+    if revisions.is_empty() {
+        return column![
+            header,
+            text("No saved versions yet.").size(12).color(ui::alpha(t.muted_fg, 0.7)),
+        ]
+            .spacing(4)
+            .into();
+    }
+
+    let mut list = Column::new().spacing(2);
+    for r in &revisions {
+        let when = chrono::DateTime::from_timestamp(r.saved_at, 0)
+            .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let selected = state.forge_revision_selected.as_deref() == Some(r.id.as_str());
+        list = list.push(
+            button(
+                row![
+                    text(when).size(12).color(if selected { t.accent } else { t.foreground }).width(Length::Fill),
+                    text(format!("{} words", r.word_count)).size(11).color(t.muted_fg),
+                ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+            )
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::TheForge(TheForgeMessage::PreviewSceneRevision(r.id.clone()))),
+        );
+    }
+
+    let mut panel = column![header, container(scrollable(list)).max_height(140.0)].spacing(4);
+
+    if let Some(r) = revisions.iter().find(|r| state.forge_revision_selected.as_deref() == Some(r.id.as_str())) {
+        let preview = container(scrollable(text(r.body.as_str()).size(13).color(t.foreground)))
+            .padding(10)
+            .max_height(180.0)
+            .width(Length::Fill)
+            .style(move |_| ui::container_style(ui::alpha(t.shell_a, 0.5), t.foreground));
+        panel = panel.push(preview).push(
+            row![
+                Space::new().width(Length::Fill),
+                ui::outline_button(
+                    t,
+                    "Restore this version".to_string(),
+                    Message::TheForge(TheForgeMessage::RestoreSceneRevision(r.id.clone())),
+                ),
+            ],
+        );
+    }
+
+    panel.into()
+}
+
+fn annotations_panel<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    if state.forge_annotations.is_empty() {
+        return Space::new().height(Length::Fixed(0.0)).into();
+    }
+
+    let mut list = Column::new().spacing(2);
+    for a in &state.forge_annotations {
+        list = list.push(
+            button(
+                row![
+                    text(format!("L{}", a.line))
+                        .size(11)
+                        .color(ui::alpha(t.muted_fg, 0.6))
+                        .width(Length::Fixed(40.0)),
+                    text(a.text.as_str()).size(12).color(t.foreground).width(Length::Fill),
+                ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+            )
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::TheForge(TheForgeMessage::JumpToAnnotation {
+                    char_offset: a.char_offset,
+                    char_len: a.char_len,
+                })),
+        );
+    }
+
+    column![
+        text(format!("Notes in this scene ({})", state.forge_annotations.len()))
+            .size(12)
+            .color(t.muted_fg),
+        container(scrollable(list)).max_height(140.0),
+    ]
+        .spacing(4)
+        .into()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn scene_row<'a>(
     t: Tokens,
     title: &'a str,
     word_count: i64,
+    target_words: i64,
+    status: WorkStatus,
+    kind: SceneKind,
+    in_compile: bool,
     scene_id: String,
     is_active: bool,
     is_renaming: bool,
+    can_move: (bool, bool), // (▲, ▼): false en la primera / última escena del chapter
 ) -> Element<'a, Message> {
     let bar = selection_bar(t, is_active);
 
+    // Icono de tipo (¶ prose / ☰ outline / ✎ note)
+    let kind_icon: Element<Message> = text(kind.icon())
+        .size(12)
+        .color(ui::alpha(t.muted_fg, 0.7))
+        .into();
+
     let title_widget: Element<Message> = if is_renaming {
         rename_input(
             t,
@@ -430,12 +877,16 @@ fn scene_row<'a>(
             Message::TheForge(TheForgeMessage::EndRename),
         )
     } else {
-        text(format!("{title}"))
+        // Fuera del compile: título tachado no existe en iced, así que marcador + más tenue
+        let label = if in_compile { title.to_string() } else { format!("⊘ {title}") };
+        text(label)
             .size(13)
             .color(if is_active {
                 t.foreground
-            } else {
+            } else if in_compile {
                 ui::alpha(t.muted_fg, 0.85)
+            } else {
+                ui::alpha(t.muted_fg, 0.5)
             })
             .width(Length::Fill)
             .into()
@@ -451,16 +902,18 @@ fn scene_row<'a>(
     let content_row = if is_renaming {
         row![bar, title_widget].spacing(10).align_y(Alignment::Center)
     } else {
-        row![bar, title_widget, wc_text]
-            .spacing(10)
-            .align_y(Alignment::Center)
+        let mut r = row![bar, kind_icon, title_widget].spacing(10).align_y(Alignment::Center);
+        if let Some(pb) = target_bar(t, word_count, target_words) {
+            r = r.push(pb);
+        }
+        r.push(status_chip(t, status, scene_id.clone())).push(wc_text)
     };
 
     let main: Element<Message> = if is_renaming {
         container(content_row)
             .padding([6, 10])
             .width(Length::Fill)
-            .style(move |_| ui::container_style(ui::alpha(Color::WHITE, 0.04), t.foreground))
+            .style(move |_| ui::container_style(ui::alpha(t.ink, 0.04), t.foreground))
             .into()
     } else {
         button(content_row)
@@ -473,8 +926,16 @@ fn scene_row<'a>(
 
     let sid = scene_id;
 
+    let (can_up, can_down) = if is_renaming { (false, false) } else { can_move };
+    let move_btns = column![
+        step_btn(t, "▲", can_up.then(|| Message::TheForge(TheForgeMessage::MoveSceneUp(sid.clone())))),
+        step_btn(t, "▼", can_down.then(|| Message::TheForge(TheForgeMessage::MoveSceneDown(sid.clone())))),
+    ];
+
     let buttons_row = row![
-        // Scenes son hoja: solo delete
+        move_btns,
+        // Scenes son hoja: merge con la anterior + delete
+        icon_btn(t, "⤒", Message::TheForge(TheForgeMessage::MergeSceneUp(sid.clone()))),
         danger_icon_btn(t, "×", Message::TheForge(TheForgeMessage::DeleteScene(sid))),
     ]
         .spacing(6)
@@ -493,8 +954,368 @@ fn scene_row<'a>(
         .into()
 }
 
+// Drag & drop del outline: handle ⠿ (press = DragStart) + destino por hover.
+// on_enter solo se escucha mientras hay un drag en curso (sin mensajes extra en uso normal).
+fn drag_row<'a>(
+    state: &'a AppState,
+    t: Tokens,
+    item: ForgeDragItem,
+    inner: Element<'a, Message>,
+) -> Element<'a, Message> {
+    let drag = state.forge_drag.as_ref();
+    let is_dragged = drag.is_some_and(|d| d.active && d.item == item);
+    let is_target = !is_dragged
+        && drag.is_some_and(|d| d.active)
+        && state.forge_drag_hover.as_ref() == Some(&item);
+
+    let handle = mouse_area(
+        text("⠿")
+            .size(12)
+            .color(ui::alpha(t.muted_fg, if is_dragged { 0.9 } else { 0.35 })),
+    )
+        .on_press(Message::TheForge(TheForgeMessage::DragStart(item.clone())));
+
+    let body = container(row![handle, inner].spacing(4).align_y(Alignment::Center))
+        .width(Length::Fill)
+        .style(move |_: &Theme| {
+            let mut s = container::Style::default();
+            if is_target {
+                s.background = Some(Background::Color(ui::alpha(t.accent, 0.06)));
+                s.border = Border {
+                    color: ui::alpha(t.accent, 0.6),
+                    width: 1.0,
+                    radius: 6.0.into(),
+                };
+            } else if is_dragged {
+                s.background = Some(Background::Color(ui::alpha(t.accent, 0.10)));
+            }
+            s
+        });
+
+    let mut area = mouse_area(body);
+    if drag.is_some() {
+        area = area.on_enter(Message::TheForge(TheForgeMessage::DragHover(item)));
+    }
+    area.into()
+}
+
+// Búsqueda en títulos + bodies del novel activo; click = saltar a la escena
+fn search_section<'a>(state: &'a AppState, t: Tokens) -> Option<Element<'a, Message>> {
+    state.active_novel_id.as_ref()?;
+
+    let mut input_row = Row::new().spacing(6).align_y(Alignment::Center).push(
+        text_input("Search in novel…", &state.forge_search_query)
+            .on_input(|s| Message::TheForge(TheForgeMessage::SearchQueryChanged(s)))
+            .size(12)
+            .padding([4, 8])
+            .width(Length::Fill)
+            .style(ui::input_style(t)),
+    );
+    if !state.forge_search_query.is_empty() {
+        input_row = input_row.push(icon_btn(t, "×", Message::TheForge(TheForgeMessage::SearchCleared)));
+    }
+
+    let mut section = Column::new().spacing(6).push(input_row);
+
+    let results_apply = state.forge_search_novel_id.is_some()
+        && state.forge_search_novel_id == state.active_novel_id
+        && !state.forge_search_query.trim().is_empty();
+
+    if results_apply {
+        if state.forge_search_results.is_empty() {
+            section = section.push(text("No matching scenes").size(11).color(ui::alpha(t.muted_fg, 0.7)));
+        } else {
+            let mut list = Column::new().spacing(4);
+            for hit in &state.forge_search_results {
+                let body = Column::new()
+                    .spacing(2)
+                    .push(text(format!("{} · {}", hit.chapter_title, hit.title)).size(12).color(t.foreground))
+                    .push(text(&hit.snippet).size(11).color(ui::alpha(t.muted_fg, 0.8)));
+
+                list = list.push(
+                    button(body)
+                        .width(Length::Fill)
+                        .padding([4, 8])
+                        .style(outline_item_style(t, state.active_scene_id.as_ref() == Some(&hit.scene_id)))
+                        .on_press(Message::TheForge(TheForgeMessage::OpenSearchHit {
+                            chapter_id: hit.chapter_id.clone(),
+                            scene_id: hit.scene_id.clone(),
+                        })),
+                );
+            }
+            section = section
+                .push(text(format!("{} scene(s)", state.forge_search_results.len())).size(11).color(t.muted_fg))
+                .push(container(scrollable(list)).max_height(200.0));
+        }
+        section = section.push(divider(t));
+    }
+
+    Some(section.into())
+}
+
+// Find / replace (Ctrl+F): Enter en "find" = siguiente, en "replace" = reemplazar uno
+fn find_bar<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    let status = match (state.forge_find_index, state.forge_find_total) {
+        (_, 0) if !state.forge_find_query.is_empty() => "No matches".to_string(),
+        (_, 0) => String::new(),
+        (Some(i), n) if i < n => format!("{}/{}", i + 1, n),
+        (_, n) => format!("{} matches", n),
+    };
+
+    let find_input = text_input("Find", &state.forge_find_query)
+        .id(Id::new("forge_find_query"))
+        .on_input(|s| Message::TheForge(TheForgeMessage::FindQueryChanged(s)))
+        .on_submit(Message::TheForge(TheForgeMessage::FindNext))
+        .size(12)
+        .padding([4, 8])
+        .width(Length::Fixed(180.0))
+        .style(ui::input_style(t));
+
+    let replace_input = text_input("Replace", &state.forge_replace_text)
+        .on_input(|s| Message::TheForge(TheForgeMessage::ReplaceTextChanged(s)))
+        .on_submit(Message::TheForge(TheForgeMessage::ReplaceOne))
+        .size(12)
+        .padding([4, 8])
+        .width(Length::Fixed(180.0))
+        .style(ui::input_style(t));
+
+    row![
+        find_input,
+        text(status).size(11).color(t.muted_fg).width(Length::Fixed(72.0)),
+        icon_btn(t, "↑", Message::TheForge(TheForgeMessage::FindPrevious)),
+        icon_btn(t, "↓", Message::TheForge(TheForgeMessage::FindNext)),
+        ui::ghost_button(
+            t,
+            if state.forge_find_case_sensitive { "✓ Aa".to_string() } else { "Aa".to_string() },
+            Message::TheForge(TheForgeMessage::FindCaseSensitiveToggled),
+        ),
+        replace_input,
+        ui::ghost_button(t, "Replace".to_string(), Message::TheForge(TheForgeMessage::ReplaceOne)),
+        ui::ghost_button(t, "Replace all".to_string(), Message::TheForge(TheForgeMessage::ReplaceAll)),
+    ]
+        .spacing(6)
+        .align_y(Alignment::Center)
+        .into()
+}
+
 // --- MAIN VIEW ---
 
+// Merge en dos pasos: ⇲ elige el origen, acá se elige el destino (luego confirm modal)
+fn merge_picker<'a>(state: &'a AppState, t: Tokens) -> Option<Element<'a, Message>> {
+    let source_id = state.forge_merge_source.as_ref()?;
+    let source = state.novels.iter().find(|n| &n.id == source_id)?;
+
+    let mut col = Column::new()
+        .spacing(6)
+        .push(text(format!("Merge '{}' into:", source.title)).size(12).color(t.muted_fg));
+
+    let mut any_target = false;
+    for novel in state.novels.iter().filter(|n| &n.id != source_id) {
+        any_target = true;
+        col = col.push(
+            button(text(&novel.title).size(13).color(t.foreground))
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(outline_item_style(t, false))
+                .on_press(Message::TheForge(TheForgeMessage::PickMergeTarget(novel.id.clone()))),
+        );
+    }
+    if !any_target {
+        col = col.push(text("No other novel to merge into.").size(12).color(t.muted_fg));
+    }
+
+    Some(
+        col.push(ui::ghost_button(t, "Cancel".to_string(), Message::TheForge(TheForgeMessage::CancelMergeNovel)))
+            .into(),
+    )
+}
+
+// Picker de universo de la Forge: universos existentes + quick-create al final
+#[derive(Debug, Clone, PartialEq)]
+enum UniverseChoice {
+    Universe { id: String, name: String },
+    New,
+}
+
+impl std::fmt::Display for UniverseChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UniverseChoice::Universe { name, .. } => write!(f, "{}", name),
+            UniverseChoice::New => write!(f, "+ New universe"),
+        }
+    }
+}
+
+fn universe_picker<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    // Inline create (solo nombre): reemplaza al picker mientras está abierto
+    if let Some(name) = state.forge_new_universe_name.as_ref() {
+        let input = text_input("New universe name…", name)
+            .id(Id::new("forge_quick_universe"))
+            .on_input(|v| Message::TheForge(TheForgeMessage::QuickUniverseNameChanged(v)))
+            .on_submit(Message::TheForge(TheForgeMessage::QuickUniverseCreate))
+            .size(12)
+            .padding([4, 8])
+            .style(ui::input_style(t));
+
+        return Row::new()
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .push(input)
+            .push(icon_btn(t, "✓", Message::TheForge(TheForgeMessage::QuickUniverseCreate)))
+            .push(icon_btn(t, "×", Message::TheForge(TheForgeMessage::QuickUniverseCancel)))
+            .into();
+    }
+
+    let mut choices: Vec<UniverseChoice> = state
+        .universes
+        .iter()
+        .map(|u| UniverseChoice::Universe { id: u.id.clone(), name: u.name.clone() })
+        .collect();
+    choices.push(UniverseChoice::New);
+
+    let selected = state.loaded_forge_universe.as_ref().and_then(|uid| {
+        choices
+            .iter()
+            .find(|c| matches!(c, UniverseChoice::Universe { id, .. } if id == uid))
+            .cloned()
+    });
+
+    pick_list(choices, selected, |c| match c {
+        UniverseChoice::Universe { id, .. } => Message::TheForge(TheForgeMessage::UniverseChanged(id)),
+        UniverseChoice::New => Message::TheForge(TheForgeMessage::StartQuickUniverse),
+    })
+        .placeholder("No universe")
+        .text_size(12)
+        .padding([4, 8])
+        .width(Length::Fill)
+        .into()
+}
+
+// Destino del "Move to…" de una huérfana: solo chapters ya cargados en el árbol
+#[derive(Debug, Clone, PartialEq)]
+struct ChapterChoice {
+    chapter_id: String,
+    label: String,
+}
+
+impl std::fmt::Display for ChapterChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+fn orphans_section<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
+    let mut choices: Vec<ChapterChoice> = Vec::new();
+    for novel in &state.novels {
+        if let Some(chapters) = state.chapters_by_novel_id.get(&novel.id) {
+            for ch in chapters {
+                choices.push(ChapterChoice {
+                    chapter_id: ch.id.clone(),
+                    label: format!("{} › {}", novel.title, ch.title),
+                });
+            }
+        }
+    }
+
+    let mut list = Column::new().spacing(4);
+    for scene in &state.forge_orphan_scenes {
+        let scene_id = scene.id.clone();
+        let move_to = pick_list(choices.clone(), None::<ChapterChoice>, move |c| {
+            Message::TheForge(TheForgeMessage::ReassignOrphanScene {
+                scene_id: scene_id.clone(),
+                chapter_id: c.chapter_id,
+            })
+        })
+            .placeholder("Move to…")
+            .text_size(12)
+            .padding([4, 8])
+            .width(Length::Fixed(120.0));
+
+        list = list.push(
+            row![
+                column![
+                    text(&scene.title).size(13).color(t.foreground),
+                    text(format!("{} words", scene.word_count)).size(11).color(t.muted_fg),
+                ]
+                .width(Length::Fill),
+                move_to,
+                danger_icon_btn(t, "×", Message::TheForge(TheForgeMessage::TrashOrphanScene(scene.id.clone()))),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    let hint = if choices.is_empty() {
+        "Expand a novel to load its chapters as targets."
+    } else {
+        "Their chapter no longer exists. Move them or send them to the trash."
+    };
+
+    Column::new()
+        .spacing(6)
+        .push(text(format!("Orphaned scenes ({})", state.forge_orphan_scenes.len())).size(12).color(t.muted_fg))
+        .push(text(hint).size(11).color(ui::alpha(t.muted_fg, 0.7)))
+        .push(container(scrollable(list)).max_height(160.0))
+        .into()
+}
+
+// Draft recovery: drafts locales más nuevos que la DB (crash antes del autosave).
+// Banner global (no solo en Forge): el escaneo corre al abrir el proyecto.
+pub fn draft_recovery_banner<'a>(state: &'a AppState, t: Tokens) -> Option<Element<'a, Message>> {
+    if state.forge_recoverable_drafts.is_empty() {
+        return None;
+    }
+
+    let mut col = Column::new().spacing(6).push(
+        text("Unsaved scene drafts were found from a previous session")
+            .size(12)
+            .color(t.foreground),
+    );
+
+    for draft in &state.forge_recoverable_drafts {
+        let draft_words = draft.draft_body.split_whitespace().count();
+        col = col.push(
+            Row::new()
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .push(
+                    text(format!(
+                        "“{}” — draft {} words, saved {} words",
+                        draft.scene.title, draft_words, draft.scene.word_count
+                    ))
+                        .size(12)
+                        .color(t.muted_fg)
+                        .width(Length::Fill),
+                )
+                .push(ui::primary_button(
+                    t,
+                    "Recover draft".to_string(),
+                    Message::TheForge(TheForgeMessage::RecoverDraft(draft.scene.id.clone())),
+                ))
+                .push(ui::ghost_button(
+                    t,
+                    "Discard".to_string(),
+                    Message::TheForge(TheForgeMessage::DiscardDraft(draft.scene.id.clone())),
+                )),
+        );
+    }
+
+    Some(
+        container(col)
+            .padding([10, 14])
+            .width(Length::Fill)
+            .style(move |_: &Theme| {
+                let mut s = ui::container_style(ui::alpha(t.accent, 0.12), t.foreground);
+                s.border.width = 1.0;
+                s.border.color = ui::alpha(t.accent, 0.4);
+                s.border.radius = 10.0.into();
+                s
+            })
+            .into(),
+    )
+}
+
 pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
     // ✅ FIX: Forzar que Iced detecte cambios en el outline (lectura intencional)
     let _ = state.forge_outline_version;
@@ -540,7 +1361,7 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
                 .map(|v| v.as_slice())
                 .unwrap_or(&[]);
 
-            for chapter in chapters {
+            for (chapter_idx, chapter) in chapters.iter().enumerate() {
                 // ✅ Menos clones: clonamos una vez por capítulo (y lo reutilizamos)
                 let chapter_id = chapter.id.clone();
 
@@ -558,15 +1379,22 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
                 outline_children.push((
                     // Key versionada: si outline_version sube, este row se reconstruye sí o sí
                     stable_key_v(2, &chapter_id, state.forge_outline_version as u64),
-                    chapter_row(
+                    drag_row(
+                        state,
                         t,
-                        &chapter.title,
-                        &chapter.status,
-                        chapter_id.clone(),
-                        is_active_chapter,
-                        is_chapter_expanded,
-                        is_renaming_chapter,
-                        state.forge_outline_version,
+                        ForgeDragItem::Chapter(chapter_id.clone()),
+                        chapter_row(
+                            t,
+                            &chapter.title,
+                            chapter_status_label(state, chapter),
+                            (chapter.target_words > 0)
+                                .then(|| (chapter_words(state, &chapter_id), chapter.target_words)),
+                            chapter_id.clone(),
+                            is_active_chapter,
+                            is_chapter_expanded,
+                            is_renaming_chapter,
+                            (chapter_idx > 0, chapter_idx + 1 < chapters.len()),
+                        ),
                     ),
                 ));
 
@@ -577,7 +1405,7 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
                         .map(|v| v.as_slice())
                         .unwrap_or(&[]);
 
-                    for scene in scenes {
+                    for (scene_idx, scene) in scenes.iter().enumerate() {
                         // ✅ Menos clones: clonamos una vez por escena
                         let scene_id = scene.id.clone();
 
@@ -587,13 +1415,23 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
 
                         outline_children.push((
                             stable_key(3, &scene_id),
-                            scene_row(
+                            drag_row(
+                                state,
                                 t,
-                                &scene.title,
-                                scene.word_count,
-                                scene_id,
-                                is_active_scene,
-                                is_renaming_scene,
+                                ForgeDragItem::Scene(scene_id.clone()),
+                                scene_row(
+                                    t,
+                                    &scene.title,
+                                    scene.word_count,
+                                    scene.target_words,
+                                    scene.work_status(),
+                                    scene.kind(),
+                                    scene.include_in_compile,
+                                    scene_id,
+                                    is_active_scene,
+                                    is_renaming_scene,
+                                    (scene_idx > 0, scene_idx + 1 < scenes.len()),
+                                ),
                             ),
                         ));
                     }
@@ -641,17 +1479,51 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
         "forge_outline_scroll_b"
     };
 
-    let outline_section = Column::new()
-        .spacing(8)
+    // Proyectos de novela standalone no cuelgan de universos: sin picker
+    let is_standalone_novel_project = matches!(
+        state.active_project.as_ref().map(|p| p.get_kind()),
+        Some(crate::model::ProjectKind::Novel)
+    );
+
+    let outline_section = Column::new().spacing(8);
+    let outline_section = if is_standalone_novel_project {
+        outline_section
+    } else {
+        outline_section.push(universe_picker(state, t))
+    };
+
+    let outline_section = outline_section
         .push(
             Row::new()
                 .align_y(Alignment::Center)
                 .push(text("Novels").size(12).color(t.muted_fg).width(Length::Fill))
-                .push(icon_btn(t, "+", Message::TheForge(TheForgeMessage::CreateNovel))),
+                .push(icon_btn(t, "⊞", Message::TheForge(TheForgeMessage::ExpandAllOutline)))
+                .push(icon_btn(t, "⊟", Message::TheForge(TheForgeMessage::CollapseAllOutline)))
+                .push(icon_btn(t, "+", Message::TheForge(TheForgeMessage::CreateNovel)))
+                .push(icon_btn(t, "‹", Message::TheForge(TheForgeMessage::ToggleOutlinePanel))),
         )
-        .push(divider(t))
+        .push(divider(t));
+
+    // Búsqueda en el novel activo (arriba del árbol)
+    let outline_section = match search_section(state, t) {
+        Some(search) => outline_section.push(search),
+        None => outline_section,
+    };
+
+    let outline_section = outline_section
         .push(scrollable(outline).id(Id::new(outline_scroll_id)).height(Length::Fill));
 
+    let outline_section = match merge_picker(state, t) {
+        Some(picker) => outline_section.push(divider(t)).push(picker),
+        None => outline_section,
+    };
+
+    let outline_section = if state.forge_orphan_scenes.is_empty() {
+        outline_section
+    } else {
+        outline_section.push(divider(t)).push(orphans_section(state, t))
+    };
+
     let outline_panel = container(outline_section)
         .padding(16)
         .width(Length::Fixed(320.0))
@@ -664,30 +1536,217 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
             s
         });
 
-    // Editor
-    let editor_content = if state.active_scene_id.is_some() {
-        column![
-            Row::new()
-                .align_y(Alignment::Center)
-                .push(text("Editor").size(12).color(t.muted_fg).width(Length::Fill)),
-            divider(t),
-            text_editor(&state.forge_content)
-                .on_action(|a| Message::TheForge(TheForgeMessage::SceneBodyChanged(a)))
-                .padding(16)
+    // Editor (o Manuscript: lectura continua del novel activo)
+    let editor_content = if state.forge_manuscript_mode {
+        manuscript_view(state, t)
+    } else if let Some(active_scene_id) = state.active_scene_id.as_ref() {
+        // Edición externa: indicador + stop si esta escena está abierta afuera
+        let is_external = state
+            .forge_external_edit
+            .as_ref()
+            .map(|s| s.scene_id == *active_scene_id)
+            .unwrap_or(false);
+
+        let mut editor_header = Row::new()
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(text("Editor").size(12).color(t.muted_fg));
+
+        // ● Unsaved: hay cambios que DB todavía no confirmó
+        if state.forge_dirty {
+            editor_header = editor_header.push(text("●").size(12).color(t.accent));
+        }
+
+        if let Some(stats) = novel_stats_line(state, t) {
+            editor_header = editor_header.push(stats);
+        }
+
+        // Sesión de escritura (+N desde el arranque o el último reset)
+        editor_header = editor_header
+            .push(
+                text(format!("+{} words this session", state.forge_session_gain()))
+                    .size(11)
+                    .color(t.accent),
+            )
+            .push(icon_btn(t, "↺", Message::TheForge(TheForgeMessage::ResetWritingSession)));
+
+        editor_header = editor_header.push(Space::new().width(Length::Fill));
+
+        // Tipo de escena (prose/outline/note)
+        let active_kind = state
+            .active_chapter_scenes
+            .iter()
+            .find(|s| s.id == *active_scene_id)
+            .map(|s| s.kind())
+            .unwrap_or_default();
+        let active_in_compile = state
+            .active_chapter_scenes
+            .iter()
+            .find(|s| s.id == *active_scene_id)
+            .map(|s| s.include_in_compile)
+            .unwrap_or(true);
+
+        editor_header = editor_header.push(
+            pick_list(SceneKind::ALL, Some(active_kind), |k| {
+                Message::TheForge(TheForgeMessage::SceneKindChanged(k))
+            })
+                .text_size(12)
+                .padding([4, 8]),
+        );
+
+        // Include in compile (exports): toggle como el resto de flags del editor
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            if active_in_compile { "✓ In compile".to_string() } else { "Excluded from compile".to_string() },
+            Message::TheForge(TheForgeMessage::SceneIncludeInCompileToggled(!active_in_compile)),
+        ));
+
+        editor_header = editor_header.push(
+            pick_list(&AUTOSAVE_PRESETS[..], Some(AutosaveDelay(state.settings.forge_autosave_ms)), |d| {
+                Message::TheForge(TheForgeMessage::AutosaveDelayChanged(d.0))
+            })
+                .text_size(12)
+                .padding([4, 8]),
+        );
+
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            if state.forge_preview_mode { "Hide preview".to_string() } else { "Preview".to_string() },
+            Message::TheForge(TheForgeMessage::TogglePreview),
+        ));
+
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            "Manuscript".to_string(),
+            Message::TheForge(TheForgeMessage::ToggleManuscript),
+        ));
+
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            if state.forge_focus_mode { "Exit focus".to_string() } else { "Focus".to_string() },
+            Message::TheForge(TheForgeMessage::ToggleFocusMode),
+        ));
+
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            if state.forge_find_open { "Hide find".to_string() } else { "Find".to_string() },
+            Message::TheForge(TheForgeMessage::ToggleFindBar),
+        ));
+
+        // Split: el texto desde el cursor pasa a una escena nueva justo después
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            "Split here".to_string(),
+            Message::TheForge(TheForgeMessage::SplitSceneAtCursor),
+        ));
+
+        // Jump to definition (también F12): palabra bajo el cursor -> glosario / bestiario
+        editor_header = editor_header.push(ui::ghost_button(
+            t,
+            "Define".to_string(),
+            Message::TheForge(TheForgeMessage::LookupWordAtCursor),
+        ));
+
+        editor_header = if is_external {
+            editor_header
+                .push(text("Editing externally…").size(11).color(t.accent))
+                .push(ui::ghost_button(
+                    t,
+                    "Stop".to_string(),
+                    Message::TheForge(TheForgeMessage::StopExternalEdit),
+                ))
+        } else {
+            editor_header.push(ui::ghost_button(
+                t,
+                "Open externally".to_string(),
+                Message::TheForge(TheForgeMessage::OpenSceneExternally(active_scene_id.clone())),
+            ))
+        };
+
+        let editor_widget: Element<Message> = text_editor(&state.forge_content)
+            .on_action(|a| Message::TheForge(TheForgeMessage::SceneBodyChanged(a)))
+            // Ctrl+Enter es el atajo de "nueva escena": no debe insertar un salto de línea
+            .key_binding(|press| {
+                if press.modifiers.command()
+                    && press.key == iced::keyboard::Key::Named(iced::keyboard::key::Named::Enter)
+                {
+                    None
+                } else {
+                    text_editor::Binding::from_key_press(press)
+                }
+            })
+            .padding(16)
+            .height(Length::Fill)
+            // Outline => monospace (listas/estructura alinean mejor)
+            .font(if active_kind == SceneKind::Outline {
+                iced::Font::MONOSPACE
+            } else {
+                iced::Font::default()
+            })
+            .style(move |theme: &Theme, status| {
+                let mut s = ui::text_editor_style(t)(theme, status);
+                // Área de texto más clara
+                s.background = Background::Color(ui::alpha(t.shell_a, 0.7));
+                s
+            })
+            .into();
+
+        // Preview Markdown: el editor sigue siendo la fuente de verdad (solo lectura al lado)
+        let editor_area: Element<Message> = if state.forge_preview_mode {
+            let preview = container(
+                scrollable(
+                    container(crate::pages::markdown_preview::render(t, &state.forge_content.text()))
+                        .padding(16),
+                )
+                    .height(Length::Fill),
+            )
+                .width(Length::FillPortion(1))
                 .height(Length::Fill)
-                .style(move |theme: &Theme, status| {
-                    let mut s = ui::text_editor_style(t)(theme, status);
-                    // Área de texto más clara
-                    s.background = Background::Color(ui::alpha(t.shell_a, 0.7));
+                .style(move |_: &Theme| {
+                    let mut s = ui::container_style(ui::alpha(t.shell_a, 0.7), t.foreground);
+                    s.border.radius = 6.0.into();
                     s
-                })
-        ]
-            .spacing(8)
+                });
+
+            row![container(editor_widget).width(Length::FillPortion(1)), preview]
+                .spacing(8)
+                .height(Length::Fill)
+                .into()
+        } else {
+            editor_widget
+        };
+
+        let mut editor_col = column![editor_header].spacing(8);
+        if let Some(scene) = state.active_chapter_scenes.iter().find(|s| s.id == *active_scene_id) {
+            editor_col = editor_col.push(targets_bar(state, t, scene));
+        }
+        if state.forge_find_open {
+            editor_col = editor_col.push(find_bar(state, t));
+        }
+        editor_col
+            .push(divider(t))
+            .push(editor_area)
+            .push(scene_notes_panel(state, t))
+            .push(scene_revisions_panel(state, t))
+            .push(annotations_panel(state, t))
     } else {
+        let mut empty_header = Row::new()
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(text("Editor").size(12).color(t.muted_fg).width(Length::Fill));
+        if let Some(stats) = novel_stats_line(state, t) {
+            empty_header = empty_header.push(stats);
+        }
+        if state.active_novel_id.is_some() {
+            empty_header = empty_header.push(ui::ghost_button(
+                t,
+                "Manuscript".to_string(),
+                Message::TheForge(TheForgeMessage::ToggleManuscript),
+            ));
+        }
+
         column![
-            Row::new()
-                .align_y(Alignment::Center)
-                .push(text("Editor").size(12).color(t.muted_fg).width(Length::Fill)),
+            empty_header,
             divider(t),
             container(
                 text("Select a scene to start writing")
@@ -701,10 +1760,18 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
             .spacing(8)
     };
 
-    let editor_panel = container(editor_content)
+    // Focus mode: columna de texto con ancho máximo, centrada en el panel
+    let editor_inner: Element<Message> = if state.forge_focus_mode {
+        container(editor_content).max_width(FOCUS_TEXT_MAX_W).into()
+    } else {
+        editor_content.into()
+    };
+
+    let editor_panel = container(editor_inner)
         .padding(16)
         .width(Length::Fill)
         .height(Length::Fill)
+        .align_x(Alignment::Center)
         .style(move |_: &Theme| {
             let mut s = ui::container_style(ui::alpha(t.shell_a, 0.5), t.foreground);
             s.border.width = 1.0;
@@ -713,10 +1780,37 @@ pub fn the_forge<'a>(state: &'a AppState, t: Tokens) -> Element<'a, Message> {
             s
         });
 
-    let main_row = Row::new()
-        .spacing(16)
-        .push(outline_panel)
-        .push(editor_panel);
+    // Focus mode: sin outline ni tira (F11 / Esc / "Exit focus" para volver)
+    let main_row = if state.forge_focus_mode {
+        Row::new().push(editor_panel)
+    } else if state.forge_outline_collapsed {
+        let strip = container(
+            button(text("›").size(16).color(t.muted_fg))
+                .padding([8, 6])
+                .style(ui::ghost_button_style(t))
+                .on_press(Message::TheForge(TheForgeMessage::ToggleOutlinePanel)),
+        )
+            .padding([16, 4])
+            .width(Length::Fixed(32.0))
+            .height(Length::Fill)
+            .style(move |_: &Theme| {
+                let mut s = ui::container_style(ui::alpha(t.shell_a, 0.5), t.foreground);
+                s.border.width = 1.0;
+                s.border.color = t.border;
+                s.border.radius = 12.0.into();
+                s
+            });
+
+        Row::new()
+            .spacing(16)
+            .push(strip)
+            .push(editor_panel)
+    } else {
+        Row::new()
+            .spacing(16)
+            .push(outline_panel)
+            .push(editor_panel)
+    };
 
     container(main_row)
         .padding(16)
@@ -1,10 +1,10 @@
 use iced::{Alignment, Color, Length, Vector, Padding};
-use iced::widget::{button, container, mouse_area, text, text_input, text_editor, pick_list, Column, Row, Space};
+use iced::widget::{button, container, mouse_area, scrollable, text, text_input, text_editor, pick_list, Column, Row, Space};
 use iced::Theme;
 use iced::border;
 
 use crate::app::{AppState, Message, TimelineMessage};
-use crate::model::{TimelineEvent, Location, TimelineEra};
+use crate::model::{TimelineEvent, Location, SceneMention, TimelineEra};
 use crate::{pages::E, ui};
 
 pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -> E<'a> {
@@ -19,6 +19,21 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
         )
         .push(Space::new().width(Length::Fill))
         .push(ui::outline_button(t, "Back".to_string(), Message::BackToUniverse(universe_id.to_string())))
+        .push(if state.timeline_export_busy {
+            ui::card(t, text("Exporting…").size(12).color(t.muted_fg).into())
+        } else {
+            ui::ghost_button(t, "Export as image".to_string(), Message::ExportTimelineImage(None))
+        })
+        .push(
+            pick_list(
+                &crate::db::timeline_data_export::TimelineExportFormat::ALL[..],
+                Some(state.timeline_export_format),
+                |f| Message::Timeline(TimelineMessage::ExportFormatChanged(f)),
+            )
+                .text_size(12)
+                .padding([4, 8]),
+        )
+        .push(ui::ghost_button(t, "Export data".to_string(), Message::Timeline(TimelineMessage::ExportData)))
         .push(ui::primary_button(t, "Add Era".to_string(), Message::Timeline(TimelineMessage::EditorOpenCreateEra)));
 
     let mut list = Column::new().spacing(0);
@@ -34,6 +49,31 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
         return ui::page_padding(content.into());
     }
 
+    list = list.push(filter_bar(state, t));
+    list = list.push(Space::new().height(Length::Fixed(16.0)));
+
+    if !state.timeline_era_overlaps.is_empty() {
+        list = list.push(era_overlap_card(state, t));
+        list = list.push(Space::new().height(Length::Fixed(16.0)));
+    }
+
+    // Filtros de vista: la fuente no se re-ordena, solo se saltean eventos
+    let era_filter = state.timeline_era_filter.as_deref();
+    let filtering = era_filter.is_some() || state.timeline_importance_filter.is_some();
+
+    if filtering && !events.iter().any(|e| state.timeline_event_passes(e)) {
+        let empty = Column::new().spacing(10)
+            .push(text("No events match these filters.").size(14).color(t.foreground))
+            .push(text("Try another era or importance, or show everything again.").size(12).color(t.muted_fg))
+            .push(ui::ghost_button(t, "Clear filters".to_string(), Message::Timeline(TimelineMessage::ClearFilters)));
+        list = list.push(ui::card(t, empty.into()));
+        let content = Column::new().spacing(20).push(header).push(list);
+        return ui::page_padding(content.into());
+    }
+
+    list = list.push(chronology_strip(state, t));
+    list = list.push(Space::new().height(Length::Fixed(24.0)));
+
     // Helper: does an event year fall inside an era?
     let in_era = |evt_year: i64, era: &TimelineEra| -> bool {
         if evt_year < era.start_year { return false; }
@@ -48,6 +88,10 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
 
     // Render eras FIRST (so empty eras show up)
     for era in eras.iter() {
+        if era_filter.is_some_and(|id| id != era.id) {
+            continue;
+        }
+
         list = list.push(era_banner_interactive(t, era));
         list = list.push(Space::new().height(Length::Fixed(12.0)));
 
@@ -55,16 +99,19 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
         for (idx, evt) in events.iter().enumerate() {
             if in_era(evt.year, era) {
                 assigned[idx] = true;
-                any = true;
-                list = list.push(timeline_row(t, evt, false, false, &state.locations, universe_id));
+                if state.timeline_event_passes(evt) {
+                    any = true;
+                    list = list.push(timeline_row(t, evt, false, false, &state.locations, &state.timeline_scene_refs, universe_id));
+                }
             }
         }
 
         if !any {
+            let empty_label = if filtering { "No matching events in this era." } else { "No events in this era yet." };
             // This was the line that previously failed due to Padding conversion in your build.
             // Using explicit Padding struct avoids E0277.
             list = list.push(
-                container(text("No events in this era yet.").size(12).color(t.muted_fg))
+                container(text(empty_label).size(12).color(t.muted_fg))
                     .padding(Padding { top: 0.0, right: 0.0, bottom: 18.0, left: 164.0 })
                     .width(Length::Fill)
             );
@@ -74,7 +121,9 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
     }
 
     // Render unassigned events (events that do not fall into any era)
-    let any_unassigned = assigned.iter().any(|v| !*v);
+    // Con filtro de era, "fuera de toda era" no aplica
+    let any_unassigned = era_filter.is_none()
+        && events.iter().zip(&assigned).any(|(e, a)| !*a && state.timeline_event_passes(e));
 
     if any_unassigned {
         let phantom = TimelineEra {
@@ -91,8 +140,8 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
         list = list.push(Space::new().height(Length::Fixed(12.0)));
 
         for (idx, evt) in events.iter().enumerate() {
-            if !assigned[idx] {
-                list = list.push(timeline_row(t, evt, false, false, &state.locations, universe_id));
+            if !assigned[idx] && state.timeline_event_passes(evt) {
+                list = list.push(timeline_row(t, evt, false, false, &state.locations, &state.timeline_scene_refs, universe_id));
             }
         }
     }
@@ -101,6 +150,196 @@ pub fn timeline<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) ->
     ui::page_padding(content.into())
 }
 
+// Aviso (no bloquea): eras con rangos solapados suelen ser un error de carga
+fn era_overlap_card<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let warn = Color::from_rgb8(234, 179, 8);
+    let mut col = Column::new().spacing(4)
+        .push(text(format!("⚠️ Overlapping eras ({})", state.timeline_era_overlaps.len())).size(13).color(warn));
+
+    for issue in state.timeline_era_overlaps.iter().take(6) {
+        col = col.push(text(issue).size(12).color(t.foreground));
+    }
+    if state.timeline_era_overlaps.len() > 6 {
+        col = col.push(text(format!("…and {} more", state.timeline_era_overlaps.len() - 6)).size(12).color(t.muted_fg));
+    }
+
+    container(col)
+        .padding([10, 14])
+        .width(Length::Fill)
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(ui::alpha(warn, 0.08), t.foreground);
+            s.border.color = ui::alpha(warn, 0.4);
+            s.border.width = 1.0;
+            s.border.radius = 8.0.into();
+            s
+        })
+        .into()
+}
+
+// Opción de los dropdowns de filtro (None = "All ...")
+#[derive(Debug, Clone, PartialEq)]
+struct FilterOption<'a> {
+    value: Option<&'a str>,
+    label: &'a str,
+}
+
+impl std::fmt::Display for FilterOption<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label)
+    }
+}
+
+const IMPORTANCE_LEVELS: [&str; 3] = ["Major", "Normal", "Minor"];
+
+fn filter_bar<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    let mut era_options = vec![FilterOption { value: None, label: "All eras" }];
+    era_options.extend(state.timeline_eras.iter().map(|e| FilterOption { value: Some(e.id.as_str()), label: e.name.as_str() }));
+    let era_selected = era_options.iter().find(|o| o.value == state.timeline_era_filter.as_deref()).cloned();
+
+    let mut importance_options = vec![FilterOption { value: None, label: "All importance" }];
+    importance_options.extend(IMPORTANCE_LEVELS.iter().map(|&i| FilterOption { value: Some(i), label: i }));
+    let importance_selected = importance_options
+        .iter()
+        .find(|o| o.value == state.timeline_importance_filter.as_deref())
+        .cloned();
+
+    Row::new().spacing(8).align_y(Alignment::Center)
+        .push(text("Show").size(12).color(t.muted_fg))
+        .push(
+            pick_list(era_options, era_selected, |o| {
+                Message::Timeline(TimelineMessage::EraFilterChanged(o.value.map(str::to_string)))
+            })
+                .text_size(12)
+                .padding([4, 8]),
+        )
+        .push(
+            pick_list(importance_options, importance_selected, |o| {
+                Message::Timeline(TimelineMessage::ImportanceFilterChanged(o.value.map(str::to_string)))
+            })
+                .text_size(12)
+                .padding([4, 8]),
+        )
+        .into()
+}
+
+// Banda cronológica horizontal: regla + eras + eventos escalonados (posiciones de state.timeline_layout)
+fn chronology_strip<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    use crate::timeline_layout::EVENT_LABEL_W;
+
+    let layout = &state.timeline_layout;
+
+    let zoom_bar = Row::new().spacing(6).align_y(Alignment::Center)
+        .push(text("Zoom").size(12).color(t.muted_fg))
+        .push(ui::ghost_button(t, "−".to_string(), Message::Timeline(TimelineMessage::ZoomOut)))
+        .push(text(format_zoom(state.timeline_zoom)).size(12).color(t.foreground))
+        .push(ui::ghost_button(t, "+".to_string(), Message::Timeline(TimelineMessage::ZoomIn)))
+        .push(ui::ghost_button(t, "Reset".to_string(), Message::Timeline(TimelineMessage::ZoomReset)))
+        .push(text("Ctrl + scroll over the strip to zoom").size(11).color(ui::alpha(t.muted_fg, 0.7)));
+
+    let mut strip = Column::new().spacing(6).width(Length::Fixed(layout.width));
+
+    // Regla
+    let mut ruler = Row::new().height(Length::Fixed(18.0));
+    let mut cursor = 0.0;
+    for (x, year) in &layout.ticks {
+        ruler = ruler
+            .push(Space::new().width(Length::Fixed((x - cursor).max(0.0))))
+            .push(text(year.to_string()).size(10).color(t.muted_fg).width(Length::Fixed(60.0)));
+        cursor = x + 60.0;
+    }
+    strip = strip.push(ruler);
+
+    // Eras (barras con su color; carriles si se solapan)
+    for lane in &layout.era_lanes {
+        let mut row = Row::new().height(Length::Fixed(22.0));
+        let mut cursor = 0.0;
+        for slot in lane {
+            let Some(era) = state.timeline_eras.get(slot.index) else { continue };
+            let color = hex_to_color(&era.color);
+            let bar = mouse_area(
+                container(text(&era.name).size(11).color(t.foreground))
+                    .padding([3, 6])
+                    .width(Length::Fixed(slot.width))
+                    .clip(true)
+                    .style(move |_: &Theme| {
+                        let mut s = ui::container_style(ui::alpha(color, 0.25), t.foreground);
+                        s.border.color = ui::alpha(color, 0.6);
+                        s.border.width = 1.0;
+                        s.border.radius = 4.0.into();
+                        s
+                    }),
+            )
+                .on_press(Message::Timeline(TimelineMessage::EraBannerClicked(era.id.clone())));
+            row = row
+                .push(Space::new().width(Length::Fixed((slot.x - cursor).max(0.0))))
+                .push(bar);
+            cursor = slot.x + slot.width;
+        }
+        strip = strip.push(row);
+    }
+
+    // Eventos (carril = escalonado vertical para los que caen muy cerca)
+    for lane in &layout.event_lanes {
+        let mut row = Row::new().height(Length::Fixed(26.0));
+        let mut cursor = 0.0;
+        for slot in lane {
+            let Some(evt) = state.timeline_events.get(slot.index) else { continue };
+            let color = hex_to_color(&evt.color);
+            let marker = mouse_area(
+                container(
+                    Row::new().spacing(6).align_y(Alignment::Center)
+                        .push(container(Space::new()).width(Length::Fixed(8.0)).height(Length::Fixed(8.0)).style(move |_: &Theme| {
+                            let mut s = ui::container_style(color, Color::TRANSPARENT);
+                            s.border.radius = 999.0.into();
+                            s
+                        }))
+                        .push(text(format!("{} · {}", evt.year, evt.title)).size(11).color(t.foreground)),
+                )
+                    .width(Length::Fixed(EVENT_LABEL_W))
+                    .clip(true),
+            )
+                .on_press(Message::Timeline(TimelineMessage::CardClicked(evt.id.clone())));
+            row = row
+                .push(Space::new().width(Length::Fixed((slot.x - cursor).max(0.0))))
+                .push(marker);
+            cursor = slot.x + EVENT_LABEL_W;
+        }
+        strip = strip.push(row);
+    }
+
+    let scroller = scrollable(container(strip).padding([8, 0]))
+        .direction(scrollable::Direction::Horizontal(scrollable::Scrollbar::default()))
+        .width(Length::Fill);
+
+    let framed = mouse_area(
+        container(scroller)
+            .width(Length::Fill)
+            .style(move |_: &Theme| {
+                let mut s = ui::container_style(t.card, t.foreground);
+                s.border.color = t.border;
+                s.border.width = 1.0;
+                s.border.radius = 8.0.into();
+                s
+            }),
+    )
+        .on_scroll(|delta| {
+            let dy = match delta {
+                iced::mouse::ScrollDelta::Lines { y, .. } | iced::mouse::ScrollDelta::Pixels { y, .. } => y,
+            };
+            Message::Timeline(TimelineMessage::StripScrolled(dy))
+        });
+
+    Column::new().spacing(8).push(zoom_bar).push(framed).into()
+}
+
+fn format_zoom(px_per_year: f32) -> String {
+    if px_per_year >= 1.0 {
+        format!("{:.1} px/year", px_per_year)
+    } else {
+        format!("{:.0} years/px", 1.0 / px_per_year)
+    }
+}
+
 fn hex_to_color(hex: &str) -> Color {
     let hex = hex.trim_start_matches('#');
     if hex.len() == 6 {
@@ -166,6 +405,7 @@ fn timeline_row<'a>(
     _is_first: bool,
     _is_last: bool,
     locations: &'a [Location],
+    scenes: &'a [SceneMention],
     universe_id: &'a str
 ) -> iced::Element<'static, Message> {
 
@@ -232,6 +472,25 @@ fn timeline_row<'a>(
         Space::new().width(Length::Shrink).height(Length::Shrink).into()
     };
 
+    // Link a la escena: solo si sigue existiendo (borrada / en papelera => no se muestra)
+    let scene_link: iced::Element<'static, Message> = match evt
+        .scene_id
+        .as_deref()
+        .and_then(|sid| scenes.iter().find(|s| s.scene_id == sid))
+    {
+        Some(m) => button(text(format!("🎬 {}", m.scene_title)).size(12).color(t.accent))
+            .padding(0)
+            .style(crate::ui::ghost_button_style(t))
+            .on_press(Message::TheForge(crate::messages::TheForgeMessage::JumpToScene {
+                universe_id: m.universe_id.clone(),
+                novel_id: m.novel_id.clone(),
+                chapter_id: m.chapter_id.clone(),
+                scene_id: m.scene_id.clone(),
+            }))
+            .into(),
+        None => Space::new().width(Length::Shrink).height(Length::Shrink).into(),
+    };
+
     let kind_badge = container(text(kind).size(10).color(accent_color))
         .padding([2, 6])
         .style(move |_: &Theme| {
@@ -245,6 +504,7 @@ fn timeline_row<'a>(
             .push(text(title).size(16).color(t.foreground))
             .push(kind_badge)
             .push(Space::new().width(Length::Fill))
+            .push(scene_link)
             .push(loc_info)
         )
         .push(text(description).size(13).color(ui::alpha(t.muted_fg, 0.8)));
@@ -284,7 +544,12 @@ fn timeline_row<'a>(
 }
 
 // Modal de Evento
-pub fn render_event_modal<'a>(t: ui::Tokens, editor: &'a crate::app::EventEditor, locations: &'a [Location]) -> E<'a> {
+pub fn render_event_modal<'a>(
+    t: ui::Tokens,
+    editor: &'a crate::app::EventEditor,
+    locations: &'a [Location],
+    scenes: &'a [SceneMention],
+) -> E<'a> {
     let title = if editor.id.is_some() { "Edit Event" } else { "Add Event" };
 
     let title_input = text_input("Event Title", &editor.title)
@@ -340,6 +605,7 @@ pub fn render_event_modal<'a>(t: ui::Tokens, editor: &'a crate::app::EventEditor
             .push(Column::new().spacing(6).push(text("Color").size(12).color(t.muted_fg)).push(color_input))
             .push(Column::new().spacing(6).push(text("Location").size(12).color(t.muted_fg)).push(loc_picker))
         )
+        .push(Column::new().spacing(6).push(text("Depicted in scene").size(12).color(t.muted_fg)).push(scene_picker(editor, scenes)))
         .push(Column::new().spacing(6).push(text("Description").size(12).color(t.muted_fg)).push(desc_input))
         .push(Row::new().spacing(10)
             .push(ui::primary_button(t, "Save".to_string(), Message::Timeline(TimelineMessage::EditorSaveEvent)))
@@ -355,6 +621,36 @@ pub fn render_event_modal<'a>(t: ui::Tokens, editor: &'a crate::app::EventEditor
         .style(move |_: &Theme| ui::container_style(Color::from_rgba8(0,0,0, 0.7), t.foreground)).into()
 }
 
+// Opción del picker de escena ("Novel › Chapter › Scene"; None = sin escena)
+#[derive(Debug, Clone, PartialEq)]
+struct SceneOption<'a> {
+    id: Option<&'a str>,
+    label: String,
+}
+
+impl std::fmt::Display for SceneOption<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+fn scene_picker<'a>(editor: &'a crate::app::EventEditor, scenes: &'a [SceneMention]) -> E<'a> {
+    let mut options = vec![SceneOption { id: None, label: "(No scene)".to_string() }];
+    options.extend(scenes.iter().map(|m| SceneOption {
+        id: Some(m.scene_id.as_str()),
+        label: format!("{} › {} › {}", m.novel_title, m.chapter_title, m.scene_title),
+    }));
+
+    // Escena borrada: el id queda guardado pero el picker no la muestra como seleccionada
+    let selected = options.iter().find(|o| o.id == editor.scene_id.as_deref()).cloned();
+
+    pick_list(options, selected, |o| Message::Timeline(TimelineMessage::SceneChanged(o.id.map(str::to_string))))
+        .placeholder("Scene...")
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
 // Modal de Era
 pub fn render_era_modal<'a>(t: ui::Tokens, editor: &'a crate::app::EraEditor) -> E<'a> {
     let title = if editor.id.is_some() { "Edit Era" } else { "New Era" };
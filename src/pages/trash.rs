@@ -1,9 +1,9 @@
 use iced::{Color, Element, Length};
 use iced::widget::{container, scrollable, text, Column, Row};
 
-use crate::{ui, messages::Message, model::TrashEntry};
+use crate::{ui, messages::Message, model::{TrashEntry, TrashSort}};
 use crate::state::AppState;
-use iced::widget::text_input;
+use iced::widget::{pick_list, text_input};
 
 pub fn trash_page(state: &AppState, t: ui::Tokens) -> Element<'_, Message> {
     let mut content = Column::new()
@@ -27,46 +27,94 @@ pub fn trash_page(state: &AppState, t: ui::Tokens) -> Element<'_, Message> {
 
     content = content.push(header);
 
-    // Search box
+    // Search box + tipo + orden (solo vista: componen sobre trash_entries)
     let search_box: iced::widget::TextInput<'_, Message> = text_input("Search in trash...", &state.trash_search_query)
         .on_input(Message::TrashSearchChanged)
         .width(Length::Fixed(300.0))
         .padding(8);
 
-    content = content.push(search_box);  // ← ESTA LÍNEA FALTABA
+    let mut types: Vec<String> = state.trash_entries.iter().map(|e| e.target_type.clone()).collect();
+    types.sort();
+    types.dedup();
+
+    let mut filter_row = Row::new()
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .push(search_box)
+        .push(
+            pick_list(types.clone(), state.trash_type_filter.clone(), |k| Message::TrashTypeFilterChanged(Some(k)))
+                .placeholder("All types")
+                .text_size(12)
+                .padding([6, 8]),
+        )
+        .push(
+            pick_list(&TrashSort::ALL[..], Some(state.trash_sort), Message::TrashSortChanged)
+                .text_size(12)
+                .padding([6, 8]),
+        );
+    if state.trash_type_filter.is_some() {
+        filter_row = filter_row.push(ui::ghost_button(t, "All types".to_string(), Message::TrashTypeFilterChanged(None)));
+    }
+
+    content = content.push(filter_row);
 
     // Buttons (Empty Trash, Clean Old Items)
     if !state.trash_entries.is_empty() {
-        let buttons = Row::new()
+        let mut buttons = Row::new()
             .spacing(8)
             .push(
                 ui::danger_button(t, "Empty Trash".to_string(), Message::EmptyTrash)
-            )
-            .push(
-                ui::ghost_button(t, "Clean Old Items (14+ days)".to_string(), Message::CleanupOldTrash)
             );
+        if !state.trash_retention.is_never() {
+            buttons = buttons.push(ui::ghost_button(
+                t,
+                format!("Clean Old Items ({}+ days)", state.trash_retention.0),
+                Message::CleanupOldTrash,
+            ));
+        }
 
         content = content.push(buttons);
+
+        // Restaurar por tipo (padres antes que hijos; lo que no se puede restaurar se saltea)
+        let restore_all = types.iter().fold(Row::new().spacing(8), |row, kind| {
+            let count = state.trash_entries.iter().filter(|e| e.target_type == *kind).count();
+            row.push(ui::ghost_button(
+                t,
+                format!("Restore all {}s ({})", kind.replace('_', " "), count),
+                Message::RestoreAllOfType(kind.clone()),
+            ))
+        });
+        content = content.push(restore_all);
+    }
+
+    // Preview del auto-cleanup (lo que se purga al conectar una vez pasado el corte)
+    if !state.trash_cleanup_preview.is_empty() {
+        content = content.push(cleanup_preview_section(state, t));
     }
 
     content = content.push(ui::h_divider(t));
 
-    // Filtrar entries según búsqueda
-    let filtered_entries: Vec<&TrashEntry> = state.trash_entries
+    // Filtrar entries según búsqueda + tipo, después ordenar
+    let query = state.trash_search_query.to_lowercase();
+    let mut filtered_entries: Vec<&TrashEntry> = state.trash_entries
         .iter()
-        .filter(|entry| {
-            if state.trash_search_query.is_empty() {
-                true
-            } else {
-                entry.display_name.to_lowercase()
-                    .contains(&state.trash_search_query.to_lowercase())
-            }
-        })
+        .filter(|entry| query.is_empty() || entry.display_name.to_lowercase().contains(&query))
+        .filter(|entry| state.trash_type_filter.as_ref().is_none_or(|k| entry.target_type == *k))
         .collect();
+    state.trash_sort.apply(&mut filtered_entries);
+
+    let filtering = !query.is_empty() || state.trash_type_filter.is_some();
+    if filtering && !state.trash_entries.is_empty() {
+        content = content.push(
+            text(format!("Showing {} of {}", filtered_entries.len(), state.trash_entries.len()))
+                .size(12)
+                .style(move |_| iced::widget::text::Style { color: Some(t.muted_fg) })
+        );
+    }
 
     // Mostrar mensaje apropiado si está vacío
     if filtered_entries.is_empty() {
-        let empty_message = if state.trash_search_query.is_empty() {
+        let empty_message = if !filtering {
             "Trash is empty"
         } else {
             "No results found"
@@ -90,6 +138,57 @@ pub fn trash_page(state: &AppState, t: ui::Tokens) -> Element<'_, Message> {
         .into()
 }
 
+fn cleanup_preview_section(state: &AppState, t: ui::Tokens) -> Element<'_, Message> {
+    let header = Row::new()
+        .spacing(12)
+        .align_y(iced::Alignment::Center)
+        .push(
+            text(format!(
+                "Pending auto-cleanup ({}) — purged {}+ days after deletion",
+                state.trash_cleanup_preview.len(),
+                state.trash_retention.0
+            ))
+                .size(14)
+                .width(Length::Fill)
+                .style(move |_| iced::widget::text::Style { color: Some(t.foreground) })
+        )
+        .push(ui::danger_button(t, "Purge Now".to_string(), Message::PurgeOldTrashPrompt));
+
+    let mut list = Column::new().spacing(4);
+    let now = chrono::Utc::now();
+    for entry in &state.trash_cleanup_preview {
+        let age_days = (now - entry.deleted_at).num_days();
+        let left = state.trash_retention.0 - age_days;
+        let when = if left <= 0 {
+            "purged at next startup".to_string()
+        } else if left == 1 {
+            "purged in 1 day".to_string()
+        } else {
+            format!("purged in {} days", left)
+        };
+
+        list = list.push(
+            text(format!("{} • {} • {}", entry.display_name, entry.target_type, when))
+                .size(12)
+                .style(move |_| iced::widget::text::Style { color: Some(t.muted_fg) })
+        );
+    }
+
+    // Mismo rojo que ui::danger_button
+    let danger = Color::from_rgba8(0xEF, 0x44, 0x44, 1.0);
+    container(Column::new().spacing(8).push(header).push(list))
+        .padding(12)
+        .width(Length::Fill)
+        .style(move |_: &iced::Theme| {
+            let mut s = ui::container_style(ui::alpha(danger, 0.06), t.foreground);
+            s.border.radius = 8.0.into();
+            s.border.width = 1.0;
+            s.border.color = ui::alpha(danger, 0.3);
+            s
+        })
+        .into()
+}
+
 fn trash_entry_row(entry: &TrashEntry, t: ui::Tokens) -> Element<'_, Message> {
     let type_badge = container(
         text(&entry.target_type)
@@ -141,6 +240,9 @@ fn trash_entry_row(entry: &TrashEntry, t: ui::Tokens) -> Element<'_, Message> {
         .push(
             ui::primary_button(t, "Restore".to_string(), Message::RestoreFromTrash(entry.id.clone()))
         )
+        .push(
+            ui::outline_button(t, "Restore & Open".to_string(), Message::RestoreAndOpen(entry.id.clone()))
+        )
         .push(
             ui::danger_button(t, "Delete Forever".to_string(), Message::PermanentDelete(entry.id.clone()))
         );
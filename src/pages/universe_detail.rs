@@ -1,9 +1,10 @@
 use iced::{Alignment, Length};
 use iced::widget::{container, text, Column, Row, text_input};
 
-use crate::app::{AppState, Message, Route, BestiaryMessage, LocationsMessage, UniverseMessage, TimelineMessage};
+use crate::app::{AppState, Message, Route, BestiaryMessage, LocationsMessage, GlossaryMessage, UniverseMessage, TimelineMessage};
 use crate::{ui, pages::E};
 use crate::state::DemoResetScope;
+use crate::model::ReplaceScope;
 
 pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -> E<'a> {
     let u = state.universes.iter().find(|x| x.id == universe_id);
@@ -19,10 +20,19 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
         .push(text(desc).size(12).color(t.muted_fg))
         .push(text("Status: Active").size(12).color(t.muted_fg));
 
-    let header_right = Row::new()
+    let mut header_right = Row::new()
         .spacing(10)
         .push(ui::outline_button(t, "Back to universes".to_string(), Message::BackToUniverses))
-        .push(ui::outline_button(t, "Go to PM Tools".to_string(), Message::Navigate(Route::PmList)))
+        .push(ui::outline_button(t, "Duplicate".to_string(), Message::Universe(UniverseMessage::Duplicate(universe_id.to_string()))))
+        .push(ui::ghost_button(
+            t,
+            if state.universe_clone_include_novels { "✓ With novels" } else { "With novels" }.to_string(),
+            Message::Universe(UniverseMessage::ToggleCloneNovels),
+        ));
+    if state.capability_enabled("pm") {
+        header_right = header_right.push(ui::outline_button(t, "Go to PM Tools".to_string(), Message::Navigate(Route::PmList)));
+    }
+    header_right = header_right
         .push(ui::outline_button(t, "Toggle Debug Overlay".to_string(), Message::Universe(UniverseMessage::ToggleDebugOverlay)));
 
     let header = Row::new()
@@ -32,28 +42,24 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
 
     let uid = universe_id.to_string();
 
+    // Herramientas con la capability apagada no se muestran (navigation_controller igual las deniega)
+    let tool_links = [
+        ("Bestiary", "bestiary", Message::Bestiary(BestiaryMessage::Open(uid.clone()))),
+        ("Locations", "locations", Message::Locations(LocationsMessage::Open(uid.clone()))),
+        ("Glossary", "worldbuilding", Message::Glossary(GlossaryMessage::Open(uid.clone()))),
+        ("Timeline", "timeline", Message::Timeline(TimelineMessage::Open(uid.clone()))),
+    ];
+    let mut tool_row = Row::new().spacing(10);
+    for (label, capability, msg) in tool_links {
+        if state.capability_enabled(capability) {
+            tool_row = tool_row.push(ui::outline_button(t, label.to_string(), msg));
+        }
+    }
+
     let tools = Column::new()
         .spacing(8)
         .push(text("Universe tools").size(12).color(t.muted_fg))
-        .push(
-            Row::new()
-                .spacing(10)
-                .push(ui::outline_button(
-                    t,
-                    "Bestiary".to_string(),
-                    Message::Bestiary(BestiaryMessage::Open(uid.clone())),
-                ))
-                .push(ui::outline_button(
-                    t,
-                    "Locations".to_string(),
-                    Message::Locations(LocationsMessage::Open(uid.clone())),
-                ))
-                .push(ui::outline_button(
-                    t,
-                    "Timeline".to_string(),
-                    Message::Timeline(TimelineMessage::Open(uid.clone())),
-                ))
-        );
+        .push(tool_row);
 
     let is_arhelis = universe_id == "u-arhelis-01";
     let busy = state.db_inflight.is_some();
@@ -61,7 +67,10 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
         .spacing(20)
         .push(header)
         .push(ui::h_divider(t))
-        .push(tools);
+        .push(tools)
+        .push(timeline_summary(state, t, universe_id))
+        .push(ui::h_divider(t))
+        .push(find_replace_section(state, t, universe_id, busy));
 
     if is_arhelis {
         let dev_header = Row::new()
@@ -120,6 +129,15 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
                         "Validate Integrity".to_string(),
                         Message::Universe(UniverseMessage::ValidateUniverse(universe_id.to_string())),
                     )
+                })
+                .push(if busy || state.integrity_busy || state.integrity_issues.is_empty() {
+                    ui::card(t, text("Repair FKs").size(12).color(t.muted_fg).into())
+                } else {
+                    ui::danger_button(
+                        t,
+                        "Repair FKs".to_string(),
+                        Message::Universe(UniverseMessage::RepairForeignKeys),
+                    )
                 });
 
             let mut snap_list = Column::new().spacing(6);
@@ -129,9 +147,29 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
                 for s in state.snapshots.iter().take(8) {
                     let sid = s.id.clone();
 
+                    // Doble click en el nombre => input inline (Enter guarda)
+                    let label: iced::Element<'_, Message> = match &state.snapshot_renaming {
+                        Some((renaming_id, draft)) if *renaming_id == s.id => text_input(&s.default_name(), draft)
+                            .id(iced::widget::Id::new("snapshot_rename"))
+                            .on_input(|v| Message::Universe(UniverseMessage::SnapshotRenameChanged(v)))
+                            .on_submit(Message::Universe(UniverseMessage::SnapshotRenameCommit))
+                            .size(12)
+                            .padding([2, 6])
+                            .width(Length::Fixed(260.0))
+                            .style(ui::input_style(t))
+                            .into(),
+                        _ => iced::widget::mouse_area(
+                            text(format!("{}{}  —  {}", if s.is_auto { "⏱ " } else { "" }, s.name, s.created_at))
+                                .size(12)
+                                .color(if s.is_auto { t.muted_fg } else { t.foreground }),
+                        )
+                            .on_press(Message::Universe(UniverseMessage::SnapshotClicked(sid.clone())))
+                            .into(),
+                    };
+
                     let row = Row::new()
                         .spacing(10)
-                        .push(text(format!("{}  —  {}", s.name, s.created_at)).size(12).color(t.foreground))
+                        .push(label)
                         .push(if busy {
                             ui::card(t, text("Restore (busy)").size(12).color(t.muted_fg).into())
                         } else {
@@ -224,6 +262,7 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
             let dev_body = Column::new()
                 .spacing(14)
                 .push(qa)
+                .push(database_section(state, t))
                 .push(snapshots_section)
                 .push(text("Demo Data Tools").size(12).color(t.muted_fg))
                 .push(inject_row)
@@ -235,3 +274,169 @@ pub fn universe_detail<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a
 
     ui::page_padding(body.width(Length::Fill).into())
 }
+
+// Find & Replace: preview obligatorio antes de aplicar (batch en una sola transacción)
+fn find_replace_section<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str, busy: bool) -> E<'a> {
+    let inputs = Row::new()
+        .spacing(10)
+        .push(
+            text_input("Find (case-sensitive)", &state.replace_find)
+                .on_input(|v| Message::Universe(UniverseMessage::ReplaceFindChanged(v)))
+                .padding(10)
+                .style(ui::input_style(t)),
+        )
+        .push(
+            text_input("Replace with", &state.replace_with)
+                .on_input(|v| Message::Universe(UniverseMessage::ReplaceWithChanged(v)))
+                .padding(10)
+                .style(ui::input_style(t)),
+        );
+
+    let mut scopes = Row::new().spacing(8).align_y(Alignment::Center)
+        .push(text("In:").size(12).color(t.muted_fg));
+    for scope in ReplaceScope::ALL {
+        let on = state.replace_scopes.contains(scope);
+        let label = if on { format!("✓ {}", scope.label()) } else { scope.label().to_string() };
+        let msg = Message::Universe(UniverseMessage::ReplaceToggleScope(*scope));
+        scopes = scopes.push(if on { ui::primary_button(t, label, msg) } else { ui::outline_button(t, label, msg) });
+    }
+
+    let has_preview = state.replace_preview_term.as_deref() == Some(state.replace_find.as_str())
+        && !state.replace_preview.is_empty();
+
+    let actions = Row::new()
+        .spacing(10)
+        .push(if busy || state.replace_preview_busy {
+            ui::card(t, text("Preview (busy)").size(12).color(t.muted_fg).into())
+        } else {
+            ui::outline_button(
+                t,
+                "Preview".to_string(),
+                Message::Universe(UniverseMessage::ReplacePreview(universe_id.to_string())),
+            )
+        })
+        .push(if busy || !has_preview {
+            ui::card(t, text("Replace all").size(12).color(t.muted_fg).into())
+        } else {
+            ui::danger_button(
+                t,
+                format!("Replace in {} items", state.replace_preview.len()),
+                Message::Universe(UniverseMessage::ReplaceApply(universe_id.to_string())),
+            )
+        });
+
+    let mut preview = Column::new().spacing(4);
+    if has_preview {
+        for m in state.replace_preview.iter().take(50) {
+            preview = preview.push(
+                text(format!("{} · {} — {} match(es)", m.scope.label(), m.display_name, m.occurrences))
+                    .size(12)
+                    .color(t.foreground),
+            );
+        }
+        if state.replace_preview.len() > 50 {
+            preview = preview.push(
+                text(format!("… and {} more", state.replace_preview.len() - 50)).size(12).color(t.muted_fg),
+            );
+        }
+    }
+
+    Column::new()
+        .spacing(10)
+        .push(text("Find & Replace").size(12).color(t.muted_fg))
+        .push(inputs)
+        .push(scopes)
+        .push(actions)
+        .push(preview)
+        .into()
+}
+
+// Resumen temporal (at-a-glance). Eventos/eras ya vienen ordenados desde TimelineFetched.
+fn timeline_summary<'a>(state: &'a AppState, t: ui::Tokens, universe_id: &'a str) -> E<'a> {
+    let loaded = state.loaded_timeline_universe.as_deref() == Some(universe_id);
+
+    let content: E<'a> = if !loaded {
+        text("Loading timeline…").size(12).color(t.muted_fg).into()
+    } else if state.timeline_events.is_empty() && state.timeline_eras.is_empty() {
+        text("No timeline data yet").size(12).color(t.muted_fg).into()
+    } else {
+        let span = match (state.timeline_events.first(), state.timeline_events.last()) {
+            (Some(first), Some(last)) => format!("Year {} → {}", first.year, last.year),
+            _ => "No events".to_string(),
+        };
+
+        let latest_era = state
+            .timeline_eras
+            .iter()
+            .max_by_key(|e| e.start_year)
+            .map(|e| e.name.clone())
+            .unwrap_or_else(|| "—".to_string());
+
+        Row::new()
+            .spacing(24)
+            .align_y(Alignment::Center)
+            .push(text(span).size(13).color(t.foreground))
+            .push(text(format!("{} events", state.timeline_events.len())).size(12).color(t.muted_fg))
+            .push(text(format!("{} eras", state.timeline_eras.len())).size(12).color(t.muted_fg))
+            .push(text(format!("Latest era: {}", latest_era)).size(12).color(t.muted_fg))
+            .into()
+    };
+
+    ui::card(
+        t,
+        Column::new()
+            .spacing(6)
+            .push(text("Timeline summary").size(12).color(t.muted_fg))
+            .push(content)
+            .into(),
+    )
+}
+
+// Dev panel: tamaño/filas + VACUUM (las stats se piden a mano, no en cada render)
+fn database_section<'a>(state: &'a AppState, t: ui::Tokens) -> E<'a> {
+    use crate::controllers::backup_controller::format_bytes;
+
+    let busy = state.db_maintenance_busy || state.db_inflight.is_some();
+
+    let actions = Row::new()
+        .spacing(10)
+        .push(if busy {
+            ui::card(t, text("Compact database (busy)").size(12).color(t.muted_fg).into())
+        } else {
+            ui::outline_button(t, "Compact database".to_string(), Message::CompactDatabase)
+        })
+        .push(if busy {
+            ui::card(t, text("Recalculate word counts (busy)").size(12).color(t.muted_fg).into())
+        } else {
+            ui::outline_button(t, "Recalculate word counts".to_string(), Message::RecalcWordCounts)
+        })
+        .push(ui::ghost_button(t, "DB stats".to_string(), Message::RefreshDbStats));
+
+    let mut col = Column::new()
+        .spacing(6)
+        .push(text("Database").size(12).color(t.muted_fg))
+        .push(actions);
+
+    if let Some(stats) = &state.db_stats {
+        col = col.push(
+            text(format!(
+                "Size: {}  ·  reclaimable: {}",
+                format_bytes(stats.size_bytes),
+                format_bytes(stats.free_bytes)
+            ))
+                .size(12)
+                .color(t.foreground),
+        );
+
+        let counts = stats
+            .tables
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(name, n)| format!("{}={}", name, n))
+            .collect::<Vec<_>>()
+            .join("  ");
+        col = col.push(text(counts).size(12).color(t.muted_fg));
+    }
+
+    ui::card(t, col.into())
+}
@@ -69,6 +69,7 @@ fn universe_card(t: ui::Tokens, u: Universe) -> iced::Element<'static, Message>
     let actions = Row::new()
         .spacing(10)
         .push(ui::outline_button(t, "Open".to_string(), Message::Universe(UniverseMessage::Open(u.id.clone()))))
+        .push(ui::outline_button(t, "Duplicate".to_string(), Message::Universe(UniverseMessage::Duplicate(u.id.clone()))))
         .push(ui::outline_button(t, "Archive".to_string(), Message::Navigate(Route::UniverseList)))
         .push(ui::danger_button(t, "Delete".to_string(), Message::Universe(UniverseMessage::Delete(u.id))));
 
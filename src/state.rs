@@ -3,11 +3,11 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use iced::widget::text_editor;
 
 use crate::model::{
-    Creature, Universe, Card, KanbanBoardData, Board, Location, TimelineEvent, TimelineEra, Project, UniverseSnapshot,
-    Novel, Chapter, Scene, TrashEntry
+    Creature, Universe, Card, KanbanBoardData, Board, Location, GlossaryTerm, TimelineEvent, TimelineEra, Project, UniverseSnapshot,
+    Novel, Chapter, Scene, TrashEntry, ReplaceScope, TextMatch
 };
 use crate::app::{Route, PmState, PmId};
-use crate::editors::{CreatureEditor, LocationEditor, EventEditor, EraEditor};
+use crate::editors::{CreatureEditor, LocationEditor, GlossaryEditor, EventEditor, EraEditor};
 
 // ================================
 // FASE 13 (PRO): Observabilidad (Debug Overlay)
@@ -52,27 +52,45 @@ pub enum DemoResetScope {
 #[derive(Debug, Clone, PartialEq)]
 pub enum DbAction {
     CreateUniverse { id: String, name: String, desc: String },
+    CloneUniverse { source_id: String, include_novels: bool },
+    RepairForeignKeys,
+    ExportAuditLog { filter: crate::model::AuditFilter, path: std::path::PathBuf },
+    // Proyecto completo <-> JSON versionado (crate::db::project_archive)
+    ExportProject { path: std::path::PathBuf },
+    ImportProject { path: std::path::PathBuf },
     InjectDemoData(String),
     ResetDemoDataScoped(String, DemoResetScope),
 
     SnapshotCreate { universe_id: String, name: String },
     SnapshotDelete { snapshot_id: String },
     SnapshotRestore { snapshot_id: String },
+    SnapshotRename { snapshot_id: String, name: String },
+    SnapshotCreateAuto { universe_id: String, name: String, keep: usize },
 
     CreateBoard { id: String, name: String },
+    CreateBoardFromTemplate { id: String, template_id: String, name: String },
+    SaveBoardTemplate { template_id: String, board_id: String, name: String },
 
     SaveCreature(Creature, String),
     ArchiveCreature(String, bool),
+    AddCreatureRelation(crate::model::CreatureRelation),
+    RemoveCreatureRelation(String), // relation_id
 
     SaveLocation(Location),
 
+    SaveGlossaryTerm(GlossaryTerm),
+
     SaveEvent(TimelineEvent),
 
     SaveEra(TimelineEra),
 
     SaveCard(Card),
     MoveCard(String, String, i64),
+    MoveCardToBoard { card_id: String, target_board_id: String },
+    ArchiveCard(String),
+    RestoreCard { card_id: String, board_id: String }, // board_id: destino si la columna original ya no existe
     RebalanceColumn(String),
+    ReorderColumn { board_id: String, column_id: String, new_index: usize },
     DeleteCard(String),
 
     // --- THE FORGE ACTIONS ---
@@ -82,13 +100,33 @@ pub enum DbAction {
     CreateChapter(String, String, String), // (chapter_id, novel_id, title)
     UpdateChapter(Chapter),
     ReorderChapter(String, i64),
+    MergeNovels { keep_id: String, merge_id: String },
+    ExportNovelJson { novel_id: String, path: std::path::PathBuf }, // solo lectura: dump JSON a disco
+    ExportNovelMarkdown { novel_id: String, path: std::path::PathBuf }, // solo lectura: compile a .md
+    ExportBestiary {
+        universe_id: String,
+        format: crate::db::bestiary_export::BestiaryExportFormat,
+        include_archived: bool,
+        path: std::path::PathBuf,
+    }, // solo lectura: CSV / JSON del bestiario
+    ExportTimeline {
+        universe_id: String,
+        format: crate::db::timeline_data_export::TimelineExportFormat,
+        path: std::path::PathBuf,
+    }, // solo lectura: CSV / JSON de eventos + eras
 
     CreateScene(String, String, String), // (scene_id, chapter_id, title)
     UpdateScene(Scene),
     ReorderScene(String, i64),
+    MoveSceneToChapter { scene_id: String, chapter_id: String },
+    MoveSceneToChapterAt { scene_id: String, from_chapter_id: String, chapter_id: String, new_index: i64 },
+    // Preferencia de UI (sin audit): nodos expandidos del outline, reemplaza el set del universo
+    SaveOutlineExpansion { universe_id: String, novel_ids: Vec<String>, chapter_ids: Vec<String> },
 
 
     MoveToTrash {
+        // Id de la trash_entry (se asigna al encolar: el toast de "Undo" lo necesita)
+        trash_id: String,
         target_type: String,
         target_id: String,
         display_name: String,
@@ -100,7 +138,141 @@ pub enum DbAction {
     RestoreFromTrash(String),      // trash_entry_id
     PermanentDelete(String),        // trash_entry_id
     EmptyTrash,
-    CleanupOldTrash,
+    CleanupOldTrash(i64), // purga entries con más de N días
+    SetTrashRetention(crate::db::trash::TrashRetention),
+
+    // --- UI ---
+    SetUiTheme(crate::ui::ThemeMode),
+
+    // --- CAPABILITIES ---
+    SetCapability { key: String, enabled: bool }, // key interna (guards::Capabilities::TOGGLES)
+
+    // --- RECENT ---
+    TouchRecentItem { kind: String, target_id: String }, // kind: "universe" | "board" | "novel"
+
+    // --- MAINTENANCE ---
+    BulkReplaceText {
+        universe_id: String,
+        from: String,
+        to: String,
+        scopes: Vec<ReplaceScope>,
+    },
+}
+
+// Backpressure de db_queue: por encima de WARN mostramos aviso persistente;
+// por encima de HARD solo aceptamos acciones críticas (guardar contenido, crear, papelera).
+pub const DB_QUEUE_WARN_LIMIT: usize = 25;
+pub const DB_QUEUE_HARD_LIMIT: usize = 100;
+
+impl DbAction {
+    /// Escrituras de contenido del usuario y altas/bajas que la UI ya aplicó en memoria
+    /// (descartarlas deja el estado local divergido de la DB): nunca se descartan por backpressure.
+    pub fn is_critical(&self) -> bool {
+        matches!(
+            self,
+            DbAction::CreateUniverse { .. }
+                | DbAction::CreateBoard { .. }
+                | DbAction::CreateBoardFromTemplate { .. }
+                | DbAction::CreateNovel(..)
+                | DbAction::CreateChapter(..)
+                | DbAction::CreateScene(..)
+                | DbAction::MoveToTrash { .. }
+                | DbAction::RestoreFromTrash(_)
+                | DbAction::UpdateScene(_)
+                | DbAction::UpdateChapter(_)
+                | DbAction::UpdateNovel(_)
+                | DbAction::SaveCreature(..)
+                | DbAction::SaveLocation(_)
+                | DbAction::SaveGlossaryTerm(_)
+                | DbAction::SaveEvent(_)
+                | DbAction::SaveEra(_)
+                | DbAction::SaveCard(_)
+        )
+    }
+
+    /// Undo global (crate::undo_stack). Match exhaustivo a propósito: cada acción nueva tiene
+    /// que declararse. Reversible = quien la encola puede registrar su inversa
+    /// (`AppState::queue_undoable`); UpdateNovel/Chapter/Scene solo se registran en renames.
+    pub fn is_undoable(&self) -> bool {
+        match self {
+            DbAction::MoveCard(..)
+            | DbAction::ReorderChapter(..)
+            | DbAction::ReorderScene(..)
+            | DbAction::UpdateNovel(_)
+            | DbAction::UpdateChapter(_)
+            | DbAction::UpdateScene(_)
+            | DbAction::MoveToTrash { .. } => true,
+
+            DbAction::CreateUniverse { .. }
+            | DbAction::CloneUniverse { .. }
+            | DbAction::RepairForeignKeys
+            | DbAction::ExportAuditLog { .. }
+            | DbAction::ExportProject { .. }
+            | DbAction::ImportProject { .. }
+            | DbAction::InjectDemoData(_)
+            | DbAction::ResetDemoDataScoped(..)
+            | DbAction::SnapshotCreate { .. }
+            | DbAction::SnapshotDelete { .. }
+            | DbAction::SnapshotRestore { .. }
+            | DbAction::SnapshotRename { .. }
+            | DbAction::SnapshotCreateAuto { .. }
+            | DbAction::CreateBoard { .. }
+            | DbAction::CreateBoardFromTemplate { .. }
+            | DbAction::SaveBoardTemplate { .. }
+            | DbAction::SaveCreature(..)
+            | DbAction::ArchiveCreature(..)
+            | DbAction::AddCreatureRelation(_)
+            | DbAction::RemoveCreatureRelation(_)
+            | DbAction::SaveLocation(_)
+            | DbAction::SaveGlossaryTerm(_)
+            | DbAction::SaveEvent(_)
+            | DbAction::SaveEra(_)
+            | DbAction::SaveCard(_)
+            | DbAction::MoveCardToBoard { .. }
+            | DbAction::ArchiveCard(_)
+            | DbAction::RestoreCard { .. }
+            | DbAction::RebalanceColumn(_)
+            | DbAction::ReorderColumn { .. }
+            | DbAction::DeleteCard(_)
+            | DbAction::CreateNovel(..)
+            | DbAction::CreateChapter(..)
+            | DbAction::MergeNovels { .. }
+            | DbAction::ExportNovelJson { .. }
+            | DbAction::ExportNovelMarkdown { .. }
+            | DbAction::ExportBestiary { .. }
+            | DbAction::ExportTimeline { .. }
+            | DbAction::CreateScene(..)
+            | DbAction::MoveSceneToChapter { .. }
+            | DbAction::MoveSceneToChapterAt { .. }
+            | DbAction::SaveOutlineExpansion { .. }
+            | DbAction::RestoreFromTrash(_)
+            | DbAction::PermanentDelete(_)
+            | DbAction::EmptyTrash
+            | DbAction::CleanupOldTrash(_)
+            | DbAction::SetTrashRetention(_)
+            | DbAction::SetUiTheme(_)
+            | DbAction::SetCapability { .. }
+            | DbAction::TouchRecentItem { .. }
+            | DbAction::BulkReplaceText { .. } => false,
+        }
+    }
+
+    /// Saves idempotentes "último gana": si ya hay uno encolado para el mismo id, se reemplaza.
+    pub(crate) fn coalesce_key(&self) -> Option<(&'static str, &str)> {
+        match self {
+            DbAction::UpdateScene(s) => Some(("scene", s.id.as_str())),
+            DbAction::UpdateChapter(c) => Some(("chapter", c.id.as_str())),
+            DbAction::UpdateNovel(n) => Some(("novel", n.id.as_str())),
+            DbAction::SaveCreature(c, _) => Some(("creature", c.id.as_str())),
+            DbAction::SaveLocation(l) => Some(("location", l.id.as_str())),
+            DbAction::SaveGlossaryTerm(g) => Some(("glossary_term", g.id.as_str())),
+            DbAction::SaveEvent(e) => Some(("event", e.id.as_str())),
+            DbAction::SaveEra(e) => Some(("era", e.id.as_str())),
+            DbAction::SaveCard(c) => Some(("card", c.id.as_str())),
+            DbAction::SaveOutlineExpansion { universe_id, .. } => Some(("outline_expansion", universe_id.as_str())),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -117,6 +289,7 @@ pub enum CoreLoadKey {
     PmBoard { board_id: String },
     Creatures { universe_id: String },
     Locations { universe_id: String },
+    Glossary { universe_id: String },
     Timeline { universe_id: String },
     Snapshots { universe_id: String },
 }
@@ -172,11 +345,65 @@ pub struct Toast {
     pub kind: ToastKind,
     pub created_at: Instant,
     pub ttl_secs: u64,
+    // Botón opcional (label, mensaje). Vive lo que vive el toast.
+    pub action: Option<(String, crate::app::Message)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ToastKind { Info, Success, Error }
 
+// ✅ Sesión de edición externa (Forge): una sola a la vez
+#[derive(Debug, Clone)]
+pub struct ExternalEditSession {
+    pub scene_id: String,
+    pub path: std::path::PathBuf,
+    pub last_modified: std::time::SystemTime,
+    pub last_activity: Instant,
+    pub poll_in_flight: bool,
+}
+
+// ✅ Salto a una escena desde fuera de Forge (bestiario, búsqueda, etc.).
+// Se re-aplica en cada fetch de Forge hasta que la escena está en cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeJumpTarget {
+    pub novel_id: String,
+    pub chapter_id: Option<String>, // None => solo seleccionar la novela
+    pub scene_id: Option<String>,   // None => solo seleccionar el capítulo
+}
+
+/// "Restore & Open": a dónde navegar cuando el restore de la entrada termine OK
+#[derive(Debug, Clone)]
+pub struct RestoreOpenTarget {
+    pub target_type: String,
+    pub target_id: String,
+    pub parent_id: Option<String>,
+}
+
+/// Item del outline de The Forge que se arrastra (o sobre el que se suelta)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForgeDragItem {
+    Chapter(String),
+    Scene(String),
+}
+
+/// Stats del novel activo (palabras + lectura), cacheadas por versión del outline/caches
+#[derive(Debug, Clone, PartialEq)]
+pub struct NovelStats {
+    pub novel_id: String,
+    pub key: (u32, u64), // (forge_outline_version, forge_cache_generation)
+    pub words: i64,
+    pub partial: bool, // hay chapters/scenes del novel que todavía no se cargaron
+}
+
+/// Drag del outline (espejo de PmState::Dragging): se activa al pasar el umbral
+#[derive(Debug, Clone)]
+pub struct ForgeDrag {
+    pub item: ForgeDragItem,
+    pub drag_start: iced::Point,
+    pub current_cursor: iced::Point,
+    pub active: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     DeleteUniverse(String),
@@ -185,14 +412,27 @@ pub enum ConfirmAction {
     DeleteChapter(String),
     DeleteScene(String),
     DeleteLocation(String),
+    DeleteGlossaryTerm(String),
     DeleteCreature(String),
     DeleteEvent(String),
     DeleteEra(String),
+    MergeNovels { keep_id: String, merge_id: String },
+    PurgeOldTrash(i64), // días (mismo corte que el preview)
+}
+
+/// Badges de un nodo del árbol de locations (0 = no se muestra).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocationCounts {
+    pub children: usize,
+    pub creatures: usize,
+    pub events: usize,
 }
 
 #[derive(Debug)]
 pub struct AppState {
     pub route: Route,
+    pub nav_history: Vec<Route>, // rutas visitadas (más vieja primero)
+    pub nav_index: usize,        // posición actual dentro de nav_history
 
     pub active_project: Option<Project>,
     pub projects: Vec<Project>,
@@ -202,6 +442,8 @@ pub struct AppState {
     pub universes: Vec<Universe>,
     pub new_universe_name: String,
     pub new_universe_desc: String,
+    // Duplicate universe: copiar también novels/chapters/scenes
+    pub universe_clone_include_novels: bool,
 
     pub pending_confirm: Option<ConfirmAction>,
     pub dev_panel_open: bool,
@@ -217,12 +459,25 @@ pub struct AppState {
 
     pub snapshot_name: String,
     pub snapshots: Vec<UniverseSnapshot>,
+    // Rename inline (doble click en la fila): (snapshot_id, borrador)
+    pub snapshot_renaming: Option<(String, String)>,
+    pub last_snapshot_click: Option<(String, Instant)>,
 
     pub integrity_issues: Vec<String>,
     pub integrity_busy: bool,
 
+    // ✅ Find & Replace (mantenimiento por universo)
+    pub replace_find: String,
+    pub replace_with: String,
+    pub replace_scopes: HashSet<ReplaceScope>,
+    pub replace_preview: Vec<TextMatch>,
+    pub replace_preview_term: Option<String>, // término con el que se generó el preview
+    pub replace_preview_busy: bool,
+    pub replace_preview_requested: bool, // lo consume post_event (una sola vez)
+
     pub loaded_creatures_universe: Option<String>,
     pub loaded_locations_universe: Option<String>,
+    pub loaded_glossary_universe: Option<String>,
     pub loaded_timeline_universe: Option<String>,
     pub loaded_snapshots_universe: Option<String>,
     pub loaded_forge_universe: Option<String>,
@@ -233,8 +488,10 @@ pub struct AppState {
     // ✅ REFACTOR A.3: Cache para búsquedas O(1) por ID
     pub creatures_index: HashMap<String, usize>, // creature_id -> index in Vec
     pub locations: Vec<Location>,
+    pub glossary: Vec<GlossaryTerm>,
     // ✅ OPTIMIZED: Cache de estructura jerárquica para evitar O(n) en cada render
     pub locations_children_map: HashMap<Option<String>, Vec<String>>, // parent_id -> Vec<child_id>
+    pub location_counts: HashMap<String, LocationCounts>, // badges del árbol (ver rebuild_location_counts)
     pub timeline_events: Vec<TimelineEvent>,
     pub timeline_eras: Vec<TimelineEra>,
 
@@ -259,6 +516,27 @@ pub struct AppState {
     pub expanded_novels: std::collections::HashSet<String>,
     pub expanded_chapters: std::collections::HashSet<String>,
 
+    // ✅ Outline panel colapsado (layout only; NO toca expanded_*)
+    pub forge_outline_collapsed: bool,
+
+    // ✅ Focus mode: sin outline, editor centrado (la escena sigue cargada y autoguardando)
+    pub forge_focus_mode: bool,
+
+    // Notas de la escena activa: (scene_id, editor). Se rearma al cambiar de escena.
+    pub forge_notes_editor: Option<(String, iced::widget::text_editor::Content)>,
+    pub forge_notes_open: bool,
+
+    // Historial de la escena activa (panel colapsable). for = escena ya pedida (None = re-fetch)
+    pub forge_revisions_open: bool,
+    pub forge_revisions: Vec<crate::model::SceneRevision>,
+    pub forge_revisions_for: Option<String>,
+    pub forge_revision_selected: Option<String>,
+
+    // Manuscript: lectura continua del novel activo (solo lectura).
+    // requested = novel cuyas scenes faltantes ya se pidieron (una vez por activación)
+    pub forge_manuscript_mode: bool,
+    pub forge_manuscript_requested: Option<String>,
+
 
     pub last_novels_reload: std::time::Instant,
     pub last_chapters_reload: std::time::Instant,
@@ -282,6 +560,7 @@ pub struct AppState {
 
     pub core_creatures_loaded_for: std::collections::HashMap<String, std::time::Instant>, // universe_id -> last load
     pub core_locations_loaded_for: std::collections::HashMap<String, std::time::Instant>, // universe_id -> last load
+    pub core_glossary_loaded_for: std::collections::HashMap<String, std::time::Instant>,  // universe_id -> last load
     pub core_timeline_loaded_for: std::collections::HashMap<String, std::time::Instant>,  // universe_id -> last load
     pub core_snapshots_loaded_for: std::collections::HashMap<String, std::time::Instant>, // universe_id -> last load
     pub pm_board_loaded_for: std::collections::HashMap<String, std::time::Instant>,       // board_id -> last load
@@ -297,6 +576,48 @@ pub struct AppState {
 
     pub forge_last_edit: Option<Instant>,
     pub forge_debounce_task_id: Option<u64>,
+    // ✅ Cambios sin confirmar por DB (distinto del timer de debounce)
+    pub forge_dirty: bool,
+    // Anotaciones `[[note: ...]]` de la escena activa (se re-parsean en debounce)
+    pub forge_annotations: Vec<crate::forge_annotations::SceneAnnotation>,
+    // Preview Markdown (solo lectura) al lado del editor
+    pub forge_preview_mode: bool,
+    // Find / replace del editor (sobre forge_content)
+    pub forge_find_open: bool,
+    pub forge_find_query: String,
+    pub forge_replace_text: String,
+    pub forge_find_case_sensitive: bool,
+    pub forge_find_index: Option<usize>, // match actual (seleccionado en el editor)
+    pub forge_find_total: usize,
+    // Expand-all del outline: chapters que lleguen después también se expanden (hasta un toggle manual)
+    pub forge_expand_all_pending: bool,
+    // Draft recovery: drafts en disco sin persistir (escaneo una vez por proyecto abierto)
+    pub forge_recoverable_drafts: Vec<crate::forge_draft::RecoverableDraft>,
+    pub forge_drafts_scanned: bool,
+    // Expansión persistida del outline (por universo): pedida para X, lista al llegar;
+    // saved = último set guardado (solo se escribe cuando difiere)
+    pub forge_expansion_universe: Option<String>,
+    pub forge_expansion_ready: bool,
+    pub forge_expansion_saved: (HashSet<String>, HashSet<String>),
+    // Sesión de escritura: scene_id -> (palabras al primer cambio, palabras actuales).
+    // Escenas borradas quedan en el mapa: borrar no resta a lo escrito en la sesión.
+    pub forge_session_words: HashMap<String, (i64, i64)>,
+    // Undo / redo del body (por escena activa, acotado)
+    pub forge_history: crate::forge_history::SceneHistory,
+    pub forge_pending_jump: Option<ForgeJumpTarget>,
+    pub pending_restore_open: HashMap<String, RestoreOpenTarget>, // trash_entry_id -> destino
+
+    // Escenas huérfanas (chapter inexistente): rescate desde el outline
+    pub forge_orphan_scenes: Vec<Scene>,
+    pub forge_orphans_loaded: bool,
+
+    // Merge de novels: origen elegido, esperando destino
+    pub forge_merge_source: Option<String>,
+    // Drag & drop del outline: item arrastrado + destino bajo el cursor (on_enter de cada row)
+    pub forge_drag: Option<ForgeDrag>,
+    pub forge_drag_hover: Option<ForgeDragItem>,
+    pub forge_new_universe_name: Option<String>, // Some = quick-create inline abierto
+    pub forge_pending_universe: Option<String>,  // id creado desde la Forge: se selecciona al confirmar
 
     pub forge_renaming_novel_id: Option<String>,
     pub forge_renaming_chapter_id: Option<String>,
@@ -306,35 +627,78 @@ pub struct AppState {
     pub forge_renaming_novel_temp: Option<Novel>,
     pub forge_renaming_chapter_temp: Option<Chapter>,
     pub forge_renaming_scene_temp: Option<Scene>,
+    pub forge_rename_original_title: Option<String>, // título al empezar el rename (inversa del undo)
     pub last_forge_novel_click: Option<(String, Instant)>,
     pub last_forge_chapter_click: Option<(String, Instant)>,
     pub last_forge_scene_click: Option<(String, Instant)>,
 
+    // ✅ Edición externa (archivo temporal + polling de mtime)
+    pub forge_external_edit: Option<ExternalEditSession>,
+
     pub boards_list: Vec<Board>,
     pub new_board_name: String,
+    pub new_board_template_id: Option<String>, // None = columnas default
+    pub board_templates: Vec<crate::model::BoardTemplate>,
+    pub board_templates_loaded: bool,
     pub pm_state: PmState,
     pub pm_data: Option<KanbanBoardData>,
 
     pub hovered_column: Option<PmId>,
     pub hovered_card: Option<PmId>,
+    // Columnas colapsadas (solo vista; el drop sigue funcionando => va al final)
+    pub pm_collapsed_columns: HashSet<String>,
+    pub pm_label_filter: Option<String>, // solo vista: oculta cards sin esa etiqueta
+    pub pm_search: String,               // solo vista: título / descripción, case-insensitive
+    pub pm_show_archived: bool,
+    pub pm_archived_cards: Vec<Card>,
+    pub pm_archived_for: Option<String>, // board_id cargado en pm_archived_cards
+    // Drag de columna (header): id de la columna arrastrada; el destino es hovered_column
+    pub pm_column_drag: Option<PmId>,
     pub last_pm_click: Option<(PmId, Instant)>,
 
     pub pm_ids: PmIdPool,
 
     pub creature_editor: Option<CreatureEditor>,
+    pub creature_mentions: Vec<crate::model::SceneMention>,
+    pub creature_mentions_for: Option<String>, // creature_id del último fetch pedido
+    pub creature_relations: Vec<crate::model::CreatureRelation>,
+    pub creature_relations_for: Option<String>, // universe_id del último fetch pedido
+    pub forge_definition_lookup: Option<(String, String)>, // (universe_id, palabra): lo consume post_event
+    // Búsqueda en el novel activo (títulos + bodies), con debounce
+    pub forge_search_query: String,
+    pub forge_search_results: Vec<crate::model::SceneHit>,
+    pub forge_search_novel_id: Option<String>, // novel de los resultados (se ocultan si cambia el activo)
+    pub forge_search_debounce_id: u64,
+    pub forge_search_requested: Option<(String, String)>, // (novel_id, query): lo consume post_event
+    pub pending_definition_open: Option<crate::model::DefinitionHit>, // se abre cuando la lista cargó
     pub last_bestiary_click: Option<(usize, Instant)>,
+    pub bestiary_danger_filter: HashSet<crate::model::DangerLevel>, // vacío = todos
+    pub bestiary_search: String,                // nombre, case-insensitive (vacío = todos)
+    pub bestiary_kind_filter: Option<String>,   // kind exacto (None = todos)
+    pub bestiary_sort: crate::model::CreatureSort, // solo vista, dura la sesión
+    pub bestiary_selected: HashSet<String>, // multi-select (creature ids) para archivar/restaurar en lote
+    pub bestiary_bulk_archive: Option<(usize, bool)>, // lote en curso: (cantidad, archived) => un solo toast al final
+    pub creature_duplicate_ack: Option<String>, // nombre (lowercase) ya advertido: el 2º save pasa
+    pub bestiary_export_format: crate::db::bestiary_export::BestiaryExportFormat,
+    pub bestiary_export_include_archived: bool, // por defecto solo activas
 
     pub location_editor: Option<LocationEditor>,
     pub last_location_click: Option<(String, Instant)>,
     pub expanded_locations: HashSet<String>,
     pub selected_location: Option<String>,
 
+    pub glossary_editor: Option<GlossaryEditor>,
+    pub glossary_search: String,
+    pub glossary_sort_desc: bool, // false = A→Z
+
     pub event_editor: Option<EventEditor>,
     pub era_editor: Option<EraEditor>,
     pub last_timeline_click: Option<(String, Instant)>,
 
     pub db_queue: VecDeque<DbAction>,
     pub db_inflight: Option<DbAction>,
+    // Acciones descartadas por backpressure desde que la cola se vació por última vez
+    pub db_queue_rejected: u64,
 
     pub toasts: Vec<Toast>,
     pub toast_counter: u64,
@@ -342,16 +706,99 @@ pub struct AppState {
     pub trash_loaded: bool,
     //search in trash
     pub trash_search_query: String,
+    pub trash_type_filter: Option<String>,
+    pub trash_sort: crate::model::TrashSort,
     pub trash_selected: HashSet<String>,
+    pub trash_restore_batch: Option<(usize, usize)>, // "Restore all": (restaurados, salteados) => un solo toast al final
+    // Entries que el auto-cleanup va a purgar pronto (se recarga junto con trash_entries)
+    pub trash_cleanup_preview: Vec<TrashEntry>,
+    // Retención del proyecto (db_meta); el fetch de trash espera a tenerla
+    pub trash_retention: crate::db::trash::TrashRetention,
+    pub trash_retention_loaded: bool,
+    // Tema de la UI (db_meta.ui_theme); se lee una vez por proyecto
+    pub ui_theme: crate::ui::ThemeMode,
+    pub ui_theme_loaded: bool,
+    // Copia sync del CapabilitiesCache (la vista no puede hacer await); None = todavía no leída
+    pub capabilities: Option<crate::guards::Capabilities>,
+    pub capabilities_loaded: bool,
+    // Overview → "Recent" (se lee al entrar a Overview); last_opened evita re-registrar lo mismo
+    pub recent_items: Vec<crate::model::RecentItem>,
+    pub recent_items_loaded: bool,
+    pub recent_last_opened: Option<(String, String)>, // (kind, target_id)
+
+    // Paleta de comandos (Ctrl+P): None = cerrada
+    pub command_palette: Option<crate::command_palette::PaletteState>,
+
+    // Undo / redo global de acciones de DB (por proyecto, se limpia al abrir otro)
+    pub undo_stack: crate::undo_stack::UndoStack,
 
     pub forge_outline_version: u32,
-
+    // Sube con cada fetch de chapters/scenes (invalida caches derivados como forge_novel_stats)
+    pub forge_cache_generation: u64,
+    pub forge_novel_stats: Option<NovelStats>,
+
+    // ✅ Preferencias globales (settings.json)
+    pub settings: crate::app_settings::AppSettings,
+    // Inputs de texto de Settings (se validan al confirmar)
+    pub settings_backup_interval_input: String,
+    pub settings_break_interval_input: String,
+    pub settings_snapshot_keep_input: String,
+
+    // Recordatorio de pausas: racha de escritura continua (independiente del debounce,
+    // que limpia forge_last_edit al guardar/cambiar de escena)
+    pub writing_session_start: Option<Instant>,
+    pub writing_last_activity: Option<Instant>,
+    pub settings_backup_keep_input: String,
+    pub settings_import_path: String,
+    pub settings_db_backup_path: String,
+    pub settings_archive_path: String,
+    // Visor de audit log (Settings): inputs de filtro + última consulta
+    pub audit_filter_from: String,
+    pub audit_filter_to: String,
+    pub audit_filter_action: String,
+    pub audit_filter_entity: String,
+    pub audit_rows: Vec<crate::model::AuditLogEntry>,
+    pub audit_loaded: bool,
+    pub settings_io_busy: bool,
+    pub timeline_export_busy: bool,
+    pub timeline_zoom: f32,         // px por año (banda cronológica)
+    pub timeline_layout: crate::timeline_layout::TimelineLayout, // se recalcula en fetch / cambio de zoom
+    pub timeline_ctrl_held: bool,   // Ctrl+scroll sobre la banda = zoom
+    pub timeline_era_filter: Option<String>,        // era_id; None = todas
+    pub timeline_era_overlaps: Vec<String>,         // avisos (no bloquean), se calculan al fetch
+    pub timeline_export_format: crate::db::timeline_data_export::TimelineExportFormat,
+    pub timeline_scene_refs: Vec<crate::model::SceneMention>, // escenas del universo (link evento -> escena)
+    pub timeline_scene_refs_for: Option<String>,    // universe_id del último fetch pedido
+    pub timeline_importance_filter: Option<String>, // "Major" / "Normal" / "Minor"; None = todas
+    pub settings_sidebar_drag: Option<usize>,
+
+    // Meta de palabras del proyecto (Overview)
+    pub project_word_total: Option<i64>,
+    pub project_word_total_stale: bool, // true => re-sumar en el próximo post_event
+    pub project_word_goal_input: String, // índice de la fila que se arrastra en el editor del sidebar
+
+    // ✅ Auto-backup (VACUUM INTO) - solo si hubo escrituras desde el último
+    pub auto_backup_last_at: Instant,
+    pub auto_backup_pending_changes: bool,
+    pub auto_backup_in_progress: bool,
+
+    // Mantenimiento (dev panel)
+    pub db_stats: Option<crate::model::DbStats>,
+    pub db_maintenance_busy: bool,
 }
 
 impl Default for AppState {
     fn default() -> Self {
+        let settings = crate::app_settings::AppSettings::load();
+        let settings_backup_interval_input = settings.auto_backup_interval_min.to_string();
+        let settings_backup_keep_input = settings.auto_backup_keep.to_string();
+        let settings_break_interval_input = settings.break_reminder_interval_min.to_string();
+        let settings_snapshot_keep_input = settings.auto_snapshot_keep.to_string();
+
         Self {
             route: Route::Overview,
+            nav_history: vec![],
+            nav_index: 0,
 
             active_project: None,
             projects: vec![],
@@ -361,6 +808,7 @@ impl Default for AppState {
             universes: vec![],
             new_universe_name: String::new(),
             new_universe_desc: String::new(),
+            universe_clone_include_novels: true,
 
             pending_confirm: None,
             dev_panel_open: true,
@@ -376,12 +824,23 @@ impl Default for AppState {
 
             snapshot_name: String::new(),
             snapshots: vec![],
+            snapshot_renaming: None,
+            last_snapshot_click: None,
 
             integrity_issues: vec![],
             integrity_busy: false,
 
+            replace_find: String::new(),
+            replace_with: String::new(),
+            replace_scopes: ReplaceScope::ALL.iter().copied().collect(),
+            replace_preview: vec![],
+            replace_preview_term: None,
+            replace_preview_busy: false,
+            replace_preview_requested: false,
+
             loaded_creatures_universe: None,
             loaded_locations_universe: None,
+            loaded_glossary_universe: None,
             loaded_timeline_universe: None,
             loaded_snapshots_universe: None,
             loaded_forge_universe: None,
@@ -389,11 +848,15 @@ impl Default for AppState {
             data_dirty: false,
 
             forge_outline_version: 0,
+            forge_cache_generation: 0,
+            forge_novel_stats: None,
 
             creatures: vec![],
             creatures_index: HashMap::new(),
             locations: vec![],
+            glossary: vec![],
             locations_children_map: HashMap::new(),
+            location_counts: HashMap::new(),
             timeline_events: vec![],
             timeline_eras: vec![],
 
@@ -425,6 +888,7 @@ impl Default for AppState {
 
             core_creatures_loaded_for: std::collections::HashMap::new(),
             core_locations_loaded_for: std::collections::HashMap::new(),
+            core_glossary_loaded_for: std::collections::HashMap::new(),
             core_timeline_loaded_for: std::collections::HashMap::new(),
             core_snapshots_loaded_for: std::collections::HashMap::new(),
             pm_board_loaded_for: std::collections::HashMap::new(),
@@ -439,6 +903,32 @@ impl Default for AppState {
 
             forge_last_edit: None,
             forge_debounce_task_id: None,
+            forge_dirty: false,
+            forge_annotations: vec![],
+            forge_preview_mode: false,
+            forge_find_open: false,
+            forge_find_query: String::new(),
+            forge_replace_text: String::new(),
+            forge_find_case_sensitive: false,
+            forge_find_index: None,
+            forge_find_total: 0,
+            forge_expand_all_pending: false,
+            forge_recoverable_drafts: Vec::new(),
+            forge_drafts_scanned: false,
+            forge_expansion_universe: None,
+            forge_expansion_ready: false,
+            forge_expansion_saved: (HashSet::new(), HashSet::new()),
+            forge_session_words: HashMap::new(),
+            forge_history: crate::forge_history::SceneHistory::default(),
+            forge_pending_jump: None,
+            pending_restore_open: HashMap::new(),
+            forge_orphan_scenes: vec![],
+            forge_orphans_loaded: false,
+            forge_merge_source: None,
+            forge_drag: None,
+            forge_drag_hover: None,
+            forge_new_universe_name: None,
+            forge_pending_universe: None,
 
             forge_renaming_novel_id: None,
             forge_renaming_chapter_id: None,
@@ -448,12 +938,18 @@ impl Default for AppState {
             forge_renaming_novel_temp: None,
             forge_renaming_chapter_temp: None,
             forge_renaming_scene_temp: None,
+            forge_rename_original_title: None,
             last_forge_novel_click: None,
             last_forge_chapter_click: None,
             last_forge_scene_click: None,
 
+            forge_external_edit: None,
+
             boards_list: vec![],
             new_board_name: String::new(),
+            new_board_template_id: None,
+            board_templates: vec![],
+            board_templates_loaded: false,
             pm_state: PmState::Idle,
             pm_data: None,
 
@@ -461,15 +957,45 @@ impl Default for AppState {
 
             hovered_column: None,
             hovered_card: None,
+            pm_collapsed_columns: HashSet::new(),
+            pm_label_filter: None,
+            pm_search: String::new(),
+            pm_show_archived: false,
+            pm_archived_cards: vec![],
+            pm_archived_for: None,
+            pm_column_drag: None,
             last_pm_click: None,
 
             creature_editor: None,
+            creature_mentions: vec![],
+            creature_mentions_for: None,
+            creature_relations: vec![],
+            creature_relations_for: None,
+            forge_definition_lookup: None,
+            forge_search_query: String::new(),
+            forge_search_results: Vec::new(),
+            forge_search_novel_id: None,
+            forge_search_debounce_id: 0,
+            forge_search_requested: None,
+            pending_definition_open: None,
             last_bestiary_click: None,
+            bestiary_danger_filter: HashSet::new(),
+            bestiary_search: String::new(),
+            bestiary_kind_filter: None,
+            bestiary_sort: crate::model::CreatureSort::Default,
+            bestiary_selected: HashSet::new(),
+            bestiary_bulk_archive: None,
+            creature_duplicate_ack: None,
+            bestiary_export_format: crate::db::bestiary_export::BestiaryExportFormat::Csv,
+            bestiary_export_include_archived: false,
 
             location_editor: None,
             last_location_click: None,
             expanded_locations: HashSet::new(),
             selected_location: None,
+            glossary_editor: None,
+            glossary_search: String::new(),
+            glossary_sort_desc: false,
 
             event_editor: None,
             era_editor: None,
@@ -477,29 +1003,188 @@ impl Default for AppState {
 
             db_queue: VecDeque::new(),
             db_inflight: None,
+            db_queue_rejected: 0,
 
             toasts: vec![],
             toast_counter: 0,
 
             expanded_novels: std::collections::HashSet::new(),
             expanded_chapters: std::collections::HashSet::new(),
+            forge_outline_collapsed: false,
+            forge_focus_mode: false,
+            forge_notes_editor: None,
+            forge_notes_open: false,
+            forge_revisions_open: false,
+            forge_revisions: Vec::new(),
+            forge_revisions_for: None,
+            forge_revision_selected: None,
+            forge_manuscript_mode: false,
+            forge_manuscript_requested: None,
 
             trash_entries: Vec::new(),
             trash_loaded: false,
 
             trash_search_query: String::new(),
+
+            trash_type_filter: None,
+
+            trash_sort: crate::model::TrashSort::default(),
             trash_selected: HashSet::new(),
+            trash_restore_batch: None,
+            trash_cleanup_preview: Vec::new(),
+            trash_retention: crate::db::trash::TrashRetention(crate::db::trash::TRASH_RETENTION_DAYS),
+            trash_retention_loaded: false,
+            ui_theme: crate::ui::ThemeMode::default(),
+            ui_theme_loaded: false,
+            capabilities: None,
+            capabilities_loaded: false,
+            recent_items: Vec::new(),
+            recent_items_loaded: false,
+            recent_last_opened: None,
+            command_palette: None,
+            undo_stack: crate::undo_stack::UndoStack::default(),
+
+            settings,
+            settings_backup_interval_input,
+            settings_backup_keep_input,
+            settings_break_interval_input,
+            settings_snapshot_keep_input,
+            writing_session_start: None,
+            writing_last_activity: None,
+            settings_import_path: String::new(),
+            settings_db_backup_path: String::new(),
+            settings_archive_path: String::new(),
+            audit_filter_from: String::new(),
+            audit_filter_to: String::new(),
+            audit_filter_action: String::new(),
+            audit_filter_entity: String::new(),
+            audit_rows: Vec::new(),
+            audit_loaded: false,
+            settings_io_busy: false,
+            timeline_export_busy: false,
+            timeline_zoom: crate::timeline_layout::DEFAULT_ZOOM,
+            timeline_layout: crate::timeline_layout::TimelineLayout::default(),
+            timeline_ctrl_held: false,
+            timeline_era_filter: None,
+            timeline_era_overlaps: vec![],
+            timeline_export_format: crate::db::timeline_data_export::TimelineExportFormat::Csv,
+            timeline_scene_refs: vec![],
+            timeline_scene_refs_for: None,
+            timeline_importance_filter: None,
+            settings_sidebar_drag: None,
+            project_word_total: None,
+            project_word_total_stale: true,
+            project_word_goal_input: String::new(),
+
+            auto_backup_last_at: Instant::now(),
+            auto_backup_pending_changes: false,
+            auto_backup_in_progress: false,
+
+            db_stats: None,
+            db_maintenance_busy: false,
         }
     }
 }
 
 impl AppState {
+    /// Opt-in (Settings): snapshot automático ANTES de una acción destructiva.
+    /// Encolar primero => la cola (FIFO) garantiza que corre antes que la acción.
+    pub fn queue_auto_snapshot(&mut self, universe_id: &str, reason: &str) {
+        if !self.settings.auto_snapshot_enabled {
+            return;
+        }
+        let name = format!("auto-before-{}-{}", reason, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        self.queue(DbAction::SnapshotCreateAuto {
+            universe_id: universe_id.to_string(),
+            name,
+            keep: self.settings.auto_snapshot_keep,
+        });
+    }
+
     pub fn queue(&mut self, action: DbAction) {
+        // 1) Coalesce: mismo save ya encolado => reemplazar en su lugar (no crece la cola).
+        // Solo si detrás de él hay únicamente otros saves: una acción estructural en el medio
+        // (trash, move, reorder...) tiene que seguir viendo la escritura en el orden original.
+        if let Some(key) = action.coalesce_key()
+            && let Some(idx) = self.db_queue.iter().rposition(|a| a.coalesce_key() == Some(key))
+            && self.db_queue.iter().skip(idx + 1).all(|a| a.coalesce_key().is_some())
+        {
+            let replaced = std::mem::replace(&mut self.db_queue[idx], action);
+            self.undo_stack.retarget(&replaced, &self.db_queue[idx]);
+            return;
+        }
+
+        // 2) Backpressure: cola desbordada (ej: writes fallando en loop) => solo críticas
+        if self.db_queue.len() >= DB_QUEUE_HARD_LIMIT && !action.is_critical() {
+            self.db_queue_rejected += 1;
+            crate::logger::warn(&format!(
+                "⚠️ db_queue full ({}): rejected {:?}",
+                self.db_queue.len(),
+                std::mem::discriminant(&action)
+            ));
+
+            // Un solo toast por racha (el aviso persistente vive en el header)
+            if self.db_queue_rejected == 1 {
+                self.show_toast(
+                    "Too many pending changes: new actions are paused until saving catches up",
+                    ToastKind::Error,
+                );
+            }
+            return;
+        }
+
+        // Borrar siempre se puede deshacer: la inversa sale de la acción misma
+        if let DbAction::MoveToTrash { trash_id, target_type, display_name, .. } = &action {
+            self.undo_stack.track(
+                action.clone(),
+                crate::undo_stack::UndoEntry {
+                    label: format!("Delete {} '{}'", target_type.replace('_', " "), display_name),
+                    forward: action.clone(),
+                    inverse: DbAction::RestoreFromTrash(trash_id.clone()),
+                    redoable: false,
+                },
+                crate::undo_stack::UndoOrigin::Fresh,
+            );
+        }
+
         self.db_queue.push_back(action);
     }
 
+    /// Encola una acción reversible con su inversa (armada con el estado previo a aplicarla).
+    /// Entra al stack de undo recién cuando la DB la confirma.
+    pub fn queue_undoable(&mut self, action: DbAction, inverse: DbAction, label: impl Into<String>) {
+        debug_assert!(action.is_undoable(), "queue_undoable with a non-undoable action");
+        self.queue(action.clone());
+
+        // Rechazada por backpressure => no hay nada que deshacer
+        if self.db_queue.contains(&action) {
+            self.undo_stack.track(
+                action.clone(),
+                crate::undo_stack::UndoEntry { label: label.into(), forward: action, inverse, redoable: true },
+                crate::undo_stack::UndoOrigin::Fresh,
+            );
+        }
+    }
+
+    /// Para la vista: lee la copia sync de capabilities. Mientras no se leyó se muestra todo
+    /// (el gate real, fail-closed, sigue en la DB).
+    pub fn capability_enabled(&self, capability: &str) -> bool {
+        self.capabilities.as_ref().is_none_or(|c| c.is_enabled(capability))
+    }
+
     pub fn show_toast(&mut self, msg: impl Into<String>, kind: ToastKind) {
-        self.show_toast_internal(msg.into(), kind);
+        self.show_toast_internal(msg.into(), kind, None, 4);
+    }
+
+    /// Toast con botón (ej: "Undo"). TTL más largo para dar tiempo a reaccionar.
+    pub fn show_toast_with_action(
+        &mut self,
+        msg: impl Into<String>,
+        kind: ToastKind,
+        label: impl Into<String>,
+        action: crate::app::Message,
+    ) {
+        self.show_toast_internal(msg.into(), kind, Some((label.into(), action)), 8);
     }
 
     // Función interna compartida (privada)
@@ -507,6 +1192,8 @@ impl AppState {
         &mut self,
         message: String,
         kind: ToastKind,
+        action: Option<(String, crate::app::Message)>,
+        ttl_secs: u64,
     ) {
         const MAX_TOASTS: usize = 10;
 
@@ -530,7 +1217,8 @@ impl AppState {
             message,
             kind,
             created_at: now,
-            ttl_secs: 4,
+            ttl_secs,
+            action,
         });
 
         // 3) Cap duro: si se pasa, drena lo más viejo
@@ -669,6 +1357,103 @@ impl AppState {
                 .or_insert_with(Vec::new)
                 .push(loc.id.clone());
         }
+
+        self.rebuild_location_counts();
+    }
+
+    /// Hijos directos + criaturas (home_location_id) + eventos (location_id) por location.
+    /// O(n); se llama al cambiar locations, criaturas o timeline (no por frame).
+    pub fn rebuild_location_counts(&mut self) {
+        self.location_counts.clear();
+
+        for (parent, children) in &self.locations_children_map {
+            if let Some(parent_id) = parent {
+                self.location_counts.entry(parent_id.clone()).or_default().children = children.len();
+            }
+        }
+        for c in &self.creatures {
+            if let Some(lid) = &c.home_location_id {
+                self.location_counts.entry(lid.clone()).or_default().creatures += 1;
+            }
+        }
+        for e in &self.timeline_events {
+            if let Some(lid) = &e.location_id {
+                self.location_counts.entry(lid.clone()).or_default().events += 1;
+            }
+        }
+    }
+
+    /// Expande los ancestros de una location para que quede visible en el árbol.
+    pub fn expand_location_ancestors(&mut self, location_id: &str) {
+        let mut current_search = Some(location_id.to_string());
+        let mut safeguard = 0;
+
+        while let Some(curr_id) = current_search {
+            if safeguard > 50 {
+                break;
+            }
+            safeguard += 1;
+
+            current_search = self
+                .locations
+                .iter()
+                .find(|l| l.id == curr_id)
+                .and_then(|l| l.parent_id.clone());
+
+            if let Some(parent_id) = &current_search {
+                self.expanded_locations.insert(parent_id.clone());
+            }
+        }
+    }
+
+    /// ¿Mover `location_id` bajo `new_parent_id` la haría ancestro de sí misma?
+    /// Sube por parent_id desde el nuevo padre (como GoToLocation); ids desconocidos cortan la subida.
+    pub fn location_would_cycle(&self, location_id: &str, new_parent_id: Option<&str>) -> bool {
+        let mut current = new_parent_id;
+        let mut safeguard = 0;
+
+        while let Some(curr_id) = current {
+            if curr_id == location_id {
+                return true;
+            }
+            // Datos ya corruptos (ciclo previo): no colgar la UI
+            if safeguard > self.locations.len() {
+                return true;
+            }
+            safeguard += 1;
+
+            current = self
+                .locations
+                .iter()
+                .find(|l| l.id == curr_id)
+                .and_then(|l| l.parent_id.as_deref());
+        }
+
+        false
+    }
+
+    /// Posiciones de la banda cronológica (eventos / eras ya ordenados por año; respeta filtros).
+    pub fn rebuild_timeline_layout(&mut self) {
+        self.timeline_layout = crate::timeline_layout::TimelineLayout::compute(
+            &self.timeline_events,
+            &self.timeline_eras,
+            self.timeline_zoom,
+            |e| self.timeline_event_passes(e),
+            |era| self.timeline_era_filter.as_ref().is_none_or(|id| *id == era.id),
+        );
+    }
+
+    /// Filtros de vista del timeline (era seleccionada + importancia). No toca el orden de la fuente.
+    pub fn timeline_event_passes(&self, evt: &TimelineEvent) -> bool {
+        let era_ok = match self.timeline_era_filter.as_ref() {
+            None => true,
+            Some(era_id) => self
+                .timeline_eras
+                .iter()
+                .find(|e| e.id == *era_id)
+                .is_some_and(|era| evt.year >= era.start_year && era.end_year.is_none_or(|end| evt.year <= end)),
+        };
+        era_ok && self.timeline_importance_filter.as_ref().is_none_or(|imp| evt.importance == *imp)
     }
 
     /// Get children IDs for a parent - O(1)
@@ -697,4 +1482,21 @@ impl AppState {
             .and_then(|&idx| self.creatures.get(idx))
     }
 
+    /// Palabras ganadas en la sesión (neto entre escenas, nunca negativo)
+    pub fn forge_session_gain(&self) -> i64 {
+        self.forge_session_words
+            .values()
+            .map(|(baseline, current)| current - baseline)
+            .sum::<i64>()
+            .max(0)
+    }
+
+    /// Registra un cambio de word_count de una escena (la baseline es el primer valor visto)
+    pub fn record_session_words(&mut self, scene_id: &str, old_count: i64, new_count: i64) {
+        self.forge_session_words
+            .entry(scene_id.to_string())
+            .or_insert((old_count, old_count))
+            .1 = new_count;
+    }
+
 }
\ No newline at end of file
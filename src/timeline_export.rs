@@ -0,0 +1,68 @@
+// src/timeline_export.rs
+// Export del timeline como imagen: captura de la ventana (iced::window::screenshot)
+// codificada a PNG a mano (flate2 ya es dependencia; evitamos sumar un crate de imágenes).
+// La captura incluye eras y eventos tal cual se ven (scroll/filtros actuales).
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+
+/// data_dir/exports/<stem>/timeline-<timestamp>.png (mismo árbol que settings export)
+pub fn image_export_path(db_path: &Path) -> PathBuf {
+    crate::db::project_settings::exports_dir(db_path)
+        .join(format!("timeline-{}.png", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    // CRC cubre tipo + datos (no el largo)
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// RGBA8 (sin padding por fila) -> PNG truecolor+alpha, filtro None por fila.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, String> {
+    let row_len = width as usize * 4;
+    if width == 0 || height == 0 || rgba.len() != row_len * height as usize {
+        return Err(format!("Invalid image buffer ({}x{}, {} bytes)", width, height, rgba.len()));
+    }
+
+    // Cada scanline lleva un byte de filtro adelante (0 = None)
+    let mut raw = Vec::with_capacity((row_len + 1) * height as usize);
+    for row in rgba.chunks_exact(row_len) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut z = ZlibEncoder::new(Vec::new(), Compression::default());
+    z.write_all(&raw).map_err(|e| e.to_string())?;
+    let idat = z.finish().map_err(|e| e.to_string())?;
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]); // 8 bits, RGBA, deflate, filtro estándar, sin interlace
+
+    let mut out = Vec::with_capacity(idat.len() + 64);
+    out.extend_from_slice(b"\x89PNG\r\n\x1a\n");
+    chunk(&mut out, b"IHDR", &ihdr);
+    chunk(&mut out, b"IDAT", &idat);
+    chunk(&mut out, b"IEND", &[]);
+    Ok(out)
+}
+
+/// Bloqueante (compresión + I/O): llamar desde spawn_blocking.
+pub fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), String> {
+    let bytes = encode_png(width, height, rgba)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
@@ -0,0 +1,132 @@
+// src/timeline_layout.rs
+// Layout horizontal del timeline (banda cronológica con zoom).
+// Posiciones en px calculadas UNA vez por fetch / cambio de zoom (no por frame).
+// Eventos cercanos en el año se escalonan en carriles para no pisarse.
+
+use crate::model::{TimelineEra, TimelineEvent};
+
+/// Zoom = píxeles por año.
+pub const DEFAULT_ZOOM: f32 = 8.0;
+pub const MIN_ZOOM: f32 = 0.01;
+pub const MAX_ZOOM: f32 = 200.0;
+/// Factor por click de +/- (y por notch de Ctrl+scroll).
+pub const ZOOM_STEP: f32 = 1.5;
+
+/// Ancho de la etiqueta de un evento (define cuándo dos eventos "chocan").
+pub const EVENT_LABEL_W: f32 = 140.0;
+const LANE_GAP: f32 = 8.0;
+// Margen a los costados para que el primer / último evento no quede pegado al borde
+const EDGE_PAD: f32 = 24.0;
+// Separación mínima deseada entre marcas de la regla
+const MIN_TICK_SPACING: f32 = 80.0;
+
+pub fn clamp_zoom(zoom: f32) -> f32 {
+    if zoom.is_finite() { zoom.clamp(MIN_ZOOM, MAX_ZOOM) } else { DEFAULT_ZOOM }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EraSlot {
+    pub index: usize, // en state.timeline_eras
+    pub x: f32,
+    pub width: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EventSlot {
+    pub index: usize, // en state.timeline_events
+    pub x: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimelineLayout {
+    pub width: f32,
+    pub ticks: Vec<(f32, i64)>, // (x, año) de la regla
+    pub era_lanes: Vec<Vec<EraSlot>>,
+    pub event_lanes: Vec<Vec<EventSlot>>,
+}
+
+impl TimelineLayout {
+    /// `events` / `eras` ya vienen ordenados por año (TimelineFetched).
+    /// Los filtros descartan sin reordenar: los índices siguen apuntando a la fuente.
+    pub fn compute(
+        events: &[TimelineEvent],
+        eras: &[TimelineEra],
+        zoom: f32,
+        keep_event: impl Fn(&TimelineEvent) -> bool,
+        keep_era: impl Fn(&TimelineEra) -> bool,
+    ) -> Self {
+        let zoom = clamp_zoom(zoom);
+
+        let events: Vec<(usize, &TimelineEvent)> = events.iter().enumerate().filter(|(_, e)| keep_event(e)).collect();
+        let eras: Vec<(usize, &TimelineEra)> = eras.iter().enumerate().filter(|(_, e)| keep_era(e)).collect();
+
+        let years = events
+            .iter()
+            .map(|(_, e)| e.year)
+            .chain(eras.iter().map(|(_, e)| e.start_year))
+            .chain(eras.iter().filter_map(|(_, e)| e.end_year));
+        let (Some(min_year), Some(max_year)) = (years.clone().min(), years.max()) else {
+            return Self::default();
+        };
+
+        let x_of = |year: i64| EDGE_PAD + (year - min_year) as f32 * zoom;
+        let width = x_of(max_year) + EVENT_LABEL_W + EDGE_PAD;
+
+        // Eras: se pueden solapar => carriles también (era sin fin = hasta el último año)
+        let mut era_lanes: Vec<Vec<EraSlot>> = Vec::new();
+        let mut era_ends: Vec<f32> = Vec::new();
+        for &(index, era) in &eras {
+            let x = x_of(era.start_year);
+            let end = x_of(era.end_year.unwrap_or(max_year).max(era.start_year));
+            let slot = EraSlot { index, x, width: (end - x).max(4.0) };
+            place(&mut era_lanes, &mut era_ends, slot, x, x + slot.width);
+        }
+
+        // Eventos: carril libre más bajo donde la etiqueta no pise a la anterior
+        let mut event_lanes: Vec<Vec<EventSlot>> = Vec::new();
+        let mut event_ends: Vec<f32> = Vec::new();
+        for &(index, evt) in &events {
+            let x = x_of(evt.year);
+            place(&mut event_lanes, &mut event_ends, EventSlot { index, x }, x, x + EVENT_LABEL_W);
+        }
+
+        let step = tick_step(zoom);
+        let first = min_year.div_euclid(step) * step;
+        let ticks = (0..)
+            .map(|i| first + i * step)
+            .take_while(|y| *y <= max_year)
+            .filter(|y| *y >= min_year)
+            .map(|y| (x_of(y), y))
+            .collect();
+
+        Self { width, ticks, era_lanes, event_lanes }
+    }
+}
+
+fn place<T>(lanes: &mut Vec<Vec<T>>, ends: &mut Vec<f32>, slot: T, start: f32, end: f32) {
+    match ends.iter().position(|e| *e + LANE_GAP <= start) {
+        Some(lane) => {
+            lanes[lane].push(slot);
+            ends[lane] = end;
+        }
+        None => {
+            lanes.push(vec![slot]);
+            ends.push(end);
+        }
+    }
+}
+
+/// Paso "redondo" (1, 2, 5 × 10^k años) con marcas separadas al menos MIN_TICK_SPACING px.
+fn tick_step(zoom: f32) -> i64 {
+    let min_years = (MIN_TICK_SPACING / zoom).max(1.0) as i64;
+    let mut magnitude: i64 = 1;
+    loop {
+        for m in [1, 2, 5] {
+            let step = m * magnitude;
+            if step >= min_years {
+                return step;
+            }
+        }
+        magnitude = magnitude.saturating_mul(10);
+    }
+}
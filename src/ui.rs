@@ -4,6 +4,7 @@ use iced::{
 };
 use iced::widget::{button, container, text, Column, Row, Space};
 use crate::app::{AppState, Message, Route, APP_NAME};
+use crate::app_settings::SidebarSection;
 use crate::state::{Toast, ToastKind};
 
 // --- [TOKENS Y ESTILOS VIEJOS SE MANTIENEN IGUALES] ---
@@ -14,8 +15,52 @@ pub struct Tokens {
     pub background: Color, pub foreground: Color, pub muted_fg: Color, pub accent: Color,
     pub shell_a: Color, pub shell_b: Color, pub card: Color, pub popover: Color, pub sidebar_bg: Color,
     pub border: Color, pub input_border: Color, pub hover_bg: Color, pub active_bg: Color,
+    pub ink: Color, // base de los overlays sutiles (blanco en dark, negro en light)
     pub radius_xl: f32,
 }
+
+/// Tema elegido (db_meta.ui_theme). El shell arma los Tokens con `Tokens::for_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 2] = [ThemeMode::Dark, ThemeMode::Light];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "dark",
+            ThemeMode::Light => "light",
+        }
+    }
+
+    /// Valores desconocidos caen a Dark (el default histórico)
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "light" => ThemeMode::Light,
+            _ => ThemeMode::Dark,
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            ThemeMode::Dark => ThemeMode::Light,
+            ThemeMode::Light => ThemeMode::Dark,
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ThemeMode::Dark => "Dark",
+            ThemeMode::Light => "Light",
+        })
+    }
+}
 impl Tokens {
     pub fn nub_dark() -> Self {
         let white = Color::from_rgba8(0xFF, 0xFF, 0xFF, 1.0);
@@ -31,9 +76,35 @@ impl Tokens {
             sidebar_bg: Color::from_rgba8(0x09, 0x09, 0x0B, 1.0),
             border: alpha(white, 0.08), input_border: alpha(white, 0.12),
             hover_bg: alpha(white, 0.04), active_bg: alpha(white, 0.08),
+            ink: white,
+            radius_xl: 16.0,
+        }
+    }
+    // Mismo accent un punto más oscuro (contraste AA sobre blanco); bordes/hover en negro translúcido
+    pub fn nub_light() -> Self {
+        let black = Color::from_rgba8(0x00, 0x00, 0x00, 1.0);
+        Self {
+            background: Color::from_rgba8(0xFA, 0xFA, 0xFA, 1.0),
+            foreground: Color::from_rgba8(0x18, 0x18, 0x1B, 1.0),
+            muted_fg: Color::from_rgba8(0x52, 0x52, 0x5B, 1.0),
+            accent: Color::from_rgba8(0x4F, 0x46, 0xE5, 1.0),
+            shell_a: Color::from_rgba8(0xFF, 0xFF, 0xFF, 1.0),
+            shell_b: Color::from_rgba8(0xF4, 0xF4, 0xF5, 1.0),
+            card: Color::from_rgba8(0xFF, 0xFF, 0xFF, 0.85),
+            popover: Color::from_rgba8(0xFF, 0xFF, 0xFF, 0.97),
+            sidebar_bg: Color::from_rgba8(0xF4, 0xF4, 0xF5, 1.0),
+            border: alpha(black, 0.10), input_border: alpha(black, 0.18),
+            hover_bg: alpha(black, 0.04), active_bg: alpha(black, 0.08),
+            ink: black,
             radius_xl: 16.0,
         }
     }
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Dark => Self::nub_dark(),
+            ThemeMode::Light => Self::nub_light(),
+        }
+    }
 }
 pub fn alpha(mut c: Color, a: f32) -> Color { c.a = a; c }
 fn svg_icon(path: &str, color: Color) -> Element<'static, Message> {
@@ -82,9 +153,71 @@ pub fn ghost_button(t: Tokens, label: String, on_press: Message) -> Element<'sta
 pub fn primary_button(t: Tokens, label: String, on_press: Message) -> Element<'static, Message> { button(text(label).size(13).color(t.foreground)).padding([8, 16]).style(primary_button_style(t)).on_press(on_press).into() }
 pub fn danger_button(_t: Tokens, label: String, on_press: Message) -> Element<'static, Message> { let danger = Color::from_rgba8(0xEF, 0x44, 0x44, 1.0); button(text(label).size(13).color(danger)).padding([6, 12]).style(move |_: &Theme, status| { let mut s = iced::widget::button::Style::default(); let bg = match status { iced::widget::button::Status::Hovered => alpha(danger, 0.1), iced::widget::button::Status::Pressed => alpha(danger, 0.05), _ => alpha(danger, 0.0) }; s.background = Some(Background::Color(bg)); s.border = Border { color: alpha(danger, 0.2), width: 1.0, radius: border::Radius::from(6.0) }; s.text_color = danger; s }).on_press(on_press).into() }
 
-pub fn h_divider(t: Tokens) -> Element<'static, Message> { container(Space::new()).width(Length::Fill).height(Length::Fixed(1.0)).style(move |_: &Theme| container_style(alpha(Color::from_rgba8(0xFF, 0xFF, 0xFF, 1.0), 0.06), t.foreground)).into() }
+/// Extensiones que el widget `image` sabe decodificar (retratos de criaturas / locations).
+pub const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+pub fn is_supported_image(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Miniatura de un archivo local. Ruta vacía / inexistente / formato raro => placeholder (nunca panic).
+pub fn image_thumbnail<'a>(t: Tokens, path: Option<&str>, size: f32) -> E<'a> {
+    let usable = path
+        .map(std::path::Path::new)
+        .filter(|p| p.is_file() && is_supported_image(p));
+
+    let content: E<'a> = match usable {
+        Some(p) => iced::widget::image(iced::widget::image::Handle::from_path(p))
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
+            .content_fit(ContentFit::Cover)
+            .into(),
+        None => {
+            let label = if path.is_some() { "Image not found" } else { "No image" };
+            container(text(label).size(11).color(t.muted_fg))
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size))
+                .center_x(Length::Fixed(size))
+                .center_y(Length::Fixed(size))
+                .into()
+        }
+    };
+
+    container(content)
+        .style(move |_: &Theme| {
+            let mut s = container_style(t.input_border, t.foreground);
+            s.border = Border { color: t.border, width: 1.0, radius: border::Radius::from(8.0) };
+            s
+        })
+        .into()
+}
+
+/// Campo "imagen" de los editores: miniatura + ruta (escribir/pegar o soltar el archivo en la ventana).
+pub fn image_path_field<'a>(t: Tokens, label: &'a str, path: &'a str, on_change: fn(String) -> Message) -> E<'a> {
+    let trimmed = path.trim().trim_matches('"');
+    let thumb = image_thumbnail(t, (!trimmed.is_empty()).then_some(trimmed), 96.0);
+
+    let input = iced::widget::text_input("Path to an image file", path)
+        .on_input(on_change)
+        .padding(8)
+        .style(input_style(t));
+
+    let mut controls = Column::new().spacing(6).width(Length::Fill)
+        .push(text(label).size(12).color(t.muted_fg))
+        .push(input)
+        .push(text("Paste a path or drop an image file onto the window.").size(11).color(alpha(t.muted_fg, 0.7)));
+    if !path.is_empty() {
+        controls = controls.push(ghost_button(t, "Remove image".to_string(), on_change(String::new())));
+    }
+
+    Row::new().spacing(12).align_y(Alignment::Center).push(thumb).push(controls).into()
+}
+
+pub fn h_divider(t: Tokens) -> Element<'static, Message> { container(Space::new()).width(Length::Fill).height(Length::Fixed(1.0)).style(move |_: &Theme| container_style(alpha(t.ink, 0.06), t.foreground)).into() }
 #[derive(Debug, Clone, Copy)] enum NavKey { Overview, Workspaces, Universe, Forge, PmTools, Assets, Trash, Settings }
-fn is_active(state: &AppState, key: NavKey) -> bool { match (key, &state.route) { (NavKey::Overview, Route::Overview) => true, (NavKey::Workspaces, _) => false, (NavKey::Universe, Route::UniverseList) | (NavKey::Universe, Route::UniverseDetail { .. }) | (NavKey::Universe, Route::Bestiary { .. }) | (NavKey::Universe, Route::Timeline { .. }) => true, (NavKey::Forge, Route::Forge) => true, (NavKey::PmTools, Route::PmList) | (NavKey::PmTools, Route::PmBoard { .. }) => true, (NavKey::Assets, Route::Assets) => true, (NavKey::Settings, Route::Account) => true, _ => false } }
+fn is_active(state: &AppState, key: NavKey) -> bool { match (key, &state.route) { (NavKey::Overview, Route::Overview) => true, (NavKey::Workspaces, _) => false, (NavKey::Universe, Route::UniverseList) | (NavKey::Universe, Route::UniverseDetail { .. }) | (NavKey::Universe, Route::Bestiary { .. }) | (NavKey::Universe, Route::Glossary { .. }) | (NavKey::Universe, Route::Timeline { .. }) => true, (NavKey::Forge, Route::Forge) => true, (NavKey::PmTools, Route::PmList) | (NavKey::PmTools, Route::PmBoard { .. }) => true, (NavKey::Assets, Route::Assets) => true, (NavKey::Settings, Route::Account) => true, _ => false } }
 fn icon_path_for(key: NavKey) -> &'static str {
     match key {
         NavKey::Overview => Icons::HOME,
@@ -101,18 +234,32 @@ fn group_label<'a>(label: &'a str, t: Tokens) -> E<'a> { container(text(label).s
 fn nav_button_style(t: Tokens, active: bool, status: iced::widget::button::Status) -> iced::widget::button::Style { let mut s = iced::widget::button::Style::default(); if active { s.background = Some(Background::Color(alpha(t.accent, 0.10))); s.text_color = t.foreground; } else { let bg = match status { iced::widget::button::Status::Hovered => t.hover_bg, iced::widget::button::Status::Pressed => t.active_bg, _ => Color::TRANSPARENT }; s.background = Some(Background::Color(bg)); s.text_color = t.muted_fg; } s.border = Border { color: Color::TRANSPARENT, width: 0.0, radius: border::Radius::from(8.0) }; s }
 fn nav_item<'a>(t: Tokens, label: &'a str, key: NavKey, on_press: Message, active: bool) -> E<'a> { let icon_color = if active { t.accent } else { alpha(t.muted_fg, 0.8) }; let icon = container(svg_icon(icon_path_for(key), icon_color)).width(Length::Fixed(20.0)).align_x(Alignment::Center); let label_widget = text(label).size(14).color(if active { t.foreground } else { t.muted_fg }); let inner = Row::new().spacing(12).align_y(Alignment::Center).push(icon).push(label_widget); Element::new( button(container(inner).width(Length::Fill).height(Length::Fill).align_y(Alignment::Center).padding([0, 12])).width(Length::Fill).height(Length::Fixed(40.0)).style(move |_: &Theme, status| nav_button_style(t, active, status)).on_press(on_press) ) }
 
+fn sidebar_target(section: SidebarSection) -> (NavKey, Message) {
+    match section {
+        SidebarSection::Overview => (NavKey::Overview, Message::Navigate(Route::Overview)),
+        SidebarSection::Workspaces => (NavKey::Workspaces, Message::Navigate(Route::Workspaces)),
+        SidebarSection::Universe => (NavKey::Universe, Message::Navigate(Route::UniverseList)),
+        // CAMBIO CRÍTICO: Enviamos None para que el controller active el modo Standalone
+        SidebarSection::Forge => (NavKey::Forge, Message::TheForge(crate::messages::TheForgeMessage::Open(None))),
+        SidebarSection::PmTools => (NavKey::PmTools, Message::Navigate(Route::PmList)),
+        SidebarSection::Assets => (NavKey::Assets, Message::Navigate(Route::Assets)),
+        SidebarSection::Trash => (NavKey::Trash, Message::Navigate(Route::Trash)),
+    }
+}
+
 pub fn sidebar<'a>(state: &'a AppState, t: Tokens) -> E<'a> {
-    let mut modules = Column::new().spacing(4).width(Length::Fill);
-    modules = modules
-        .push(group_label("MODULES", t))
-        .push(nav_item(t, "Overview", NavKey::Overview, Message::Navigate(Route::Overview), is_active(state, NavKey::Overview)))
-        .push(nav_item(t, "Workspaces", NavKey::Workspaces, Message::Navigate(Route::Workspaces), is_active(state, NavKey::Workspaces)))
-        .push(nav_item(t, "Universe", NavKey::Universe, Message::Navigate(Route::UniverseList), is_active(state, NavKey::Universe)))
-        // CAMBIO CRÍTICO: Enviamos "" para que el controller active el modo Standalone
-        .push(nav_item(t, "The Forge", NavKey::Forge, Message::TheForge(crate::messages::TheForgeMessage::Open(None)), is_active(state, NavKey::Forge)))
-        .push(nav_item(t, "PM Tools", NavKey::PmTools, Message::Navigate(Route::PmList), is_active(state, NavKey::PmTools)))
-        .push(nav_item(t, "Assets", NavKey::Assets, Message::Navigate(Route::Assets), is_active(state, NavKey::Assets)))
-        .push(nav_item(t, "Trash", NavKey::Trash, Message::Navigate(Route::Trash), is_active(state, NavKey::Trash)));
+    let mut modules = Column::new().spacing(4).width(Length::Fill).push(group_label("MODULES", t));
+    // Orden/visibilidad desde settings; las secciones ocultas siguen accesibles por sus rutas.
+    // Capability apagada en el proyecto => la sección no aparece
+    for entry in state
+        .settings
+        .sidebar_sections
+        .iter()
+        .filter(|e| e.visible && e.section.capability().is_none_or(|c| state.capability_enabled(c)))
+    {
+        let (key, msg) = sidebar_target(entry.section);
+        modules = modules.push(nav_item(t, entry.section.label(), key, msg, is_active(state, key)));
+    }
 
     let mut account = Column::new().spacing(4).width(Length::Fill);
     account = account.push(nav_item(t, "Settings", NavKey::Settings, Message::Navigate(Route::Account), is_active(state, NavKey::Settings)));
@@ -133,24 +280,68 @@ pub fn sidebar<'a>(state: &'a AppState, t: Tokens) -> E<'a> {
     )
 }
 
-fn workspace_pill(t: Tokens, label: String) -> Element<'static, Message> { container(text(label).size(12).color(t.foreground)).padding([6, 12]).style(move |_: &Theme| { let mut s = container_style(alpha(Color::from_rgba8(0xFF, 0xFF, 0xFF, 1.0), 0.04), t.foreground); s.border = Border { color: alpha(Color::from_rgba8(0xFF, 0xFF, 0xFF, 1.0), 0.1), width: 1.0, radius: border::Radius::from(999.0) }; s }).into() }
-pub fn header<'a>(state: &'a AppState, t: Tokens) -> E<'a> { let ws_name = state.active_project.as_ref().map(|p| p.name.clone()).unwrap_or("Launcher".to_string()); let brand = Column::new().spacing(0).push(text(APP_NAME).size(15).color(t.foreground)).push(text(APP_SLOGAN).size(12).color(alpha(t.muted_fg, 0.7))); let right = Row::new().spacing(12).align_y(Alignment::Center).push(workspace_pill(t, ws_name)); let bar = Row::new().align_y(Alignment::Center).push(container(brand).width(Length::Fill)).push(right).padding(Padding { top: 16.0, right: 32.0, bottom: 16.0, left: 32.0 }); Element::new( container(bar).width(Length::Fill).style(move |_: &Theme| container_style(Color::TRANSPARENT, t.foreground)) ) }
-pub fn recents_card_universe<'a>(t: Tokens) -> E<'a> { card(t, Column::new().push(text("Arhelis (Universe)").size(14).color(t.foreground)).into()) }
-pub fn recents_card_forge<'a>(t: Tokens) -> E<'a> { card(t, Column::new().push(text("Chapter 1: The Awakening").size(14).color(t.foreground)).into()) }
+fn workspace_pill(t: Tokens, label: String) -> Element<'static, Message> { container(text(label).size(12).color(t.foreground)).padding([6, 12]).style(move |_: &Theme| { let mut s = container_style(alpha(t.ink, 0.04), t.foreground); s.border = Border { color: alpha(t.ink, 0.1), width: 1.0, radius: border::Radius::from(999.0) }; s }).into() }
+// Status de la cola DB: neutro si es chica; aviso persistente si hay backpressure
+fn db_queue_pill(t: Tokens, depth: usize, rejected: u64) -> Element<'static, Message> {
+    let warn = depth >= crate::state::DB_QUEUE_WARN_LIMIT || rejected > 0;
+    let label = if rejected > 0 {
+        format!("⚠ Saving backlog: {} pending · {} paused", depth, rejected)
+    } else if warn {
+        format!("⚠ Saving backlog: {} pending", depth)
+    } else {
+        format!("Saving… {}", depth)
+    };
+    let color = if warn { Color::from_rgb8(234, 179, 8) } else { t.muted_fg };
+    container(text(label).size(12).color(color))
+        .padding([6, 12])
+        .style(move |_: &Theme| {
+            let mut s = container_style(alpha(color, 0.08), t.foreground);
+            s.border = Border { color: alpha(color, 0.3), width: 1.0, radius: border::Radius::from(999.0) };
+            s
+        })
+        .into()
+}
+// Back/Forward del historial: flecha apagada (texto) cuando no hay a dónde ir
+fn history_button(t: Tokens, label: &'static str, enabled: bool, msg: Message) -> Element<'static, Message> {
+    if enabled {
+        button(text(label).size(14).color(t.foreground)).padding([4, 10]).style(ghost_button_style(t)).on_press(msg).into()
+    } else {
+        container(text(label).size(14).color(alpha(t.muted_fg, 0.4))).padding([4, 10]).into()
+    }
+}
+fn history_controls(state: &AppState, t: Tokens) -> Element<'static, Message> {
+    let can_back = state.nav_index > 0;
+    let can_forward = state.nav_index + 1 < state.nav_history.len();
+    Row::new().spacing(2).align_y(Alignment::Center)
+        .push(history_button(t, "←", can_back, Message::NavBack))
+        .push(history_button(t, "→", can_forward, Message::NavForward))
+        .into()
+}
+pub fn header<'a>(state: &'a AppState, t: Tokens) -> E<'a> { let ws_name = state.active_project.as_ref().map(|p| p.name.clone()).unwrap_or("Launcher".to_string()); let brand = Row::new().spacing(16).align_y(Alignment::Center).push(history_controls(state, t)).push(Column::new().spacing(0).push(text(APP_NAME).size(15).color(t.foreground)).push(text(APP_SLOGAN).size(12).color(alpha(t.muted_fg, 0.7)))); let depth = state.db_queue.len() + usize::from(state.db_inflight.is_some()); let mut right = Row::new().spacing(12).align_y(Alignment::Center); if depth > 0 || state.db_queue_rejected > 0 { right = right.push(db_queue_pill(t, depth, state.db_queue_rejected)); } let right = right.push(workspace_pill(t, ws_name)); let bar = Row::new().align_y(Alignment::Center).push(container(brand).width(Length::Fill)).push(right).padding(Padding { top: 16.0, right: 32.0, bottom: 16.0, left: 32.0 }); Element::new( container(bar).width(Length::Fill).style(move |_: &Theme| container_style(Color::TRANSPARENT, t.foreground)) ) }
 
 pub fn toasts_overlay<'a>(t: Tokens, toasts: &'a [Toast]) -> E<'a> {
     let mut col = Column::new().spacing(10);
     for toast in toasts {
-        let (bg, icon) = match toast.kind {
-            ToastKind::Info => (t.shell_b, "ℹ"),
-            ToastKind::Success => (Color::from_rgb8(22, 101, 52), "✓"),
-            ToastKind::Error => (Color::from_rgb8(153, 27, 27), "!"),
+        // Success / Error tienen fondo saturado en ambos temas: texto blanco fijo
+        let (bg, fg, icon) = match toast.kind {
+            ToastKind::Info => (t.popover, t.foreground, "ℹ"),
+            ToastKind::Success => (Color::from_rgb8(22, 101, 52), Color::WHITE, "✓"),
+            ToastKind::Error => (Color::from_rgb8(153, 27, 27), Color::WHITE, "!"),
         };
-        let content = Row::new().align_y(Alignment::Center).spacing(12)
-            .push(text(icon).size(16).color(t.foreground))
-            .push(text(&toast.message).size(14).color(t.foreground).width(Length::Fill))
-            .push(button(text("×").size(16).color(t.muted_fg)).style(ghost_button_style(t)).on_press(Message::ToastDismiss(toast.id)));
-        let card = container(content).width(Length::Fixed(320.0)).padding(12).style(move |_| { let mut s = container_style(bg, t.foreground); s.border = Border { color: t.border, width: 1.0, radius: border::Radius::from(8.0) }; s.shadow = Shadow { color: Color::BLACK, offset: Vector::new(0.0, 4.0), blur_radius: 12.0 }; s });
+        let tt = Tokens { foreground: fg, hover_bg: alpha(fg, 0.08), active_bg: alpha(fg, 0.12), ..t };
+        let mut content = Row::new().align_y(Alignment::Center).spacing(12)
+            .push(text(icon).size(16).color(fg))
+            .push(text(&toast.message).size(14).color(fg).width(Length::Fill));
+        if let Some((label, _)) = &toast.action {
+            content = content.push(
+                button(text(label.as_str()).size(13).color(fg))
+                    .style(ghost_button_style(tt))
+                    .on_press(Message::ToastAction(toast.id)),
+            );
+        }
+        let content = content
+            .push(button(text("×").size(16).color(alpha(fg, 0.7))).style(ghost_button_style(tt)).on_press(Message::ToastDismiss(toast.id)));
+        let card = container(content).width(Length::Fixed(320.0)).padding(12).style(move |_| { let mut s = container_style(bg, fg); s.border = Border { color: t.border, width: 1.0, radius: border::Radius::from(8.0) }; s.shadow = Shadow { color: alpha(Color::BLACK, 0.35), offset: Vector::new(0.0, 4.0), blur_radius: 12.0 }; s });
         col = col.push(card);
     }
     container(col).padding(20).width(Length::Fill).height(Length::Fill).align_x(iced::alignment::Horizontal::Right).align_y(iced::alignment::Vertical::Bottom).into()
@@ -272,6 +272,10 @@ fn confirm_modal(action: &ConfirmAction, t: ui::Tokens) -> Element<'_, Message>
             "Delete Location?",
             "This location will be moved to trash. You can restore it later.",
         ),
+        ConfirmAction::DeleteGlossaryTerm(_) => (
+            "Delete Glossary Term?",
+            "This term will be moved to trash. You can restore it later.",
+        ),
         ConfirmAction::DeleteEvent(_) => (
             "Delete Event?",
             "This timeline event will be moved to trash. You can restore it later.",
@@ -280,6 +284,19 @@ fn confirm_modal(action: &ConfirmAction, t: ui::Tokens) -> Element<'_, Message>
             "Delete Era?",
             "This timeline era will be moved to trash. You can restore it later.",
         ),
+        ConfirmAction::MergeNovels { .. } => (
+            "Merge Novels?",
+            "All chapters will be appended to the target novel and the merged novel will be removed.",
+        ),
+        ConfirmAction::PurgeOldTrash(_) => (
+            "Purge Old Items?",
+            "Items listed as pending auto-cleanup will be permanently deleted now.",
+        ),
+    };
+    let confirm_label = match action {
+        ConfirmAction::MergeNovels { .. } => "Merge",
+        ConfirmAction::PurgeOldTrash(_) => "Purge",
+        _ => "Delete",
     };
 
     let content = Column::new()
@@ -301,7 +318,7 @@ fn confirm_modal(action: &ConfirmAction, t: ui::Tokens) -> Element<'_, Message>
                     ui::ghost_button(t, "Cancel".to_string(), Message::CancelConfirm)
                 )
                 .push(
-                    ui::danger_button(t, "Delete".to_string(), Message::ConfirmDelete)
+                    ui::danger_button(t, confirm_label.to_string(), Message::ConfirmDelete)
                 )
         );
 
@@ -336,9 +353,87 @@ fn confirm_modal(action: &ConfirmAction, t: ui::Tokens) -> Element<'_, Message>
     overlay_bg.into()
 }
 
+// --- COMMAND PALETTE (Ctrl+P) ---
+fn command_palette<'a>(
+    state: &'a AppState,
+    palette: &'a crate::command_palette::PaletteState,
+    t: ui::Tokens,
+) -> Element<'a, Message> {
+    let input = iced::widget::text_input("Type a command or a name...", &palette.query)
+        .id(iced::widget::Id::new("command_palette"))
+        .on_input(Message::PaletteQueryChanged)
+        .on_submit(Message::PaletteSubmit)
+        .padding(10)
+        .size(15)
+        .style(ui::input_style(t));
+
+    let results = crate::command_palette::matches(state, &palette.query);
+    let mut list = Column::new().spacing(2);
+    if results.is_empty() {
+        list = list.push(container(text("No matching commands").size(13).color(t.muted_fg)).padding([8, 12]));
+    }
+    for (i, command) in results.into_iter().enumerate() {
+        let selected = i == palette.selected;
+        let row = Row::new()
+            .spacing(12)
+            .align_y(iced::Alignment::Center)
+            .push(text(command.label).size(14).color(t.foreground).width(Length::Fill))
+            .push(text(command.group).size(11).color(t.muted_fg));
+
+        list = list.push(
+            iced::widget::button(row)
+                .width(Length::Fill)
+                .padding([8, 12])
+                .style(move |_: &Theme, status| {
+                    let bg = match status {
+                        _ if selected => ui::alpha(t.accent, 0.15),
+                        iced::widget::button::Status::Hovered => t.hover_bg,
+                        _ => Color::TRANSPARENT,
+                    };
+                    iced::widget::button::Style {
+                        background: Some(bg.into()),
+                        text_color: t.foreground,
+                        border: iced::Border { color: Color::TRANSPARENT, width: 0.0, radius: 6.0.into() },
+                        ..Default::default()
+                    }
+                })
+                .on_press(Message::PaletteRun(i)),
+        );
+    }
+
+    let panel = container(
+        Column::new()
+            .spacing(10)
+            .push(input)
+            .push(list)
+            .push(text("↑ ↓ to select · Enter to run · Esc to close").size(11).color(t.muted_fg)),
+    )
+        .padding(12)
+        .width(Length::Fixed(560.0))
+        .style(move |_: &Theme| {
+            let mut s = ui::container_style(t.shell_b, t.foreground);
+            s.border.color = t.border;
+            s.border.width = 1.0;
+            s.border.radius = 12.0.into();
+            s.shadow = iced::Shadow {
+                color: Color::BLACK,
+                offset: Vector::new(0.0, 12.0),
+                blur_radius: 24.0,
+            };
+            s
+        });
+
+    container(panel)
+        .padding(iced::Padding { top: 80.0, right: 0.0, bottom: 0.0, left: 0.0 })
+        .center_x(Length::Fill)
+        .height(Length::Fill)
+        .style(move |_: &Theme| ui::container_style(ui::alpha(Color::BLACK, 0.4), t.foreground))
+        .into()
+}
+
 // --- VIEW DISPATCHER ---
 pub fn view(state: &AppState) -> Element<'_, Message> {
-    let t = ui::Tokens::nub_dark();
+    let t = ui::Tokens::for_mode(state.ui_theme);
 
     // 1) LAUNCHER MODE
     if state.active_project.is_none() {
@@ -356,6 +451,7 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
         Route::UniverseDetail { universe_id } => pages::universe_detail(state, t, universe_id),
         Route::Bestiary { universe_id } => pages::bestiary(state, t, universe_id),
         Route::Locations { universe_id } => pages::locations::locations(state, t, universe_id),
+        Route::Glossary { universe_id } => pages::glossary::glossary(state, t, universe_id),
         Route::Timeline { universe_id } => pages::timeline::timeline(state, t, universe_id),
         Route::PmList => pages::pm_list::pm_list(state, t),
         Route::PmBoard { .. } => pages::pm_board::pm_board(state, t, &state.pm_data),
@@ -365,7 +461,7 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
         Route::Forge => pages::the_forge(state, t),
 
         Route::Assets => pages::assets_stub(state, t),
-        Route::Account => pages::account_stub(state, t),
+        Route::Account => pages::settings(state, t),
     };
 
     // IMPORTANT:
@@ -376,9 +472,11 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
         _ => scrollable(page).width(Length::Fill).height(Length::Fill).into(),
     };
 
-    let right = Column::new()
-        .spacing(14)
-        .push(header)
+    let mut right = Column::new().spacing(14).push(header);
+    if let Some(banner) = pages::the_forge::draft_recovery_banner(state, t) {
+        right = right.push(container(banner).padding([0, 16]));
+    }
+    let right = right
         .push(page_host)
         .width(Length::Fill)
         .height(Length::Fill);
@@ -397,27 +495,42 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
         title,
         description,
         priority,
+        labels,
+        due_date,
         card_id,
         ..
     } = &state.pm_state
     {
-        let is_new = card_id.is_none();
-        stack = stack.push(crate::pages::pm_board::render_modal(
-            t,
+        // Destinos para "Move to board": todos menos el board abierto
+        let current_board_id = state.pm_data.as_ref().map(|d| d.board.id.as_str());
+        let move_targets: Vec<crate::model::Board> = state
+            .boards_list
+            .iter()
+            .filter(|b| Some(b.id.as_str()) != current_board_id)
+            .cloned()
+            .collect();
+
+        let form = crate::pages::pm_board::CardForm {
             title,
-            description,
+            desc: description,
             priority,
-            is_new,
-        ));
+            labels,
+            due_date,
+            card_id: card_id.as_deref(),
+        };
+        stack = stack.push(crate::pages::pm_board::render_modal(t, form, move_targets));
     }
     if let Some(editor) = &state.creature_editor {
-        stack = stack.push(pages::bestiary::render_creature_modal(t, editor, &state.locations));
+        stack = stack.push(pages::bestiary::render_creature_modal(t, editor, &state.locations, &state.creature_mentions, &state.creatures, &state.creature_relations));
     }
     if let Some(editor) = &state.location_editor {
-        stack = stack.push(pages::locations::render_location_modal(t, editor));
+        stack = stack.push(pages::locations::render_location_modal(t, editor, &state.locations));
+    }
+    if let Some(editor) = &state.glossary_editor {
+        stack = stack.push(pages::glossary::render_glossary_modal(t, editor));
     }
     if let Some(editor) = &state.event_editor {
-        stack = stack.push(pages::timeline::render_event_modal(t, editor, &state.locations));
+        stack = stack.push(pages::timeline::render_event_modal(t, editor, &state.locations, &state.timeline_scene_refs));
     }
     if let Some(editor) = &state.era_editor {
         stack = stack.push(pages::timeline::render_era_modal(t, editor));
@@ -468,6 +581,11 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
             }));
         }
     }
+    // Paleta de comandos: encima de la página y los editores, debajo del confirm
+    if let Some(palette) = &state.command_palette {
+        stack = stack.push(command_palette(state, palette, t));
+    }
+
     // Confirm Modal (debe estar ANTES del debug overlay para aparecer encima)
     if let Some(ref action) = state.pending_confirm {
         stack = stack.push(confirm_modal(action, t));
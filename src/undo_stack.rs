@@ -0,0 +1,107 @@
+// src/undo_stack.rs
+// Undo / redo global de acciones de DB (renames, mover cards, reordenar, borrar).
+// Cada entrada = acción + inversa. La inversa se arma al encolar (ahí se conoce el estado
+// previo) pero la entrada se apila recién cuando la DB confirma (action_done):
+// el stack nunca tiene algo que no pasó. Qué es reversible lo declara `DbAction::is_undoable`.
+
+use std::collections::VecDeque;
+
+use crate::state::DbAction;
+
+/// Máximo de entradas de undo (se descarta la más vieja).
+pub const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoEntry {
+    pub label: String, // "Rename chapter 'X'" => toast de Undone/Redone
+    pub forward: DbAction,
+    pub inverse: DbAction,
+    // Borrar se deshace (restore) pero no se rehace: el payload del trash ya no sirve
+    pub redoable: bool,
+}
+
+/// De dónde viene una acción en vuelo con entrada de undo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoOrigin {
+    Fresh, // acción normal del usuario
+    Undo,  // inversa aplicada por Ctrl+Z
+    Redo,  // forward re-aplicada por Ctrl+Y
+}
+
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    // Encoladas, esperando a la DB: (acción que corre, entrada, origen)
+    pending: Vec<(DbAction, UndoEntry, UndoOrigin)>,
+}
+
+impl UndoStack {
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.pending.clear();
+    }
+
+    pub fn track(&mut self, running: DbAction, entry: UndoEntry, origin: UndoOrigin) {
+        self.pending.push((running, entry, origin));
+    }
+
+    /// Un save coalescido en la cola ("último gana") reemplazó a `old`: su entrada espera a `new`.
+    pub fn retarget(&mut self, old: &DbAction, new: &DbAction) {
+        for (running, _, _) in self.pending.iter_mut().filter(|(running, _, _)| running == old) {
+            *running = new.clone();
+        }
+    }
+
+    /// La DB confirmó `done`: su entrada pasa al stack que corresponde.
+    pub fn complete(&mut self, done: &DbAction) -> Option<(UndoEntry, UndoOrigin)> {
+        let (_, entry, origin) = self.take_pending(done)?;
+        match origin {
+            UndoOrigin::Fresh => {
+                self.redo.clear();
+                self.push_undo(entry.clone());
+            }
+            UndoOrigin::Undo => {
+                if entry.redoable {
+                    self.redo.push(entry.clone());
+                }
+            }
+            UndoOrigin::Redo => self.push_undo(entry.clone()),
+        }
+        Some((entry, origin))
+    }
+
+    /// Falló en la DB: la entrada se descarta (un undo que no se pudo aplicar no se reintenta).
+    pub fn fail(&mut self, done: &DbAction) -> Option<UndoOrigin> {
+        self.take_pending(done).map(|(_, _, origin)| origin)
+    }
+
+    pub fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.undo.pop_back()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<UndoEntry> {
+        self.redo.pop()
+    }
+
+    /// Quita entradas que ya no pueden aplicarse (ej: trash entry restaurada a mano o purgada).
+    pub fn forget_where(&mut self, stale: impl Fn(&UndoEntry) -> bool) {
+        self.undo.retain(|e| !stale(e));
+        self.redo.retain(|e| !stale(e));
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if self.undo.len() >= MAX_ENTRIES {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    // Match exacto: otro save de la misma fila (sin entrada de undo) no se lleva la entrada.
+    // Los coalescidos ya quedaron apuntando a la acción que corre (retarget).
+    fn take_pending(&mut self, done: &DbAction) -> Option<(DbAction, UndoEntry, UndoOrigin)> {
+        let idx = self.pending.iter().position(|(running, _, _)| running == done)?;
+        Some(self.pending.remove(idx))
+    }
+}